@@ -1,6 +1,6 @@
 
 
-use differential_growth::{generate_points_on_circle, DifferentialGrowth};
+use differential_growth::{draw_differential_growth, generate_points_on_circle, DifferentialGrowth, DrawStyle};
 use nalgebra::Point2;
 use nannou::{event::Update, prelude::*, window, App, Frame};
 
@@ -46,33 +46,15 @@ fn update(_app: &App, _model: &mut Model, _update: Update) {
 
 // colors: https://docs.rs/nannou/0.11.1/nannou/color/index.html#constants
 fn view(app: &App, _model: &Model, frame: Frame) {
-    // Get a Vector of points
-    let points: Vec<Point2<f64>> = _model.differential_growth.get_points();
-
     let draw: Draw = app.draw();
     draw.background().color(MINTCREAM);
 
-    // Drawing line between consecutive elements
-    for window in points.windows(2) {
-        let point1: Point2<f64> = window[0];
-        let point2: Point2<f64> = window[1];
-
-        draw.line()
-            .start(Vec2::new(point1.x as f32, point1.y as f32))
-            .end(Vec2::new(point2.x as f32, point2.y as f32))
-            .color(NAVY);
-    }
-
-    // Drawing line between first and last element
-    {
-        let point1: Point2<f64> = points[0];
-        let point2: Point2<f64> = points[points.len() - 1];
-
-        draw.line()
-            .start(Vec2::new(point1.x as f32, point1.y as f32))
-            .end(Vec2::new(point2.x as f32, point2.y as f32))
-            .color(NAVY);
-    }
+    // Draw the path as one polyline, closed since the path is closed.
+    let style: DrawStyle = DrawStyle {
+        color: NAVY.into_lin_srgba(),
+        ..DrawStyle::default()
+    };
+    draw_differential_growth(&draw, &_model.differential_growth, &style);
 
     draw.to_frame(app, &frame).unwrap();
 }
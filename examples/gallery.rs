@@ -0,0 +1,79 @@
+// Regenerates the showcase images from the scene files in `examples/scenes`.
+// Every scene is run twice to check it is deterministic before its SVG is written.
+//
+// cargo run --release --example gallery --features serde [output directory]
+
+use std::{env, fs, path::PathBuf};
+
+use differential_growth::{MultiPathGrowth, Scene};
+use nalgebra::Point2;
+
+const SCENES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/scenes");
+const MARGIN: f64 = 10.0;
+
+fn main() {
+    let output: PathBuf = env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/images/gallery")));
+    fs::create_dir_all(&output).expect("failed to create the output directory");
+
+    let mut scene_files: Vec<PathBuf> = fs::read_dir(SCENES)
+        .expect("failed to read the scenes")
+        .map(|entry| entry.expect("failed to read the scenes").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    scene_files.sort();
+
+    for scene_file in scene_files {
+        let json: String = fs::read_to_string(&scene_file).expect("failed to read a scene");
+        let scene: Scene = serde_json::from_str(&json)
+            .unwrap_or_else(|error| panic!("invalid scene {}: {}", scene_file.display(), error));
+
+        let multi_path_growth: MultiPathGrowth = scene.run();
+        let points: Vec<Vec<Point2<f64>>> = multi_path_growth.get_points();
+
+        assert!(
+            points.iter().flatten().all(|point| point.x.is_finite() && point.y.is_finite()),
+            "{} blew up",
+            scene_file.display()
+        );
+        assert_eq!(
+            points,
+            scene.run().get_points(),
+            "{} is not deterministic",
+            scene_file.display()
+        );
+
+        let name = scene_file.file_stem().expect("scene files have a name");
+        let svg_file: PathBuf = output.join(name).with_extension("svg");
+        fs::write(&svg_file, to_svg(&multi_path_growth)).expect("failed to write the svg");
+
+        let amount_of_nodes: usize = points.iter().map(|path| path.len()).sum();
+        println!("{} ({} nodes)", svg_file.display(), amount_of_nodes);
+    }
+}
+
+// Draws every path of the simulation in a single SVG fitting all of them.
+fn to_svg(multi_path_growth: &MultiPathGrowth) -> String {
+    let (min, max): (Point2<f64>, Point2<f64>) = multi_path_growth
+        .bounding_box()
+        .unwrap_or((Point2::origin(), Point2::origin()));
+
+    let mut svg: String = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min.x - MARGIN,
+        min.y - MARGIN,
+        max.x - min.x + 2.0 * MARGIN,
+        max.y - min.y + 2.0 * MARGIN,
+    );
+    for path in multi_path_growth.paths() {
+        svg.push_str(&format!(
+            "  <path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"0.5\"/>\n",
+            path.to_svg_path()
+        ));
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
@@ -0,0 +1,331 @@
+// An interactive playground to try out parameters and the interactive APIs.
+//
+// cargo run --release --example playground --features serde
+//
+// Left drag          draw a new seed path, released it starts growing
+// Shift + left click stimulate growth around the cursor
+// Ctrl + left click  inhibit growth around the cursor
+// Right drag         pan
+// Mouse wheel        zoom
+// Up / Down          select a parameter
+// Left / Right       decrease / increase the selected parameter
+// Space              pause / resume
+// R                  restart from a circle
+// S / L              save / load the simulation to / from playground.json
+
+use std::fs;
+
+use differential_growth::{generate_points_on_circle, DifferentialGrowth};
+use nalgebra::Point2;
+use nannou::{event::Update, prelude::*, window, App, Frame};
+
+const SAVE_FILE: &str = "playground.json";
+const STIMULUS_RADIUS: f64 = 40.0;
+const STIMULUS_TICKS: usize = 120;
+
+fn main() {
+    nannou::app(model)
+        .update(update)
+        .run();
+}
+
+// The parameters that can be changed with the arrow keys.
+#[derive(Copy, Clone)]
+enum Param {
+    MaxForce,
+    MaxSpeed,
+    DesiredSeparation,
+    SeparationCohesionRatio,
+    MaxEdgeLength,
+    Alignment,
+    Damping,
+}
+
+const PARAMS: [Param; 7] = [
+    Param::MaxForce,
+    Param::MaxSpeed,
+    Param::DesiredSeparation,
+    Param::SeparationCohesionRatio,
+    Param::MaxEdgeLength,
+    Param::Alignment,
+    Param::Damping,
+];
+
+impl Param {
+    fn name(&self) -> &'static str {
+        match self {
+            Param::MaxForce => "max force",
+            Param::MaxSpeed => "max speed",
+            Param::DesiredSeparation => "desired separation",
+            Param::SeparationCohesionRatio => "separation cohesion ratio",
+            Param::MaxEdgeLength => "max edge length",
+            Param::Alignment => "alignment",
+            Param::Damping => "damping",
+        }
+    }
+
+    fn get(&self, differential_growth: &DifferentialGrowth) -> f64 {
+        match self {
+            Param::MaxForce => differential_growth.max_force,
+            Param::MaxSpeed => differential_growth.max_speed,
+            Param::DesiredSeparation => differential_growth.desired_separation,
+            Param::SeparationCohesionRatio => differential_growth.separation_cohesion_ratio(),
+            Param::MaxEdgeLength => differential_growth.max_edge_length,
+            Param::Alignment => differential_growth.alignment,
+            Param::Damping => differential_growth.damping,
+        }
+    }
+
+    fn step(&self, differential_growth: &mut DifferentialGrowth, up: bool) {
+        let value: f64 = self.get(differential_growth);
+        // The parameters that start at 0 change linearly, the others by a constant factor.
+        let value: f64 = match (matches!(self, Param::Alignment | Param::Damping), up) {
+            (true, true) => value + 0.05,
+            (true, false) => (value - 0.05).max(0.0),
+            (false, true) => value * 1.1,
+            (false, false) => value / 1.1,
+        };
+
+        match self {
+            Param::MaxForce => differential_growth.set_max_force(value),
+            Param::MaxSpeed => differential_growth.set_max_speed(value),
+            Param::DesiredSeparation => differential_growth.set_desired_separation(value),
+            Param::SeparationCohesionRatio => differential_growth.set_separation_cohesion_ratio(value),
+            Param::MaxEdgeLength => differential_growth.set_max_edge_length(value),
+            Param::Alignment => differential_growth.set_alignment(value),
+            Param::Damping => differential_growth.set_damping(value),
+        }
+    }
+}
+
+struct Model {
+    _window: window::Id,
+    differential_growth: DifferentialGrowth,
+    paused: bool,
+    selected: usize,
+    // The world position at the center of the window and the pixels per world unit.
+    camera: Point2<f64>,
+    zoom: f64,
+    // The seed being drawn with the left mouse button, in world coordinates.
+    drawing: Option<Vec<Point2<f64>>>,
+    // The last mouse position while panning, in window coordinates.
+    panning: Option<Vec2>,
+    status: String,
+}
+
+fn model(app: &App) -> Model {
+    let _window = app
+        .new_window()
+        .title("differential growth playground")
+        .view(view)
+        .mouse_pressed(mouse_pressed)
+        .mouse_released(mouse_released)
+        .mouse_moved(mouse_moved)
+        .mouse_wheel(mouse_wheel)
+        .key_pressed(key_pressed)
+        .build()
+        .unwrap();
+
+    Model {
+        _window,
+        differential_growth: new_growth(generate_points_on_circle(0.0, 0.0, 10.0, 10)),
+        paused: false,
+        selected: 0,
+        camera: Point2::new(0.0, 0.0),
+        zoom: 1.0,
+        drawing: None,
+        panning: None,
+        status: String::new(),
+    }
+}
+
+fn new_growth(starting_points: Vec<Point2<f64>>) -> DifferentialGrowth {
+    DifferentialGrowth::builder()
+        .points(starting_points)
+        .warmup_ticks(20)
+        .build()
+}
+
+fn to_world(model: &Model, position: Vec2) -> Point2<f64> {
+    Point2::new(
+        position.x as f64 / model.zoom + model.camera.x,
+        position.y as f64 / model.zoom + model.camera.y,
+    )
+}
+
+fn to_screen(model: &Model, point: &Point2<f64>) -> Vec2 {
+    Vec2::new(
+        ((point.x - model.camera.x) * model.zoom) as f32,
+        ((point.y - model.camera.y) * model.zoom) as f32,
+    )
+}
+
+fn mouse_pressed(app: &App, model: &mut Model, button: MouseButton) {
+    let position: Point2<f64> = to_world(model, app.mouse.position());
+
+    match button {
+        MouseButton::Left if app.keys.mods.shift() => {
+            model
+                .differential_growth
+                .stimulate(position, STIMULUS_RADIUS, 2.0, STIMULUS_TICKS);
+        }
+        MouseButton::Left if app.keys.mods.ctrl() => {
+            model
+                .differential_growth
+                .inhibit(position, STIMULUS_RADIUS, STIMULUS_TICKS);
+        }
+        MouseButton::Left => model.drawing = Some(vec![position]),
+        MouseButton::Right => model.panning = Some(app.mouse.position()),
+        _ => {}
+    }
+}
+
+fn mouse_released(_app: &App, model: &mut Model, button: MouseButton) {
+    match button {
+        MouseButton::Left => {
+            // A few points are needed to form a path, a click alone starts nothing.
+            if let Some(points) = model.drawing.take() {
+                if points.len() >= 3 {
+                    model.differential_growth = new_growth(points);
+                    model.status = String::from("planted a new seed");
+                }
+            }
+        }
+        MouseButton::Right => model.panning = None,
+        _ => {}
+    }
+}
+
+fn mouse_moved(_app: &App, model: &mut Model, position: Vec2) {
+    if let Some(last) = model.panning {
+        let delta: Vec2 = position - last;
+        model.camera.x -= delta.x as f64 / model.zoom;
+        model.camera.y -= delta.y as f64 / model.zoom;
+        model.panning = Some(position);
+    }
+
+    let point: Point2<f64> = to_world(model, position);
+    // Only keep points far enough apart to give the path a reasonable amount of nodes.
+    let min_distance: f64 = model.differential_growth.max_edge_length;
+    if let Some(points) = model.drawing.as_mut() {
+        if points
+            .last()
+            .map_or(true, |last| nalgebra::distance(last, &point) >= min_distance)
+        {
+            points.push(point);
+        }
+    }
+}
+
+fn mouse_wheel(app: &App, model: &mut Model, delta: MouseScrollDelta, _phase: TouchPhase) {
+    let amount: f64 = match delta {
+        MouseScrollDelta::LineDelta(_, y) => y as f64,
+        MouseScrollDelta::PixelDelta(position) => position.y / 40.0,
+    };
+
+    // Zooms around the cursor, so the point under it stays in place.
+    let before: Point2<f64> = to_world(model, app.mouse.position());
+    model.zoom = (model.zoom * 1.1f64.powf(amount)).clamp(0.05, 50.0);
+    let after: Point2<f64> = to_world(model, app.mouse.position());
+    model.camera += before - after;
+}
+
+fn key_pressed(_app: &App, model: &mut Model, key: Key) {
+    match key {
+        Key::Space => model.paused = !model.paused,
+        Key::R => {
+            model.differential_growth = new_growth(generate_points_on_circle(0.0, 0.0, 10.0, 10));
+            model.status = String::from("restarted");
+        }
+        Key::Up => model.selected = (model.selected + PARAMS.len() - 1) % PARAMS.len(),
+        Key::Down => model.selected = (model.selected + 1) % PARAMS.len(),
+        Key::Left => PARAMS[model.selected].step(&mut model.differential_growth, false),
+        Key::Right => PARAMS[model.selected].step(&mut model.differential_growth, true),
+        Key::S => {
+            model.status = match serde_json::to_string(&model.differential_growth)
+                .map_err(|error| error.to_string())
+                .and_then(|json| fs::write(SAVE_FILE, json).map_err(|error| error.to_string()))
+            {
+                Ok(()) => format!("saved to {}", SAVE_FILE),
+                Err(error) => format!("saving failed: {}", error),
+            };
+        }
+        Key::L => {
+            model.status = match fs::read_to_string(SAVE_FILE)
+                .map_err(|error| error.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|error| error.to_string()))
+            {
+                Ok(differential_growth) => {
+                    model.differential_growth = differential_growth;
+                    format!("loaded {}", SAVE_FILE)
+                }
+                Err(error) => format!("loading failed: {}", error),
+            };
+        }
+        _ => {}
+    }
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    if !model.paused && model.drawing.is_none() {
+        model.differential_growth.tick();
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw: Draw = app.draw();
+    draw.background().color(MINTCREAM);
+
+    // Regions that are stimulated or inhibited.
+    for overlay in model.differential_growth.overlays() {
+        let color = if overlay.growth_factor >= 1.0 { LIGHTGREEN } else { LIGHTPINK };
+        draw.ellipse()
+            .xy(to_screen(model, &overlay.center))
+            .radius((overlay.radius * model.zoom) as f32)
+            .color(color);
+    }
+
+    let mut points: Vec<Vec2> = model
+        .differential_growth
+        .nodes()
+        .iter()
+        .map(|node| to_screen(model, &node.position))
+        .collect();
+    if let Some(&first) = points.first() {
+        points.push(first);
+    }
+    draw.polyline().weight(1.5).points(points).color(NAVY);
+
+    if let Some(drawing) = &model.drawing {
+        let points: Vec<Vec2> = drawing.iter().map(|point| to_screen(model, point)).collect();
+        draw.polyline().weight(1.5).points(points).color(ORANGERED);
+    }
+
+    // The parameters, with the selected one marked.
+    let mut lines: Vec<String> = PARAMS
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let marker: &str = if i == model.selected { ">" } else { " " };
+            format!("{} {}: {:.3}", marker, param.name(), param.get(&model.differential_growth))
+        })
+        .collect();
+    lines.push(format!(
+        "  {} nodes, iteration {}{}",
+        model.differential_growth.nodes().len(),
+        model.differential_growth.iteration(),
+        if model.paused { ", paused" } else { "" }
+    ));
+    lines.push(format!("  {}", model.status));
+
+    let window: Rect = app.window_rect();
+    draw.text(&lines.join("\n"))
+        .x_y(window.left() + 160.0, window.top() - 90.0)
+        .w(300.0)
+        .left_justify()
+        .align_text_top()
+        .font_size(14)
+        .color(BLACK);
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,179 @@
+use core::fmt;
+
+use crate::prelude::*;
+use crate::{changes::NodeEvent, differential_growth::DifferentialGrowth, node::Node, node_data::Source};
+
+/// Error returned when changes made through [`DifferentialGrowth::with_nodes_mut()`]
+/// would corrupt the simulation. The changes are undone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodesError {
+    /// The position, velocity or acceleration of the node at `index` isn't a finite number.
+    NotFinite { index: usize },
+    /// The mass of the node at `index` isn't a positive finite number.
+    InvalidMass { index: usize },
+    /// The path was changed to `len` nodes, too few to form a path with its topology.
+    TooFewNodes { len: usize, min: usize },
+}
+
+impl fmt::Display for NodesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodesError::NotFinite { index } => write!(f, "node {} isn't finite", index),
+            NodesError::InvalidMass { index } => write!(f, "node {} has a mass that isn't positive", index),
+            NodesError::TooFewNodes { len, min } => {
+                write!(f, "{} nodes can't form a path, at least {} are needed", len, min)
+            }
+        }
+    }
+}
+
+// `core::error::Error` is only stable since Rust 1.81, without std there's no `Error` trait.
+#[cfg(feature = "std")]
+impl std::error::Error for NodesError {}
+
+impl DifferentialGrowth {
+    /// Returns the nodes of the path, in order.
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the current index of the node with `id`, see [`Node::id`],
+    /// or `None` when it was removed. Searches the nodes in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let id: usize = differential_growth.nodes()[5].id;
+    ///
+    /// differential_growth.tick_n(20);
+    ///
+    /// // Without merging no node is removed, but nodes may have been inserted before it.
+    /// let index: usize = differential_growth.node_index_of(id).unwrap();
+    /// assert!(index >= 5);
+    /// assert_eq!(differential_growth.nodes()[index].id, id);
+    /// assert_eq!(differential_growth.node_index_of(usize::MAX), None);
+    /// ```
+    ///
+    #[must_use]
+    pub fn node_index_of(&self, id: usize) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+
+    /// Gives mutable access to the nodes for changes the rest of the API doesn't cover,
+    /// and checks they leave a valid simulation behind. Returns what `change` returns,
+    /// or an error after undoing the changes if a node isn't finite or too few nodes are left.
+    ///
+    /// Nodes can be moved, inserted and removed. Inserted nodes that share the id of another
+    /// node get a new id and count as born now, attached node data follows the nodes by id
+    /// and [`NodeEvent::Reset`] is recorded when nodes were inserted, removed or reordered.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - Changes the nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{Node, NodesError};
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Kick every node outwards.
+    /// differential_growth
+    ///     .with_nodes_mut(|nodes| {
+    ///         for node in nodes.iter_mut() {
+    ///             node.velocity = node.position.coords.normalize();
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// // Adds a node between the first two.
+    /// differential_growth
+    ///     .with_nodes_mut(|nodes| nodes.insert(1, Node::new(Point2::new(10.0, 3.0), 1.0, 1.5)))
+    ///     .unwrap();
+    /// assert_eq!(differential_growth.nodes().len(), 11);
+    /// assert_ne!(differential_growth.nodes()[1].id, differential_growth.nodes()[0].id);
+    ///
+    /// let result = differential_growth.with_nodes_mut(|nodes| nodes[3].velocity = Vector2::new(f64::NAN, 0.0));
+    /// assert_eq!(result, Err(NodesError::NotFinite { index: 3 }));
+    /// assert!(differential_growth.nodes()[3].velocity.x.is_finite());
+    /// ```
+    ///
+    pub fn with_nodes_mut<R>(&mut self, change: impl FnOnce(&mut Vec<Node>) -> R) -> Result<R, NodesError> {
+        let original: Vec<Node> = self.nodes.clone();
+        let result: R = change(&mut self.nodes);
+
+        if let Err(error) = self.validate_nodes(original.len()) {
+            self.nodes = original;
+            return Err(error);
+        }
+
+        self.neighbor_search.invalidate();
+
+        let ids_changed: bool = self.nodes.len() != original.len()
+            || self.nodes.iter().zip(&original).any(|(node, original)| node.id != original.id);
+        if ids_changed {
+            let original_indices: HashMap<usize, usize> = original
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, node)| (node.id, i))
+                .collect();
+            let sources: Vec<Source> = self
+                .nodes
+                .iter()
+                .map(|node| original_indices.get(&node.id).map_or(Source::New, |&i| Source::Node(i)))
+                .collect();
+            self.remap_node_values(&sources);
+
+            self.give_new_nodes_ids(&original);
+            self.push_node_event(NodeEvent::Reset);
+        }
+
+        Ok(result)
+    }
+
+    fn validate_nodes(&self, original_len: usize) -> Result<(), NodesError> {
+        let finite = |node: &Node| {
+            node.position.iter().chain(node.velocity.iter()).chain(node.acceleration.iter()).all(|value| value.is_finite())
+        };
+        if let Some(index) = self.nodes.iter().position(|node| !finite(node)) {
+            return Err(NodesError::NotFinite { index });
+        }
+        if let Some(index) = self.nodes.iter().position(|node| !(node.mass.is_finite() && node.mass > 0.0)) {
+            return Err(NodesError::InvalidMass { index });
+        }
+
+        // Paths that were already too small when they were created are left alone.
+        let len: usize = self.nodes.len();
+        let min: usize = self.topology.min_nodes();
+        if len != original_len && len < min && len > 0 {
+            return Err(NodesError::TooFewNodes { len, min });
+        }
+
+        Ok(())
+    }
+
+    // Nodes with an id that didn't exist before or that is shared with a node before them
+    // are new, typically made with `Node::new()`.
+    fn give_new_nodes_ids(&mut self, original: &[Node]) {
+        let original_ids: HashSet<usize> = original.iter().map(|node| node.id).collect();
+        let mut seen: HashSet<usize> = HashSet::new();
+
+        for i in 0..self.nodes.len() {
+            let id: usize = self.nodes[i].id;
+            let is_duplicate: bool = !seen.insert(id);
+            let is_unknown: bool = !original_ids.contains(&id);
+            if is_duplicate || is_unknown {
+                self.nodes[i].id = self.take_node_id();
+                self.nodes[i].birth_tick = self.iteration();
+                seen.insert(self.nodes[i].id);
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use core::fmt::Write as _;
+use std::{borrow::Cow, fs, fs::File, io, io::Write, path::Path};
+
+use gif::{Encoder, Frame, Repeat};
+use nalgebra::{Point2, Vector2};
+
+use crate::{differential_growth::DifferentialGrowth, recording::Recording, topology::Topology};
+
+/// How [`DifferentialGrowth::encode_gif()`] renders the run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GifOptions {
+    /// The width of the animation in pixels. Defaults to `400`.
+    pub width: u16,
+    /// The height of the animation in pixels. Defaults to `400`.
+    pub height: u16,
+    /// The amount of ticks between two frames. Defaults to `5`.
+    pub ticks_per_frame: usize,
+    /// How long every frame is shown, in hundredths of a second. Defaults to `4`, 25 frames per second.
+    pub frame_delay: u16,
+    /// The space around the path in world units. Defaults to `10.0`.
+    pub padding: f64,
+    /// The background color as RGB. Defaults to white.
+    pub background: [u8; 3],
+    /// The color of the path as RGB. Defaults to black.
+    pub stroke: [u8; 3],
+    /// Whether the animation loops. Defaults to `true`.
+    pub repeat: bool,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        GifOptions {
+            width: 400,
+            height: 400,
+            ticks_per_frame: 5,
+            frame_delay: 4,
+            padding: 10.0,
+            background: [255, 255, 255],
+            stroke: [0, 0, 0],
+            repeat: true,
+        }
+    }
+}
+
+/// Whether [`Recording::export_animation()`] writes one animation or an image per frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AnimationFormat {
+    /// A single animated GIF.
+    #[default]
+    Gif,
+    /// Numbered SVG images, to assemble into a video with other tools.
+    SvgFrames,
+}
+
+/// How [`Recording::export_animation()`] renders a recording.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AnimationOptions {
+    /// Renders every `every`th recorded frame. Defaults to `1`, every frame.
+    pub every: usize,
+    /// What to write. Defaults to [`AnimationFormat::Gif`].
+    pub format: AnimationFormat,
+    /// The size, timing and colors of the frames. Its `ticks_per_frame` isn't used,
+    /// the interval of the recording and `every` decide which states are shown.
+    pub style: GifOptions,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            every: 1,
+            format: AnimationFormat::Gif,
+            style: GifOptions::default(),
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Runs the simulation and encodes it as an animated GIF, like the previews in the README,
+    /// without a window or external tools.
+    ///
+    /// The first frame shows the path as it is now, every next frame `ticks_per_frame` ticks
+    /// later. All frames share the same view, fitted around the path over the whole run
+    /// without stretching it, so the view doesn't jump while the path grows.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the GIF is written to.
+    /// * `frames` - The amount of frames of the animation.
+    /// * `options` - The size, timing and colors of the animation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mut gif: Vec<u8> = Vec::new();
+    /// differential_growth.encode_gif(&mut gif, 10, &differential_growth::GifOptions::default()).unwrap();
+    /// assert!(gif.starts_with(b"GIF89a"));
+    /// assert_eq!(differential_growth.iteration(), 45);
+    /// ```
+    ///
+    pub fn encode_gif(&mut self, writer: impl Write, frames: usize, options: &GifOptions) -> io::Result<()> {
+        // The view depends on the whole run, so the paths are collected before anything is drawn.
+        let mut paths: Vec<Vec<Point2<f64>>> = Vec::with_capacity(frames);
+        for frame in 0..frames {
+            if frame > 0 {
+                self.tick_n(options.ticks_per_frame);
+            }
+            paths.push(self.get_points());
+        }
+
+        encode_paths(writer, &paths, self.topology == Topology::Closed, options)
+    }
+
+    /// Writes the animation of [`DifferentialGrowth::encode_gif()`] to a file.
+    pub fn write_gif(&mut self, path: impl AsRef<Path>, frames: usize, options: &GifOptions) -> io::Result<()> {
+        self.encode_gif(io::BufWriter::new(File::create(path)?), frames, options)
+    }
+}
+
+impl Recording {
+    /// Renders every `every`th recorded frame as an animated GIF or as numbered SVG images,
+    /// to turn a run into a preview without a rendering stack. All frames share the same view,
+    /// fitted around the path over the whole recording like [`DifferentialGrowth::encode_gif()`] does.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The GIF file, or the directory the images are written into as `frame_00000.svg`,
+    ///   `frame_00001.svg` and so on. The directory is created when it doesn't exist.
+    /// * `options` - Which frames to render and how.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{AnimationFormat, AnimationOptions};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// differential_growth.start_recording(1);
+    /// differential_growth.tick_n(100);
+    /// let recording = differential_growth.stop_recording().unwrap();
+    ///
+    /// let options = AnimationOptions {
+    ///     every: 5,
+    ///     ..Default::default()
+    /// };
+    /// let gif = std::env::temp_dir().join("differential_growth_animation.gif");
+    /// recording.export_animation(&gif, &options).unwrap();
+    /// assert!(std::fs::read(&gif).unwrap().starts_with(b"GIF89a"));
+    ///
+    /// // The 101 recorded states, of which every 5th is rendered.
+    /// let options = AnimationOptions {
+    ///     format: AnimationFormat::SvgFrames,
+    ///     ..options
+    /// };
+    /// let frames = std::env::temp_dir().join("differential_growth_frames");
+    /// recording.export_animation(&frames, &options).unwrap();
+    /// assert!(frames.join("frame_00020.svg").exists());
+    /// ```
+    ///
+    pub fn export_animation(&self, path: impl AsRef<Path>, options: &AnimationOptions) -> io::Result<()> {
+        let paths: Vec<Vec<Point2<f64>>> = (0..self.len())
+            .step_by(options.every.max(1))
+            .filter_map(|frame_index| self.replay(frame_index))
+            .collect();
+        let closed: bool = self.topology() == Topology::Closed;
+
+        match options.format {
+            AnimationFormat::Gif => encode_paths(io::BufWriter::new(File::create(path)?), &paths, closed, &options.style),
+            AnimationFormat::SvgFrames => {
+                fs::create_dir_all(&path)?;
+                let view: View = View::fit(&paths, &options.style);
+                let [r, g, b] = options.style.background;
+                let background: String = format!("rgb({},{},{})", r, g, b);
+                let [r, g, b] = options.style.stroke;
+                let stroke: String = format!("rgb({},{},{})", r, g, b);
+
+                for (frame_index, frame) in paths.iter().enumerate() {
+                    let mut data: String = String::new();
+                    for (i, point) in frame.iter().enumerate() {
+                        let point: Point2<f64> = view.to_pixel(point);
+                        let command: &str = if i == 0 { "M" } else { " L" };
+                        // Writing to a String can't fail.
+                        let _ = write!(data, "{}{} {}", command, point.x, point.y);
+                    }
+                    if closed && !frame.is_empty() {
+                        data.push_str(" Z");
+                    }
+
+                    let svg: String = format!(
+                        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n  <rect width=\"100%\" height=\"100%\" fill=\"{2}\"/>\n  <path d=\"{3}\" fill=\"none\" stroke=\"{4}\" stroke-linejoin=\"round\" stroke-linecap=\"round\"/>\n</svg>\n",
+                        options.style.width, options.style.height, background, data, stroke,
+                    );
+                    fs::write(path.as_ref().join(format!("frame_{:05}.svg", frame_index)), svg)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+// Maps world positions to the pixels of an image, fitted around every path without stretching them.
+struct View {
+    min: Point2<f64>,
+    scale: f64,
+    offset: Vector2<f64>,
+}
+
+impl View {
+    fn fit(paths: &[Vec<Point2<f64>>], options: &GifOptions) -> View {
+        let (mut min, mut max): (Point2<f64>, Point2<f64>) = paths
+            .iter()
+            .flatten()
+            .fold(None, |bounds: Option<(Point2<f64>, Point2<f64>)>, point| match bounds {
+                Some((min, max)) => Some((min.inf(point), max.sup(point))),
+                None => Some((*point, *point)),
+            })
+            .unwrap_or_default();
+        min.iter_mut().for_each(|value| *value -= options.padding);
+        max.iter_mut().for_each(|value| *value += options.padding);
+
+        let (width, height): (f64, f64) = (f64::from(options.width), f64::from(options.height));
+        let mut scale: f64 = (width / (max.x - min.x)).min(height / (max.y - min.y));
+        // A single point without padding has no size to fit.
+        if !scale.is_finite() {
+            scale = 1.0;
+        }
+        // Centers the view along the axis with room to spare.
+        let offset: Vector2<f64> = Vector2::new(
+            (width - (max.x - min.x) * scale) / 2.0,
+            (height - (max.y - min.y) * scale) / 2.0,
+        );
+
+        View { min, scale, offset }
+    }
+
+    fn to_pixel(&self, point: &Point2<f64>) -> Point2<f64> {
+        Point2::from((point - self.min) * self.scale + self.offset)
+    }
+}
+
+// Encodes every path as a frame of an animated GIF, all in the same view.
+fn encode_paths(writer: impl Write, paths: &[Vec<Point2<f64>>], closed: bool, options: &GifOptions) -> io::Result<()> {
+    let view: View = View::fit(paths, options);
+    let (width, height): (usize, usize) = (options.width as usize, options.height as usize);
+
+    let palette: Vec<u8> = options.background.iter().chain(options.stroke.iter()).copied().collect();
+    let mut encoder = Encoder::new(writer, options.width, options.height, &palette).map_err(io::Error::other)?;
+    if options.repeat {
+        encoder.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+    }
+
+    let mut pixels: Vec<u8> = vec![0; width * height];
+    for path in paths {
+        pixels.iter_mut().for_each(|pixel| *pixel = 0);
+
+        let pixel_path: Vec<Point2<f64>> = path.iter().map(|point| view.to_pixel(point)).collect();
+        let edges = pixel_path.windows(2).map(|edge| (edge[0], edge[1]));
+        let closing = (closed && pixel_path.len() > 2).then(|| (pixel_path[pixel_path.len() - 1], pixel_path[0]));
+        for (a, b) in edges.chain(closing) {
+            draw_line(&mut pixels, width, height, &a, &b);
+        }
+
+        let frame: Frame = Frame {
+            width: options.width,
+            height: options.height,
+            delay: options.frame_delay,
+            buffer: Cow::Borrowed(&pixels),
+            ..Default::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    encoder.into_inner()?.flush()
+}
+
+// Sets the pixels along the line from `a` to `b` to the stroke color, one step per pixel along the longest axis.
+fn draw_line(pixels: &mut [u8], width: usize, height: usize, a: &Point2<f64>, b: &Point2<f64>) {
+    let steps: usize = (b.x - a.x).abs().max((b.y - a.y).abs()).ceil().clamp(1.0, 1e6) as usize;
+
+    for step in 0..=steps {
+        let point: Point2<f64> = a + (b - a) * (step as f64 / steps as f64);
+        if point.x >= 0.0 && point.y >= 0.0 && point.x < width as f64 && point.y < height as f64 {
+            pixels[point.y as usize * width + point.x as usize] = 1;
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(not(feature = "rayon"))]
+use std::thread;
+
+use nalgebra::Point2;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// Owns many independent simulations, like variations of a drawing with different
+/// seeds or parameters, and ticks them in parallel on all available threads.
+///
+/// Every simulation runs on a single thread, so the results are the same as
+/// ticking them one after the other. The `rayon` feature uses its thread pool,
+/// otherwise the simulations are split over scoped threads.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{BatchRunner, DifferentialGrowth};
+///
+/// let mut batch: BatchRunner = (0..8)
+///     .map(|i| {
+///         let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+///         DifferentialGrowth::builder()
+///             .points(starting_points)
+///             .max_edge_length(4.0 + i as f64 * 0.5)
+///             .build()
+///     })
+///     .collect();
+///
+/// batch.tick_n(20);
+///
+/// let results = batch.points();
+/// assert_eq!(results.len(), 8);
+/// assert!(batch.simulations().iter().all(|simulation| simulation.iteration() == 20));
+/// ```
+///
+#[derive(Clone, Default)]
+pub struct BatchRunner {
+    simulations: Vec<DifferentialGrowth>,
+}
+
+impl BatchRunner {
+    /// Returns a runner for `simulations`.
+    pub fn new(simulations: Vec<DifferentialGrowth>) -> BatchRunner {
+        BatchRunner { simulations }
+    }
+
+    /// Adds a simulation to the batch.
+    pub fn push(&mut self, differential_growth: DifferentialGrowth) {
+        self.simulations.push(differential_growth);
+    }
+
+    /// Returns the amount of simulations.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.simulations.len()
+    }
+
+    /// Returns whether there are no simulations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.simulations.is_empty()
+    }
+
+    /// Returns the simulations, in the order they were added.
+    #[must_use]
+    pub fn simulations(&self) -> &[DifferentialGrowth] {
+        &self.simulations
+    }
+
+    /// Returns the simulations mutably, to change them in between runs.
+    pub fn simulations_mut(&mut self) -> &mut [DifferentialGrowth] {
+        &mut self.simulations
+    }
+
+    /// Returns the simulations, in the order they were added.
+    #[must_use]
+    pub fn into_simulations(self) -> Vec<DifferentialGrowth> {
+        self.simulations
+    }
+
+    /// Advances every simulation by `n` iterations.
+    pub fn tick_n(&mut self, n: usize) {
+        for_each_parallel(&mut self.simulations, |differential_growth| differential_growth.tick_n(n));
+    }
+
+    /// Ticks every simulation until `done` returns `true` for it, see
+    /// [`DifferentialGrowth::run_until()`]. Returns the amount of ticks every simulation executed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{BatchRunner, DifferentialGrowth};
+    ///
+    /// let mut batch: BatchRunner = [3.0, 5.0]
+    ///     .into_iter()
+    ///     .map(|max_edge_length| {
+    ///         let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    ///         DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, max_edge_length)
+    ///     })
+    ///     .collect();
+    ///
+    /// let ticks = batch.run_until(|differential_growth| differential_growth.nodes().len() >= 100);
+    /// assert_eq!(ticks.len(), 2);
+    /// assert!(batch.simulations().iter().all(|simulation| simulation.nodes().len() >= 100));
+    /// ```
+    ///
+    pub fn run_until(&mut self, done: impl Fn(&DifferentialGrowth) -> bool + Sync) -> Vec<usize> {
+        let mut ticks: Vec<usize> = vec![0; self.simulations.len()];
+        let mut runs: Vec<(&mut DifferentialGrowth, &mut usize)> =
+            self.simulations.iter_mut().zip(ticks.iter_mut()).collect();
+
+        for_each_parallel(&mut runs, |(differential_growth, ticks)| {
+            **ticks = differential_growth.run_until(&done);
+        });
+
+        ticks
+    }
+
+    /// Returns the positions of the nodes of every simulation, see [`DifferentialGrowth::get_points()`].
+    #[must_use]
+    pub fn points(&self) -> Vec<Vec<Point2<f64>>> {
+        self.simulations.iter().map(DifferentialGrowth::get_points).collect()
+    }
+}
+
+impl FromIterator<DifferentialGrowth> for BatchRunner {
+    fn from_iter<I: IntoIterator<Item = DifferentialGrowth>>(iter: I) -> Self {
+        BatchRunner::new(iter.into_iter().collect())
+    }
+}
+
+// Calls `f` for every item, spread over the available threads.
+fn for_each_parallel<T: Send>(items: &mut [T], f: impl Fn(&mut T) + Sync) {
+    #[cfg(feature = "rayon")]
+    items.par_iter_mut().for_each(&f);
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let threads: usize = thread::available_parallelism().map_or(1, |threads| threads.get());
+        let chunk_size: usize = items.len().div_ceil(threads).max(1);
+        let f = &f;
+        thread::scope(|scope| {
+            for chunk in items.chunks_mut(chunk_size) {
+                scope.spawn(move || chunk.iter_mut().for_each(f));
+            }
+        });
+    }
+}
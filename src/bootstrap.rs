@@ -0,0 +1,75 @@
+use core::f64::consts::PI;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+impl DifferentialGrowth {
+    /// Returns a DifferentialGrowth instance with the default parameters of
+    /// [`DifferentialGrowth::builder()`] that blooms from a single point.
+    ///
+    /// A path needs at least 3 nodes when closed or 2 when open. Simulations starting
+    /// from fewer points, like this one, get the missing nodes at the start of their
+    /// first tick: a small triangle around a single point, or a node slightly beside the
+    /// middle of two points. Their directions are jittered with the random seed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::from_seed(Point2::new(0.0, 0.0));
+    /// assert_eq!(differential_growth.nodes().len(), 1);
+    ///
+    /// differential_growth.tick_n(50);
+    /// assert!(differential_growth.nodes().len() > 3);
+    ///
+    /// // Two points work the same way.
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(2.0, 0.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick();
+    /// assert_eq!(differential_growth.nodes().len(), 3);
+    /// ```
+    ///
+    pub fn from_seed(seed: Point2<f64>) -> DifferentialGrowth {
+        DifferentialGrowth::builder().points(vec![seed]).build()
+    }
+
+    // Appends the nodes a path of one or two nodes is missing, a quarter of
+    // the maximum edge length away so they don't split right away.
+    pub(crate) fn bootstrap(&mut self) {
+        let len: usize = self.nodes.len();
+        if len == 0 || len >= self.topology.min_nodes() {
+            return;
+        }
+
+        let spacing: f64 = self.max_edge_length / 4.0;
+        let angle: f64 = self.rng.range(0.0, 2.0 * PI);
+        let direction = |angle: f64| Vector2::new(angle.cos(), angle.sin());
+        let first: Point2<f64> = self.nodes[0].position;
+
+        let points: Vec<Point2<f64>> = match (self.topology, len) {
+            (Topology::Open, _) => vec![first + direction(angle) * spacing],
+            (Topology::Closed, 1) => vec![
+                first + direction(angle) * spacing,
+                first + direction(angle + PI / 3.0) * spacing,
+            ],
+            _ => {
+                let last: Point2<f64> = self.nodes[len - 1].position;
+                let difference: Vector2<f64> = self.difference(&first, &last);
+                let length: f64 = difference.norm();
+                if length > 0.0 {
+                    // To either side of the middle, by a fifth to a tenth of the distance.
+                    let side: f64 = if self.rng.next_f64() < 0.5 { -1.0 } else { 1.0 };
+                    let normal: Vector2<f64> = Vector2::new(-difference.y, difference.x) / length;
+                    vec![first + difference / 2.0 + normal * (side * length * self.rng.range(0.1, 0.2))]
+                } else {
+                    vec![first + direction(angle) * spacing]
+                }
+            }
+        };
+
+        self.extend_path(points);
+    }
+}
@@ -0,0 +1,157 @@
+use nalgebra::Point2;
+
+use crate::prelude::*;
+
+/// The brightness of an image laid over the simulation, which weights the chance that an
+/// edge splits by how dark the image is at its middle, so growth traces the dark regions
+/// of a photograph. Unlike the growth multipliers of overlays it leaves the maximum edge
+/// length alone, the spacing of the nodes stays the same everywhere.
+///
+/// Pixels are square and stored row by row starting at `min`. Outside the image
+/// the nearest pixel is used.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::BrightnessMap;
+/// use nalgebra::Point2;
+///
+/// // Black on the left half and white on the right half.
+/// let pixels: Vec<u8> = (0..100 * 100).map(|i| if i % 100 < 50 { 0 } else { 255 }).collect();
+/// let brightness_map = BrightnessMap::from_luma8(100, 100, &pixels, Point2::new(-50.0, -50.0), 1.0).unwrap();
+/// assert_eq!(brightness_map.growth_weight_at(&Point2::new(-20.0, 0.0)), 1.0);
+/// assert_eq!(brightness_map.growth_weight_at(&Point2::new(20.0, 0.0)), 0.0);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_brightness_map(brightness_map);
+/// differential_growth.tick_n(30);
+///
+/// // New nodes only appear on the dark side.
+/// let left: usize = differential_growth.nodes().iter().filter(|node| node.position.x < 0.0).count();
+/// let right: usize = differential_growth.nodes().len() - left;
+/// assert!(left > right);
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrightnessMap {
+    /// The corner of the image with the smallest coordinates.
+    pub min: Point2<f64>,
+    /// The width and height of a single pixel.
+    pub pixel_size: f64,
+    /// Shapes the weights: the growth weight is the darkness raised to this power,
+    /// so larger values keep growth to the darkest regions. Defaults to `1.0`.
+    pub contrast: f64,
+    /// Whether bright regions grow instead of dark ones. Defaults to `false`.
+    pub invert: bool,
+    width: usize,
+    height: usize,
+    // The brightness of every pixel, between 0 for black and 1 for white.
+    values: Vec<f64>,
+}
+
+impl BrightnessMap {
+    /// Returns the brightness of an 8 bit grayscale image, or `None` when there aren't
+    /// `width * height` pixels or the image is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The amount of pixels along the x axis.
+    /// * `height` - The amount of pixels along the y axis.
+    /// * `pixels` - The gray value of every pixel, row by row.
+    /// * `min` - Where the corner of the first pixel lies in the simulation.
+    /// * `pixel_size` - The width and height of a pixel in the simulation.
+    ///
+    pub fn from_luma8(width: usize, height: usize, pixels: &[u8], min: Point2<f64>, pixel_size: f64) -> Option<BrightnessMap> {
+        if width == 0 || height == 0 || pixels.len() != width * height {
+            return None;
+        }
+
+        Some(BrightnessMap::from_values(
+            width,
+            height,
+            pixels.iter().map(|&pixel| f64::from(pixel) / 255.0).collect(),
+            min,
+            pixel_size,
+        ))
+    }
+
+    /// Returns the brightness of an 8 bit RGBA image, like the buffers of most image
+    /// decoders, or `None` when there aren't `width * height` pixels or the image is empty.
+    /// Transparent pixels count as white.
+    ///
+    /// See [`BrightnessMap::from_luma8()`] for the other arguments.
+    pub fn from_rgba8(width: usize, height: usize, pixels: &[u8], min: Point2<f64>, pixel_size: f64) -> Option<BrightnessMap> {
+        if width == 0 || height == 0 || pixels.len() != width * height * 4 {
+            return None;
+        }
+
+        let values: Vec<f64> = pixels
+            .chunks_exact(4)
+            .map(|pixel| {
+                let [r, g, b, a]: [f64; 4] = [0, 1, 2, 3].map(|channel| f64::from(pixel[channel]) / 255.0);
+                let luma: f64 = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+                // Composited onto white.
+                1.0 - a * (1.0 - luma)
+            })
+            .collect();
+
+        Some(BrightnessMap::from_values(width, height, values, min, pixel_size))
+    }
+
+    fn from_values(width: usize, height: usize, values: Vec<f64>, min: Point2<f64>, pixel_size: f64) -> BrightnessMap {
+        BrightnessMap {
+            min,
+            pixel_size,
+            contrast: 1.0,
+            invert: false,
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Returns the amount of pixels along the x axis.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the amount of pixels along the y axis.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the brightness at `point` between 0 and 1, interpolated between the centers
+    /// of the surrounding pixels.
+    #[must_use]
+    pub fn brightness_at(&self, point: &Point2<f64>) -> f64 {
+        // Relative to the center of the first pixel, in pixels.
+        let x: f64 = ((point.x - self.min.x) / self.pixel_size - 0.5).clamp(0.0, (self.width - 1) as f64);
+        let y: f64 = ((point.y - self.min.y) / self.pixel_size - 0.5).clamp(0.0, (self.height - 1) as f64);
+        if x.is_nan() || y.is_nan() {
+            return 1.0;
+        }
+
+        let (x0, y0): (usize, usize) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1): (usize, usize) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (tx, ty): (f64, f64) = (x - x0 as f64, y - y0 as f64);
+        let value = |x: usize, y: usize| self.values[y * self.width + x];
+
+        value(x0, y0) * (1.0 - tx) * (1.0 - ty)
+            + value(x1, y0) * tx * (1.0 - ty)
+            + value(x0, y1) * (1.0 - tx) * ty
+            + value(x1, y1) * tx * ty
+    }
+
+    /// Returns the weight of the growth probability at `point`, between 0 and 1.
+    #[must_use]
+    pub fn growth_weight_at(&self, point: &Point2<f64>) -> f64 {
+        let brightness: f64 = self.brightness_at(point);
+        let weight: f64 = if self.invert { brightness } else { 1.0 - brightness };
+
+        weight.clamp(0.0, 1.0).powf(self.contrast)
+    }
+}
@@ -0,0 +1,474 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    cohesion::CohesionTarget,
+    constraints::{Boundary, BrownianNoise},
+    differential_growth::DifferentialGrowth,
+    endpoints::EndpointCondition,
+    geometry,
+    insertion::{InsertionStrategy, Midpoint},
+    integrator::Integrator,
+    oscillation::Oscillation,
+    params::{ParamSchedule, ParamSet},
+    quality::Quality,
+    renormalization::Renormalization,
+    self_contact::SelfAvoidance,
+    separation::SeparationKernel,
+    spatial::{DefaultIndex, RebuildPolicy, SpatialIndex},
+    topology::Topology,
+    velocity::InitialVelocity,
+};
+
+/// Builder for [`DifferentialGrowth`] with named setters and sensible defaults.
+///
+/// Every parameter that isn't set falls back to a value that has been tested
+/// to produce nice results with the included examples.
+///
+/// # Examples
+///
+/// ```rust
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+///
+/// let differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .desired_separation(14.0)
+///     .build();
+/// ```
+///
+pub struct DifferentialGrowthBuilder {
+    points: Vec<Point2<f64>>,
+    stroke_spacing: Option<f64>,
+    params: ParamSet,
+    topology: Topology,
+    insertion_strategy: Box<dyn InsertionStrategy>,
+    growth_probability: f64,
+    min_edge_length: Option<f64>,
+    degenerate_epsilon: Option<f64>,
+    max_nodes: Option<usize>,
+    rebuild_policy: RebuildPolicy,
+    spatial_index: Box<dyn SpatialIndex>,
+    warmup_ticks: usize,
+    damping: f64,
+    alignment: f64,
+    curvature_gain: f64,
+    redistribution: bool,
+    prevent_self_intersections: bool,
+    integrator: Integrator,
+    cohesion_target: CohesionTarget,
+    cohesion_strength: Option<f64>,
+    separation_kernel: SeparationKernel,
+    edge_separation: bool,
+    endpoint_condition: EndpointCondition,
+    boundary: Option<Boundary>,
+    brownian_noise: Option<BrownianNoise>,
+    self_avoidance: Option<SelfAvoidance>,
+    param_schedule: Option<ParamSchedule>,
+    renormalization: Option<Renormalization>,
+    oscillation: Option<Oscillation>,
+    constant_force: Vector2<f64>,
+    initial_velocity: InitialVelocity,
+    seed: u64,
+    deterministic: bool,
+}
+
+impl Default for DifferentialGrowthBuilder {
+    fn default() -> Self {
+        DifferentialGrowthBuilder {
+            points: Vec::new(),
+            stroke_spacing: None,
+            params: ParamSet::default(),
+            topology: Topology::Closed,
+            insertion_strategy: Box::new(Midpoint),
+            growth_probability: 1.0,
+            min_edge_length: None,
+            degenerate_epsilon: None,
+            max_nodes: None,
+            rebuild_policy: RebuildPolicy::default(),
+            spatial_index: Box::new(DefaultIndex::default()),
+            warmup_ticks: 0,
+            damping: 0.0,
+            alignment: 0.0,
+            curvature_gain: 0.0,
+            redistribution: false,
+            prevent_self_intersections: false,
+            integrator: Integrator::Euler,
+            cohesion_target: CohesionTarget::Midpoint,
+            cohesion_strength: None,
+            separation_kernel: SeparationKernel::Inverse,
+            edge_separation: false,
+            endpoint_condition: EndpointCondition::Free,
+            boundary: None,
+            brownian_noise: None,
+            self_avoidance: None,
+            param_schedule: None,
+            renormalization: None,
+            oscillation: None,
+            constant_force: Vector2::default(),
+            initial_velocity: InitialVelocity::Zero,
+            seed: 0,
+            deterministic: false,
+        }
+    }
+}
+
+impl DifferentialGrowthBuilder {
+    /// Returns a builder with all parameters set to their defaults.
+    pub fn new() -> DifferentialGrowthBuilder {
+        DifferentialGrowthBuilder::default()
+    }
+
+    /// The starting points. These are converted into Nodes.
+    /// Defaults to no points at all so this should always be set.
+    pub fn points(mut self, points: Vec<Point2<f64>>) -> Self {
+        self.points = points;
+        self.stroke_spacing = None;
+        self
+    }
+
+    /// Starting points from a hand-drawn stroke, like the positions of the mouse while
+    /// dragging. The stroke is resampled to points `spacing` apart when the simulation is
+    /// built, following the topology, so unevenly spaced or repeated points can't make the
+    /// first ticks explode or stall. Replaces the points set before.
+    ///
+    /// Choose a `spacing` below the maximum edge length, or every edge splits right away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    /// use nalgebra::{distance, Point2};
+    ///
+    /// // Bunched up where the mouse moved slowly, sparse where it moved fast.
+    /// let stroke = vec![
+    ///     Point2::new(0.0, 0.0),
+    ///     Point2::new(0.1, 0.0),
+    ///     Point2::new(0.1, 0.0),
+    ///     Point2::new(0.3, 0.0),
+    ///     Point2::new(20.0, 0.0),
+    ///     Point2::new(20.0, 10.0),
+    /// ];
+    /// let differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .stroke(stroke, 2.0)
+    ///     .topology(Topology::Open)
+    ///     .build();
+    ///
+    /// let points = differential_growth.get_points();
+    /// assert_eq!(points.len(), 16);
+    /// assert_eq!(points[15], Point2::new(20.0, 10.0));
+    /// assert!(points.windows(2).all(|pair| (distance(&pair[0], &pair[1]) - 2.0).abs() < 1e-9));
+    /// ```
+    ///
+    pub fn stroke(mut self, points: Vec<Point2<f64>>, spacing: f64) -> Self {
+        self.points = points;
+        self.stroke_spacing = Some(spacing);
+        self
+    }
+
+    /// Sets all algorithm parameters at once, for example
+    /// from [`ParamSet::random_within()`].
+    pub fn params(mut self, params: ParamSet) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// The maximum force nodes can exert on eachother. Defaults to `1.5`.
+    pub fn max_force(mut self, max_force: f64) -> Self {
+        self.params.max_force = max_force;
+        self
+    }
+
+    /// The maximum magnitude of a node's velocity. Defaults to `1.0`.
+    pub fn max_speed(mut self, max_speed: f64) -> Self {
+        self.params.max_speed = max_speed;
+        self
+    }
+
+    /// The desired separation between nodes. Defaults to `14.0`.
+    pub fn desired_separation(mut self, desired_separation: f64) -> Self {
+        self.params.desired_separation = desired_separation;
+        self
+    }
+
+    /// The ratio between separation and cohesion forces. Defaults to `1.1`.
+    pub fn separation_cohesion_ratio(mut self, separation_cohesion_ratio: f64) -> Self {
+        self.params.separation_cohesion_ratio = separation_cohesion_ratio;
+        self
+    }
+
+    /// The maximum length between two connected nodes. Defaults to `5.0`.
+    pub fn max_edge_length(mut self, max_edge_length: f64) -> Self {
+        self.params.max_edge_length = max_edge_length;
+        self
+    }
+
+    /// Whether the path is closed or open. Defaults to [`Topology::Closed`].
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Where new nodes are placed when an edge is split. Defaults to [`Midpoint`].
+    pub fn insertion_strategy(mut self, insertion_strategy: impl InsertionStrategy + 'static) -> Self {
+        self.insertion_strategy = Box::new(insertion_strategy);
+        self
+    }
+
+    /// The probability, between 0 and 1, that an over-length edge is split
+    /// during a tick. Defaults to `1.0`.
+    pub fn growth_probability(mut self, growth_probability: f64) -> Self {
+        self.growth_probability = growth_probability;
+        self
+    }
+
+    /// Connected nodes closer than this are merged at the end of every tick.
+    /// Disabled by default.
+    pub fn min_edge_length(mut self, min_edge_length: f64) -> Self {
+        self.min_edge_length = Some(min_edge_length);
+        self
+    }
+
+    /// Nodes closer than this to an earlier node are removed at the end of every tick.
+    /// Disabled by default.
+    pub fn degenerate_epsilon(mut self, degenerate_epsilon: f64) -> Self {
+        self.degenerate_epsilon = Some(degenerate_epsilon);
+        self
+    }
+
+    /// The amount of nodes after which no new nodes are inserted. Unlimited by default.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// When the spatial index used to find neighboring nodes is rebuilt.
+    /// Defaults to every tick.
+    pub fn rebuild_policy(mut self, rebuild_policy: RebuildPolicy) -> Self {
+        self.rebuild_policy = rebuild_policy;
+        self
+    }
+
+    /// Uses the rebuild policy and insertion strategy of a quality tier,
+    /// replacing the ones set before. Defaults to [`Quality::Balanced`].
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.rebuild_policy = quality.rebuild_policy();
+        self.insertion_strategy = quality.insertion_strategy();
+        self
+    }
+
+    /// The spatial index used to find neighboring nodes. Defaults to [`crate::KdTreeIndex`],
+    /// or [`crate::GridIndex`] without the `std` feature.
+    pub fn spatial_index(mut self, spatial_index: impl SpatialIndex + 'static) -> Self {
+        self.spatial_index = Box::new(spatial_index);
+        self
+    }
+
+    /// The amount of ticks over which forces are ramped up to full strength.
+    /// Defaults to `0`, no warm-up.
+    pub fn warmup_ticks(mut self, warmup_ticks: usize) -> Self {
+        self.warmup_ticks = warmup_ticks;
+        self
+    }
+
+    /// The fraction of their velocity nodes lose every tick, between 0 and 1.
+    /// Defaults to `0.0`, no damping.
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// The weight of the force steering nodes towards the average velocity
+    /// of the nodes around them. Defaults to `0.0`, no alignment.
+    pub fn alignment(mut self, alignment: f64) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// The gain of the force along the normal proportional to the excess curvature,
+    /// positive for frillier and negative for smoother paths. Defaults to `0.0`, no curvature force.
+    pub fn curvature_gain(mut self, curvature_gain: f64) -> Self {
+        self.curvature_gain = curvature_gain;
+        self
+    }
+
+    /// Whether nodes next to an over-length edge are first slid along the path
+    /// before a new node is inserted. Defaults to `false`.
+    pub fn redistribution(mut self, redistribution: bool) -> Self {
+        self.redistribution = redistribution;
+        self
+    }
+
+    /// Whether moves that make the path cross itself are undone. Defaults to `false`.
+    pub fn prevent_self_intersections(mut self, prevent_self_intersections: bool) -> Self {
+        self.prevent_self_intersections = prevent_self_intersections;
+        self
+    }
+
+    /// How the forces are integrated into the movement of the nodes.
+    /// Defaults to [`Integrator::Euler`].
+    pub fn integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// The point every node is pulled towards by the cohesion force.
+    /// Defaults to [`CohesionTarget::Midpoint`].
+    pub fn cohesion_target(mut self, cohesion_target: CohesionTarget) -> Self {
+        self.cohesion_target = cohesion_target;
+        self
+    }
+
+    /// The stiffness of the spring pulling every node towards its cohesion target,
+    /// see [`DifferentialGrowth::set_cohesion_strength()`]. Not set by default.
+    pub fn cohesion_strength(mut self, cohesion_strength: f64) -> Self {
+        self.cohesion_strength = Some(cohesion_strength);
+        self
+    }
+
+    /// How strongly neighbors push a node away depending on their distance.
+    /// Defaults to [`SeparationKernel::Inverse`].
+    pub fn separation_kernel(mut self, separation_kernel: SeparationKernel) -> Self {
+        self.separation_kernel = separation_kernel;
+        self
+    }
+
+    /// Whether separation also pushes nodes away from the edges around them,
+    /// see [`DifferentialGrowth::set_edge_separation()`]. Defaults to `false`.
+    pub fn edge_separation(mut self, edge_separation: bool) -> Self {
+        self.edge_separation = edge_separation;
+        self
+    }
+
+    /// How the endpoints of an open path behave. Defaults to [`EndpointCondition::Free`].
+    pub fn endpoint_condition(mut self, endpoint_condition: EndpointCondition) -> Self {
+        self.endpoint_condition = endpoint_condition;
+        self
+    }
+
+    /// The rectangular world nodes can't leave, see [`Boundary`]. Defaults to none.
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = Some(boundary);
+        self
+    }
+
+    /// A small random force on every node every tick, see [`BrownianNoise`]. Defaults to none.
+    pub fn brownian_noise(mut self, brownian_noise: BrownianNoise) -> Self {
+        self.brownian_noise = Some(brownian_noise);
+        self
+    }
+
+    /// A force pushing nodes away from other parts of the path that come too close,
+    /// see [`SelfAvoidance`]. Defaults to none.
+    pub fn self_avoidance(mut self, self_avoidance: SelfAvoidance) -> Self {
+        self.self_avoidance = Some(self_avoidance);
+        self
+    }
+
+    /// Parameters that change as the simulation progresses, see [`ParamSchedule`].
+    /// Defaults to none.
+    pub fn param_schedule(mut self, param_schedule: ParamSchedule) -> Self {
+        self.param_schedule = Some(param_schedule);
+        self
+    }
+
+    /// A periodic pass countering floating point drift in long runs, see [`Renormalization`].
+    /// Defaults to none.
+    pub fn renormalization(mut self, renormalization: Renormalization) -> Self {
+        self.renormalization = Some(renormalization);
+        self
+    }
+
+    /// A wobble of the desired separation of every node, see [`Oscillation`].
+    /// Defaults to none.
+    pub fn oscillation(mut self, oscillation: Oscillation) -> Self {
+        self.oscillation = Some(oscillation);
+        self
+    }
+
+    /// The force applied to every node every tick, like gravity or wind.
+    /// Defaults to a zero vector, no force.
+    pub fn constant_force(mut self, constant_force: Vector2<f64>) -> Self {
+        self.constant_force = constant_force;
+        self
+    }
+
+    /// The velocity the starting points start out with. Defaults to [`InitialVelocity::Zero`].
+    pub fn initial_velocity(mut self, initial_velocity: InitialVelocity) -> Self {
+        self.initial_velocity = initial_velocity;
+        self
+    }
+
+    /// The seed for the stochastic parts of the algorithm. Defaults to `0`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Whether neighbor contributions are summed in the order of the nodes, so the result
+    /// doesn't depend on the spatial index. Defaults to `false`,
+    /// see [`DifferentialGrowth::set_deterministic()`].
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Returns a DifferentialGrowth instance with the configured parameters.
+    pub fn build(self) -> DifferentialGrowth {
+        let points: Vec<Point2<f64>> = match self.stroke_spacing {
+            Some(spacing) => geometry::resample_evenly(&self.points, self.topology == Topology::Closed, spacing),
+            None => self.points,
+        };
+        let mut differential_growth: DifferentialGrowth = DifferentialGrowth::new(
+            points,
+            self.params.max_force,
+            self.params.max_speed,
+            self.params.desired_separation,
+            self.params.separation_cohesion_ratio,
+            self.params.max_edge_length,
+        );
+        differential_growth.topology = self.topology;
+        differential_growth.insertion_strategy = self.insertion_strategy;
+        differential_growth.set_growth_probability(self.growth_probability);
+        differential_growth.min_edge_length = self.min_edge_length;
+        differential_growth.degenerate_epsilon = self.degenerate_epsilon;
+        differential_growth.max_nodes = self.max_nodes;
+        differential_growth.set_rebuild_policy(self.rebuild_policy);
+        differential_growth.set_spatial_index(self.spatial_index);
+        differential_growth.set_warmup_ticks(self.warmup_ticks);
+        differential_growth.set_damping(self.damping);
+        differential_growth.set_alignment(self.alignment);
+        differential_growth.set_curvature_gain(self.curvature_gain);
+        differential_growth.set_redistribution(self.redistribution);
+        differential_growth.set_prevent_self_intersections(self.prevent_self_intersections);
+        differential_growth.set_integrator(self.integrator);
+        differential_growth.set_cohesion_target(self.cohesion_target);
+        differential_growth.cohesion_strength = self.cohesion_strength;
+        differential_growth.set_separation_kernel(self.separation_kernel);
+        differential_growth.set_edge_separation(self.edge_separation);
+        differential_growth.set_endpoint_condition(self.endpoint_condition);
+        if let Some(boundary) = self.boundary {
+            differential_growth.set_boundary(boundary);
+        }
+        if let Some(brownian_noise) = self.brownian_noise {
+            differential_growth.set_brownian_noise(brownian_noise);
+        }
+        if let Some(self_avoidance) = self.self_avoidance {
+            differential_growth.set_self_avoidance(self_avoidance);
+        }
+        if let Some(param_schedule) = self.param_schedule {
+            differential_growth.set_param_schedule(param_schedule);
+        }
+        if let Some(renormalization) = self.renormalization {
+            differential_growth.set_renormalization(renormalization);
+        }
+        if let Some(oscillation) = self.oscillation {
+            differential_growth.set_oscillation(oscillation);
+        }
+        differential_growth.set_constant_force(self.constant_force);
+        differential_growth.set_velocities(self.initial_velocity);
+        differential_growth.set_seed(self.seed);
+        differential_growth.set_deterministic(self.deterministic);
+
+        differential_growth
+    }
+}
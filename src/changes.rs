@@ -0,0 +1,66 @@
+use core::ops::Range;
+
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::node::Node;
+
+/// What happened to the positions in a [`ChangedRange`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The slots hold a different position than before the tick,
+    /// because their node moved or because earlier insertions or removals shifted them.
+    Moved,
+    /// The slots hold nodes that were inserted during the tick.
+    Inserted,
+}
+
+/// A contiguous range of indices into the positions buffer that changed during a tick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedRange {
+    /// The changed indices.
+    pub range: Range<usize>,
+    /// How they changed.
+    pub kind: ChangeKind,
+}
+
+/// A change to the structure of the path, see [`DifferentialGrowth::take_node_events()`].
+///
+/// Indices refer to the nodes as they are after applying all earlier events,
+/// so replaying the events in order on a copy of the nodes keeps it in sync.
+///
+/// [`DifferentialGrowth::take_node_events()`]: crate::DifferentialGrowth::take_node_events
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeEvent {
+    /// The node with `id` was inserted at `index`, shifting the nodes from there on up by one.
+    Inserted { index: usize, id: usize },
+    /// The node with `id` at `index` was removed, shifting the nodes after it down by one.
+    Removed { index: usize, id: usize },
+    /// All nodes were replaced, for example by a rollback of the watchdog,
+    /// copies of the nodes have to be rebuilt from scratch.
+    Reset,
+}
+
+/// Compares the positions before a tick with the nodes after it, slot by slot,
+/// and merges the changed slots into ranges.
+pub(crate) fn changed_ranges(before: &[Point2<f64>], nodes: &[Node], inserted: &[bool]) -> Vec<ChangedRange> {
+    let mut ranges: Vec<ChangedRange> = Vec::new();
+
+    for (i, node) in nodes.iter().enumerate() {
+        let kind: ChangeKind = if inserted.get(i).copied().unwrap_or(false) {
+            ChangeKind::Inserted
+        } else if before.get(i) != Some(&node.position) {
+            ChangeKind::Moved
+        } else {
+            continue;
+        };
+
+        match ranges.last_mut() {
+            Some(last) if last.kind == kind && last.range.end == i => last.range.end = i + 1,
+            _ => ranges.push(ChangedRange { range: i..i + 1, kind }),
+        }
+    }
+
+    ranges
+}
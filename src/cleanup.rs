@@ -0,0 +1,129 @@
+use core::ops::{AddAssign, SubAssign};
+
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::differential_growth::DifferentialGrowth;
+
+impl DifferentialGrowth {
+    /// Enables a cleanup pass at the end of every tick which removes nodes that are closer
+    /// than `epsilon` to an earlier node, anywhere along the path. This covers zero length
+    /// edges and parts of the path that collapsed onto each other, where differences between
+    /// nodes have no direction and forces are dropped instead of pushing the nodes apart.
+    ///
+    /// Unlike the pruning pass of [`DifferentialGrowth::set_min_edge_length()`] nothing is
+    /// merged, `epsilon` is meant to be tiny compared to the desired separation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_degenerate_epsilon(1e-6);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_degenerate_epsilon(&mut self, epsilon: f64) {
+        self.degenerate_epsilon = Some(epsilon);
+    }
+
+    /// Disables the cleanup pass.
+    pub fn clear_degenerate_epsilon(&mut self) {
+        self.degenerate_epsilon = None;
+    }
+
+    /// Removes every node closer than `epsilon` to an earlier node once, like the cleanup pass
+    /// of [`DifferentialGrowth::set_degenerate_epsilon()`], for example after editing the nodes.
+    /// The leader and clamped endpoints are kept, and no path is reduced below the
+    /// minimum amount of nodes of its topology. Returns the amount of removed nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![
+    ///     Point2::new(0.0, 0.0),
+    ///     Point2::new(10.0, 0.0),
+    ///     Point2::new(10.0, 0.0),
+    ///     Point2::new(10.0, 10.0),
+    ///     Point2::new(0.0, 0.0),
+    /// ];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.remove_degenerate_nodes(1e-9), 2);
+    /// assert_eq!(differential_growth.nodes().len(), 3);
+    /// ```
+    ///
+    pub fn remove_degenerate_nodes(&mut self, epsilon: f64) -> usize {
+        let keep: Vec<bool> = self.non_degenerate_nodes(epsilon);
+        let removed: usize = keep.iter().filter(|&&keep| !keep).count();
+        self.remove_nodes(&keep);
+
+        removed
+    }
+
+    // The cleanup pass, keeps `inserted` in line with the nodes.
+    pub(crate) fn clean_up_degenerate_nodes(&mut self, inserted: &mut Vec<bool>) {
+        let epsilon: f64 = match self.degenerate_epsilon {
+            Some(epsilon) => epsilon,
+            None => return,
+        };
+
+        let keep: Vec<bool> = self.non_degenerate_nodes(epsilon);
+        if keep.iter().all(|&keep| keep) {
+            return;
+        }
+
+        self.remove_nodes(&keep);
+        let mut index: usize = 0;
+        inserted.retain(|_| {
+            index.add_assign(1);
+            keep.get(index - 1).copied().unwrap_or(true)
+        });
+    }
+
+    // Whether every node is kept, nodes are hashed into cells of `epsilon` so only
+    // nodes in the surrounding cells are compared.
+    fn non_degenerate_nodes(&self, epsilon: f64) -> Vec<bool> {
+        let mut keep: Vec<bool> = vec![true; self.nodes.len()];
+        if epsilon.is_nan() || epsilon <= 0.0 {
+            return keep;
+        }
+
+        let protected: [Option<usize>; 3] = match self.clamped_endpoints() {
+            Some((first, last)) => [self.leader_index(), Some(first), Some(last)],
+            None => [self.leader_index(), None, None],
+        };
+        let mut removable: usize = self.nodes.len().saturating_sub(self.topology.min_nodes());
+
+        let cell_of = |i: usize| -> (i64, i64) {
+            let position: Point2<f64> = self.nodes[i].position;
+            ((position.x / epsilon).floor() as i64, (position.y / epsilon).floor() as i64)
+        };
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, keep) in keep.iter_mut().enumerate() {
+            let (cx, cy): (i64, i64) = cell_of(i);
+            let is_degenerate: bool = removable > 0
+                && !protected.contains(&Some(i))
+                && (cx.saturating_sub(1)..=cx.saturating_add(1)).any(|x| {
+                    (cy.saturating_sub(1)..=cy.saturating_add(1)).any(|y| {
+                        cells.get(&(x, y)).is_some_and(|kept| {
+                            kept.iter().any(|&j| {
+                                self.difference(&self.nodes[i].position, &self.nodes[j].position).norm() < epsilon
+                            })
+                        })
+                    })
+                });
+
+            if is_degenerate {
+                *keep = false;
+                removable.sub_assign(1);
+            } else {
+                cells.entry((cx, cy)).or_default().push(i);
+            }
+        }
+
+        keep
+    }
+}
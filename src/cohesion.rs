@@ -0,0 +1,104 @@
+use core::ops::AddAssign;
+
+use nalgebra::Vector2;
+
+use crate::prelude::*;
+use crate::{periodic::Period, soa::NodeArrays, topology::Topology};
+
+/// The point every node is pulled towards by the cohesion force.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::CohesionTarget;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_cohesion_target(CohesionTarget::ArcCentroid { neighborhood: 3 });
+///
+/// for _ in 0..20 {
+///     differential_growth.tick();
+/// }
+/// assert!(differential_growth.nodes().len() > 10);
+/// ```
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CohesionTarget {
+    /// The middle between the two neighbors of the node. This is the default.
+    #[default]
+    Midpoint,
+    /// The centroid of the path through the `neighborhood` nodes on either side of the node,
+    /// skipping the node itself, weighted by arc length. Unevenly spaced nodes shrink the
+    /// path less than with the midpoint and larger neighborhoods smooth out small wiggles.
+    /// A neighborhood of 1 is the same as the midpoint.
+    ArcCentroid {
+        /// The amount of nodes on either side of the node that are taken into account.
+        neighborhood: usize,
+    },
+}
+
+impl CohesionTarget {
+    // Returns the offset from node `i` to its target, or `None` when it has no neighbors on both sides.
+    pub(crate) fn offset(
+        &self,
+        arrays: &NodeArrays,
+        i: usize,
+        topology: Topology,
+        period: Option<&Period>,
+    ) -> Option<Vector2<f64>> {
+        let len: usize = arrays.len();
+        let (prev, next) = match topology.neighbors(i, len) {
+            (Some(prev), Some(next)) => (prev, next),
+            _ => return None,
+        };
+
+        // Across a periodic boundary every node is where it is closest to node `i`.
+        let origin: Vector2<f64> = Vector2::new(arrays.xs[i], arrays.ys[i]);
+        let position = |j: usize| match period {
+            Some(period) => origin + period.wrap(Vector2::new(arrays.xs[j], arrays.ys[j]) - origin),
+            None => Vector2::new(arrays.xs[j], arrays.ys[j]),
+        };
+        let midpoint: Vector2<f64> = (position(prev) + position(next)) / 2.0 - position(i);
+
+        let neighborhood: usize = match *self {
+            CohesionTarget::Midpoint => return Some(midpoint),
+            // On a closed path both sides of the window must not overlap.
+            CohesionTarget::ArcCentroid { neighborhood } => neighborhood.min(len.saturating_sub(1) / 2),
+        };
+        if neighborhood <= 1 {
+            return Some(midpoint);
+        }
+
+        // Walks the same amount of nodes in both directions, so open paths get a
+        // smaller window close to their ends instead of a lopsided one.
+        let mut backward: Vec<usize> = vec![prev];
+        let mut forward: Vec<usize> = vec![next];
+        while backward.len() < neighborhood {
+            match (topology.prev(backward[backward.len() - 1], len), topology.next(forward[forward.len() - 1], len)) {
+                (Some(prev), Some(next)) => {
+                    backward.push(prev);
+                    forward.push(next);
+                }
+                _ => break,
+            }
+        }
+
+        let window: Vec<usize> = backward.into_iter().rev().chain(forward).collect();
+        let mut weighted: Vector2<f64> = Vector2::default();
+        let mut length: f64 = 0.0;
+        for pair in window.windows(2) {
+            let (a, b): (Vector2<f64>, Vector2<f64>) = (position(pair[0]), position(pair[1]));
+            let edge_length: f64 = (b - a).norm();
+            weighted.add_assign((a + b) / 2.0 * edge_length);
+            length.add_assign(edge_length);
+        }
+
+        // All nodes in the window on top of eachother have no length to weigh by.
+        if length == 0.0 {
+            return Some(midpoint);
+        }
+
+        Some(weighted / length - position(i))
+    }
+}
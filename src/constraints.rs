@@ -0,0 +1,570 @@
+use core::ops::AddAssign;
+
+use nalgebra::{distance, Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    geometry::{self, closest_point_on_segment},
+    node::Node,
+    rng::Rng,
+};
+
+/// Keeps all nodes within a maximum distance of a reference polyline,
+/// so growth decorates a backbone curve while still developing folds.
+///
+/// Nodes further away than `max_distance` are pulled back towards the closest
+/// point on the guide by a force proportional to how far they overshoot.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::GuidePath;
+/// use nalgebra::Point2;
+///
+/// let guide = GuidePath::new(vec![Point2::new(-50.0, 0.0), Point2::new(50.0, 0.0)], false, 15.0, 0.1);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_guide_path(guide);
+/// differential_growth.tick();
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GuidePath {
+    /// The points of the reference polyline.
+    pub points: Vec<Point2<f64>>,
+    /// Whether the last point connects back to the first.
+    pub closed: bool,
+    /// How far nodes can wander from the guide before being pulled back.
+    pub max_distance: f64,
+    /// How strongly overshooting nodes are pulled back, per unit of overshoot.
+    pub stiffness: f64,
+}
+
+impl GuidePath {
+    /// Returns a GuidePath.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points of the reference polyline.
+    /// * `closed` - Whether the last point connects back to the first.
+    /// * `max_distance` - How far nodes can wander from the guide before being pulled back.
+    /// * `stiffness` - How strongly overshooting nodes are pulled back, per unit of overshoot.
+    ///
+    pub fn new(points: Vec<Point2<f64>>, closed: bool, max_distance: f64, stiffness: f64) -> GuidePath {
+        GuidePath {
+            points,
+            closed,
+            max_distance,
+            stiffness,
+        }
+    }
+
+    /// Returns the point on the guide closest to `point`.
+    pub fn closest_point(&self, point: &Point2<f64>) -> Option<Point2<f64>> {
+        match self.points.len() {
+            0 => None,
+            1 => Some(self.points[0]),
+            len => {
+                let segments: usize = if self.closed { len } else { len - 1 };
+                (0..segments)
+                    .map(|i| closest_point_on_segment(point, &self.points[i], &self.points[(i + 1) % len]).0)
+                    .min_by(|a, b| distance(point, a).total_cmp(&distance(point, b)))
+            }
+        }
+    }
+
+    /// Returns the force pulling a node at `position` back within range of the guide.
+    pub fn force(&self, position: &Point2<f64>) -> Vector2<f64> {
+        let closest: Point2<f64> = match self.closest_point(position) {
+            Some(closest) => closest,
+            None => return Vector2::default(),
+        };
+
+        let to_guide: Vector2<f64> = closest - position;
+        let distance: f64 = to_guide.norm();
+
+        if distance <= self.max_distance {
+            return Vector2::default();
+        }
+
+        to_guide / distance * (distance - self.max_distance) * self.stiffness
+    }
+}
+
+/// Pulls nodes within `radius` towards a point, strongest at the
+/// point itself and falling off linearly to nothing at the radius.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attractor {
+    /// The point nodes are pulled towards.
+    pub position: Point2<f64>,
+    /// How far the pull reaches.
+    pub radius: f64,
+    /// The magnitude of the pull right next to the point.
+    pub strength: f64,
+}
+
+impl Attractor {
+    /// Returns an Attractor.
+    pub fn new(position: Point2<f64>, radius: f64, strength: f64) -> Attractor {
+        Attractor {
+            position,
+            radius,
+            strength,
+        }
+    }
+
+    /// Returns the force pulling a node at `position` towards the attractor.
+    pub fn force(&self, position: &Point2<f64>) -> Vector2<f64> {
+        let to_attractor: Vector2<f64> = self.position - position;
+        let distance: f64 = to_attractor.norm();
+
+        if distance == 0.0 || distance >= self.radius {
+            return Vector2::default();
+        }
+
+        to_attractor / distance * (1.0 - distance / self.radius) * self.strength
+    }
+}
+
+/// A small random force on every node every tick, so perfectly symmetric starting
+/// shapes like the circle generator break their symmetry and develop folds.
+///
+/// Every axis of the force is normally distributed with `amplitude` as its standard
+/// deviation. The same seed always produces the same run.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::BrownianNoise;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut first = differential_growth::DifferentialGrowth::new(starting_points.clone(), 1.5, 1.0, 14.0, 1.1, 5.0);
+/// let mut second = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// first.set_brownian_noise(BrownianNoise::new(0.1, 42));
+/// second.set_brownian_noise(BrownianNoise::new(0.1, 42));
+///
+/// for _ in 0..20 {
+///     first.tick();
+///     second.tick();
+/// }
+/// assert!(first.diff(&second).is_identical());
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrownianNoise {
+    /// The standard deviation of the force along every axis.
+    pub amplitude: f64,
+    rng: Rng,
+}
+
+impl BrownianNoise {
+    /// Returns a BrownianNoise force.
+    ///
+    /// # Arguments
+    ///
+    /// * `amplitude` - The standard deviation of the force along every axis.
+    /// * `seed` - The same seed always produces the same forces.
+    ///
+    pub fn new(amplitude: f64, seed: u64) -> BrownianNoise {
+        BrownianNoise {
+            amplitude,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Returns the next random force.
+    pub fn force(&mut self) -> Vector2<f64> {
+        // Box-Muller transform, 1 - next_f64() is never 0 so the logarithm is finite.
+        let radius: f64 = (-2.0 * (1.0 - self.rng.next_f64()).ln()).sqrt();
+        let angle: f64 = self.rng.range(0.0, core::f64::consts::TAU);
+
+        Vector2::new(angle.cos(), angle.sin()) * radius * self.amplitude
+    }
+}
+
+/// A static shape nodes are pushed out of.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Obstacle;
+/// use nalgebra::{Point2, Vector2};
+///
+/// let square = Obstacle::Polygon {
+///     points: vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0), Point2::new(0.0, 10.0)],
+///     margin: 4.0,
+///     strength: 0.5,
+/// };
+///
+/// assert_eq!(square.force(&Point2::new(12.0, 5.0)), Vector2::new(0.25, 0.0));
+/// assert_eq!(square.force(&Point2::new(5.0, 9.0)), Vector2::new(0.0, 0.5));
+/// assert_eq!(square.force(&Point2::new(20.0, 5.0)), Vector2::new(0.0, 0.0));
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Obstacle {
+    /// Pushes nodes away from the center, strongest at the center and
+    /// falling off linearly to nothing at the radius. Cheaper than a polygon,
+    /// it doesn't go over any edges, but strong enough growth can push through.
+    Circle {
+        center: Point2<f64>,
+        radius: f64,
+        strength: f64,
+    },
+    /// Keeps nodes out of the polygon through `points`, using the even-odd rule, and pushes
+    /// nodes within `margin` of its edges away, strongest at the edges and falling off linearly
+    /// to nothing at the margin. Nodes that still end up inside are pushed out at full strength.
+    Polygon {
+        points: Vec<Point2<f64>>,
+        margin: f64,
+        strength: f64,
+    },
+}
+
+impl Obstacle {
+    /// Returns the force pushing a node at `position` out of the obstacle.
+    pub fn force(&self, position: &Point2<f64>) -> Vector2<f64> {
+        match self {
+            Obstacle::Circle {
+                center,
+                radius,
+                strength,
+            } => {
+                let away: Vector2<f64> = position - center;
+                let distance: f64 = away.norm();
+
+                if distance == 0.0 || distance >= *radius {
+                    return Vector2::default();
+                }
+
+                away / distance * (1.0 - distance / radius) * *strength
+            }
+            Obstacle::Polygon {
+                points,
+                margin,
+                strength,
+            } => {
+                let closest: Point2<f64> = match closest_point_on_polygon(points, position, *margin) {
+                    Some(closest) => closest,
+                    None => return Vector2::default(),
+                };
+                let away: Vector2<f64> = position - closest;
+                let distance: f64 = away.norm();
+
+                if distance == 0.0 {
+                    return Vector2::default();
+                }
+                // Inside the way out is towards the closest edge.
+                if geometry::polygon_contains(points, position) {
+                    return -away / distance * *strength;
+                }
+                if distance >= *margin {
+                    return Vector2::default();
+                }
+
+                away / distance * (1.0 - distance / margin) * *strength
+            }
+        }
+    }
+
+    /// Moves the obstacle by `offset`, to shepherd the growth between ticks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Obstacle;
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.add_obstacle(Obstacle::Circle { center: Point2::new(-30.0, 0.0), radius: 15.0, strength: 1.0 });
+    ///
+    /// // Sweep the obstacle across the path, then take it away.
+    /// for _ in 0..60 {
+    ///     differential_growth.obstacles_mut()[0].translate(Vector2::new(1.0, 0.0));
+    ///     differential_growth.tick();
+    /// }
+    /// let obstacle: Obstacle = differential_growth.remove_obstacle(0).unwrap();
+    /// assert_eq!(obstacle, Obstacle::Circle { center: Point2::new(30.0, 0.0), radius: 15.0, strength: 1.0 });
+    /// assert!(differential_growth.obstacles().is_empty());
+    /// ```
+    ///
+    pub fn translate(&mut self, offset: Vector2<f64>) {
+        match self {
+            Obstacle::Circle { center, .. } => center.add_assign(offset),
+            Obstacle::Polygon { points, .. } => points.iter_mut().for_each(|point| point.add_assign(offset)),
+        }
+    }
+
+    /// Puts a node that ended up inside a polygon back on its closest edge and stops it,
+    /// returning whether it was moved. Circles only push.
+    pub fn keep_out(&self, node: &mut Node) -> bool {
+        let points: &[Point2<f64>] = match self {
+            Obstacle::Polygon { points, .. } if points.len() >= 3 => points,
+            _ => return false,
+        };
+        if !geometry::polygon_contains(points, &node.position) {
+            return false;
+        }
+
+        match closest_point_on_polygon(points, &node.position, f64::INFINITY) {
+            Some(closest) => {
+                node.position = closest;
+                node.velocity = Vector2::default();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// The point on the edges of the closed polygon through `points` closest to `position`, when
+// `position` lies within `margin` of its bounding box. Skipping far away polygons by their
+// bounding box saves going over their edges for every node.
+fn closest_point_on_polygon(points: &[Point2<f64>], position: &Point2<f64>, margin: f64) -> Option<Point2<f64>> {
+    if points.len() < 3 {
+        return None;
+    }
+    let within_box: bool = (0..2).all(|axis| {
+        let min: f64 = points.iter().map(|point| point[axis]).fold(f64::INFINITY, f64::min);
+        let max: f64 = points.iter().map(|point| point[axis]).fold(f64::NEG_INFINITY, f64::max);
+        min - margin <= position[axis] && position[axis] <= max + margin
+    });
+    if !within_box {
+        return None;
+    }
+
+    (0..points.len())
+        .map(|i| closest_point_on_segment(position, &points[i], &points[(i + 1) % points.len()]).0)
+        .min_by(|a, b| distance(a, position).total_cmp(&distance(b, position)))
+}
+
+/// Gently squeezes the path along its longer axis so its bounding box converges towards
+/// a target aspect ratio, like 2:3 for a print, instead of having to crop an organic shape.
+///
+/// While the bounding box is too wide, nodes are pulled towards the vertical center line,
+/// the nodes on the far left and right the most. A box that is too tall is squeezed the
+/// other way. The pull grows with how far off the ratio is and stops once it's reached,
+/// so it settles where it balances the growth: the stronger, the closer to the ratio.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::AspectConstraint;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_aspect_constraint(AspectConstraint::new(2.0, 3.0, 0.5));
+///
+/// for _ in 0..300 {
+///     differential_growth.tick();
+/// }
+///
+/// let (min, max) = differential_growth.bounding_box().unwrap();
+/// assert!((max.x - min.x) < (max.y - min.y));
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AspectConstraint {
+    /// The target width divided by the target height.
+    pub ratio: f64,
+    /// The largest force applied to a node, reached on the edge of
+    /// the bounding box when it's twice as wide or tall as it should be.
+    pub strength: f64,
+}
+
+impl AspectConstraint {
+    /// Returns an AspectConstraint towards a `width` by `height` bounding box.
+    pub fn new(width: f64, height: f64, strength: f64) -> AspectConstraint {
+        AspectConstraint {
+            ratio: width / height,
+            strength,
+        }
+    }
+
+    /// Returns the force squeezing a node at `position` when the
+    /// path has the bounding box between `min` and `max`.
+    pub fn force(&self, position: &Point2<f64>, min: &Point2<f64>, max: &Point2<f64>) -> Vector2<f64> {
+        let (width, height): (f64, f64) = (max.x - min.x, max.y - min.y);
+        if width <= 0.0 || height <= 0.0 || self.ratio <= 0.0 {
+            return Vector2::default();
+        }
+
+        let ratio: f64 = width / height;
+        // Squeezes the axis that is too long, relative to how much too long it is.
+        let (axis, excess): (usize, f64) = if ratio > self.ratio {
+            (0, ratio / self.ratio - 1.0)
+        } else {
+            (1, self.ratio / ratio - 1.0)
+        };
+
+        let center: f64 = (min[axis] + max[axis]) / 2.0;
+        let half: f64 = (max[axis] - min[axis]) / 2.0;
+        let mut force: Vector2<f64> = Vector2::default();
+        force[axis] = -(position[axis] - center) / half * excess.min(1.0) * self.strength;
+        force
+    }
+}
+
+/// What happens to a node that leaves the rectangle of a [`Boundary`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryBehavior {
+    /// Puts the node back on the edge and stops it from moving further out.
+    Clamp,
+    /// Mirrors the node back into the rectangle and reverses its velocity,
+    /// keeping `restitution` of it, between 0 and 1.
+    Bounce { restitution: f64 },
+    /// Moves the node to the opposite edge. Its edges then span the whole rectangle.
+    Wrap,
+    /// Moves the node to the opposite edge like [`BoundaryBehavior::Wrap`], but the rectangle is
+    /// treated as a torus: nodes feel the nodes close to them across the edges and edges across
+    /// the edges are as short as they are across them, so the path grows into a seamless tile.
+    /// See [`crate::DifferentialGrowth::get_unwrapped_points()`] to draw it.
+    ///
+    /// Preventing self intersections and the other shapes acting on the path, like guide paths
+    /// and obstacles, don't take the edges across into account.
+    Periodic,
+    /// Removes the node from the path.
+    Delete,
+    /// Pushes nodes within `falloff` of an edge back in instead of moving them, with a force
+    /// growing smoothly from 0 to `strength` at the edge and beyond. Nodes pile up against the
+    /// edge more loosely than when clamped and can still cross it when other forces are stronger.
+    Soft { falloff: f64, strength: f64 },
+}
+
+/// A rectangular world nodes can't leave, applied after the nodes have moved.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Boundary, BoundaryBehavior};
+/// use nalgebra::Point2;
+///
+/// let boundary = Boundary::new(Point2::new(-20.0, -20.0), Point2::new(20.0, 20.0), BoundaryBehavior::Clamp);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_boundary(boundary);
+///
+/// for _ in 0..100 {
+///     differential_growth.tick();
+/// }
+/// assert!(differential_growth.nodes().iter().all(|node| boundary.contains(&node.position)));
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Boundary {
+    /// The corner with the smallest coordinates.
+    pub min: Point2<f64>,
+    /// The corner with the largest coordinates.
+    pub max: Point2<f64>,
+    pub behavior: BoundaryBehavior,
+}
+
+impl Boundary {
+    /// Returns a Boundary spanning the rectangle between the corners `min` and `max`.
+    pub fn new(min: Point2<f64>, max: Point2<f64>, behavior: BoundaryBehavior) -> Boundary {
+        Boundary { min, max, behavior }
+    }
+
+    /// Returns whether `position` lies within the rectangle, including its edges.
+    pub fn contains(&self, position: &Point2<f64>) -> bool {
+        (0..2).all(|axis| self.min[axis] <= position[axis] && position[axis] <= self.max[axis])
+    }
+
+    /// Brings a node that left the rectangle back in, returning `false`
+    /// when the node should be removed instead.
+    pub fn apply(&self, node: &mut Node) -> bool {
+        if self.contains(&node.position) {
+            return true;
+        }
+
+        for axis in 0..2 {
+            let (min, max): (f64, f64) = (self.min[axis], self.max[axis]);
+            let position: f64 = node.position[axis];
+            if min <= position && position <= max {
+                continue;
+            }
+
+            match self.behavior {
+                BoundaryBehavior::Clamp => {
+                    node.position[axis] = position.clamp(min, max);
+                    node.velocity[axis] = 0.0;
+                }
+                BoundaryBehavior::Bounce { restitution } => {
+                    let edge: f64 = if position < min { min } else { max };
+                    // A node moving faster than the rectangle is wide still ends up inside.
+                    node.position[axis] = (edge + (edge - position) * restitution).clamp(min, max);
+                    node.velocity[axis] = -node.velocity[axis] * restitution;
+                }
+                BoundaryBehavior::Wrap | BoundaryBehavior::Periodic => {
+                    if max > min {
+                        node.position[axis] = min + (position - min).rem_euclid(max - min);
+                    } else {
+                        node.position[axis] = min;
+                    }
+                }
+                BoundaryBehavior::Delete => return false,
+                // The force of the boundary brings the node back.
+                BoundaryBehavior::Soft { .. } => {}
+            }
+        }
+
+        true
+    }
+
+    /// Returns the force pushing a node at `position` back in, only a [`BoundaryBehavior::Soft`]
+    /// boundary pushes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{Boundary, BoundaryBehavior};
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let behavior = BoundaryBehavior::Soft { falloff: 10.0, strength: 0.5 };
+    /// let boundary = Boundary::new(Point2::new(-20.0, -20.0), Point2::new(20.0, 20.0), behavior);
+    ///
+    /// assert_eq!(boundary.force(&Point2::new(0.0, 0.0)), Vector2::new(0.0, 0.0));
+    /// assert_eq!(boundary.force(&Point2::new(15.0, 0.0)), Vector2::new(-0.25, 0.0));
+    /// assert_eq!(boundary.force(&Point2::new(0.0, -25.0)), Vector2::new(0.0, 0.5));
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_boundary(boundary);
+    /// differential_growth.tick_n(300);
+    ///
+    /// let loose = Boundary::new(Point2::new(-25.0, -25.0), Point2::new(25.0, 25.0), behavior);
+    /// assert!(differential_growth.nodes().iter().all(|node| loose.contains(&node.position)));
+    /// ```
+    ///
+    pub fn force(&self, position: &Point2<f64>) -> Vector2<f64> {
+        let (falloff, strength): (f64, f64) = match self.behavior {
+            BoundaryBehavior::Soft { falloff, strength } => (falloff, strength),
+            _ => return Vector2::default(),
+        };
+
+        let mut force: Vector2<f64> = Vector2::default();
+        for axis in 0..2 {
+            // How far into the falloff the node is from either edge, 1 at the edge and beyond.
+            let depth = |distance_to_edge: f64| match falloff > 0.0 {
+                true => (1.0 - distance_to_edge / falloff).clamp(0.0, 1.0),
+                false if distance_to_edge <= 0.0 => 1.0,
+                false => 0.0,
+            };
+            // Smoothstep, so the force starts and levels off without a jolt.
+            let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+            force[axis] = strength
+                * (smooth(depth(position[axis] - self.min[axis])) - smooth(depth(self.max[axis] - position[axis])));
+        }
+        force
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// Error returned when reading points from CSV fails.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The reader failed.
+    Io(io::Error),
+    /// A line isn't made up of 2 or 4 numbers, `line` starts counting at 1.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(error) => write!(f, "failed to read csv: {}", error),
+            CsvError::Parse { line, message } => write!(f, "invalid csv on line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::Io(error) => Some(error),
+            CsvError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(error: io::Error) -> Self {
+        CsvError::Io(error)
+    }
+}
+
+impl DifferentialGrowth {
+    /// Returns a DifferentialGrowth instance with the default parameters of
+    /// [`DifferentialGrowth::builder()`], starting from points read as CSV.
+    ///
+    /// Every line holds a point as `x,y`, or `x,y,vx,vy` to also restore its velocity.
+    /// Empty lines are skipped, as is the first line that isn't empty when none of its
+    /// fields are numbers, the header. Values that aren't finite are an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let csv: &str = "x,y\n0,0\n10,0\n0,10\n";
+    /// let differential_growth = differential_growth::DifferentialGrowth::from_csv(csv.as_bytes()).unwrap();
+    /// assert_eq!(differential_growth.get_points()[1], Point2::new(10.0, 0.0));
+    /// ```
+    ///
+    pub fn from_csv(reader: impl Read) -> Result<DifferentialGrowth, CsvError> {
+        let mut positions: Vec<Point2<f64>> = Vec::new();
+        let mut velocities: Vec<Vector2<f64>> = Vec::new();
+
+        let mut is_first: bool = true;
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line: String = line?;
+            let line: &str = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let is_header: bool = is_first && fields.iter().all(|field| field.parse::<f64>().is_err());
+            is_first = false;
+            if is_header {
+                continue;
+            }
+
+            let mut values: Vec<f64> = Vec::with_capacity(fields.len());
+            for field in fields {
+                let message: Option<String> = match field.parse::<f64>() {
+                    Ok(value) if value.is_finite() => {
+                        values.push(value);
+                        None
+                    }
+                    Ok(_) => Some(format!("{:?} isn't a finite number", field)),
+                    Err(error) => Some(format!("{:?}: {}", field, error)),
+                };
+                if let Some(message) = message {
+                    return Err(CsvError::Parse {
+                        line: index + 1,
+                        message,
+                    });
+                }
+            }
+
+            match values[..] {
+                [x, y] => {
+                    positions.push(Point2::new(x, y));
+                    velocities.push(Vector2::default());
+                }
+                [x, y, vx, vy] => {
+                    positions.push(Point2::new(x, y));
+                    velocities.push(Vector2::new(vx, vy));
+                }
+                _ => {
+                    return Err(CsvError::Parse {
+                        line: index + 1,
+                        message: format!("expected 2 or 4 values, found {}", values.len()),
+                    })
+                }
+            }
+        }
+
+        let mut differential_growth: DifferentialGrowth = DifferentialGrowth::builder().points(positions).build();
+        for (node, velocity) in differential_growth.nodes.iter_mut().zip(velocities) {
+            node.velocity = velocity;
+        }
+
+        Ok(differential_growth)
+    }
+
+    /// Writes the current nodes as CSV with a header line, readable by
+    /// [`DifferentialGrowth::from_csv()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the CSV is written to.
+    /// * `include_velocities` - Whether to write `x,y,vx,vy` instead of `x,y`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick();
+    ///
+    /// let mut csv: Vec<u8> = Vec::new();
+    /// differential_growth.write_csv(&mut csv, true).unwrap();
+    ///
+    /// let restored = differential_growth::DifferentialGrowth::from_csv(csv.as_slice()).unwrap();
+    /// assert_eq!(restored.get_points(), differential_growth.get_points());
+    /// assert_eq!(restored.nodes()[0].velocity, differential_growth.nodes()[0].velocity);
+    /// ```
+    ///
+    pub fn write_csv(&self, mut writer: impl Write, include_velocities: bool) -> io::Result<()> {
+        if include_velocities {
+            writeln!(writer, "x,y,vx,vy")?;
+            for node in &self.nodes {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    node.position.x, node.position.y, node.velocity.x, node.velocity.y
+                )?;
+            }
+        } else {
+            writeln!(writer, "x,y")?;
+            for node in &self.nodes {
+                writeln!(writer, "{},{}", node.position.x, node.position.y)?;
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use super::CsvError;
+    use crate::DifferentialGrowth;
+
+    fn parse_error_line(csv: &str) -> Option<usize> {
+        match DifferentialGrowth::from_csv(csv.as_bytes()) {
+            Err(CsvError::Parse { line, .. }) => Some(line),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn header_after_empty_lines() {
+        let differential_growth = DifferentialGrowth::from_csv("\n\nx,y\n0,0\n10,0\n0,10\n".as_bytes()).unwrap();
+        assert_eq!(differential_growth.get_points()[0], Point2::new(0.0, 0.0));
+        assert_eq!(differential_growth.nodes().len(), 3);
+    }
+
+    #[test]
+    fn malformed_first_line_is_no_header() {
+        assert_eq!(parse_error_line("1,abc\n0,0\n10,0\n0,10\n"), Some(1));
+        assert_eq!(parse_error_line("x,y\nx,y\n0,0\n"), Some(2));
+    }
+
+    #[test]
+    fn values_must_be_finite() {
+        assert_eq!(parse_error_line("0,0\nNaN,0\n0,10\n"), Some(2));
+        assert_eq!(parse_error_line("x,y\n0,0\n10,inf\n"), Some(3));
+        assert_eq!(parse_error_line("0,0,-infinity,0\n"), Some(1));
+    }
+}
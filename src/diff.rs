@@ -0,0 +1,103 @@
+use core::ops::AddAssign;
+
+use nalgebra::distance;
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// How far two simulations of the same path have diverged, see [`DifferentialGrowth::diff()`].
+///
+/// Nodes are compared by index, so when the node counts differ only the nodes
+/// both simulations have are compared.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// The amount of nodes of the simulation `diff()` was called on.
+    pub node_count: usize,
+    /// The amount of nodes of the other simulation.
+    pub other_node_count: usize,
+    /// The amount of ticks the simulation `diff()` was called on has executed.
+    pub iteration: usize,
+    /// The amount of ticks the other simulation has executed.
+    pub other_iteration: usize,
+    /// The index of the first node that is in a different position, if any.
+    pub first_differing_node: Option<usize>,
+    /// The largest distance between the positions of nodes with the same index.
+    pub max_position_delta: f64,
+    /// The average distance between the positions of nodes with the same index.
+    pub mean_position_delta: f64,
+    /// The largest difference between the velocities of nodes with the same index.
+    pub max_velocity_delta: f64,
+}
+
+impl StateDiff {
+    /// Returns whether both simulations have the same nodes at exactly the same positions.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.node_count == self.other_node_count && self.first_differing_node.is_none()
+    }
+
+    /// Returns whether both simulations have the same amount of nodes
+    /// and no node moved further than `tolerance` from its counterpart.
+    #[must_use]
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.node_count == self.other_node_count && self.max_position_delta <= tolerance
+    }
+}
+
+impl DifferentialGrowth {
+    /// Compares the nodes of this simulation with those of another simulation of the same path,
+    /// to validate an alternative backend or parameter tweak against a reference run.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut reference = differential_growth::DifferentialGrowth::new(starting_points.clone(), 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let mut candidate = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// for _ in 0..10 {
+    ///     reference.tick();
+    ///     candidate.tick();
+    /// }
+    /// assert!(reference.diff(&candidate).is_identical());
+    ///
+    /// candidate.with_nodes_mut(|nodes| nodes[3].position.x += 0.5).unwrap();
+    /// let diff = reference.diff(&candidate);
+    /// assert_eq!(diff.first_differing_node, Some(3));
+    /// assert!(diff.is_within(0.5) && !diff.is_within(0.1));
+    /// ```
+    ///
+    #[must_use]
+    pub fn diff(&self, other: &DifferentialGrowth) -> StateDiff {
+        let mut state_diff: StateDiff = StateDiff {
+            node_count: self.nodes.len(),
+            other_node_count: other.nodes.len(),
+            iteration: self.iteration(),
+            other_iteration: other.iteration(),
+            ..Default::default()
+        };
+
+        let mut total_position_delta: f64 = 0.0;
+        let mut compared: usize = 0;
+
+        for (i, (node, other_node)) in self.nodes.iter().zip(&other.nodes).enumerate() {
+            if state_diff.first_differing_node.is_none() && node.position != other_node.position {
+                state_diff.first_differing_node = Some(i);
+            }
+
+            let position_delta: f64 = distance(&node.position, &other_node.position);
+            state_diff.max_position_delta = state_diff.max_position_delta.max(position_delta);
+            state_diff.max_velocity_delta = state_diff
+                .max_velocity_delta
+                .max((node.velocity - other_node.velocity).norm());
+            total_position_delta.add_assign(position_delta);
+            compared.add_assign(1);
+        }
+
+        if compared > 0 {
+            state_diff.mean_position_delta = total_position_delta / compared as f64;
+        }
+
+        state_diff
+    }
+}
@@ -1,14 +1,96 @@
-use std::ops::{MulAssign, AddAssign, SubAssign, DivAssign, Add, Div, Sub};
+use alloc::sync::Arc;
+use core::ops::{MulAssign, AddAssign, SubAssign};
 
-use kd_tree::KdTree2;
 use nalgebra::{Point2, Vector2, distance};
 
-use crate::node::Node;
+use crate::prelude::*;
+#[cfg(feature = "profiling")]
+use crate::profile::TickProfile;
+#[cfg(feature = "rhai")]
+use crate::scripting::ScriptRules;
+use crate::{
+    brightness::BrightnessMap,
+    builder::DifferentialGrowthBuilder,
+    changes::{self, ChangedRange, NodeEvent},
+    cohesion::CohesionTarget,
+    constraints::{AspectConstraint, Boundary, BoundaryBehavior, BrownianNoise, GuidePath, Obstacle},
+    edge_separation::EdgeIndex,
+    endpoints::EndpointCondition,
+    explain::{NodeConstraint, NodeForces},
+    force::Force,
+    geometry,
+    insertion::{InsertionStrategy, Midpoint},
+    integrator::Integrator,
+    leader::Trajectory,
+    lod::LevelOfDetail,
+    node::Node,
+    node_data::{NodeData, Source},
+    node_params::NodeParams,
+    observer::{AppliedForces, Observer},
+    oscillation::Oscillation,
+    overlay::{self, ParamOverlay, RadialGradient},
+    param_field::ParamField,
+    parallel,
+    params::{ParamSchedule, ParamSet},
+    periodic::Period,
+    profile::Phase,
+    recording::Recording,
+    region::Region,
+    renormalization::Renormalization,
+    rng::Rng,
+    scratch::Scratch,
+    self_contact::SelfAvoidance,
+    separation::SeparationKernel,
+    soa::NodeArrays,
+    spatial::{DefaultIndex, NeighborSearch, RebuildPolicy, SpatialIndex},
+    topology::Topology,
+    trail::TrailMap,
+    velocity::InitialVelocity,
+    watchdog::{Snapshot, Watchdog, WatchdogEvent, WatchdogState},
+};
+
+/// Nodes only slide to shorten an edge while the edge on their other side stays
+/// below this fraction of the maximum edge length. Sliding all the way up to the
+/// maximum leaves every edge at the maximum and stalls growth.
+const MAX_REDISTRIBUTED_EDGE: f64 = 0.75;
 
 /// The differential growth algorithm.
+///
+/// With the `serde` feature the full simulation state can be serialized to
+/// checkpoint a long running growth and resume it later exactly where it left off.
+/// The insertion strategy, spatial index, leader and script are not part of
+/// the state: they fall back to their defaults and need to be set again after deserializing.
+/// Resuming exactly requires a format that round-trips floats, like `serde_json`
+/// with its `float_roundtrip` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_growth_probability(0.5);
+/// for _ in 0..10 {
+///     differential_growth.tick();
+/// }
+///
+/// let checkpoint: String = serde_json::to_string(&differential_growth).unwrap();
+/// let mut resumed: differential_growth::DifferentialGrowth = serde_json::from_str(&checkpoint).unwrap();
+///
+/// for _ in 0..10 {
+///     differential_growth.tick();
+///     resumed.tick();
+/// }
+/// assert_eq!(differential_growth.get_points(), resumed.get_points());
+/// # }
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DifferentialGrowth {
-    /// A Vec of Node objects.
-    pub nodes: Vec<Node>,
+    /// A Vec of Node objects, read them with [`DifferentialGrowth::nodes()`]
+    /// and change them with [`DifferentialGrowth::with_nodes_mut()`].
+    pub(crate) nodes: Vec<Node>,
     /// The maximum force nodes can exert on eachother.
     pub max_force: f64,
     ///  The maximum magnitude of a node's velocity.
@@ -16,13 +98,295 @@ pub struct DifferentialGrowth {
     /// The desired separation between nodes.
     pub desired_separation: f64,
     /// The ratio between separation and cohesion forces.
+    #[deprecated(note = "misspelled, use `separation_cohesion_ratio()` and `set_separation_cohesion_ratio()` instead")]
     pub separation_cohesion_ration: f64,
     /// The maximum length between two connected nodes.
     pub max_edge_length: f64,
+    /// Whether the path is closed or open.
+    pub topology: Topology,
+    /// The probability, between 0 and 1, that an edge longer than
+    /// the maximum edge length is split during a tick.
+    pub growth_probability: f64,
+    /// Connected nodes closer than this are merged into one, if set.
+    pub min_edge_length: Option<f64>,
+    /// Nodes closer than this to an earlier node are removed, if set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub degenerate_epsilon: Option<f64>,
+    /// The amount of nodes after which no new nodes are inserted, if any.
+    pub max_nodes: Option<usize>,
+    /// The amount of ticks over which forces are ramped up from 0 to full strength.
+    pub warmup_ticks: usize,
+    /// The fraction of their velocity nodes lose every tick, between 0 and 1.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub damping: f64,
+    /// The weight of the force steering nodes towards the average velocity
+    /// of the nodes around them, 0 to leave it out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alignment: f64,
+    /// The gain of the force along the normal proportional to the excess curvature,
+    /// positive to fold bends further and negative to smooth them out, 0 to leave it out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub curvature_gain: f64,
+    /// Whether a node next to an over-length edge is first slid along the path
+    /// to shorten it, only inserting a new node when that isn't possible.
+    pub redistribution: bool,
+    /// Whether moves that make the path cross itself are undone.
+    pub prevent_self_intersections: bool,
+    /// How the forces are integrated into the movement of the nodes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub integrator: Integrator,
+    /// The point every node is pulled towards by the cohesion force.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cohesion_target: CohesionTarget,
+    /// The stiffness of the spring pulling every node towards its cohesion target, if set.
+    /// Otherwise nodes steer towards it at their maximum speed, limited by their maximum force.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cohesion_strength: Option<f64>,
+    /// How strongly neighbors push a node away depending on their distance.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub separation_kernel: SeparationKernel,
+    /// Whether separation also pushes nodes away from the edges around them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub edge_separation: bool,
+    /// How the endpoints of an open path behave.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub endpoint_condition: EndpointCondition,
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_insertion_strategy"))]
+    pub(crate) insertion_strategy: Box<dyn InsertionStrategy>,
+    overlays: Vec<ParamOverlay>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    param_schedule: Option<ParamSchedule>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) param_field: Option<ParamField>,
+    // The parameters of the field at every node, sampled at the start of the tick.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) field_params: Vec<ParamSet>,
+    guide_path: Option<GuidePath>,
+    boundary: Option<Boundary>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    obstacles: Vec<Obstacle>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) growth_mask: Option<Vec<Region>>,
+    level_of_detail: Option<LevelOfDetail>,
+    radial_gradient: Option<RadialGradient>,
+    trail_map: Option<TrailMap>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    brightness_map: Option<BrightnessMap>,
+    aspect_constraint: Option<AspectConstraint>,
+    brownian_noise: Option<BrownianNoise>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    self_avoidance: Option<SelfAvoidance>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) renormalization: Option<Renormalization>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) oscillation: Option<Oscillation>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) origin: Vector2<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    constant_force: Vector2<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deterministic: bool,
+    // The forces applied from outside for the next tick, one per node or none at all.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) external_forces: Vec<Vector2<f64>>,
+    // The separation sums of every node for the next force evaluation, computed elsewhere
+    // like on the GPU. Only used when the amount of nodes still matches.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) separation_sums: Option<Vec<Vector2<f64>>>,
+    // Whether every node is frozen in place, or empty when none are.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) frozen: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    leader: Option<Box<dyn Trajectory>>,
+    pub(crate) rng: Rng,
+    pub(crate) neighbor_search: NeighborSearch,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    arrays: NodeArrays,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scratch: Scratch,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) watchdog: Option<WatchdogState>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) recording: Option<Recording>,
+    // The ids and force magnitudes of the nodes for the next frame of the recording.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) force_magnitudes: Vec<(usize, f32)>,
+    iteration: usize,
+    // The id of the next node the simulation creates.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) next_node_id: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) changes: Vec<ChangedRange>,
+    // The amount of nodes appended by `extend_path()` since the last tick.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    appended: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) node_data: Option<Box<dyn NodeData>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) node_params: Option<Vec<NodeParams>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    node_events: Option<Vec<NodeEvent>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observers: Vec<Box<dyn Observer>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    forces: Vec<Arc<dyn Force>>,
+    #[cfg(feature = "rhai")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    script: Option<ScriptRules>,
+    #[cfg(feature = "profiling")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) profile: TickProfile,
+}
+
+impl Default for DifferentialGrowth {
+    /// Returns a DifferentialGrowth without nodes, using the defaults of [`DifferentialGrowth::builder()`].
+    fn default() -> Self {
+        DifferentialGrowth::builder().build()
+    }
+}
+
+impl Clone for DifferentialGrowth {
+    /// Returns a copy of the simulation that continues exactly like this one.
+    ///
+    /// Forces added with [`DifferentialGrowth::add_force()`] and the field set with
+    /// [`DifferentialGrowth::set_param_field()`] are shared with the copy.
+    /// Like when serializing, the insertion strategy, spatial index and leader are not
+    /// copied and fall back to their defaults, and the copy starts without observers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_growth_probability(0.5);
+    /// differential_growth.tick_n(10);
+    ///
+    /// let mut copy = differential_growth.clone();
+    /// assert!(copy == differential_growth);
+    ///
+    /// differential_growth.tick_n(10);
+    /// assert!(copy != differential_growth);
+    /// copy.tick_n(10);
+    /// assert!(copy == differential_growth);
+    /// ```
+    ///
+    #[allow(deprecated)]
+    fn clone(&self) -> Self {
+        let mut neighbor_search: NeighborSearch = NeighborSearch::new(self.neighbor_search.policy, Box::new(DefaultIndex::default()));
+        neighbor_search.set_period(self.period());
+
+        DifferentialGrowth {
+            nodes: self.nodes.clone(),
+            max_force: self.max_force,
+            max_speed: self.max_speed,
+            desired_separation: self.desired_separation,
+            separation_cohesion_ration: self.separation_cohesion_ration,
+            max_edge_length: self.max_edge_length,
+            topology: self.topology,
+            growth_probability: self.growth_probability,
+            min_edge_length: self.min_edge_length,
+            degenerate_epsilon: self.degenerate_epsilon,
+            max_nodes: self.max_nodes,
+            warmup_ticks: self.warmup_ticks,
+            damping: self.damping,
+            alignment: self.alignment,
+            curvature_gain: self.curvature_gain,
+            redistribution: self.redistribution,
+            prevent_self_intersections: self.prevent_self_intersections,
+            integrator: self.integrator,
+            cohesion_target: self.cohesion_target,
+            cohesion_strength: self.cohesion_strength,
+            separation_kernel: self.separation_kernel,
+            edge_separation: self.edge_separation,
+            endpoint_condition: self.endpoint_condition,
+            insertion_strategy: Box::new(Midpoint),
+            overlays: self.overlays.clone(),
+            param_schedule: self.param_schedule.clone(),
+            param_field: self.param_field.clone(),
+            field_params: self.field_params.clone(),
+            guide_path: self.guide_path.clone(),
+            boundary: self.boundary,
+            obstacles: self.obstacles.clone(),
+            growth_mask: self.growth_mask.clone(),
+            level_of_detail: self.level_of_detail,
+            radial_gradient: self.radial_gradient,
+            trail_map: self.trail_map.clone(),
+            brightness_map: self.brightness_map.clone(),
+            aspect_constraint: self.aspect_constraint,
+            brownian_noise: self.brownian_noise.clone(),
+            self_avoidance: self.self_avoidance,
+            renormalization: self.renormalization,
+            oscillation: self.oscillation,
+            origin: self.origin,
+            constant_force: self.constant_force,
+            deterministic: self.deterministic,
+            external_forces: self.external_forces.clone(),
+            separation_sums: self.separation_sums.clone(),
+            frozen: self.frozen.clone(),
+            leader: None,
+            rng: self.rng.clone(),
+            neighbor_search,
+            arrays: self.arrays.clone(),
+            scratch: Scratch::default(),
+            watchdog: self.watchdog.clone(),
+            recording: self.recording.clone(),
+            force_magnitudes: self.force_magnitudes.clone(),
+            iteration: self.iteration,
+            next_node_id: self.next_node_id,
+            changes: self.changes.clone(),
+            appended: self.appended,
+            node_data: self.node_data.clone(),
+            node_params: self.node_params.clone(),
+            node_events: self.node_events.clone(),
+            observers: Vec::new(),
+            forces: self.forces.clone(),
+            #[cfg(feature = "rhai")]
+            script: self.script.clone(),
+            #[cfg(feature = "profiling")]
+            profile: self.profile,
+        }
+    }
+}
+
+impl PartialEq for DifferentialGrowth {
+    /// Returns whether both simulations have the same nodes, parameters and settings.
+    /// Their insertion strategies, spatial indices, leaders, observers, forces, fields,
+    /// scripts and node data are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.settings_eq(other)
+    }
 }
 
 impl DifferentialGrowth {
+    /// Returns an open path grown from a hand-drawn stroke, with the defaults of the builder.
+    /// The stroke is resampled to points `target_spacing` apart first, see
+    /// [`DifferentialGrowthBuilder::stroke()`] to change the parameters or the topology.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let stroke = vec![Point2::new(0.0, 0.0), Point2::new(0.2, 0.1), Point2::new(30.0, 0.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::from_stroke(stroke, 3.0);
+    /// assert_eq!(differential_growth.nodes().len(), 11);
+    ///
+    /// differential_growth.tick_n(50);
+    /// assert!(differential_growth.nodes().iter().all(|node| node.position.x.is_finite()));
+    /// ```
+    ///
+    pub fn from_stroke(points: Vec<Point2<f64>>, target_spacing: f64) -> DifferentialGrowth {
+        DifferentialGrowth::builder()
+            .stroke(points, target_spacing)
+            .topology(Topology::Open)
+            .build()
+    }
+
     /// Returns a DifferentialGrowth instance with the given parameters.
+    /// See [`DifferentialGrowth::try_new()`] to have them checked first.
+    ///
+    /// One or two starting points are enough, the first tick adds the nodes
+    /// a path needs, see [`DifferentialGrowth::from_seed()`].
     /// 
     /// # Arguments
     /// 
@@ -41,6 +405,7 @@ impl DifferentialGrowth {
     /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
     /// ```
     /// 
+    #[allow(deprecated)]
     pub fn new(
         input_points: Vec<Point2<f64>>,
         max_force: f64,
@@ -53,8 +418,13 @@ impl DifferentialGrowth {
         let nodes: Vec<Node> =
             input_points
                 .into_iter()
-                .map(|point: Point2<f64>| Node::new(point, max_speed, max_force))
+                .enumerate()
+                .map(|(id, point): (usize, Point2<f64>)| Node {
+                    id,
+                    ..Node::new(point, max_speed, max_force)
+                })
                 .collect();
+        let next_node_id: usize = nodes.len();
 
         DifferentialGrowth {
             nodes,
@@ -63,10 +433,91 @@ impl DifferentialGrowth {
             desired_separation,
             separation_cohesion_ration: separation_cohesion_ratio,
             max_edge_length: max_edge_len,
+            topology: Topology::Closed,
+            growth_probability: 1.0,
+            min_edge_length: None,
+            degenerate_epsilon: None,
+            max_nodes: None,
+            warmup_ticks: 0,
+            damping: 0.0,
+            alignment: 0.0,
+            curvature_gain: 0.0,
+            redistribution: false,
+            prevent_self_intersections: false,
+            integrator: Integrator::Euler,
+            cohesion_target: CohesionTarget::Midpoint,
+            cohesion_strength: None,
+            separation_kernel: SeparationKernel::Inverse,
+            edge_separation: false,
+            endpoint_condition: EndpointCondition::Free,
+            insertion_strategy: Box::new(Midpoint),
+            overlays: Vec::new(),
+            param_schedule: None,
+            param_field: None,
+            field_params: Vec::new(),
+            guide_path: None,
+            boundary: None,
+            obstacles: Vec::new(),
+            growth_mask: None,
+            level_of_detail: None,
+            radial_gradient: None,
+            trail_map: None,
+            brightness_map: None,
+            aspect_constraint: None,
+            brownian_noise: None,
+            self_avoidance: None,
+            renormalization: None,
+            oscillation: None,
+            origin: Vector2::default(),
+            constant_force: Vector2::default(),
+            deterministic: false,
+            external_forces: Vec::new(),
+            separation_sums: None,
+            frozen: Vec::new(),
+            leader: None,
+            rng: Rng::new(0),
+            neighbor_search: NeighborSearch::new(RebuildPolicy::default(), Box::new(DefaultIndex::default())),
+            arrays: NodeArrays::default(),
+            scratch: Scratch::default(),
+            watchdog: None,
+            recording: None,
+            force_magnitudes: Vec::new(),
+            iteration: 0,
+            next_node_id,
+            changes: Vec::new(),
+            appended: 0,
+            node_data: None,
+            node_params: None,
+            node_events: None,
+            observers: Vec::new(),
+            forces: Vec::new(),
+            #[cfg(feature = "rhai")]
+            script: None,
+            #[cfg(feature = "profiling")]
+            profile: TickProfile::default(),
         }
     }
 
+    /// Returns a [`DifferentialGrowthBuilder`] to configure a DifferentialGrowth
+    /// instance using named parameters and defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    ///
+    /// let differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .max_edge_length(5.0)
+    ///     .build();
+    /// ```
+    ///
+    pub fn builder() -> DifferentialGrowthBuilder {
+        DifferentialGrowthBuilder::new()
+    }
+
     /// Advanced the algorithm by 1 iteration.
+    /// Every tick is a time step of 1, see [`crate::Units`] to relate ticks to seconds.
     /// 
     /// # Examples
     /// 
@@ -77,8 +528,194 @@ impl DifferentialGrowth {
     /// ```
     /// 
     pub fn tick(&mut self) {
-        self.differentiate();
-        self.growth();
+        self.tick_dt(1.0);
+    }
+
+    /// Advances the algorithm by 1 iteration covering a time step of `dt` ticks, so the
+    /// speed of the simulation doesn't depend on the frame rate of an interactive app.
+    ///
+    /// Nodes move and edges split as if `dt` ticks passed. Everything counted in
+    /// iterations, like the iteration count, warmup, overlay durations and the leader,
+    /// still advances by 1.
+    ///
+    /// The look of the growth depends on the step size: large steps are less stable and
+    /// small steps let a symmetric seed settle before it starts folding. To get the exact
+    /// same result at any frame rate, tick a varying amount of fixed steps per frame instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The length of the time step in ticks, for example the seconds since the
+    ///   last frame times [`crate::Units::ticks_per_second`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // A 30 fps app advances 2 ticks of a simulation tuned for 60 ticks per second every frame.
+    /// let frame_seconds: f64 = 1.0 / 30.0;
+    /// differential_growth.tick_dt(frame_seconds * 60.0);
+    /// ```
+    ///
+    pub fn tick_dt(&mut self, dt: f64) {
+        self.profiled_tick(|differential_growth| differential_growth.step(dt));
+    }
+
+    fn step(&mut self, dt: f64) {
+        self.neighbor_search.next_tick();
+        self.bootstrap();
+        self.apply_param_schedule();
+        #[cfg(feature = "rhai")]
+        self.apply_script_params();
+        self.sample_param_field();
+
+        // Appended nodes weren't part of the positions after the last tick.
+        let mut before: Vec<Point2<f64>> = core::mem::take(&mut self.scratch.before);
+        self.get_points_into(&mut before);
+        before.truncate(self.nodes.len().saturating_sub(self.appended));
+        self.appended = 0;
+
+        self.move_leader();
+
+        let needs_previous_positions: bool = self.prevent_self_intersections
+            || self
+                .watchdog
+                .as_ref()
+                .is_some_and(|watchdog| watchdog.config.max_displacement.is_some());
+        // Left empty when nothing looks back at them.
+        let mut previous_positions: Vec<Point2<f64>> = core::mem::take(&mut self.scratch.previous_positions);
+        previous_positions.clear();
+        if needs_previous_positions {
+            self.get_points_into(&mut previous_positions);
+        }
+
+        self.notify_observers(|observer, differential_growth| observer.before_differentiate(differential_growth));
+        if let Some(forces) = self.differentiate(dt) {
+            self.notify_observers(|observer, differential_growth| {
+                observer.after_differentiate(differential_growth, &forces)
+            });
+        }
+
+        if self.recover_from_blow_up(&previous_positions) {
+            self.changes = changes::changed_ranges(&before, &self.nodes, &[]);
+            self.scratch.before = before;
+            self.scratch.previous_positions = previous_positions;
+            return;
+        }
+
+        if self.prevent_self_intersections {
+            self.undo_crossing_moves(&previous_positions);
+        }
+        self.scratch.previous_positions = previous_positions;
+
+        self.apply_boundary();
+        self.apply_obstacles();
+
+        self.notify_observers(|observer, differential_growth| observer.before_growth(differential_growth));
+        let mut inserted: Vec<bool> = self.profiled(Phase::Growth, |differential_growth| differential_growth.growth(dt));
+        self.notify_observers(|observer, differential_growth| observer.after_growth(differential_growth, &inserted));
+        self.profiled(Phase::Growth, |differential_growth| differential_growth.prune(&mut inserted));
+        self.clean_up_degenerate_nodes(&mut inserted);
+        self.renormalize_if_due();
+        self.changes = changes::changed_ranges(&before, &self.nodes, &inserted);
+        self.scratch.before = before;
+        if let Some(trail_map) = self.trail_map.as_mut() {
+            trail_map.update(&self.nodes, dt);
+        }
+        self.advance_overlays();
+        self.iteration.add_assign(1);
+        self.take_snapshot_if_due();
+        self.record_if_due();
+    }
+
+    /// Advances the simulation by `n` iterations, for offline renders that only need the result.
+    ///
+    /// The buffers of a tick are reused by the next one and the neighbor index is only rebuilt
+    /// when nodes were inserted or removed or the [`RebuildPolicy`] calls for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// differential_growth.tick_n(25);
+    /// assert_eq!(differential_growth.iteration(), 25);
+    /// ```
+    ///
+    pub fn tick_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    /// Ticks until `done` returns `true` or an observer asks to stop, see [`DifferentialGrowth::should_stop()`].
+    /// Both are checked before every tick. Returns the amount of ticks that were executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `done` - Decides whether the simulation is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let ticks = differential_growth.run_until(|differential_growth| differential_growth.nodes().len() >= 100);
+    /// assert!(differential_growth.nodes().len() >= 100);
+    /// assert_eq!(ticks, differential_growth.iteration());
+    /// ```
+    ///
+    pub fn run_until(&mut self, mut done: impl FnMut(&DifferentialGrowth) -> bool) -> usize {
+        let mut ticks: usize = 0;
+
+        while !done(self) && !self.should_stop() {
+            self.tick();
+            ticks.add_assign(1);
+        }
+
+        ticks
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    #[must_use]
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Returns which contiguous index ranges of [`DifferentialGrowth::get_points()`]
+    /// changed during the last tick, so renderers can patch their GPU buffers
+    /// instead of uploading every position each frame.
+    ///
+    /// Slots outside the ranges still hold the position they had before the tick.
+    /// The buffer is truncated when nodes were removed, compare its length with the
+    /// amount of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::ChangeKind;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mut buffer = differential_growth.get_points();
+    /// differential_growth.tick();
+    ///
+    /// let points = differential_growth.get_points();
+    /// buffer.resize(points.len(), Default::default());
+    /// for changed in differential_growth.changed_ranges() {
+    ///     buffer[changed.range.clone()].copy_from_slice(&points[changed.range.clone()]);
+    /// }
+    /// assert_eq!(buffer, points);
+    /// assert!(differential_growth.changed_ranges().iter().any(|changed| changed.kind == ChangeKind::Inserted));
+    /// ```
+    ///
+    #[must_use]
+    pub fn changed_ranges(&self) -> &[ChangedRange] {
+        &self.changes
     }
 
     /// Get the positions of the current state of the nodes.
@@ -93,9 +730,10 @@ impl DifferentialGrowth {
     /// 
     /// // draw the result by
     /// // - drawing a line between consecutive Vec elements.
-    /// // - drawing a line between the first and the last element.
+    /// // - drawing a line between the first and the last element if the topology is closed.
     /// ```
     /// 
+    #[must_use]
     pub fn get_points(&self) -> Vec<Point2<f64>> {
         let mut result = Vec::new();
 
@@ -106,155 +744,2915 @@ impl DifferentialGrowth {
         result
     }
 
-    fn insert_node_at(&mut self, node: Node, index: usize) {
-        self.nodes.insert(index, node);
+    /// Get the positions of the current state of the nodes.
+    /// The same as [`DifferentialGrowth::get_points()`], named after the Rust API guidelines.
+    #[must_use]
+    pub fn points(&self) -> Vec<Point2<f64>> {
+        self.get_points()
     }
 
-    fn growth(&mut self) {
-        let mut new_nodes: Vec<(Node, usize)> = Vec::with_capacity(self.nodes.len());
-        let mut amount_nodes_added = 0;
+    /// Overwrites `buffer` with the positions of the nodes, like [`DifferentialGrowth::get_points()`]
+    /// but reusing the allocation of `buffer`, so renderers don't allocate every frame.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mut buffer = Vec::new();
+    /// for _ in 0..10 {
+    ///     differential_growth.tick();
+    ///     differential_growth.get_points_into(&mut buffer);
+    ///     assert_eq!(buffer, differential_growth.get_points());
+    /// }
+    /// ```
+    ///
+    pub fn get_points_into(&self, buffer: &mut Vec<Point2<f64>>) {
+        buffer.clear();
+        buffer.extend(self.positions());
+    }
 
-        for i in 0..self.nodes.len() {
-            let n1: &Node = &self.nodes[i];
-            // Wrapping around to 0 if we are on last i.
-            let n2: &Node = if i == self.nodes.len() - 1 {
-                &self.nodes[0]
-            } else {
-                &self.nodes[i + 1]
-            };
+    /// Returns an iterator over the positions of the nodes, in order, for
+    /// reading them without collecting them into a `Vec` first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.positions().len(), 10);
+    /// assert!(differential_growth.positions().all(|position| (position.coords.norm() - 10.0).abs() < 1e-9));
+    /// ```
+    ///
+    pub fn positions(&self) -> impl ExactSizeIterator<Item = Point2<f64>> + DoubleEndedIterator + '_ {
+        self.nodes.iter().map(|node| node.position)
+    }
 
-            let distance: f64 = distance(&n1.position, &n2.position);
+    /// Overwrites `buffer` with the positions of the nodes as interleaved
+    /// `[x0, y0, x1, y1, ...]` floats, ready to upload as a vertex buffer.
+    ///
+    /// With `close` set the first position is repeated at the end of a closed path,
+    /// so it can be drawn as a line strip. Open paths never get a closing point.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mut buffer: Vec<f32> = Vec::new();
+    /// differential_growth.positions_f32(&mut buffer, false);
+    /// assert_eq!(buffer, vec![0.0, 0.0, 10.0, 0.0, 0.0, 10.0]);
+    ///
+    /// differential_growth.positions_f32(&mut buffer, true);
+    /// assert_eq!(buffer, vec![0.0, 0.0, 10.0, 0.0, 0.0, 10.0, 0.0, 0.0]);
+    /// ```
+    ///
+    pub fn positions_f32(&self, buffer: &mut Vec<f32>, close: bool) {
+        let closing: Option<&Node> = match self.topology {
+            Topology::Closed if close => self.nodes.first(),
+            _ => None,
+        };
 
-            if distance > self.max_edge_length {
-                // Inserting new nodes shifts the index of the original nodes.
-                // To compensate we shift the index with it.
-                let index: usize = i + 1 + amount_nodes_added;
-                amount_nodes_added.add_assign(1);
-                let middle_node: Vector2<f64> = n1.position.coords.add(n2.position.coords).div(2.0);
-                new_nodes.push((
-                    Node::new(
-                        Point2::new(middle_node.x, middle_node.y),
-                        self.max_speed,
-                        self.max_force,
-                    ),
-                    index,
-                ));
-            }
+        buffer.clear();
+        buffer.reserve(2 * (self.nodes.len() + 1));
+        for node in self.nodes.iter().chain(closing) {
+            buffer.extend([node.position.x as f32, node.position.y as f32]);
         }
+    }
 
-        for new_node in new_nodes {
-            self.insert_node_at(new_node.0, new_node.1);
-        }
+    /// Get the age of every node, the amount of ticks since it was created.
+    /// Starting points are as old as the simulation, nodes inserted during
+    /// the last tick have an age of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// for _ in 0..20 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// let ages = differential_growth.get_ages();
+    /// assert_eq!(ages.len(), differential_growth.nodes().len());
+    /// assert_eq!(ages.iter().max(), Some(&20));
+    /// assert!(ages.iter().any(|&age| age < 20));
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_ages(&self) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .map(|node| self.iteration.saturating_sub(node.birth_tick))
+            .collect()
+    }
+
+    /// Get the id of every node, in the order of [`DifferentialGrowth::get_points()`],
+    /// to follow nodes from one export to the next, see [`Node::id`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let before = differential_growth.get_ids();
+    ///
+    /// differential_growth.tick_n(20);
+    ///
+    /// let after = differential_growth.get_ids();
+    /// assert_eq!(after.len(), differential_growth.get_points().len());
+    /// assert!(before.iter().all(|id| after.contains(id)));
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_ids(&self) -> Vec<usize> {
+        self.nodes.iter().map(|node| node.id).collect()
     }
 
-    fn differentiate(&mut self) {
-        let separation_forces: Vec<Vector2<f64>> = self.get_separation_forces();
-        let cohesion_forces: Vec<Vector2<f64>> = self.get_edge_cohesion_forces();
+    /// Get the direction of the path at every node as a unit vector.
+    ///
+    /// The tangent at a node points from its previous neighbor to its next neighbor,
+    /// so the endpoints of an open path use their single edge. Nodes without a
+    /// direction, like nodes on top of both their neighbors, get a zero vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let tangents = differential_growth.get_tangents();
+    /// assert_eq!(tangents.len(), 10);
+    /// for (tangent, node) in tangents.iter().zip(differential_growth.nodes()) {
+    ///     assert!((tangent.norm() - 1.0).abs() < 1e-9);
+    ///     // On a circle the tangent is perpendicular to the radius.
+    ///     assert!(tangent.dot(&node.position.coords).abs() < 1e-9);
+    /// }
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_tangents(&self) -> Vec<Vector2<f64>> {
+        let len: usize = self.nodes.len();
 
-        for i in 0..self.nodes.len() {
-            let mut separation: Vector2<f64> = separation_forces[i];
-            let cohesion: Vector2<f64> = cohesion_forces[i];
+        (0..len)
+            .map(|i| {
+                let before: usize = self.topology.prev(i, len).unwrap_or(i);
+                let after: usize = self.topology.next(i, len).unwrap_or(i);
+                let direction: Vector2<f64> = self.nodes[after].position - self.nodes[before].position;
+                direction.try_normalize(f64::EPSILON).unwrap_or_default()
+            })
+            .collect()
+    }
 
-            separation.mul_assign(self.separation_cohesion_ration);
+    /// Get the normal of the path at every node as a unit vector, the tangent of
+    /// [`DifferentialGrowth::get_tangents()`] turned a quarter counter-clockwise.
+    /// Offset the points along their normals to draw ribbons or variable-width strokes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let tangents = differential_growth.get_tangents();
+    /// let normals = differential_growth.get_normals();
+    /// for (tangent, normal) in tangents.iter().zip(&normals) {
+    ///     assert!(tangent.dot(normal).abs() < 1e-9);
+    ///     assert!((tangent.perp(normal) - 1.0).abs() < 1e-9);
+    /// }
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_normals(&self) -> Vec<Vector2<f64>> {
+        self.get_tangents()
+            .into_iter()
+            .map(|tangent| Vector2::new(-tangent.y, tangent.x))
+            .collect()
+    }
 
-            self.nodes[i].apply_force(&separation);
-            self.nodes[i].apply_force(&cohesion);
-            self.nodes[i].update();
+    /// Get points spaced evenly along the path, independent of where the nodes are.
+    ///
+    /// Sampling starts at the first node and follows the path, including the edge
+    /// back to the first node when the topology is closed. The last piece of the
+    /// path is dropped when it is shorter than `spacing`. A `spacing` that isn't
+    /// positive returns the positions of the nodes instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - The arc length between consecutive points.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(10.0, 0.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.topology = differential_growth::Topology::Open;
+    ///
+    /// let points = differential_growth.get_points_resampled(2.5);
+    /// assert_eq!(points.len(), 5);
+    /// assert!((points[1].x - 2.5).abs() < 1e-9);
+    /// assert_eq!(points[4], Point2::new(10.0, 0.0));
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_points_resampled(&self, spacing: f64) -> Vec<Point2<f64>> {
+        if spacing <= 0.0 || spacing.is_nan() || self.nodes.is_empty() {
+            return self.get_points();
         }
-    }
 
-    fn get_separation_forces(&self) -> Vec<Vector2<f64>> {
-        // Constructing a kdtree each frame so we can optimise looking for neighbors.
-        // This technique is the single most important optimisation we can do.
-        let kdtree = KdTree2::build_by_ordered_float(self.nodes.clone());
+        let mut result: Vec<Point2<f64>> = vec![self.nodes[0].position];
+        // The arc length left to walk before the next point.
+        let mut remaining: f64 = spacing;
+
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            let start: Point2<f64> = self.nodes[i].position;
+            let end: Point2<f64> = self.nodes[j].position;
+            let length: f64 = distance(&start, &end);
+            let mut walked: f64 = 0.0;
+
+            while length - walked >= remaining {
+                walked.add_assign(remaining);
+                result.push(start + (end - start) * (walked / length));
+                remaining = spacing;
+            }
+            remaining.sub_assign(length - walked);
+        }
 
-        let nodes_len: usize = self.nodes.len();
-        let mut separate_forces: Vec<Vector2<f64>> = vec![Vector2::default(); nodes_len];
+        // A closed path ends where it started.
+        if self.topology == Topology::Closed && result.len() > 1 && remaining == spacing {
+            result.pop();
+        }
 
-        for i in 0..nodes_len {
-            let nodei = &self.nodes[i];
+        result
+    }
 
-            // We can assume no forces CAN happen outside of desired_separation range and
-            // forces MUST happen withing desired_separation range.
-            let close_nodes: Vec<&Node> = kdtree.within_radius(nodei, self.desired_separation);
+    /// Get the positions of the nodes with nearly collinear nodes left out,
+    /// using the Ramer–Douglas–Peucker algorithm. The result stays within `epsilon`
+    /// of the path, keeping the shape while exports and plots get much smaller.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - How far the simplified path may deviate from the nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![
+    ///     Point2::new(0.0, 0.0),
+    ///     Point2::new(5.0, 0.1),
+    ///     Point2::new(10.0, 0.0),
+    ///     Point2::new(10.0, 10.0),
+    ///     Point2::new(0.0, 10.0),
+    /// ];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let points = differential_growth.get_points_simplified(0.5);
+    /// assert_eq!(points.len(), 4);
+    /// assert!(!points.contains(&Point2::new(5.0, 0.1)));
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_points_simplified(&self, epsilon: f64) -> Vec<Point2<f64>> {
+        let mut points: Vec<Point2<f64>> = self.get_points();
+        let len: usize = points.len();
+        if len < 3 {
+            return points;
+        }
 
-            let _amount_of_close_nodes = close_nodes.len();
+        let mut keep: Vec<bool> = vec![false; len];
+        keep[0] = true;
 
-            for close_node in close_nodes {
-                let force: Vector2<f64> = self.get_separation_force(nodei, close_node);
-                separate_forces[i].add_assign(force);
+        match self.topology {
+            Topology::Open => {
+                keep[len - 1] = true;
+                geometry::douglas_peucker(&points, 0, len - 1, epsilon, &mut keep);
             }
+            Topology::Closed => {
+                // Split the loop into two open halves at the node farthest from the first one.
+                let farthest: usize = (1..len)
+                    .max_by(|&a, &b| {
+                        distance(&points[0], &points[a]).total_cmp(&distance(&points[0], &points[b]))
+                    })
+                    .unwrap_or(1);
+                keep[farthest] = true;
 
-            // This doesn't do much difference visually so I think this can be removed
-            // to minimise branching in a hot loop.
-            if _amount_of_close_nodes > 0 {
-                separate_forces[i].div_assign(_amount_of_close_nodes as f64);
+                points.push(points[0]);
+                keep.push(true);
+                geometry::douglas_peucker(&points, 0, farthest, epsilon, &mut keep);
+                geometry::douglas_peucker(&points, farthest, len, epsilon, &mut keep);
+                points.pop();
             }
+        }
 
-            // Set magnitude can make the separation force become a NaN value.
-            // Since this breaks everything, x or y is set to 0 when NaN is detected.
-            separate_forces[i].set_magnitude(self.max_speed);
-            if separate_forces[i].x.is_nan() {separate_forces[i].x = 0.0;};
-            if separate_forces[i].y.is_nan() {separate_forces[i].y = 0.0;};
+        points
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(point, keep)| keep.then_some(point))
+            .collect()
+    }
+
+    /// Returns the signed curvature at every node, the inverse of the radius of the
+    /// circle through the node and its neighbors. Positive where the path turns
+    /// counter-clockwise, negative where it turns clockwise.
+    ///
+    /// The endpoints of an open path have a curvature of `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Every node of a counter-clockwise circle with radius 10 has a curvature of 0.1.
+    /// assert!(differential_growth.curvatures().iter().all(|curvature| (curvature - 0.1).abs() < 1e-9));
+    /// ```
+    ///
+    #[must_use]
+    pub fn curvatures(&self) -> Vec<f64> {
+        (0..self.nodes.len()).map(|i| self.curvature_at(i)).collect()
+    }
+
+    pub(crate) fn curvature_at(&self, i: usize) -> f64 {
+        match self.topology.neighbors(i, self.nodes.len()) {
+            (Some(prev), Some(next)) => geometry::signed_curvature(
+                &self.nodes[prev].position,
+                &self.nodes[i].position,
+                &self.nodes[next].position,
+            ),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the curvatures of [`DifferentialGrowth::curvatures()`], smoothed by
+    /// averaging every value with its neighbors `passes` times. Useful to keep the
+    /// noise of individual nodes out of stroke widths or colors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// for _ in 0..20 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// let curvatures = differential_growth.smoothed_curvatures(3);
+    /// assert_eq!(curvatures.len(), differential_growth.nodes().len());
+    /// ```
+    ///
+    #[must_use]
+    pub fn smoothed_curvatures(&self, passes: usize) -> Vec<f64> {
+        let len: usize = self.nodes.len();
+        let mut curvatures: Vec<f64> = self.curvatures();
+
+        for _ in 0..passes {
+            curvatures = (0..len)
+                .map(|i| {
+                    // Endpoints of an open path only average with their single neighbor.
+                    let (prev, next) = self.topology.neighbors(i, len);
+                    let mut sum: f64 = curvatures[i];
+                    let mut count: f64 = 1.0;
+                    for neighbor in [prev, next].into_iter().flatten() {
+                        sum.add_assign(curvatures[neighbor]);
+                        count.add_assign(1.0);
+                    }
+                    sum / count
+                })
+                .collect();
+        }
+
+        curvatures
+    }
+
+    /// Returns the points where the segment from `a` to `b` crosses the path,
+    /// ordered from `a` to `b`. Useful for hatching and other scan line based rendering.
+    ///
+    /// Only edges near the segment are tested, found using the spatial index.
+    /// That's why this takes `&mut self`: the index is rebuilt first when nodes were
+    /// inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let intersections = differential_growth.intersections_with_line(Point2::new(-20.0, 1.0), Point2::new(20.0, 1.0));
+    /// assert_eq!(intersections.len(), 2);
+    /// assert!(intersections[0].x < 0.0 && intersections[1].x > 0.0);
+    /// ```
+    ///
+    pub fn intersections_with_line(&mut self, a: Point2<f64>, b: Point2<f64>) -> Vec<Point2<f64>> {
+        let len: usize = self.nodes.len();
+        let max_edge_length: f64 = self
+            .topology
+            .edges(len)
+            .map(|(i, j)| distance(&self.nodes[i].position, &self.nodes[j].position))
+            .fold(0.0, f64::max);
+        let line_length: f64 = distance(&a, &b);
 
-            separate_forces[i].sub_assign(self.nodes[i].velocity);
-            separate_forces[i] = separate_forces[i].cap_magnitude(self.max_force);
+        if max_edge_length == 0.0 || line_length == 0.0 {
+            return Vec::new();
         }
 
-        return separate_forces;
+        // An edge crossing the line has an endpoint within half its length of the crossing,
+        // and sampling the line every max_edge_length puts a sample within half that of it.
+        let samples: usize = (line_length / max_edge_length).ceil() as usize;
+        let points: Vec<Point2<f64>> = (0..=samples)
+            .map(|k| a + (b - a) * (k as f64 / samples as f64))
+            .collect();
+        let close_nodes: Vec<usize> = self
+            .neighbor_search
+            .within_radius_of_points(&self.nodes, &points, max_edge_length);
+
+        // Edges are identified by the index of the node they start from.
+        let mut edges: Vec<usize> = close_nodes
+            .iter()
+            .flat_map(|&i| [self.topology.prev(i, len), Some(i)])
+            .flatten()
+            .filter(|&i| self.topology.next(i, len).is_some())
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut intersections: Vec<(Point2<f64>, f64)> = edges
+            .into_iter()
+            .filter_map(|i| {
+                let j: usize = self.topology.next(i, len)?;
+                geometry::segment_intersection(&a, &b, &self.nodes[i].position, &self.nodes[j].position)
+            })
+            .collect();
+        intersections.sort_by(|first, second| first.1.total_cmp(&second.1));
+
+        intersections.into_iter().map(|(point, _)| point).collect()
+    }
+
+    /// Returns the index of the node closest to `point` and its distance, for hit-testing
+    /// the path in a user interface. Returns `None` when there are no nodes.
+    ///
+    /// This uses the spatial index of the simulation. That's why this takes `&mut self`:
+    /// the index is rebuilt first when nodes were inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.nearest_node(&Point2::new(12.0, 7.0)), Some((2, 13.0_f64.sqrt())));
+    /// assert_eq!(differential_growth.nearest_node(&Point2::new(500.0, 0.0)).unwrap().0, 1);
+    /// ```
+    ///
+    pub fn nearest_node(&mut self, point: &Point2<f64>) -> Option<(usize, f64)> {
+        self.k_nearest_nodes(point, 1).first().copied()
     }
 
-    fn get_separation_force(&self, n1: &Node, n2: &Node) -> Vector2<f64> {
-        let mut steer: Vector2<f64> = Vector2::default();
+    /// Returns the indices of the `k` nodes closest to `point` with their distances, from
+    /// the closest, or all nodes when there are fewer. See [`DifferentialGrowth::nearest_node()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let nearest: Vec<usize> = differential_growth
+    ///     .k_nearest_nodes(&Point2::new(9.0, 2.0), 2)
+    ///     .into_iter()
+    ///     .map(|(index, _)| index)
+    ///     .collect();
+    /// assert_eq!(nearest, vec![1, 2]);
+    /// assert_eq!(differential_growth.k_nearest_nodes(&Point2::new(9.0, 2.0), 10).len(), 3);
+    /// ```
+    ///
+    pub fn k_nearest_nodes(&mut self, point: &Point2<f64>, k: usize) -> Vec<(usize, f64)> {
+        let (min, max) = match self.bounding_box() {
+            Some(bounding_box) if k > 0 => bounding_box,
+            _ => return Vec::new(),
+        };
+        let k: usize = k.min(self.nodes.len());
+
+        // Grow the search radius until it reaches enough nodes. No node is further away
+        // than the corner of the bounding box furthest from the point.
+        let dx: f64 = (point.x - min.x).abs().max((point.x - max.x).abs());
+        let dy: f64 = (point.y - min.y).abs().max((point.y - max.y).abs());
+        let furthest: f64 = dx.hypot(dy);
+        let mut radius: f64 = self.max_edge_length.min(furthest).max(f64::MIN_POSITIVE);
+        let mut close_nodes: Vec<usize> = self.neighbor_search.within_radius_of_points(&self.nodes, &[*point], radius);
+        while close_nodes.len() < k && radius < furthest {
+            radius = (radius * 2.0).min(furthest);
+            close_nodes = self.neighbor_search.within_radius_of_points(&self.nodes, &[*point], radius);
+        }
+
+        let mut nearest: Vec<(usize, f64)> = close_nodes
+            .into_iter()
+            .map(|i| (i, distance(point, &self.nodes[i].position)))
+            .collect();
+        nearest.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        nearest.truncate(k);
+        nearest
+    }
 
-        // Optimised version by defering sqrt() to inside if statement.
-        let distance_sq: f64 =
-            (n2.position.x - n1.position.x).powi(2) + (n2.position.y - n1.position.y).powi(2);
+    /// Returns the indices of all nodes within `radius` of `point`, in the order of the path.
+    /// See [`DifferentialGrowth::nearest_node()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.nodes_within_radius(&Point2::new(10.0, 5.0), 6.0), vec![1, 2]);
+    /// assert!(differential_growth.nodes_within_radius(&Point2::new(-10.0, 0.0), 5.0).is_empty());
+    /// ```
+    ///
+    pub fn nodes_within_radius(&mut self, point: &Point2<f64>, radius: f64) -> Vec<usize> {
+        self.neighbor_search.within_radius_of_points(&self.nodes, &[*point], radius)
+    }
 
-        if distance_sq > 0.0 {
-            let mut diff: Vector2<f64> = n1.position.sub(n2.position);
-            diff = diff.normalize();
-            diff.div_assign(distance_sq.sqrt());
-            steer.add_assign(diff);
+    /// Returns the point on the path closest to `point`, together with its arc length
+    /// measured along the path from the first node and the index of the edge it lies on.
+    /// Edge `i` runs from node `i` to the next node. Returns `None` for paths without edges.
+    ///
+    /// Only edges near `point` are tested, found using the spatial index.
+    /// That's why this takes `&mut self`: the index is rebuilt first when nodes were
+    /// inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.topology = differential_growth::Topology::Open;
+    ///
+    /// let (closest, arc_length, edge) = differential_growth.closest_point_on_path(&Point2::new(12.0, 4.0)).unwrap();
+    /// assert_eq!(closest, Point2::new(10.0, 4.0));
+    /// assert_eq!(arc_length, 14.0);
+    /// assert_eq!(edge, 1);
+    /// ```
+    ///
+    pub fn closest_point_on_path(&mut self, point: &Point2<f64>) -> Option<(Point2<f64>, f64, usize)> {
+        let len: usize = self.nodes.len();
+        let edge_lengths: Vec<f64> = self
+            .topology
+            .edges(len)
+            .map(|(i, j)| distance(&self.nodes[i].position, &self.nodes[j].position))
+            .collect();
+        let max_edge_length: f64 = edge_lengths.iter().copied().fold(0.0, f64::max);
+        if edge_lengths.is_empty() {
+            return None;
         }
 
-        return steer;
+        // Every point on an edge lies within half its length of one of its nodes, so the
+        // closest edge has a node within this radius.
+        let (_, closest_node): (usize, f64) = self.nearest_node(point)?;
+        let close_nodes: Vec<usize> = self.neighbor_search.within_radius_of_points(
+            &self.nodes,
+            &[*point],
+            closest_node + max_edge_length / 2.0,
+        );
+
+        let (closest, t, edge): (Point2<f64>, f64, usize) = close_nodes
+            .iter()
+            .flat_map(|&i| [self.topology.prev(i, len), Some(i)])
+            .flatten()
+            .filter_map(|i| {
+                let j: usize = self.topology.next(i, len)?;
+                let (closest, t) =
+                    geometry::closest_point_on_segment(point, &self.nodes[i].position, &self.nodes[j].position);
+                Some((closest, t, i))
+            })
+            .min_by(|a, b| distance(point, &a.0).total_cmp(&distance(point, &b.0)))?;
+
+        let arc_length: f64 = edge_lengths[..edge].iter().sum::<f64>() + edge_lengths[edge] * t;
+
+        Some((closest, arc_length, edge))
+    }
+
+    /// Returns the minimum and maximum corner of the axis aligned box around all nodes,
+    /// or `None` if there are no nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 5.0), Point2::new(10.0, 0.0), Point2::new(-2.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.bounding_box(), Some((Point2::new(-2.0, 0.0), Point2::new(10.0, 10.0))));
+    /// ```
+    ///
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Point2<f64>, Point2<f64>)> {
+        let first: Point2<f64> = self.nodes.first()?.position;
+        Some(self.nodes.iter().fold((first, first), |(min, max), node| {
+            (min.inf(&node.position), max.sup(&node.position))
+        }))
     }
 
-    fn get_edge_cohesion_forces(&self) -> Vec<Vector2<f64>> {
-        let n: usize = self.nodes.len();
-        let mut cohesion_forces: Vec<Vector2<f64>> = Vec::with_capacity(n);
+    /// Returns whether `point` lies inside the path, using the even-odd rule
+    /// so areas where the path overlaps itself count as outside.
+    ///
+    /// Open paths don't enclose anything and never contain a point.
+    /// Points outside of the bounding box of the path are rejected without looking at every edge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert!(differential_growth.contains(&Point2::new(1.0, 2.0)));
+    /// assert!(!differential_growth.contains(&Point2::new(11.0, 0.0)));
+    /// ```
+    ///
+    #[must_use]
+    pub fn contains(&self, point: &Point2<f64>) -> bool {
+        if self.topology == Topology::Open || self.nodes.len() < 3 {
+            return false;
+        }
+
+        let (min, max) = match self.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return false,
+        };
+        if point.x < min.x || point.x > max.x || point.y < min.y || point.y > max.y {
+            return false;
+        }
+
+        // Counts the edges crossed by a ray from the point towards positive x.
+        let mut inside: bool = false;
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            let (a, b) = (&self.nodes[i].position, &self.nodes[j].position);
+            if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+                inside = !inside;
+            }
+        }
+
+        inside
+    }
 
-        // I'm doing the cohesion force calculation of the first and last
-        // node separately to prevent branching in a hot loop.
+    /// Returns the area enclosed by the path, using the shoelace formula.
+    /// Returns `None` for open paths and paths with less than 3 nodes.
+    ///
+    /// Areas where the path overlaps itself cancel out or count double
+    /// depending on the direction they are wound in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 5.0), Point2::new(0.0, 5.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.area(), Some(50.0));
+    /// assert_eq!(differential_growth.centroid(), Some(Point2::new(5.0, 2.5)));
+    /// ```
+    ///
+    #[must_use]
+    pub fn area(&self) -> Option<f64> {
+        self.signed_area().map(f64::abs)
+    }
 
-        // cohesion force of i == 0 (first node)
-        {
-            let mut sum: Vector2<f64> = Vector2::default();
-            sum.add_assign(self.nodes[n - 1].position.coords);
-            sum.add_assign(self.nodes[0 + 1].position.coords);
-            sum.div_assign(2.0);
-            cohesion_forces.push(self.nodes[0].seek(&sum));
+    /// Returns the center of mass of the area enclosed by the path.
+    /// Returns `None` for open paths, paths with less than 3 nodes and paths enclosing no area.
+    #[must_use]
+    pub fn centroid(&self) -> Option<Point2<f64>> {
+        let signed_area: f64 = self.signed_area()?;
+        if signed_area == 0.0 {
+            return None;
         }
 
-        // cohesion force of everything in between
-        for i in 1..n - 1 {
-            let mut sum: Vector2<f64> = Vector2::default();
-            sum.add_assign(self.nodes[i - 1].position.coords);
-            sum.add_assign(self.nodes[i + 1].position.coords);
-            sum.div_assign(2.0);
-            cohesion_forces.push(self.nodes[i].seek(&sum));
+        let mut sum: Vector2<f64> = Vector2::default();
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            let (a, b) = (&self.nodes[i].position, &self.nodes[j].position);
+            sum.add_assign((a.coords + b.coords) * a.coords.perp(&b.coords));
         }
 
-        // cohesion force of i == n-1 (last node)
-        {
-            let mut sum: Vector2<f64> = Vector2::default();
-            sum.add_assign(self.nodes[n - 1 - 1].position.coords);
-            sum.add_assign(self.nodes[0].position.coords);
-            sum.div_assign(2.0);
-            cohesion_forces.push(self.nodes[n - 1].seek(&sum));
+        Some(Point2::from(sum / (6.0 * signed_area)))
+    }
+
+    // Positive when the nodes run counter-clockwise.
+    fn signed_area(&self) -> Option<f64> {
+        if self.topology == Topology::Open || self.nodes.len() < 3 {
+            return None;
         }
 
-        return cohesion_forces;
+        let twice_area: f64 = self
+            .topology
+            .edges(self.nodes.len())
+            .map(|(i, j)| self.nodes[i].position.coords.perp(&self.nodes[j].position.coords))
+            .sum();
+
+        Some(twice_area / 2.0)
     }
-}
\ No newline at end of file
+
+    /// Overwrites the velocity of every node, for example to give the starting
+    /// points an initial push. See [`InitialVelocity`].
+    pub fn set_velocities(&mut self, initial_velocity: InitialVelocity) {
+        initial_velocity.apply(&mut self.nodes, self.topology);
+    }
+
+    /// Appends points to the end of the path while the simulation is running,
+    /// for example while they arrive from a digitizer or over the network.
+    /// Returns the amount of points that were appended.
+    ///
+    /// Points that aren't finite or that coincide with the current last node are skipped
+    /// instead of corrupting the simulation. New nodes start with the velocity of the
+    /// last node so the tail keeps moving along. Edges longer than the maximum edge
+    /// length are split by the following ticks as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    /// use nalgebra::Point2;
+    ///
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(vec![Point2::new(0.0, 0.0), Point2::new(4.0, 0.0)])
+    ///     .topology(Topology::Open)
+    ///     .build();
+    ///
+    /// for x in 2..10 {
+    ///     differential_growth.tick();
+    ///     let appended = differential_growth.extend_path([Point2::new(x as f64 * 4.0, 0.0), Point2::new(f64::NAN, 0.0)]);
+    ///     assert_eq!(appended, 1);
+    /// }
+    /// assert!(differential_growth.nodes().iter().all(|node| node.position.x.is_finite()));
+    /// ```
+    ///
+    pub fn extend_path(&mut self, points: impl IntoIterator<Item = Point2<f64>>) -> usize {
+        let len: usize = self.nodes.len();
+        let mut appended: usize = 0;
+
+        for point in points {
+            if !(point.x.is_finite() && point.y.is_finite()) {
+                continue;
+            }
+
+            let (velocity, mass): (Vector2<f64>, f64) = match self.nodes.last() {
+                Some(last) if last.position == point => continue,
+                Some(last) => (last.velocity, last.mass),
+                None => (Vector2::default(), 1.0),
+            };
+
+            let mut node: Node = Node::new(point, self.max_speed, self.max_force);
+            node.velocity = velocity;
+            node.mass = mass;
+            node.birth_tick = self.iteration;
+            node.id = self.take_node_id();
+            self.nodes.push(node);
+            self.push_node_event(NodeEvent::Inserted {
+                index: self.nodes.len() - 1,
+                id: node.id,
+            });
+            appended.add_assign(1);
+        }
+
+        if appended > 0 {
+            self.appended.add_assign(appended);
+            self.neighbor_search.invalidate();
+
+            // Appended nodes carry on with the value of the previous last node.
+            if self.has_node_values() {
+                let mut sources: Vec<Source> = (0..len).map(Source::Node).collect();
+                sources.resize(len + appended, Source::Node(len.saturating_sub(1)));
+                self.remap_node_values(&sources);
+            }
+        }
+
+        appended
+    }
+
+    /// Sets the maximum force nodes can exert on eachother,
+    /// including on all nodes that already exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_max_force(0.5);
+    /// assert!(differential_growth.nodes().iter().all(|node| node.max_force == 0.5));
+    /// ```
+    ///
+    pub fn set_max_force(&mut self, max_force: f64) {
+        self.max_force = max_force;
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.max_force = max_force;
+            if let Some(max_force) = self.node_params.as_ref().and_then(|node_params| node_params.get(i)?.max_force) {
+                node.max_force = max_force;
+            }
+        }
+    }
+
+    /// Sets the maximum magnitude of a node's velocity,
+    /// including on all nodes that already exist.
+    pub fn set_max_speed(&mut self, max_speed: f64) {
+        self.max_speed = max_speed;
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.max_speed = max_speed;
+            if let Some(max_speed) = self.node_params.as_ref().and_then(|node_params| node_params.get(i)?.max_speed) {
+                node.max_speed = max_speed;
+            }
+        }
+    }
+
+    /// Returns the ratio between separation and cohesion forces.
+    #[must_use]
+    #[allow(deprecated)]
+    pub fn separation_cohesion_ratio(&self) -> f64 {
+        self.separation_cohesion_ration
+    }
+
+    /// Sets the desired separation between nodes.
+    pub fn set_desired_separation(&mut self, desired_separation: f64) {
+        self.desired_separation = desired_separation;
+    }
+
+    /// Sets the ratio between separation and cohesion forces.
+    #[allow(deprecated)]
+    pub fn set_separation_cohesion_ratio(&mut self, separation_cohesion_ratio: f64) {
+        self.separation_cohesion_ration = separation_cohesion_ratio;
+    }
+
+    /// Sets the maximum length between two connected nodes.
+    pub fn set_max_edge_length(&mut self, max_edge_length: f64) {
+        self.max_edge_length = max_edge_length;
+    }
+
+    /// Sets the probability, between 0 and 1, that an edge longer than the
+    /// maximum edge length is split during a tick. Lower values slow down and
+    /// randomize growth. Defaults to `1.0`, always splitting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_growth_probability(0.25);
+    /// differential_growth.set_seed(42);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_growth_probability(&mut self, growth_probability: f64) {
+        self.growth_probability = growth_probability.clamp(0.0, 1.0);
+    }
+
+    /// Sets whether nodes next to an over-length edge are first slid along the path
+    /// to shorten it before a new node is inserted. Nodes only slide when the edge on
+    /// their other side stays well within the maximum edge length, so the path spreads
+    /// its existing nodes more evenly and needs fewer of them. Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut redistributing = differential_growth::DifferentialGrowth::new(starting_points.clone(), 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// redistributing.set_redistribution(true);
+    /// let mut inserting = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// for _ in 0..100 {
+    ///     redistributing.tick();
+    ///     inserting.tick();
+    /// }
+    /// assert!(redistributing.nodes().len() < inserting.nodes().len());
+    /// ```
+    ///
+    pub fn set_redistribution(&mut self, redistribution: bool) {
+        self.redistribution = redistribution;
+    }
+
+    /// Sets whether moves that make the path cross itself are undone.
+    ///
+    /// After the nodes moved, the nodes of every pair of crossing edges are put back
+    /// where they were at the start of the tick and stopped, until no edges cross.
+    /// A path that doesn't cross itself keeps it that way while moving, so thin folds
+    /// can't tunnel through eachother at high speeds. Inserting nodes with a strategy
+    /// that leaves the edge, like [`crate::JitteredMidpoint`], can still cause crossings.
+    /// This runs [`DifferentialGrowth::self_intersections()`] every tick. Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .max_speed(2.5)
+    ///     .build();
+    /// differential_growth.set_prevent_self_intersections(true);
+    ///
+    /// for _ in 0..50 {
+    ///     differential_growth.tick();
+    /// }
+    /// assert!(differential_growth.self_intersections().is_empty());
+    /// ```
+    ///
+    pub fn set_prevent_self_intersections(&mut self, prevent_self_intersections: bool) {
+        self.prevent_self_intersections = prevent_self_intersections;
+    }
+
+    /// Enables a pruning pass at the end of every tick which merges connected nodes
+    /// that are closer than `min_edge_length` into a single node at their midpoint.
+    /// This keeps clusters of nearly coincident nodes from wasting memory and time.
+    ///
+    /// `min_edge_length` should be well below the maximum edge length,
+    /// otherwise merged edges are split again on the next tick.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_min_edge_length(1.0);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_min_edge_length(&mut self, min_edge_length: f64) {
+        self.min_edge_length = Some(min_edge_length);
+    }
+
+    /// Disables the pruning pass.
+    pub fn clear_min_edge_length(&mut self) {
+        self.min_edge_length = None;
+    }
+
+    /// Stops inserting new nodes once the path has `max_nodes` nodes.
+    /// The existing nodes keep relaxing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_max_nodes(12);
+    /// for _ in 0..10 {
+    ///     differential_growth.tick();
+    /// }
+    /// assert!(differential_growth.nodes().len() <= 12);
+    /// ```
+    ///
+    pub fn set_max_nodes(&mut self, max_nodes: usize) {
+        self.max_nodes = Some(max_nodes);
+    }
+
+    /// Removes the cap on the amount of nodes.
+    pub fn clear_max_nodes(&mut self) {
+        self.max_nodes = None;
+    }
+
+    /// Ramps all forces up linearly from 0 to full strength over the first `warmup_ticks`
+    /// ticks, so densely packed starting points don't explode on the first iterations.
+    /// Defaults to `0`, no warm-up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 2.0, 50);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_warmup_ticks(20);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_warmup_ticks(&mut self, warmup_ticks: usize) {
+        self.warmup_ticks = warmup_ticks;
+    }
+
+    /// Sets the point every node is pulled towards by the cohesion force, see [`CohesionTarget`].
+    /// Defaults to [`CohesionTarget::Midpoint`].
+    pub fn set_cohesion_target(&mut self, cohesion_target: CohesionTarget) {
+        self.cohesion_target = cohesion_target;
+    }
+
+    /// Pulls every node towards its cohesion target like a spring with stiffness `cohesion_strength`,
+    /// so the force grows with the distance to the target.
+    ///
+    /// By default nodes steer towards their target at their maximum speed, limited by their
+    /// maximum force, which ties the strength of cohesion to [`DifferentialGrowth::set_max_speed()`]
+    /// and [`DifferentialGrowth::set_max_force()`]. With a stiffness cohesion only depends on this
+    /// setting and separation only on the separation cohesion ratio, so both can be tuned
+    /// independently. Nodes remain limited to their maximum speed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_cohesion_strength(0.2);
+    ///
+    /// differential_growth.tick_n(20);
+    /// assert!(differential_growth.nodes().len() > 10);
+    ///
+    /// differential_growth.clear_cohesion_strength();
+    /// assert_eq!(differential_growth.cohesion_strength, None);
+    /// ```
+    ///
+    pub fn set_cohesion_strength(&mut self, cohesion_strength: f64) {
+        self.cohesion_strength = Some(cohesion_strength);
+    }
+
+    /// Steers nodes towards their cohesion target at their maximum speed again, the default.
+    pub fn clear_cohesion_strength(&mut self) {
+        self.cohesion_strength = None;
+    }
+
+    /// Sets how strongly neighbors push a node away depending on their distance, see [`SeparationKernel`].
+    /// Defaults to [`SeparationKernel::Inverse`].
+    pub fn set_separation_kernel(&mut self, separation_kernel: SeparationKernel) {
+        self.separation_kernel = separation_kernel;
+    }
+
+    /// Sets the fraction of their velocity nodes lose every tick, so the path relaxes
+    /// smoothly instead of ringing around its equilibrium. Can be changed at any time.
+    /// Defaults to `0.0`, no damping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_damping(0.1);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_damping(&mut self, damping: f64) {
+        self.damping = damping.clamp(0.0, 1.0);
+    }
+
+    /// Sets the mass of every node to what `mass` returns for it, by position to make
+    /// a region heavier or by id for single nodes. Heavier nodes move sluggishly, so
+    /// parts of the path stay stiff while others billow out. Inserted nodes get the
+    /// average mass of the nodes on both ends of the split edge.
+    ///
+    /// Masses that aren't positive and finite are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // The left half is 5 times as heavy as the right half.
+    /// differential_growth.set_masses(|node| if node.position.x < 0.0 { 5.0 } else { 1.0 });
+    ///
+    /// for _ in 0..50 {
+    ///     differential_growth.tick();
+    /// }
+    /// assert!(differential_growth.nodes().iter().all(|node| (1.0..=5.0).contains(&node.mass)));
+    /// ```
+    ///
+    pub fn set_masses(&mut self, mass: impl Fn(&Node) -> f64) {
+        for node in &mut self.nodes {
+            let mass: f64 = mass(node);
+            if mass.is_finite() && mass > 0.0 {
+                node.mass = mass;
+            }
+        }
+    }
+
+    /// Sets the weight of a third force next to separation and cohesion, which steers every node
+    /// towards the average velocity of the nodes within the desired separation, like the
+    /// alignment rule of boids. Gives smoother, wave-like growth fronts. Can be changed at any time.
+    /// Defaults to `0.0`, no alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_alignment(0.5);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_alignment(&mut self, alignment: f64) {
+        self.alignment = alignment;
+    }
+
+    /// Sets the gain of a force pushing every node along the normal of the path, proportional
+    /// to how much more the path bends there than on average. A positive gain pushes bends away
+    /// from their center so they fold further and the structure gets frillier, a negative gain
+    /// pulls them in and keeps the path smooth. The force is capped at the maximum force.
+    /// Can be changed at any time. Defaults to `0.0`, no curvature force.
+    ///
+    /// Gains around `0.3` already fold noticeably, higher gains can fold faster
+    /// than the separation force keeps the path apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let smooth_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut smooth = differential_growth::DifferentialGrowth::new(smooth_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// smooth.set_curvature_gain(-0.3);
+    /// smooth.tick_n(100);
+    ///
+    /// let frilly_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut frilly = differential_growth::DifferentialGrowth::new(frilly_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// frilly.set_curvature_gain(0.3);
+    /// frilly.tick_n(100);
+    ///
+    /// // Folding packs more path into the same time.
+    /// assert!(frilly.nodes().len() > smooth.nodes().len());
+    /// ```
+    ///
+    pub fn set_curvature_gain(&mut self, curvature_gain: f64) {
+        self.curvature_gain = curvature_gain;
+    }
+
+    /// Reseeds the random number generator used by the stochastic parts of the
+    /// algorithm. The same seed and parameters always produce the same result.
+    ///
+    /// With the `rayon` feature the forces are computed on several threads in fixed
+    /// chunks and summed in a fixed order, so the result is also the same on any
+    /// amount of threads and without the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let run = || {
+    ///     let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    ///     let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///         .points(starting_points)
+    ///         .curvature_gain(0.2)
+    ///         .growth_probability(0.5)
+    ///         .build();
+    ///     differential_growth.set_seed(42);
+    ///     differential_growth.tick_n(150);
+    ///     differential_growth.get_points()
+    /// };
+    ///
+    /// let first = run();
+    /// assert_eq!(first, run());
+    ///
+    /// # #[cfg(feature = "rayon")]
+    /// # {
+    /// // Bit-identical on one thread and on four.
+    /// let serial = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap().install(run);
+    /// let parallel = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap().install(run);
+    /// assert_eq!(serial, parallel);
+    /// assert_eq!(serial, first);
+    /// # }
+    /// ```
+    ///
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Makes the result independent of the order the spatial index finds neighbors in,
+    /// by summing their contributions in the order of the nodes. Defaults to `false`.
+    ///
+    /// Reruns with the same seed are always bit-identical, in this mode the result also
+    /// doesn't change with the [`SpatialIndex`] or the [`RebuildPolicy`], for example
+    /// when an update of the kd-tree changes its traversal. Separation sums from the
+    /// `gpu` feature are summed in any order and aren't covered. See the crate
+    /// documentation on determinism.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::GridIndex;
+    ///
+    /// let run = |grid: bool| {
+    ///     let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    ///     let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///         .points(starting_points)
+    ///         .deterministic(true)
+    ///         .build();
+    ///     if grid {
+    ///         differential_growth.set_spatial_index(GridIndex::new(3.0));
+    ///     }
+    ///     differential_growth.tick_n(150);
+    ///     differential_growth.get_points()
+    /// };
+    ///
+    /// // The default kd-tree and a grid find the same neighbors in a different order.
+    /// assert_eq!(run(false), run(true));
+    /// ```
+    ///
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Returns whether neighbor contributions are summed in the order of the nodes,
+    /// see [`DifferentialGrowth::set_deterministic()`].
+    #[must_use]
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Sets when the spatial index used to find neighboring nodes is rebuilt.
+    /// Defaults to rebuilding every tick, see [`RebuildPolicy`].
+    pub fn set_rebuild_policy(&mut self, rebuild_policy: RebuildPolicy) {
+        self.neighbor_search.policy = rebuild_policy;
+    }
+
+    /// Returns when the spatial index used to find neighboring nodes is rebuilt.
+    #[must_use]
+    pub fn rebuild_policy(&self) -> RebuildPolicy {
+        self.neighbor_search.policy
+    }
+
+    /// Sets the spatial index used to find neighboring nodes.
+    /// Defaults to [`crate::KdTreeIndex`], or [`crate::GridIndex`] without the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_spatial_index(differential_growth::GridIndex::new(14.0));
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn set_spatial_index(&mut self, spatial_index: impl SpatialIndex + 'static) {
+        self.neighbor_search.set_index(Box::new(spatial_index));
+    }
+
+    /// Sets the strategy deciding where new nodes are placed when an edge is split.
+    /// Defaults to [`Midpoint`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_insertion_strategy(differential_growth::CurvatureWeighted::new(1.0));
+    /// ```
+    ///
+    pub fn set_insertion_strategy(&mut self, insertion_strategy: impl InsertionStrategy + 'static) {
+        self.insertion_strategy = Box::new(insertion_strategy);
+    }
+
+    /// Makes the path bloom around `center` for the next `duration` ticks by
+    /// temporarily boosting how eagerly edges are split and how strongly
+    /// forces act within `radius`. The effect falls off linearly towards the radius.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The center of the stimulated region.
+    /// * `radius` - The radius of the stimulated region.
+    /// * `strength` - How much stronger growth and forces get at the center. `1.0` doubles them.
+    /// * `duration` - The amount of ticks the stimulus stays active.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.stimulate(Point2::new(10.0, 0.0), 20.0, 1.0, 2);
+    /// differential_growth.tick();
+    /// differential_growth.tick();
+    /// assert!(differential_growth.overlays().is_empty());
+    /// ```
+    ///
+    pub fn stimulate(&mut self, center: Point2<f64>, radius: f64, strength: f64, duration: usize) {
+        self.add_overlay(ParamOverlay::new(center, radius, 1.0 + strength, 1.0 + strength, duration));
+    }
+
+    /// Suppresses growth within `radius` of `center`. The suppression is complete at
+    /// the center when applied and decays linearly, both towards the radius and over
+    /// the next `duration` ticks. Relaxation keeps working inside the region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.stimulate(Point2::new(10.0, 0.0), 20.0, 1.0, 50);
+    /// differential_growth.inhibit(Point2::new(-10.0, 0.0), 20.0, 100);
+    /// differential_growth.tick();
+    /// ```
+    ///
+    pub fn inhibit(&mut self, center: Point2<f64>, radius: f64, duration: usize) {
+        let mut overlay: ParamOverlay = ParamOverlay::new(center, radius, 0.0, 1.0, duration);
+        overlay.decays = true;
+        self.add_overlay(overlay);
+    }
+
+    /// Adds a temporary parameter overlay.
+    pub fn add_overlay(&mut self, overlay: ParamOverlay) {
+        self.overlays.push(overlay);
+    }
+
+    /// Returns the parameter overlays that are still active.
+    #[must_use]
+    pub fn overlays(&self) -> &[ParamOverlay] {
+        &self.overlays
+    }
+
+    /// Removes all parameter overlays.
+    pub fn clear_overlays(&mut self) {
+        self.overlays.clear();
+    }
+
+    /// Changes the parameters as the simulation progresses, see [`ParamSchedule`].
+    /// The parameters of a tick are applied at its start, before the scripted rules.
+    pub fn set_param_schedule(&mut self, param_schedule: ParamSchedule) {
+        self.param_schedule = Some(param_schedule);
+    }
+
+    /// Returns the parameter schedule, if any.
+    #[must_use]
+    pub fn param_schedule(&self) -> Option<&ParamSchedule> {
+        self.param_schedule.as_ref()
+    }
+
+    /// Removes the parameter schedule, the current parameters stay.
+    pub fn clear_param_schedule(&mut self) {
+        self.param_schedule = None;
+    }
+
+    fn apply_param_schedule(&mut self) {
+        let params: Option<ParamSet> = self
+            .param_schedule
+            .as_ref()
+            .and_then(|param_schedule| param_schedule.params_at(self.iteration));
+        if let Some(params) = params {
+            if params != ParamSet::from_differential_growth(self) {
+                params.apply(self);
+            }
+        }
+    }
+
+    /// Sets the scripted rules that are run every tick.
+    #[cfg(feature = "rhai")]
+    pub fn set_script(&mut self, script: ScriptRules) {
+        self.script = Some(script);
+    }
+
+    /// Returns the scripted rules, if any.
+    #[cfg(feature = "rhai")]
+    #[must_use]
+    pub fn script(&self) -> Option<&ScriptRules> {
+        self.script.as_ref()
+    }
+
+    /// Removes the scripted rules.
+    #[cfg(feature = "rhai")]
+    pub fn clear_script(&mut self) {
+        self.script = None;
+    }
+
+    #[cfg(feature = "rhai")]
+    fn apply_script_params(&mut self) {
+        let params: ParamSet = ParamSet::from_differential_growth(self);
+        let changes: Option<(ParamSet, f64)> = match self.script.as_mut() {
+            Some(script) => script.params(self.iteration, &params, self.growth_probability),
+            None => None,
+        };
+
+        if let Some((new_params, growth_probability)) = changes {
+            if new_params != params {
+                new_params.apply(self);
+            }
+            self.set_growth_probability(growth_probability);
+        }
+    }
+
+    /// Keeps all nodes close to a guide path, see [`GuidePath`].
+    pub fn set_guide_path(&mut self, guide_path: GuidePath) {
+        self.guide_path = Some(guide_path);
+    }
+
+    /// Returns the guide path, if any.
+    #[must_use]
+    pub fn guide_path(&self) -> Option<&GuidePath> {
+        self.guide_path.as_ref()
+    }
+
+    /// Removes the guide path.
+    pub fn clear_guide_path(&mut self) {
+        self.guide_path = None;
+    }
+
+    /// Scales growth and separation with the distance from a center point, see [`RadialGradient`].
+    pub fn set_radial_gradient(&mut self, radial_gradient: RadialGradient) {
+        self.radial_gradient = Some(radial_gradient);
+    }
+
+    /// Returns the radial gradient, if any.
+    #[must_use]
+    pub fn radial_gradient(&self) -> Option<&RadialGradient> {
+        self.radial_gradient.as_ref()
+    }
+
+    /// Removes the radial gradient.
+    pub fn clear_radial_gradient(&mut self) {
+        self.radial_gradient = None;
+    }
+
+    /// Lets the path deposit a trail that feeds back into its growth, see [`TrailMap`].
+    pub fn set_trail_map(&mut self, trail_map: TrailMap) {
+        self.trail_map = Some(trail_map);
+    }
+
+    /// Returns the trail map, if any.
+    #[must_use]
+    pub fn trail_map(&self) -> Option<&TrailMap> {
+        self.trail_map.as_ref()
+    }
+
+    /// Returns the trail map to paint into it or change its parameters, if any.
+    pub fn trail_map_mut(&mut self) -> Option<&mut TrailMap> {
+        self.trail_map.as_mut()
+    }
+
+    /// Removes the trail map.
+    pub fn clear_trail_map(&mut self) {
+        self.trail_map = None;
+    }
+
+    /// Weights the growth probability by the brightness of an image, see [`BrightnessMap`].
+    pub fn set_brightness_map(&mut self, brightness_map: BrightnessMap) {
+        self.brightness_map = Some(brightness_map);
+    }
+
+    /// Returns the brightness map, if any.
+    #[must_use]
+    pub fn brightness_map(&self) -> Option<&BrightnessMap> {
+        self.brightness_map.as_ref()
+    }
+
+    /// Returns the brightness map to change its parameters, if any.
+    pub fn brightness_map_mut(&mut self) -> Option<&mut BrightnessMap> {
+        self.brightness_map.as_mut()
+    }
+
+    /// Removes the brightness map.
+    pub fn clear_brightness_map(&mut self) {
+        self.brightness_map = None;
+    }
+
+    /// Steers the bounding box of the path towards an aspect ratio, see [`AspectConstraint`].
+    pub fn set_aspect_constraint(&mut self, aspect_constraint: AspectConstraint) {
+        self.aspect_constraint = Some(aspect_constraint);
+    }
+
+    /// Returns the aspect constraint, if any.
+    #[must_use]
+    pub fn aspect_constraint(&self) -> Option<&AspectConstraint> {
+        self.aspect_constraint.as_ref()
+    }
+
+    /// Removes the aspect constraint.
+    pub fn clear_aspect_constraint(&mut self) {
+        self.aspect_constraint = None;
+    }
+
+    /// Adds a small random force to every node every tick, see [`BrownianNoise`].
+    pub fn set_brownian_noise(&mut self, brownian_noise: BrownianNoise) {
+        self.brownian_noise = Some(brownian_noise);
+    }
+
+    /// Returns the Brownian noise, if any.
+    #[must_use]
+    pub fn brownian_noise(&self) -> Option<&BrownianNoise> {
+        self.brownian_noise.as_ref()
+    }
+
+    /// Removes the Brownian noise.
+    pub fn clear_brownian_noise(&mut self) {
+        self.brownian_noise = None;
+    }
+
+    /// Pushes nodes away from other parts of the path that come too close, see [`SelfAvoidance`].
+    pub fn set_self_avoidance(&mut self, self_avoidance: SelfAvoidance) {
+        self.self_avoidance = Some(self_avoidance);
+    }
+
+    /// Returns the self-avoidance, if any.
+    #[must_use]
+    pub fn self_avoidance(&self) -> Option<&SelfAvoidance> {
+        self.self_avoidance.as_ref()
+    }
+
+    /// Removes the self-avoidance.
+    pub fn clear_self_avoidance(&mut self) {
+        self.self_avoidance = None;
+    }
+
+    /// Applies the same force to every node every tick, like gravity or wind, to bias
+    /// growth in a direction for overhanging or dripping shapes. Like every force it is
+    /// divided by the mass of a node. A zero vector, the default, turns it off.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Vector2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_constant_force(Vector2::new(0.0, -0.2));
+    ///
+    /// for _ in 0..50 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// // The whole path sagged down.
+    /// let (min, max) = differential_growth.bounding_box().unwrap();
+    /// assert!(min.y + max.y < 0.0);
+    /// ```
+    ///
+    pub fn set_constant_force(&mut self, force: Vector2<f64>) {
+        self.constant_force = force;
+    }
+
+    /// Returns the force applied to every node every tick.
+    #[must_use]
+    pub fn constant_force(&self) -> Vector2<f64> {
+        self.constant_force
+    }
+
+    /// Keeps all nodes within a rectangular world, see [`Boundary`].
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = Some(boundary);
+        self.neighbor_search.set_period(boundary.period());
+    }
+
+    /// Returns the boundary, if any.
+    #[must_use]
+    pub fn boundary(&self) -> Option<&Boundary> {
+        self.boundary.as_ref()
+    }
+
+    /// Removes the boundary, nodes can move anywhere again.
+    pub fn clear_boundary(&mut self) {
+        self.boundary = None;
+        self.neighbor_search.set_period(None);
+    }
+
+    /// Adds a static shape the nodes are pushed out of, see [`Obstacle`]. The nodes grow
+    /// around polygons, they are put back on the closest edge when they end up inside one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Obstacle;
+    /// use nalgebra::Point2;
+    ///
+    /// // A wall right above the path.
+    /// let wall = vec![Point2::new(-40.0, 14.0), Point2::new(40.0, 14.0), Point2::new(40.0, 20.0), Point2::new(-40.0, 20.0)];
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.add_obstacle(Obstacle::Polygon { points: wall, margin: 3.0, strength: 0.5 });
+    /// differential_growth.tick_n(200);
+    ///
+    /// assert!(differential_growth.nodes().iter().all(|node| node.position.y <= 14.0));
+    /// assert_eq!(differential_growth.obstacles().len(), 1);
+    /// ```
+    ///
+    pub fn add_obstacle(&mut self, obstacle: Obstacle) {
+        self.obstacles.push(obstacle);
+    }
+
+    /// Returns the obstacles.
+    #[must_use]
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    /// Returns the obstacles to move or reshape them between ticks, see [`Obstacle::translate()`].
+    pub fn obstacles_mut(&mut self) -> &mut [Obstacle] {
+        &mut self.obstacles
+    }
+
+    /// Removes and returns the obstacle at `index`, or `None` when there is no obstacle at `index`.
+    /// The obstacles after it move down one index.
+    pub fn remove_obstacle(&mut self, index: usize) -> Option<Obstacle> {
+        (index < self.obstacles.len()).then(|| self.obstacles.remove(index))
+    }
+
+    /// Removes all obstacles.
+    pub fn clear_obstacles(&mut self) {
+        self.obstacles.clear();
+    }
+
+    /// Simulates nodes far away from a point of focus less often, see [`LevelOfDetail`].
+    pub fn set_level_of_detail(&mut self, level_of_detail: LevelOfDetail) {
+        self.level_of_detail = Some(level_of_detail);
+    }
+
+    /// Returns the level of detail, if any.
+    #[must_use]
+    pub fn level_of_detail(&self) -> Option<&LevelOfDetail> {
+        self.level_of_detail.as_ref()
+    }
+
+    /// Moves the point of focus of the level of detail, for example to follow a camera.
+    /// Has no effect without a level of detail.
+    pub fn set_focus(&mut self, focus: Point2<f64>) {
+        if let Some(level_of_detail) = self.level_of_detail.as_mut() {
+            level_of_detail.focus = focus;
+        }
+    }
+
+    /// Removes the level of detail, all nodes are updated every tick again.
+    pub fn clear_level_of_detail(&mut self) {
+        self.level_of_detail = None;
+    }
+
+    fn apply_boundary(&mut self) {
+        let boundary: Boundary = match self.boundary {
+            Some(boundary) => boundary,
+            None => return,
+        };
+
+        let keep: Vec<bool> = self.nodes.iter_mut().map(|node| boundary.apply(node)).collect();
+        self.remove_nodes(&keep);
+    }
+
+    // Puts the nodes that ended up inside an obstacle back out. Frozen nodes stay where they are.
+    fn apply_obstacles(&mut self) {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            if self.frozen.get(i).is_some_and(|&frozen| frozen) {
+                continue;
+            }
+            for obstacle in &self.obstacles {
+                obstacle.keep_out(node);
+            }
+        }
+    }
+
+    // Removes the nodes that aren't kept, their neighbors on either side become neighbors.
+    pub(crate) fn remove_nodes(&mut self, keep: &[bool]) {
+        if keep.iter().all(|&keep| keep) {
+            return;
+        }
+
+        let mut removed: usize = 0;
+        for (i, &keep) in keep.iter().enumerate() {
+            if !keep {
+                self.push_node_event(NodeEvent::Removed {
+                    index: i - removed,
+                    id: self.nodes[i].id,
+                });
+                removed.add_assign(1);
+            }
+        }
+
+        let mut index: usize = 0;
+        self.nodes.retain(|_| {
+            index.add_assign(1);
+            keep[index - 1]
+        });
+        self.neighbor_search.invalidate();
+
+        if self.has_node_values() {
+            let sources: Vec<Source> = (0..keep.len()).filter(|&i| keep[i]).map(Source::Node).collect();
+            self.remap_node_values(&sources);
+        }
+    }
+
+    /// Makes the last node of an open path follow `trajectory`, while the rest of the
+    /// path grows and relaxes behind it. The leader ignores all forces and is moved to
+    /// the position of the trajectory at the start of every tick.
+    ///
+    /// Has no effect on closed paths, they have no endpoint to lead with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{PolylineTrajectory, Topology};
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .topology(Topology::Open)
+    ///     .build();
+    /// differential_growth.set_leader(PolylineTrajectory::new(vec![Point2::new(1.0, 0.0), Point2::new(101.0, 0.0)], 1.0));
+    ///
+    /// for _ in 0..50 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// let leader = differential_growth.nodes().last().unwrap();
+    /// assert_eq!(leader.position, Point2::new(50.0, 0.0));
+    /// assert!(differential_growth.nodes().len() > 2);
+    /// ```
+    ///
+    pub fn set_leader(&mut self, trajectory: impl Trajectory + 'static) {
+        self.leader = Some(Box::new(trajectory));
+    }
+
+    /// Stops the leader from following its trajectory, it becomes a regular node again.
+    pub fn clear_leader(&mut self) {
+        self.leader = None;
+    }
+
+    // The index of the node that is following the trajectory, if any.
+    pub(crate) fn leader_index(&self) -> Option<usize> {
+        match (self.topology, &self.leader) {
+            (Topology::Open, Some(_)) => self.nodes.len().checked_sub(1),
+            _ => None,
+        }
+    }
+
+    fn move_leader(&mut self) {
+        let leader_index: usize = match self.leader_index() {
+            Some(leader_index) => leader_index,
+            None => return,
+        };
+
+        if let Some(trajectory) = self.leader.as_mut() {
+            let leader: &mut Node = &mut self.nodes[leader_index];
+            leader.position = trajectory.position(self.iteration);
+            leader.velocity = Vector2::default();
+            leader.acceleration = Vector2::default();
+        }
+    }
+
+    /// Enables a [`Watchdog`] that rolls the simulation back to a recent snapshot
+    /// when it blows up. A first snapshot is taken right away.
+    pub fn set_watchdog(&mut self, watchdog: Watchdog) {
+        self.watchdog = Some(WatchdogState {
+            config: watchdog,
+            snapshot: self.capture_snapshot(),
+            events: Vec::new(),
+        });
+    }
+
+    /// Disables the watchdog, dropping its snapshot and unread events.
+    pub fn clear_watchdog(&mut self) {
+        self.watchdog = None;
+    }
+
+    /// Returns the recoveries the watchdog made since the events were last taken.
+    #[must_use]
+    pub fn watchdog_events(&self) -> &[WatchdogEvent] {
+        self.watchdog.as_ref().map_or(&[], |watchdog| &watchdog.events)
+    }
+
+    /// Returns and forgets the recoveries the watchdog made.
+    pub fn take_watchdog_events(&mut self) -> Vec<WatchdogEvent> {
+        self.watchdog
+            .as_mut()
+            .map(|watchdog| core::mem::take(&mut watchdog.events))
+            .unwrap_or_default()
+    }
+
+    /// Starts or stops recording the nodes that are inserted and removed, so copies of the
+    /// nodes like GPU vertex buffers or per-node attributes can be kept in sync
+    /// without rebuilding them every frame. Stopping drops the unread events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::NodeEvent;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .min_edge_length(2.0)
+    ///     .build();
+    /// differential_growth.record_node_events(true);
+    ///
+    /// // A copy of the birth tick of every node, kept in sync with the events.
+    /// let mut birth_ticks: Vec<usize> = vec![0; 10];
+    /// for _ in 0..50 {
+    ///     differential_growth.tick();
+    ///     for event in differential_growth.take_node_events() {
+    ///         match event {
+    ///             NodeEvent::Inserted { index, .. } => birth_ticks.insert(index, differential_growth.iteration()),
+    ///             NodeEvent::Removed { index, .. } => {
+    ///                 birth_ticks.remove(index);
+    ///             }
+    ///             NodeEvent::Reset => birth_ticks = differential_growth.nodes().iter().map(|node| node.birth_tick).collect(),
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(birth_ticks.len(), differential_growth.nodes().len());
+    /// ```
+    ///
+    pub fn record_node_events(&mut self, record: bool) {
+        self.node_events = if record { Some(self.node_events.take().unwrap_or_default()) } else { None };
+    }
+
+    /// Returns the nodes inserted and removed since the events were last taken, in order.
+    #[must_use]
+    pub fn node_events(&self) -> &[NodeEvent] {
+        self.node_events.as_deref().unwrap_or_default()
+    }
+
+    /// Returns and forgets the nodes inserted and removed, in order.
+    pub fn take_node_events(&mut self) -> Vec<NodeEvent> {
+        self.node_events.as_mut().map(core::mem::take).unwrap_or_default()
+    }
+
+    /// Calls `observer` during every tick, see [`Observer`].
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Removes all observers.
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    /// Adds `force` to the forces on the nodes every tick, see [`Force`].
+    pub fn add_force(&mut self, force: impl Force + 'static) {
+        self.forces.push(Arc::new(force));
+    }
+
+    /// Removes all forces added with [`DifferentialGrowth::add_force()`].
+    pub fn clear_forces(&mut self) {
+        self.forces.clear();
+    }
+
+    /// Returns whether both simulations are equal like with `==`, except that the positions,
+    /// velocities and accelerations of their nodes only need to be within `epsilon` of each other.
+    /// Useful to compare results that went through floating point operations in another order,
+    /// like another integrator or a round trip through a text format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mut nudged = differential_growth.clone();
+    /// nudged.with_nodes_mut(|nodes| nodes[0].position.x += 1e-12).unwrap();
+    /// assert!(nudged != differential_growth);
+    /// assert!(nudged.approx_eq(&differential_growth, 1e-9));
+    /// ```
+    ///
+    #[must_use]
+    pub fn approx_eq(&self, other: &DifferentialGrowth, epsilon: f64) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self.nodes.iter().zip(&other.nodes).all(|(a, b)| a.approx_eq(b, epsilon))
+            && self.settings_eq(other)
+    }
+
+    // Compares everything `==` compares except the nodes.
+    #[allow(deprecated)]
+    fn settings_eq(&self, other: &DifferentialGrowth) -> bool {
+        self.max_force == other.max_force
+            && self.max_speed == other.max_speed
+            && self.desired_separation == other.desired_separation
+            && self.separation_cohesion_ration == other.separation_cohesion_ration
+            && self.max_edge_length == other.max_edge_length
+            && self.topology == other.topology
+            && self.growth_probability == other.growth_probability
+            && self.min_edge_length == other.min_edge_length
+            && self.degenerate_epsilon == other.degenerate_epsilon
+            && self.max_nodes == other.max_nodes
+            && self.warmup_ticks == other.warmup_ticks
+            && self.damping == other.damping
+            && self.alignment == other.alignment
+            && self.curvature_gain == other.curvature_gain
+            && self.redistribution == other.redistribution
+            && self.prevent_self_intersections == other.prevent_self_intersections
+            && self.integrator == other.integrator
+            && self.cohesion_target == other.cohesion_target
+            && self.cohesion_strength == other.cohesion_strength
+            && self.separation_kernel == other.separation_kernel
+            && self.edge_separation == other.edge_separation
+            && self.endpoint_condition == other.endpoint_condition
+            && self.overlays == other.overlays
+            && self.param_schedule == other.param_schedule
+            && self.guide_path == other.guide_path
+            && self.boundary == other.boundary
+            && self.obstacles == other.obstacles
+            && self.growth_mask == other.growth_mask
+            && self.level_of_detail == other.level_of_detail
+            && self.radial_gradient == other.radial_gradient
+            && self.trail_map == other.trail_map
+            && self.brightness_map == other.brightness_map
+            && self.aspect_constraint == other.aspect_constraint
+            && self.brownian_noise == other.brownian_noise
+            && self.self_avoidance == other.self_avoidance
+            && self.renormalization == other.renormalization
+            && self.oscillation == other.oscillation
+            && self.origin == other.origin
+            && self.constant_force == other.constant_force
+            && self.deterministic == other.deterministic
+            && self.external_forces == other.external_forces
+            && self.frozen == other.frozen
+            && self.rng == other.rng
+            && self.neighbor_search.policy == other.neighbor_search.policy
+            && self.iteration == other.iteration
+            && self.next_node_id == other.next_node_id
+            && self.node_params == other.node_params
+    }
+
+    /// Returns whether any observer asks to stop the simulation.
+    #[must_use]
+    pub fn should_stop(&self) -> bool {
+        self.observers.iter().any(|observer| observer.should_stop())
+    }
+
+    // The observers are taken out while they are called, so they can read the rest of the simulation.
+    fn notify_observers(&mut self, mut notify: impl FnMut(&mut dyn Observer, &DifferentialGrowth)) {
+        if self.observers.is_empty() {
+            return;
+        }
+
+        let mut observers: Vec<Box<dyn Observer>> = core::mem::take(&mut self.observers);
+        for observer in observers.iter_mut() {
+            notify(observer.as_mut(), self);
+        }
+        self.observers = observers;
+    }
+
+    pub(crate) fn push_node_event(&mut self, event: NodeEvent) {
+        if let Some(node_events) = self.node_events.as_mut() {
+            node_events.push(event);
+        }
+    }
+
+    pub(crate) fn capture_snapshot(&self) -> Snapshot {
+        Snapshot {
+            nodes: self.nodes.clone(),
+            node_data: self.node_data.clone(),
+            node_params: self.node_params.clone(),
+            frozen: self.frozen.clone(),
+            overlays: self.overlays.clone(),
+            rng: self.rng.clone(),
+            iteration: self.iteration,
+        }
+    }
+
+    pub(crate) fn restore_snapshot(&mut self, snapshot: Snapshot) {
+        self.nodes = snapshot.nodes;
+        self.node_data = snapshot.node_data;
+        self.node_params = snapshot.node_params;
+        self.frozen = snapshot.frozen;
+        self.external_forces.clear();
+        self.push_node_event(NodeEvent::Reset);
+        self.overlays = snapshot.overlays;
+        self.rng = snapshot.rng;
+        self.iteration = snapshot.iteration;
+        self.neighbor_search.invalidate();
+    }
+
+    fn take_snapshot_if_due(&mut self) {
+        let is_due: bool = self.watchdog.as_ref().is_some_and(|watchdog| {
+            self.iteration.saturating_sub(watchdog.snapshot.iteration) >= watchdog.config.snapshot_interval.max(1)
+        });
+        if is_due {
+            let snapshot: Snapshot = self.capture_snapshot();
+            if let Some(watchdog) = self.watchdog.as_mut() {
+                watchdog.snapshot = snapshot;
+            }
+        }
+    }
+
+    // Rolls back to the last snapshot with damped parameters if the last step blew up.
+    fn recover_from_blow_up(&mut self, previous_positions: &[Point2<f64>]) -> bool {
+        let watchdog: &WatchdogState = match &self.watchdog {
+            Some(watchdog) => watchdog,
+            None => return false,
+        };
+        let reason = match watchdog.config.detect(&self.nodes, previous_positions) {
+            Some(reason) => reason,
+            None => return false,
+        };
+
+        let snapshot: Snapshot = watchdog.snapshot.clone();
+        let damping: f64 = watchdog.config.damping;
+        let iteration: usize = self.iteration;
+
+        self.restore_snapshot(snapshot);
+        self.set_max_speed(self.max_speed * damping);
+        self.set_max_force(self.max_force * damping);
+
+        let event: WatchdogEvent = WatchdogEvent {
+            iteration,
+            rolled_back_to: self.iteration,
+            reason,
+            max_speed: self.max_speed,
+            max_force: self.max_force,
+        };
+        if let Some(watchdog) = self.watchdog.as_mut() {
+            watchdog.events.push(event);
+        }
+
+        true
+    }
+
+    fn advance_overlays(&mut self) {
+        for overlay in self.overlays.iter_mut() {
+            overlay.remaining_ticks = overlay.remaining_ticks.saturating_sub(1);
+        }
+        self.overlays.retain(|overlay| overlay.remaining_ticks > 0);
+    }
+
+    pub(crate) fn take_node_id(&mut self) -> usize {
+        self.next_node_id.add_assign(1);
+        self.next_node_id - 1
+    }
+
+    fn insert_node_at(&mut self, node: Node, index: usize) {
+        self.nodes.insert(index, node);
+    }
+
+    // Returns which of the nodes were inserted.
+    fn growth(&mut self, dt: f64) -> Vec<bool> {
+        // The chance an edge splits at least once during `dt` ticks.
+        let growth_probability: f64 = if dt == 1.0 {
+            self.growth_probability
+        } else {
+            1.0 - (1.0 - self.growth_probability).powf(dt)
+        };
+
+        let mut new_nodes: Vec<(Node, usize)> = Vec::with_capacity(self.nodes.len());
+        let mut split_edges: Vec<(usize, usize)> = Vec::new();
+        let mut amount_nodes_added = 0;
+
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            let n1: &Node = &self.nodes[i];
+            let n2: &Node = &self.nodes[j];
+
+            let edge: Vector2<f64> = self.difference(&n1.position, &n2.position);
+            // The edge goes across a periodic boundary when that is shorter.
+            let across: bool = edge != n2.position - n1.position;
+            let distance: f64 = edge.norm();
+            let middle: Point2<f64> = self.middle(&n1.position, &n2.position);
+            let max_edge_length: f64 = self.max_edge_length_at(&middle);
+
+            // A growth multiplier of 0 results in an infinite edge length so nothing splits.
+            let (max_edge_length, growth_probability): (f64, f64) =
+                if self.overlays.is_empty() && self.radial_gradient.is_none() && self.trail_map.is_none() {
+                    (max_edge_length, growth_probability)
+                } else {
+                    let mut multiplier: f64 = overlay::growth_multiplier(&self.overlays, &middle);
+                    if let Some(radial_gradient) = &self.radial_gradient {
+                        multiplier.mul_assign(radial_gradient.growth_multiplier_at(&middle));
+                    }
+                    if let Some(trail_map) = &self.trail_map {
+                        multiplier.mul_assign(trail_map.growth_multiplier_at(&middle));
+                    }
+                    (max_edge_length / multiplier, growth_probability * multiplier)
+                };
+            // Only the probability, the image doesn't change the spacing of the nodes.
+            let growth_probability: f64 = match &self.brightness_map {
+                Some(brightness_map) => growth_probability * brightness_map.growth_weight_at(&middle),
+                None => growth_probability,
+            };
+
+            if distance > max_edge_length {
+                if !self.grows_at(&middle) {
+                    continue;
+                }
+
+                #[cfg(feature = "rhai")]
+                if let Some(script) = self.script.as_mut() {
+                    if !script.grow(&middle, distance) {
+                        continue;
+                    }
+                }
+
+                if self.redistribution && self.redistribute(i, j, max_edge_length) {
+                    continue;
+                }
+
+                if self.max_nodes.is_some_and(|max_nodes| self.nodes.len() + amount_nodes_added >= max_nodes) {
+                    break;
+                }
+
+                // Only draw random numbers when needed so the default
+                // doesn't pay for them.
+                if growth_probability < 1.0 && self.rng.next_f64() >= growth_probability {
+                    continue;
+                }
+
+                // Inserting new nodes shifts the index of the original nodes.
+                // To compensate we shift the index with it.
+                let index: usize = i + 1 + amount_nodes_added;
+                amount_nodes_added.add_assign(1);
+                // Insertion strategies only see where the nodes are, so an edge across
+                // a periodic boundary is split in its middle instead.
+                let position: Point2<f64> = if across {
+                    middle
+                } else {
+                    self.insertion_strategy.insertion_point(&self.nodes, i, j, self.topology)
+                };
+                let mut node: Node = Node::new(position, self.max_speed, self.max_force);
+                node.mass = (self.nodes[i].mass + self.nodes[j].mass) / 2.0;
+                // Counts as born at the end of this tick, so it has an age of 0 afterwards.
+                node.birth_tick = self.iteration + 1;
+                node.id = self.take_node_id();
+                new_nodes.push((node, index));
+                split_edges.push((i, j));
+            }
+        }
+
+        if !new_nodes.is_empty() {
+            self.neighbor_search.invalidate();
+        }
+
+        let mut inserted: Vec<bool> = vec![false; self.nodes.len() + new_nodes.len()];
+        for new_node in new_nodes {
+            inserted[new_node.1] = true;
+            self.insert_node_at(new_node.0, new_node.1);
+            self.push_node_event(NodeEvent::Inserted {
+                index: new_node.1,
+                id: new_node.0.id,
+            });
+        }
+
+        if self.has_node_values() {
+            let mut sources: Vec<Source> = Vec::with_capacity(inserted.len());
+            let mut split_edges = split_edges.into_iter();
+            let mut original: usize = 0;
+            for &is_inserted in &inserted {
+                if is_inserted {
+                    if let Some((i, j)) = split_edges.next() {
+                        sources.push(Source::Between(i, j));
+                    }
+                } else {
+                    sources.push(Source::Node(original));
+                    original.add_assign(1);
+                }
+            }
+            self.remap_node_values(&sources);
+        }
+
+        inserted
+    }
+
+    // Puts the nodes of crossing edges back at their previous positions until nothing
+    // crosses. Every round puts back at least one node, so this ends at the latest
+    // when every node is back where it was.
+    fn undo_crossing_moves(&mut self, previous_positions: &[Point2<f64>]) {
+        let len: usize = self.nodes.len();
+
+        loop {
+            let mut undone: bool = false;
+
+            for intersection in self.self_intersections() {
+                for edge in [intersection.first, intersection.second] {
+                    for i in [Some(edge), self.topology.next(edge, len)].into_iter().flatten() {
+                        if self.nodes[i].position != previous_positions[i] {
+                            self.nodes[i].position = previous_positions[i];
+                            self.nodes[i].velocity = Vector2::default();
+                            undone = true;
+                        }
+                    }
+                }
+            }
+
+            if !undone {
+                break;
+            }
+        }
+    }
+
+    // Slides node `i` towards `j`, or `j` towards `i`, just far enough to bring the edge
+    // back to `max_edge_length`, if the edge on the other side of the node stays short enough.
+    fn redistribute(&mut self, i: usize, j: usize, max_edge_length: f64) -> bool {
+        let len: usize = self.nodes.len();
+        let leader_index: Option<usize> = self.leader_index();
+        let candidates: [(usize, usize, Option<usize>); 2] =
+            [(i, j, self.topology.prev(i, len)), (j, i, self.topology.next(j, len))];
+
+        for (moving, towards, other) in candidates {
+            let other: usize = match other {
+                Some(other) if other != towards && Some(moving) != leader_index && !self.is_node_frozen(moving) => other,
+                _ => continue,
+            };
+
+            let position: Point2<f64> = self.nodes[moving].position;
+            let edge: Vector2<f64> = self.difference(&position, &self.nodes[towards].position);
+            let length: f64 = edge.norm();
+            let slid: Point2<f64> = self.wrap_point(position + edge * ((length - max_edge_length) / length));
+
+            if self.difference(&slid, &self.nodes[other].position).norm() <= max_edge_length * MAX_REDISTRIBUTED_EDGE {
+                self.nodes[moving].position = slid;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Keeps `inserted` in line with the nodes, a merged node counts as moved.
+    fn prune(&mut self, inserted: &mut Vec<bool>) {
+        let min_edge_length: f64 = match self.min_edge_length {
+            Some(min_edge_length) => min_edge_length,
+            None => return,
+        };
+
+        // Never prune a path down to something that can't form a path anymore.
+        let min_nodes: usize = self.topology.min_nodes();
+        let clamped_endpoints: Option<(usize, usize)> = self.clamped_endpoints();
+
+        let len: usize = self.nodes.len();
+        let mut pruned: Vec<Node> = Vec::with_capacity(len);
+        let mut pruned_inserted: Vec<bool> = Vec::with_capacity(len);
+        let mut sources: Vec<Source> = Vec::with_capacity(len);
+        let mut events: Vec<NodeEvent> = Vec::new();
+        let mut i: usize = 0;
+
+        while i < len {
+            let node: Node = self.nodes[i];
+            let remaining: usize = pruned.len() + (len - i);
+
+            // The wrapping edge of a closed path is handled after the loop.
+            if i + 1 < len
+                && remaining > min_nodes
+                && self.difference(&node.position, &self.nodes[i + 1].position).norm() < min_edge_length
+            {
+                let mut merged: Node = merge_nodes(&node, &self.nodes[i + 1], self.middle(&node.position, &self.nodes[i + 1].position));
+                // A clamped endpoint keeps its place when its neighbor merges into it.
+                match clamped_endpoints {
+                    Some((first, _)) if i == first => merged.position = node.position,
+                    Some((_, last)) if i + 1 == last => merged.position = self.nodes[i + 1].position,
+                    _ => {}
+                }
+                pruned.push(merged);
+                // The merged node takes the place and the id of the first node.
+                events.push(NodeEvent::Removed {
+                    index: pruned.len(),
+                    id: self.nodes[i + 1].id,
+                });
+                pruned_inserted.push(false);
+                sources.push(Source::Between(i, i + 1));
+                i.add_assign(2);
+            } else {
+                pruned.push(node);
+                pruned_inserted.push(inserted[i]);
+                sources.push(Source::Node(i));
+                i.add_assign(1);
+            }
+        }
+
+        if self.topology == Topology::Closed && pruned.len() > min_nodes {
+            let last: Node = pruned[pruned.len() - 1];
+            if self.difference(&last.position, &pruned[0].position).norm() < min_edge_length {
+                let first_id: usize = pruned[0].id;
+                pruned[0] = merge_nodes(&last, &pruned[0], self.middle(&last.position, &pruned[0].position));
+                pruned[0].id = first_id;
+                pruned_inserted[0] = false;
+                // The last and first node of the original path end up in the merged node.
+                sources[0] = Source::Between(len - 1, 0);
+                pruned.pop();
+                pruned_inserted.pop();
+                sources.pop();
+                events.push(NodeEvent::Removed {
+                    index: pruned.len(),
+                    id: last.id,
+                });
+            }
+        }
+
+        self.remap_node_values(&sources);
+
+        if let Some(node_events) = self.node_events.as_mut() {
+            node_events.append(&mut events);
+        }
+
+        if pruned.len() != self.nodes.len() {
+            self.neighbor_search.invalidate();
+        }
+
+        self.nodes = pruned;
+        *inserted = pruned_inserted;
+    }
+
+    // Returns the applied forces when there are observers to pass them to.
+    fn differentiate(&mut self, dt: f64) -> Option<AppliedForces> {
+        let active: Option<Vec<bool>> = self
+            .level_of_detail
+            .map(|level_of_detail| level_of_detail.active(self.nodes.iter().map(|node| node.position), self.iteration));
+        let leader_index: Option<usize> = self.leader_index();
+        let clamped_endpoints: Option<(usize, usize)> = self.clamped_endpoints();
+
+        // The leader only moves along its trajectory, clamped endpoints and frozen nodes
+        // don't move at all and nodes outside the level of detail skip this tick.
+        let mut moving: Vec<usize> = core::mem::take(&mut self.scratch.moving);
+        moving.clear();
+        moving.extend((0..self.nodes.len()).filter(|&i| {
+            Some(i) != leader_index
+                && !self.is_node_frozen(i)
+                && clamped_endpoints.map_or(true, |(first, last)| i != first && i != last)
+                && active.as_ref().map_or(true, |active| active[i])
+        }));
+
+        self.apply_external_forces(&moving);
+        let applied_forces: Option<AppliedForces> = self.integrate(&moving, dt);
+        self.scratch.moving = moving;
+
+        applied_forces
+    }
+
+    // Adds the forces at the current positions to the acceleration of the moving nodes.
+    pub(crate) fn accumulate_forces(&mut self, moving: &[usize]) -> Option<AppliedForces> {
+        // A deserialized simulation only knows its boundary is periodic from here on.
+        self.neighbor_search.set_period(self.period());
+        self.profiled(Phase::NeighborIndex, |differential_growth| {
+            differential_growth.neighbor_search.update(&differential_growth.nodes)
+        });
+        self.arrays.load(&self.nodes);
+
+        let mut active: Vec<bool> = core::mem::take(&mut self.scratch.active);
+        active.clear();
+        active.resize(self.nodes.len(), false);
+        for &i in moving {
+            active[i] = true;
+        }
+        let mut separation_forces: Vec<Vector2<f64>> = core::mem::take(&mut self.scratch.separation_forces);
+        self.profiled(Phase::Separation, |differential_growth| {
+            differential_growth.get_separation_forces(Some(&active), &mut separation_forces)
+        });
+        self.separation_sums = None;
+        let mut cohesion_forces: Vec<Vector2<f64>> = core::mem::take(&mut self.scratch.cohesion_forces);
+        self.profiled(Phase::Cohesion, |differential_growth| {
+            differential_growth.get_edge_cohesion_forces(Some(&active), &mut cohesion_forces)
+        });
+        let alignment_forces: Option<Vec<Vector2<f64>>> = (self.alignment != 0.0).then(|| self.get_alignment_forces(&active));
+        let curvature_forces: Option<Vec<Vector2<f64>>> = (self.curvature_gain != 0.0).then(|| self.get_curvature_forces(&active));
+        let self_avoidance_forces: Option<Vec<Vector2<f64>>> = self
+            .self_avoidance
+            .map(|self_avoidance| self.get_self_avoidance_forces(&self_avoidance, &active));
+        let custom_forces: Option<Vec<Vector2<f64>>> = (!self.forces.is_empty()).then(|| {
+            let mut out: Vec<Vector2<f64>> = vec![Vector2::default(); self.nodes.len()];
+            for force in &self.forces {
+                force.accumulate(&self.nodes, &mut out);
+            }
+            out
+        });
+
+        let warmup: f64 = self.warmup();
+
+        let bounding_box: Option<(Point2<f64>, Point2<f64>)> = self.aspect_constraint.and(self.bounding_box());
+        let soft_boundary: Option<Boundary> = self
+            .boundary
+            .filter(|boundary| matches!(boundary.behavior, BoundaryBehavior::Soft { .. }));
+
+        let mut applied_forces: Option<AppliedForces> = (!self.observers.is_empty()).then(|| AppliedForces {
+            separation: vec![Vector2::default(); self.nodes.len()],
+            cohesion: vec![Vector2::default(); self.nodes.len()],
+            alignment: vec![Vector2::default(); self.nodes.len()],
+        });
+
+        for &i in moving {
+            let alignment: Vector2<f64> = match &alignment_forces {
+                Some(alignment_forces) => alignment_forces[i] * self.alignment,
+                None => Vector2::default(),
+            };
+            let (separation, cohesion, alignment): (Vector2<f64>, Vector2<f64>, Vector2<f64>) =
+                self.scale_flocking_forces(i, warmup, separation_forces[i], cohesion_forces[i], alignment);
+
+            self.nodes[i].apply_force(&separation);
+            self.nodes[i].apply_force(&cohesion);
+            if alignment_forces.is_some() {
+                self.nodes[i].apply_force(&alignment);
+            }
+
+            if let Some(applied_forces) = applied_forces.as_mut() {
+                applied_forces.separation[i] = separation;
+                applied_forces.cohesion[i] = cohesion;
+                applied_forces.alignment[i] = alignment;
+            }
+
+            if let Some(guide_path) = &self.guide_path {
+                let force: Vector2<f64> = guide_path.force(&self.nodes[i].position);
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if let Some(trail_map) = &self.trail_map {
+                let force: Vector2<f64> = trail_map.force_at(&self.nodes[i].position);
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if let (Some(aspect_constraint), Some((min, max))) = (&self.aspect_constraint, &bounding_box) {
+                let force: Vector2<f64> = aspect_constraint.force(&self.nodes[i].position, min, max);
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if let Some(boundary) = &soft_boundary {
+                let force: Vector2<f64> = boundary.force(&self.nodes[i].position);
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if !self.obstacles.is_empty() {
+                let force: Vector2<f64> = self.obstacle_force(&self.nodes[i].position);
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if let Some(brownian_noise) = self.brownian_noise.as_mut() {
+                let force: Vector2<f64> = brownian_noise.force();
+                self.nodes[i].apply_force(&(force * warmup));
+            }
+
+            if let Some(curvature_forces) = &curvature_forces {
+                self.nodes[i].apply_force(&(curvature_forces[i] * warmup));
+            }
+
+            if let Some(self_avoidance_forces) = &self_avoidance_forces {
+                self.nodes[i].apply_force(&(self_avoidance_forces[i] * warmup));
+            }
+
+            if let Some(custom_forces) = &custom_forces {
+                self.nodes[i].apply_force(&(custom_forces[i] * warmup));
+            }
+
+            if self.constant_force != Vector2::default() {
+                self.nodes[i].apply_force(&(self.constant_force * warmup));
+            }
+
+            #[cfg(feature = "rhai")]
+            if let Some(script) = self.script.as_mut() {
+                if let Some(force) = script.force(&self.nodes[i].position) {
+                    self.nodes[i].apply_force(&(force * warmup));
+                }
+            }
+        }
+
+        self.keep_force_magnitudes_if_due();
+        self.scratch.active = active;
+        self.scratch.separation_forces = separation_forces;
+        self.scratch.cohesion_forces = cohesion_forces;
+
+        applied_forces
+    }
+
+    // The force of all obstacles on a node at `position`.
+    fn obstacle_force(&self, position: &Point2<f64>) -> Vector2<f64> {
+        self.obstacles.iter().map(|obstacle| obstacle.force(position)).sum()
+    }
+
+    // How much of their strength the forces have this tick.
+    fn warmup(&self) -> f64 {
+        if self.iteration < self.warmup_ticks {
+            (self.iteration + 1) as f64 / self.warmup_ticks as f64
+        } else {
+            1.0
+        }
+    }
+
+    // Scales the separation, cohesion and alignment of node `i` by the separation cohesion
+    // ratio, warmup, overlays and radial gradient.
+    fn scale_flocking_forces(
+        &self,
+        i: usize,
+        warmup: f64,
+        mut separation: Vector2<f64>,
+        mut cohesion: Vector2<f64>,
+        mut alignment: Vector2<f64>,
+    ) -> (Vector2<f64>, Vector2<f64>, Vector2<f64>) {
+        separation.mul_assign(
+            self.field_params_of(i)
+                .map_or(self.separation_cohesion_ratio(), |field_params| field_params.separation_cohesion_ratio),
+        );
+
+        if warmup < 1.0 {
+            separation.mul_assign(warmup);
+            cohesion.mul_assign(warmup);
+            alignment.mul_assign(warmup);
+        }
+
+        if !self.overlays.is_empty() {
+            let multiplier: f64 = overlay::force_multiplier(&self.overlays, &self.nodes[i].position);
+            separation.mul_assign(multiplier);
+            cohesion.mul_assign(multiplier);
+            alignment.mul_assign(multiplier);
+        }
+
+        if let Some(radial_gradient) = &self.radial_gradient {
+            separation.mul_assign(radial_gradient.separation_multiplier_at(&self.nodes[i].position));
+        }
+
+        (separation, cohesion, alignment)
+    }
+
+    // The forces on node `i` at the current positions, one by one, as `accumulate_forces()`
+    // applies them. Brownian noise and scripts are left out, evaluating them changes their state.
+    pub(crate) fn node_forces(&mut self, i: usize) -> NodeForces {
+        self.neighbor_search.set_period(self.period());
+        self.neighbor_search.update(&self.nodes);
+        self.arrays.load(&self.nodes);
+
+        let mut active: Vec<bool> = vec![false; self.nodes.len()];
+        active[i] = true;
+        let warmup: f64 = self.warmup();
+        let position: Point2<f64> = self.nodes[i].position;
+
+        let alignment: Vector2<f64> = if self.alignment != 0.0 {
+            self.get_node_alignment_force(i) * self.alignment
+        } else {
+            Vector2::default()
+        };
+        let (separation, cohesion, alignment): (Vector2<f64>, Vector2<f64>, Vector2<f64>) = self.scale_flocking_forces(
+            i,
+            warmup,
+            self.get_node_separation_force(i, self.edge_index().as_ref()),
+            self.get_node_cohesion_force(i),
+            alignment,
+        );
+
+        let curvature: Vector2<f64> = if self.curvature_gain != 0.0 {
+            self.get_curvature_forces(&active)[i]
+        } else {
+            Vector2::default()
+        };
+        let self_avoidance: Vector2<f64> = match self.self_avoidance {
+            Some(self_avoidance) => self.get_self_avoidance_forces(&self_avoidance, &active)[i],
+            None => Vector2::default(),
+        };
+        let custom: Vector2<f64> = if self.forces.is_empty() {
+            Vector2::default()
+        } else {
+            let mut out: Vec<Vector2<f64>> = vec![Vector2::default(); self.nodes.len()];
+            for force in &self.forces {
+                force.accumulate(&self.nodes, &mut out);
+            }
+            out[i]
+        };
+        let aspect: Vector2<f64> = match (&self.aspect_constraint, self.bounding_box()) {
+            (Some(aspect_constraint), Some((min, max))) => aspect_constraint.force(&position, &min, &max),
+            _ => Vector2::default(),
+        };
+        let external: Vector2<f64> = match self.external_forces.get(i) {
+            Some(force) => force.cap_magnitude(self.nodes[i].max_force),
+            None => Vector2::default(),
+        };
+
+        NodeForces {
+            separation,
+            cohesion,
+            alignment,
+            curvature: curvature * warmup,
+            self_avoidance: self_avoidance * warmup,
+            guide_path: self.guide_path.as_ref().map_or(Vector2::default(), |guide_path| guide_path.force(&position) * warmup),
+            trail_map: self.trail_map.as_ref().map_or(Vector2::default(), |trail_map| trail_map.force_at(&position) * warmup),
+            aspect: aspect * warmup,
+            boundary: self.boundary.map_or(Vector2::default(), |boundary| boundary.force(&position) * warmup),
+            obstacles: self.obstacle_force(&position) * warmup,
+            custom: custom * warmup,
+            constant: self.constant_force * warmup,
+            external,
+        }
+    }
+
+    // What keeps node `i` from moving by force during the next tick, or keeps it in place.
+    pub(crate) fn node_constraints(&self, i: usize) -> Vec<NodeConstraint> {
+        let position: Point2<f64> = self.nodes[i].position;
+        let mut constraints: Vec<NodeConstraint> = Vec::new();
+        if self.leader_index() == Some(i) {
+            constraints.push(NodeConstraint::Leader);
+        }
+        if self.clamped_endpoints().is_some_and(|(first, last)| i == first || i == last) {
+            constraints.push(NodeConstraint::ClampedEndpoint);
+        }
+        if self.is_node_frozen(i) {
+            constraints.push(NodeConstraint::Frozen);
+        }
+        if let Some(level_of_detail) = &self.level_of_detail {
+            if !level_of_detail.active(core::iter::once(position), self.iteration)[0] {
+                constraints.push(NodeConstraint::LevelOfDetail);
+            }
+        }
+        if let Some(boundary) = &self.boundary {
+            if (0..2).any(|axis| position[axis] == boundary.min[axis] || position[axis] == boundary.max[axis]) {
+                constraints.push(NodeConstraint::Boundary);
+            }
+        }
+        constraints
+    }
+
+    // Inactive nodes are skipped this tick and don't need a force.
+    fn get_separation_forces(&self, active: Option<&[bool]>, out: &mut Vec<Vector2<f64>>) {
+        // Sums from the GPU are only computed with the default kernel and without edges.
+        let separation_sums: Option<&[Vector2<f64>]> = self
+            .separation_sums
+            .as_deref()
+            .filter(|separation_sums| separation_sums.len() == self.nodes.len())
+            .filter(|_| self.separation_kernel == SeparationKernel::Inverse && !self.edge_separation);
+        let edge_index: Option<EdgeIndex> = self.edge_index();
+        let force = |i: usize| match (active, separation_sums) {
+            (Some(active), _) if !active[i] => Vector2::default(),
+            (_, Some(separation_sums)) => self.steer_separation(i, separation_sums[i]),
+            (_, None) => self.get_node_separation_force(i, edge_index.as_ref()),
+        };
+
+        // Every node only reads the shared spatial index so this is embarrassingly parallel.
+        parallel::map_into(self.nodes.len(), force, out);
+    }
+
+    fn get_node_separation_force(&self, i: usize, edge_index: Option<&EdgeIndex>) -> Vector2<f64> {
+        let arrays: &NodeArrays = &self.arrays;
+        let (x, y): (f64, f64) = (arrays.xs[i], arrays.ys[i]);
+
+        // We can assume no forces CAN happen outside of desired_separation range and
+        // forces MUST happen withing desired_separation range.
+        let desired_separation: f64 = self.desired_separation_of(i);
+        let close_nodes: Vec<usize> = self.close_nodes(i, desired_separation);
+        let period: Option<Period> = self.period();
+
+        // Every difference is normalized and scaled by the kernel in a single division.
+        // Averaging over the neighbors is skipped since the magnitude is set afterwards anyway.
+        let mut separate_x: f64 = 0.0;
+        let mut separate_y: f64 = 0.0;
+        for close_node in close_nodes {
+            let (mut dx, mut dy): (f64, f64) = (x - arrays.xs[close_node], y - arrays.ys[close_node]);
+            if let Some(period) = &period {
+                let difference: Vector2<f64> = period.wrap(Vector2::new(dx, dy));
+                (dx, dy) = (difference.x, difference.y);
+            }
+            let distance_sq: f64 = dx * dx + dy * dy;
+            if distance_sq > 0.0 {
+                let divisor: f64 = self.separation_kernel.divisor(distance_sq, desired_separation);
+                separate_x.add_assign(dx / divisor);
+                separate_y.add_assign(dy / divisor);
+            }
+        }
+
+        let mut separate_force: Vector2<f64> = Vector2::new(separate_x, separate_y);
+        if let Some(edge_index) = edge_index {
+            separate_force.add_assign(self.edge_separation_sum(i, edge_index, desired_separation));
+        }
+
+        self.steer_separation(i, separate_force)
+    }
+
+    // The nodes within `radius` of node `i`, in the order of the nodes in deterministic mode.
+    // The spatial index must be up to date, like during a tick.
+    pub(crate) fn close_nodes(&self, i: usize, radius: f64) -> Vec<usize> {
+        let mut close_nodes: Vec<usize> = self.neighbor_search.within_radius(&self.nodes, &self.nodes[i], radius);
+        if self.deterministic {
+            close_nodes.sort_unstable();
+        }
+        close_nodes
+    }
+
+    // Steers node `i` away from its neighbors, given the sum of their differences
+    // to the node divided by their squared distances.
+    fn steer_separation(&self, i: usize, mut separate_force: Vector2<f64>) -> Vector2<f64> {
+        let arrays: &NodeArrays = &self.arrays;
+
+        // A zero sum has no direction, setting its magnitude would result in NaN.
+        let magnitude: f64 = separate_force.magnitude();
+        let node: &Node = &self.nodes[i];
+        if magnitude > 0.0 {
+            separate_force.mul_assign(node.max_speed / magnitude);
+        }
+
+        separate_force.sub_assign(Vector2::new(arrays.vxs[i], arrays.vys[i]));
+        separate_force.cap_magnitude(node.max_force)
+    }
+
+    fn get_edge_cohesion_forces(&self, active: Option<&[bool]>, out: &mut Vec<Vector2<f64>>) {
+        let force = |i: usize| match active {
+            Some(active) if !active[i] => Vector2::default(),
+            _ => self.get_node_cohesion_force(i),
+        };
+
+        parallel::map_into(self.nodes.len(), force, out);
+    }
+
+    fn get_alignment_forces(&self, active: &[bool]) -> Vec<Vector2<f64>> {
+        let force = |i: usize| {
+            if active[i] {
+                self.get_node_alignment_force(i)
+            } else {
+                Vector2::default()
+            }
+        };
+
+        parallel::map(self.nodes.len(), force)
+    }
+
+    // Steers towards the average velocity of the nodes within the desired separation, like boids.
+    fn get_node_alignment_force(&self, i: usize) -> Vector2<f64> {
+        let arrays: &NodeArrays = &self.arrays;
+        let close_nodes: Vec<usize> = self.close_nodes(i, self.desired_separation_of(i));
+
+        let mut velocity_x: f64 = 0.0;
+        let mut velocity_y: f64 = 0.0;
+        for close_node in close_nodes {
+            if close_node != i {
+                velocity_x.add_assign(arrays.vxs[close_node]);
+                velocity_y.add_assign(arrays.vys[close_node]);
+            }
+        }
+
+        // Neighbors at rest or moving in opposite directions give no direction to align with.
+        let mut desired: Vector2<f64> = Vector2::new(velocity_x, velocity_y);
+        let magnitude: f64 = desired.magnitude();
+        if magnitude == 0.0 {
+            return Vector2::default();
+        }
+        let node: &Node = &self.nodes[i];
+        desired.mul_assign(node.max_speed / magnitude);
+
+        desired.sub_assign(Vector2::new(arrays.vxs[i], arrays.vys[i]));
+        desired.cap_magnitude(node.max_force)
+    }
+
+    // Pushes along the normal, away from the center of the bend for a positive gain. Only the
+    // curvature in excess of the mean of the path counts, a path that bends evenly like a circle
+    // would otherwise just be inflated or deflated as a whole. The curvature is measured over the
+    // nodes two steps away, so alternating nodes don't amplify into a zigzag.
+    fn get_curvature_forces(&self, active: &[bool]) -> Vec<Vector2<f64>> {
+        let len: usize = self.nodes.len();
+        let wide_neighbors = |i: usize| {
+            let (prev, next): (Option<usize>, Option<usize>) = self.topology.neighbors(i, len);
+            let prev: Option<usize> = prev.and_then(|prev| self.topology.neighbors(prev, len).0);
+            let next: Option<usize> = next.and_then(|next| self.topology.neighbors(next, len).1);
+            prev.zip(next).filter(|(prev, next)| prev != next && *prev != i)
+        };
+
+        let period: Option<Period> = self.period();
+        let bends: Vec<Option<(Vector2<f64>, f64)>> = parallel::map(len, |i| {
+            let (prev, next): (usize, usize) = wide_neighbors(i)?;
+            let b: &Point2<f64> = &self.nodes[i].position;
+            // Across a periodic boundary the neighbors are where they are closest.
+            let around = |j: usize| match &period {
+                Some(period) => b + period.wrap(self.nodes[j].position - b),
+                None => self.nodes[j].position,
+            };
+            let (a, c): (Point2<f64>, Point2<f64>) = (around(prev), around(next));
+            let tangent: Vector2<f64> = c - a;
+            let length: f64 = tangent.magnitude();
+            if length == 0.0 {
+                return None;
+            }
+            // Positive curvature turns counter-clockwise with the center of the bend on the left.
+            let left: Vector2<f64> = Vector2::new(-tangent.y, tangent.x) / length;
+            Some((left, geometry::signed_curvature(&a, b, &c)))
+        });
+
+        let curvatures: Vec<f64> = bends.iter().flatten().map(|(_, curvature)| *curvature).collect();
+        if curvatures.is_empty() {
+            return vec![Vector2::default(); len];
+        }
+        let mean: f64 = parallel::ordered_sum(&curvatures) / curvatures.len() as f64;
+
+        parallel::map(len, |i| match bends[i] {
+            Some((left, curvature)) if active[i] => {
+                let force: Vector2<f64> = left * (-self.curvature_gain * (curvature - mean) * self.desired_separation_of(i));
+                force.cap_magnitude(self.nodes[i].max_force)
+            }
+            _ => Vector2::default(),
+        })
+    }
+
+    fn get_node_cohesion_force(&self, i: usize) -> Vector2<f64> {
+        let arrays: &NodeArrays = &self.arrays;
+
+        // Endpoints of an open path have no midpoint to move towards,
+        // their endpoint condition decides where they go.
+        let offset: Option<Vector2<f64>> = self
+            .cohesion_target
+            .offset(arrays, i, self.topology, self.period().as_ref())
+            .or_else(|| self.endpoint_condition.offset(arrays, i, self.topology, self.period().as_ref()));
+        match (offset, self.cohesion_strength) {
+            (Some(offset), Some(cohesion_strength)) => offset * cohesion_strength,
+            (Some(mut desired), None) => {
+                let node: &Node = &self.nodes[i];
+                if desired.magnitude() != 0.0 {
+                    desired.set_magnitude(node.max_speed);
+                }
+                desired.sub_assign(Vector2::new(arrays.vxs[i], arrays.vys[i]));
+                desired.cap_magnitude(node.max_force)
+            }
+            (None, _) => Vector2::default(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_insertion_strategy() -> Box<dyn InsertionStrategy> {
+    Box::new(Midpoint)
+}
+
+// Merges two nodes into one at `middle`, halfway between them.
+fn merge_nodes(n1: &Node, n2: &Node, middle: Point2<f64>) -> Node {
+    let mut merged: Node = *n1;
+    merged.position = middle;
+    merged.velocity = (n1.velocity + n2.velocity) / 2.0;
+    merged.birth_tick = n1.birth_tick.min(n2.birth_tick);
+    merged.mass = (n1.mass + n2.mass) / 2.0;
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use nalgebra::{distance, Point2};
+
+    use crate::prelude::*;
+    use crate::{differential_growth::DifferentialGrowth, geometry};
+
+    // The corners of a square with sides of 10.
+    fn corners() -> Vec<Point2<f64>> {
+        vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ]
+    }
+
+    // A closed square with sides of 10, starting at the first corner.
+    fn square(nodes_per_side: usize) -> Vec<Point2<f64>> {
+        let corners: Vec<Point2<f64>> = corners();
+        (0..4)
+            .flat_map(|side| {
+                let (a, b): (Point2<f64>, Point2<f64>) = (corners[side], corners[(side + 1) % 4]);
+                (0..nodes_per_side).map(move |k| a + (b - a) * (k as f64 / nodes_per_side as f64))
+            })
+            .collect()
+    }
+
+    // The distance from `point` to the closed polygon through `polygon`.
+    fn distance_to_polygon(polygon: &[Point2<f64>], point: &Point2<f64>) -> f64 {
+        (0..polygon.len())
+            .map(|i| {
+                let (closest, _): (Point2<f64>, f64) =
+                    geometry::closest_point_on_segment(point, &polygon[i], &polygon[(i + 1) % polygon.len()]);
+                distance(point, &closest)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn resampled_closed_path() {
+        let differential_growth = DifferentialGrowth::new(square(2), 1.5, 1.0, 14.0, 1.1, 5.0);
+
+        // The spacing divides the perimeter, the first point isn't repeated at the end.
+        let points: Vec<Point2<f64>> = differential_growth.get_points_resampled(2.5);
+        assert_eq!(points.len(), 16);
+        assert_eq!(points[0], corners()[0]);
+        for i in 0..points.len() {
+            assert!((distance(&points[i], &points[(i + 1) % points.len()]) - 2.5).abs() < 1e-9);
+        }
+
+        // Otherwise the piece back to the first point is shorter.
+        let points: Vec<Point2<f64>> = differential_growth.get_points_resampled(3.0);
+        assert_eq!(points.len(), 14);
+        assert!(points.iter().all(|point| distance_to_polygon(&corners(), point) < 1e-9));
+        assert_eq!(points[13], Point2::new(0.0, 1.0));
+
+        // A spacing of the whole perimeter leaves the first point.
+        assert_eq!(differential_growth.get_points_resampled(40.0), vec![corners()[0]]);
+    }
+
+    #[test]
+    fn simplified_closed_path_keeps_the_corners() {
+        let differential_growth = DifferentialGrowth::new(square(4), 1.5, 1.0, 14.0, 1.1, 5.0);
+        assert_eq!(differential_growth.get_points_simplified(0.1), corners());
+
+        // The first node is always kept, even in the middle of a side.
+        let mut points: Vec<Point2<f64>> = square(4);
+        points.rotate_left(2);
+        let differential_growth = DifferentialGrowth::new(points, 1.5, 1.0, 14.0, 1.1, 5.0);
+        let mut expected: Vec<Point2<f64>> = corners();
+        expected.rotate_left(1);
+        expected.insert(0, Point2::new(5.0, 0.0));
+        assert_eq!(differential_growth.get_points_simplified(0.1), expected);
+    }
+
+    #[test]
+    fn simplified_closed_path_stays_within_epsilon() {
+        let points: Vec<Point2<f64>> = (0..60)
+            .map(|i| {
+                let angle: f64 = 2.0 * PI * i as f64 / 60.0;
+                let radius: f64 = 10.0 + (5.0 * angle).sin();
+                Point2::new(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        let differential_growth = DifferentialGrowth::new(points.clone(), 1.5, 1.0, 14.0, 1.1, 5.0);
+
+        for epsilon in [0.05, 0.5, 2.0] {
+            let simplified: Vec<Point2<f64>> = differential_growth.get_points_simplified(epsilon);
+            assert!(simplified.len() >= 3 && simplified.len() < points.len());
+            assert_eq!(simplified[0], points[0]);
+            // The kept points are a subsequence of the nodes, in order.
+            let mut remaining = points.iter();
+            assert!(simplified.iter().all(|point| remaining.any(|node| node == point)));
+            assert!(points.iter().all(|point| distance_to_polygon(&simplified, point) <= epsilon + 1e-9));
+        }
+    }
+}
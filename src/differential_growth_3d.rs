@@ -0,0 +1,254 @@
+use std::ops::{AddAssign, MulAssign, SubAssign};
+
+use kd_tree::KdTree3;
+use nalgebra::{distance, Point3, Vector3};
+
+use crate::{rng::Rng, spatial::IndexedPoint3, topology::Topology};
+
+/// A single point of a path in space, moved around by the forces acting on it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Node3 {
+    pub position: Point3<f64>,
+    pub velocity: Vector3<f64>,
+    pub acceleration: Vector3<f64>,
+    pub max_force: f64,
+    pub max_speed: f64,
+}
+
+impl Node3 {
+    pub fn new(position: Point3<f64>, max_speed: f64, max_force: f64) -> Node3 {
+        Node3 {
+            position,
+            velocity: Vector3::default(),
+            acceleration: Vector3::default(),
+            max_speed,
+            max_force,
+        }
+    }
+
+    pub fn apply_force(&mut self, force: &Vector3<f64>) {
+        self.acceleration.add_assign(force);
+    }
+
+    pub fn update(&mut self) {
+        self.velocity.add_assign(self.acceleration);
+        self.velocity = self.velocity.cap_magnitude(self.max_speed);
+        self.position.add_assign(self.velocity);
+        self.acceleration.mul_assign(0.0);
+    }
+
+    pub fn seek(&self, target: &Vector3<f64>) -> Vector3<f64> {
+        let mut desired: Vector3<f64> = target - self.position.coords;
+        if desired.magnitude() != 0.0 {
+            desired.set_magnitude(self.max_speed);
+        }
+        let steer: Vector3<f64> = desired - self.velocity;
+        steer.cap_magnitude(self.max_force)
+    }
+}
+
+/// The differential growth algorithm for paths in space.
+///
+/// Works exactly like [`crate::DifferentialGrowth`] but on [`Point3`]s, so the
+/// path can fold in every direction, for example for 3D printing or importing into Blender.
+///
+/// New nodes are displaced by a small random offset, otherwise a path that starts
+/// out flat has no reason to ever leave its plane.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::DifferentialGrowth3D;
+/// use nalgebra::Point3;
+///
+/// let starting_points: Vec<Point3<f64>> = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10)
+///     .into_iter()
+///     .map(|point| Point3::new(point.x, point.y, 0.0))
+///     .collect();
+/// let mut differential_growth = DifferentialGrowth3D::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+///
+/// for _ in 0..50 {
+///     differential_growth.tick();
+/// }
+///
+/// assert!(differential_growth.get_points().iter().any(|point| point.z != 0.0));
+/// ```
+///
+pub struct DifferentialGrowth3D {
+    /// A Vec of Node3 objects.
+    pub nodes: Vec<Node3>,
+    /// The maximum force nodes can exert on eachother.
+    pub max_force: f64,
+    /// The maximum magnitude of a node's velocity.
+    pub max_speed: f64,
+    /// The desired separation between nodes.
+    pub desired_separation: f64,
+    /// The ratio between separation and cohesion forces.
+    pub separation_cohesion_ratio: f64,
+    /// The maximum length between two connected nodes.
+    pub max_edge_length: f64,
+    /// Whether the path is closed or open.
+    pub topology: Topology,
+    /// The maximum distance a new node is displaced from the middle of the split edge.
+    pub insertion_jitter: f64,
+    rng: Rng,
+    iteration: usize,
+}
+
+impl DifferentialGrowth3D {
+    /// Returns a DifferentialGrowth3D instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_nodes` - A Vec of starting points. These are converted into Nodes.
+    /// * `max_force` - The maximum force nodes can exert on eachother.
+    /// * `max_speed` - The maximum magnitude of a node's velocity.
+    /// * `desired_separation` - The desired separation between nodes.
+    /// * `separation_cohesion_ratio` - The ratio between separation and cohesion forces.
+    /// * `max_edge_len` - The maximum length between two connected nodes.
+    ///
+    pub fn new(
+        input_nodes: Vec<Point3<f64>>,
+        max_force: f64,
+        max_speed: f64,
+        desired_separation: f64,
+        separation_cohesion_ratio: f64,
+        max_edge_len: f64,
+    ) -> DifferentialGrowth3D {
+        let nodes: Vec<Node3> = input_nodes
+            .into_iter()
+            .map(|position| Node3::new(position, max_speed, max_force))
+            .collect();
+
+        DifferentialGrowth3D {
+            nodes,
+            max_force,
+            max_speed,
+            desired_separation,
+            separation_cohesion_ratio,
+            max_edge_length: max_edge_len,
+            topology: Topology::Closed,
+            insertion_jitter: max_edge_len * 0.05,
+            rng: Rng::new(0),
+            iteration: 0,
+        }
+    }
+
+    /// Advances the algorithm by 1 iteration.
+    pub fn tick(&mut self) {
+        self.differentiate();
+        self.growth();
+        self.iteration.add_assign(1);
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    #[must_use]
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Get the positions of the current state of the nodes.
+    #[must_use]
+    pub fn get_points(&self) -> Vec<Point3<f64>> {
+        self.nodes.iter().map(|node| node.position).collect()
+    }
+
+    /// Reseeds the random number generator used to displace new nodes.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    fn growth(&mut self) {
+        let mut new_nodes: Vec<(Node3, usize)> = Vec::new();
+        let mut amount_nodes_added: usize = 0;
+
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            let (n1, n2) = (&self.nodes[i], &self.nodes[j]);
+            if distance(&n1.position, &n2.position) > self.max_edge_length {
+                let middle: Point3<f64> = nalgebra::center(&n1.position, &n2.position);
+                let offset: Vector3<f64> = self.random_offset();
+
+                // Inserting new nodes shifts the index of the original nodes.
+                // To compensate we shift the index with it.
+                let index: usize = i + 1 + amount_nodes_added;
+                amount_nodes_added.add_assign(1);
+                new_nodes.push((Node3::new(middle + offset, self.max_speed, self.max_force), index));
+            }
+        }
+
+        for (node, index) in new_nodes {
+            self.nodes.insert(index, node);
+        }
+    }
+
+    fn differentiate(&mut self) {
+        let indexed_points: Vec<IndexedPoint3> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| IndexedPoint3 { position: node.position, index })
+            .collect();
+        let tree: KdTree3<IndexedPoint3> = KdTree3::build_by_ordered_float(indexed_points);
+
+        let forces: Vec<Vector3<f64>> = (0..self.nodes.len())
+            .map(|i| {
+                let mut separation: Vector3<f64> = self.get_node_separation_force(&tree, i);
+                separation.mul_assign(self.separation_cohesion_ratio);
+                separation + self.get_node_cohesion_force(i)
+            })
+            .collect();
+
+        for (node, force) in self.nodes.iter_mut().zip(forces) {
+            node.apply_force(&force);
+            node.update();
+        }
+    }
+
+    fn get_node_separation_force(&self, tree: &KdTree3<IndexedPoint3>, i: usize) -> Vector3<f64> {
+        let nodei: &Node3 = &self.nodes[i];
+        let query: IndexedPoint3 = IndexedPoint3 { position: nodei.position, index: i };
+        let mut separate_force: Vector3<f64> = Vector3::default();
+
+        for close in tree.within_radius(&query, self.desired_separation) {
+            let diff: Vector3<f64> = nodei.position - self.nodes[close.index].position;
+            let distance_sq: f64 = diff.norm_squared();
+            if distance_sq > 0.0 {
+                separate_force.add_assign(diff / distance_sq);
+            }
+        }
+
+        // A zero sum has no direction, setting its magnitude would result in NaN.
+        let magnitude: f64 = separate_force.magnitude();
+        if magnitude > 0.0 {
+            separate_force.mul_assign(self.max_speed / magnitude);
+        }
+
+        separate_force.sub_assign(nodei.velocity);
+        separate_force.cap_magnitude(self.max_force)
+    }
+
+    fn get_node_cohesion_force(&self, i: usize) -> Vector3<f64> {
+        // Endpoints of an open path have no midpoint to move towards
+        // so they are left free.
+        match self.topology.neighbors(i, self.nodes.len()) {
+            (Some(prev), Some(next)) => {
+                let middle: Vector3<f64> = (self.nodes[prev].position.coords + self.nodes[next].position.coords) / 2.0;
+                self.nodes[i].seek(&middle)
+            }
+            _ => Vector3::default(),
+        }
+    }
+
+    fn random_offset(&mut self) -> Vector3<f64> {
+        if self.insertion_jitter == 0.0 {
+            return Vector3::default();
+        }
+
+        let offset: Vector3<f64> = Vector3::new(
+            self.rng.range(-1.0, 1.0),
+            self.rng.range(-1.0, 1.0),
+            self.rng.range(-1.0, 1.0),
+        );
+        offset * self.insertion_jitter
+    }
+}
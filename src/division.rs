@@ -0,0 +1,119 @@
+use nalgebra::distance;
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+/// When the paths of a [`crate::MultiPathGrowth`] divide like cells, see
+/// [`DifferentialGrowth::split_at_pinch()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{DifferentialGrowth, Division, Interaction, MultiPathGrowth};
+/// use nalgebra::Point2;
+///
+/// // Two circles touching in the middle, traced as one path.
+/// let circle = |center_x: f64, start: f64| {
+///     (0..20).map(move |i| {
+///         let angle: f64 = start + core::f64::consts::TAU * i as f64 / 20.0;
+///         Point2::new(center_x + 8.0 * angle.cos(), 8.0 * angle.sin())
+///     })
+/// };
+/// let points: Vec<Point2<f64>> = circle(8.5, core::f64::consts::PI).chain(circle(-8.5, 0.0)).collect();
+///
+/// let mut multi_path_growth = MultiPathGrowth::new(Interaction::RepelAll);
+/// multi_path_growth.add_path(DifferentialGrowth::builder().points(points).build(), 0);
+/// multi_path_growth.division = Some(Division { threshold: 3.0, min_nodes: 10 });
+///
+/// multi_path_growth.tick();
+/// assert_eq!(multi_path_growth.paths().len(), 2);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Division {
+    /// Two nodes closer than this pinch the path. Defaults to `1.0`.
+    pub threshold: f64,
+    /// The minimum amount of nodes of both pieces, so nodes close to eachother
+    /// along the path don't count as a pinch. Defaults to `10`.
+    pub min_nodes: usize,
+}
+
+impl Default for Division {
+    fn default() -> Self {
+        Division {
+            threshold: 1.0,
+            min_nodes: 10,
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Splits the path in two where it pinches: where two nodes are closer than `threshold`
+    /// while both pieces between them have at least `min_nodes` nodes. The stretch between the
+    /// two nodes is cut off as a new closed path with the same parameters and returned, the rest
+    /// stays in this path, closed when it was closed and open when it was open. Returns `None`
+    /// when the path doesn't pinch anywhere.
+    ///
+    /// Of several pinches the tightest is split. Node data and overrides follow the nodes into
+    /// both paths and [`crate::NodeEvent::Reset`] is recorded. The new path has no leader and no observers.
+    ///
+    /// This takes `&mut self` because the spatial index is rebuilt first when
+    /// nodes were inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    /// use nalgebra::Point2;
+    ///
+    /// // A line that curls into a loop, with its two ends touching where the loop starts.
+    /// let mut points: Vec<Point2<f64>> = (0..10).map(|i| Point2::new(-30.0 + 3.0 * i as f64, 0.0)).collect();
+    /// for i in 0..20 {
+    ///     let angle: f64 = -core::f64::consts::FRAC_PI_2 + core::f64::consts::TAU * i as f64 / 20.0;
+    ///     points.push(Point2::new(8.0 * angle.cos(), 8.0 + 8.0 * angle.sin()));
+    /// }
+    /// points.extend((0..9).map(|i| Point2::new(0.5 + 3.0 * i as f64, 0.5)));
+    /// let mut line = differential_growth::DifferentialGrowth::builder()
+    ///     .points(points)
+    ///     .topology(Topology::Open)
+    ///     .build();
+    ///
+    /// let cell = line.split_at_pinch(1.0, 5).unwrap();
+    /// assert_eq!(cell.topology, Topology::Closed);
+    /// assert_eq!(cell.nodes().len(), 20);
+    /// assert_eq!(line.nodes().len(), 19);
+    /// assert!(line.split_at_pinch(1.0, 5).is_none());
+    /// ```
+    ///
+    pub fn split_at_pinch(&mut self, threshold: f64, min_nodes: usize) -> Option<DifferentialGrowth> {
+        let len: usize = self.nodes.len();
+        // The cut off piece is closed, the rest keeps the topology of the path.
+        let min_inside: usize = min_nodes.max(Topology::Closed.min_nodes());
+        let min_outside: usize = min_nodes.max(self.topology.min_nodes());
+        if len < min_inside + min_outside {
+            return None;
+        }
+
+        let close_nodes: Vec<Vec<usize>> = self.neighbor_search.within_radius_of_each(&self.nodes, threshold);
+        let (first, last): (usize, usize) = close_nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, close_nodes)| close_nodes.iter().map(move |&j| (i, j)))
+            .filter(|&(i, j)| i < j && j - i >= min_inside && len - (j - i) >= min_outside)
+            .min_by(|a, b| {
+                let length = |(i, j): (usize, usize)| distance(&self.nodes[i].position, &self.nodes[j].position);
+                length(*a).total_cmp(&length(*b)).then(a.cmp(b))
+            })?;
+
+        let mut piece: DifferentialGrowth = self.clone();
+        piece.topology = Topology::Closed;
+        piece.with_nodes_mut(|nodes| *nodes = nodes[first..last].to_vec()).ok()?;
+        self.with_nodes_mut(|nodes| {
+            nodes.drain(first..last);
+        })
+        .ok()?;
+
+        Some(piece)
+    }
+}
@@ -0,0 +1,96 @@
+use nannou::{
+    color::LinSrgba,
+    geom::{pt2, Point2},
+    Draw,
+};
+
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+/// How [`draw_differential_growth()`] draws the path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DrawMode {
+    /// One polyline through all nodes, back to the first one when the path is closed.
+    Polyline,
+    /// The inside of the path filled like a polygon, also for an open path.
+    Polygon,
+    /// A separate `line()` per edge, like the example used to. Slow for large paths.
+    Lines,
+}
+
+/// How [`draw_differential_growth()`] renders the path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DrawStyle {
+    /// Defaults to [`DrawMode::Polyline`].
+    pub mode: DrawMode,
+    /// The color of the line or the fill. Defaults to black.
+    pub color: LinSrgba,
+    /// The width of the line, unused for [`DrawMode::Polygon`]. Defaults to `1.0`.
+    pub weight: f32,
+}
+
+impl Default for DrawStyle {
+    fn default() -> Self {
+        DrawStyle {
+            mode: DrawMode::Polyline,
+            color: LinSrgba::new(0.0, 0.0, 0.0, 1.0),
+            weight: 1.0,
+        }
+    }
+}
+
+/// Draws the path with nannou, closing it when it is closed.
+///
+/// Paths on a [`crate::BoundaryBehavior::Periodic`] boundary are drawn as returned by
+/// [`DifferentialGrowth::get_unwrapped_points()`], so edges across the boundary don't
+/// streak over the whole window.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use differential_growth::{DrawMode, DrawStyle};
+/// use nannou::prelude::*;
+///
+/// fn view(app: &App, differential_growth: &differential_growth::DifferentialGrowth, frame: Frame) {
+///     let draw: Draw = app.draw();
+///     draw.background().color(MINTCREAM);
+///
+///     let style = DrawStyle { color: NAVY.into_lin_srgba(), weight: 2.0, ..DrawStyle::default() };
+///     differential_growth::draw_differential_growth(&draw, differential_growth, &style);
+///
+///     draw.to_frame(app, &frame).unwrap();
+/// }
+/// ```
+///
+pub fn draw_differential_growth(draw: &Draw, differential_growth: &DifferentialGrowth, style: &DrawStyle) {
+    let points: Vec<Point2> = differential_growth
+        .get_unwrapped_points()
+        .iter()
+        .map(|point| pt2(point.x as f32, point.y as f32))
+        .collect();
+    let closing_point: Option<Point2> = match differential_growth.topology {
+        Topology::Closed if points.len() > 2 => points.first().copied(),
+        _ => None,
+    };
+
+    match style.mode {
+        DrawMode::Polyline => {
+            draw.polyline()
+                .weight(style.weight)
+                .points(points.into_iter().chain(closing_point))
+                .color(style.color);
+        }
+        DrawMode::Polygon => {
+            draw.polygon().color(style.color).points(points);
+        }
+        DrawMode::Lines => {
+            let points: Vec<Point2> = points.into_iter().chain(closing_point).collect();
+            for edge in points.windows(2) {
+                draw.line()
+                    .start(edge[0])
+                    .end(edge[1])
+                    .weight(style.weight)
+                    .color(style.color);
+            }
+        }
+    }
+}
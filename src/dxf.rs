@@ -0,0 +1,48 @@
+use std::{fmt::Write as _, fs, io, path::Path};
+
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+impl DifferentialGrowth {
+    /// Returns a minimal ASCII DXF drawing with the current path as a single
+    /// `LWPOLYLINE` on layer `0`, closed if the path is, for pen plotters and CAD tools.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let dxf: String = differential_growth.to_dxf();
+    /// assert!(dxf.contains("LWPOLYLINE"));
+    /// assert!(dxf.ends_with("0\nEOF\n"));
+    /// ```
+    ///
+    pub fn to_dxf(&self) -> String {
+        let closed: u8 = match self.topology {
+            Topology::Closed => 1,
+            Topology::Open => 0,
+        };
+
+        let mut dxf: String = String::new();
+        // Writing to a String can't fail.
+        let _ = write!(
+            dxf,
+            "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1015\n0\nENDSEC\n\
+             0\nSECTION\n2\nENTITIES\n\
+             0\nLWPOLYLINE\n100\nAcDbEntity\n8\n0\n100\nAcDbPolyline\n90\n{}\n70\n{}\n",
+            self.nodes.len(),
+            closed,
+        );
+        for node in &self.nodes {
+            let _ = write!(dxf, "10\n{}\n20\n{}\n", node.position.x, node.position.y);
+        }
+        dxf.push_str("0\nENDSEC\n0\nEOF\n");
+
+        dxf
+    }
+
+    /// Writes the DXF drawing of [`DifferentialGrowth::to_dxf()`] to a file.
+    pub fn write_dxf(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_dxf())
+    }
+}
@@ -0,0 +1,198 @@
+use core::ops::AddAssign;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, geometry, node::Node, periodic::Period, topology::Topology};
+
+// A uniform grid of the edges of a path, every edge is listed in every cell its bounding box overlaps.
+//
+// Across a periodic boundary an edge is stored once starting at each of its nodes, so both
+// pieces of an edge that wraps around lie within the rectangle of the boundary.
+pub(crate) struct EdgeIndex {
+    cell_size: f64,
+    // The index of every edge with its start and the vector to its end.
+    segments: Vec<(usize, Point2<f64>, Vector2<f64>)>,
+    // The lowest cell every segment is listed in.
+    first_cells: Vec<(i64, i64)>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl EdgeIndex {
+    pub(crate) fn new(nodes: &[Node], topology: Topology, period: Option<&Period>, cell_size: f64) -> EdgeIndex {
+        // Without a usable cell size everything ends up in a single cell.
+        let cell_size: f64 = if cell_size > 0.0 && cell_size.is_finite() {
+            cell_size
+        } else {
+            f64::INFINITY
+        };
+
+        let mut segments: Vec<(usize, Point2<f64>, Vector2<f64>)> = Vec::with_capacity(nodes.len());
+        for (i, j) in topology.edges(nodes.len()) {
+            let (start, end): (Point2<f64>, Point2<f64>) = (nodes[i].position, nodes[j].position);
+            match period {
+                Some(period) => {
+                    let vector: Vector2<f64> = period.wrap(end - start);
+                    segments.push((i, start, vector));
+                    if vector != end - start {
+                        segments.push((i, end, -vector));
+                    }
+                }
+                None => segments.push((i, start, end - start)),
+            }
+        }
+
+        let mut edge_index: EdgeIndex = EdgeIndex {
+            cell_size,
+            first_cells: Vec::with_capacity(segments.len()),
+            segments,
+            cells: HashMap::new(),
+        };
+        for (k, (_, start, vector)) in edge_index.segments.iter().enumerate() {
+            let end: Point2<f64> = start + vector;
+            let min: Point2<f64> = Point2::new(start.x.min(end.x), start.y.min(end.y));
+            let max: Point2<f64> = Point2::new(start.x.max(end.x), start.y.max(end.y));
+            edge_index.first_cells.push(edge_index.cell(&min));
+            for cell in edge_index.cells_between(&min, &max) {
+                edge_index.cells.entry(cell).or_default().push(k);
+            }
+        }
+
+        edge_index
+    }
+
+    // Calls `f` with the segments that might lie within `radius` of `point`, with the index of
+    // their edge, their start and the vector to their end. Every segment is visited once, but
+    // across a periodic boundary an edge can have two segments.
+    fn for_each_near(&self, point: &Point2<f64>, radius: f64, mut f: impl FnMut(usize, &Point2<f64>, &Vector2<f64>)) {
+        let offset: Vector2<f64> = Vector2::new(radius, radius);
+        let (min_x, min_y): (i64, i64) = self.cell(&(point - offset));
+        for cell in self.cells_between(&(point - offset), &(point + offset)) {
+            for &k in self.cells.get(&cell).into_iter().flatten() {
+                // A segment is listed in every cell it overlaps, it is only visited in the lowest
+                // one that is searched as well.
+                let (first_x, first_y): (i64, i64) = self.first_cells[k];
+                if cell != (min_x.max(first_x), min_y.max(first_y)) {
+                    continue;
+                }
+
+                let (edge, start, vector) = &self.segments[k];
+                f(*edge, start, vector);
+            }
+        }
+    }
+
+    fn cell(&self, point: &Point2<f64>) -> (i64, i64) {
+        (
+            (point.x / self.cell_size).floor() as i64,
+            (point.y / self.cell_size).floor() as i64,
+        )
+    }
+
+    fn cells_between(&self, min: &Point2<f64>, max: &Point2<f64>) -> impl Iterator<Item = (i64, i64)> {
+        let ((min_x, min_y), (max_x, max_y)): ((i64, i64), (i64, i64)) = (self.cell(min), self.cell(max));
+
+        (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+    }
+}
+
+impl DifferentialGrowth {
+    /// Sets whether separation also pushes nodes away from the edges around them, not only
+    /// from their nodes. Defaults to `false`.
+    ///
+    /// Separation only looks at the distances between nodes, so a node can slip in between
+    /// two nodes of another part of the path that are further apart than the desired
+    /// separation and tunnel through the edge connecting them. With edge separation every
+    /// edge that comes within the desired separation of a node pushes it away from the
+    /// closest point on the edge, through the same [`crate::SeparationKernel`] as its nodes,
+    /// which keeps dense packings from crossing themselves.
+    ///
+    /// The edges are looked up in a grid rebuilt every tick, which makes ticks slower.
+    /// Separation sums computed on the GPU are not used while this is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// // The last node is 5 above the middle of an edge 40 long, far from its nodes.
+    /// let starting_points = vec![
+    ///     Point2::new(-20.0, 0.0),
+    ///     Point2::new(20.0, 0.0),
+    ///     Point2::new(20.0, 40.0),
+    ///     Point2::new(0.0, 5.0),
+    /// ];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let id: usize = differential_growth.get_ids()[3];
+    /// assert_eq!(differential_growth.explain_node(id).unwrap().forces.separation.y, 0.0);
+    ///
+    /// differential_growth.set_edge_separation(true);
+    /// assert!(differential_growth.explain_node(id).unwrap().forces.separation.y > 0.0);
+    /// ```
+    ///
+    pub fn set_edge_separation(&mut self, edge_separation: bool) {
+        self.edge_separation = edge_separation;
+    }
+
+    // The grid of the edges used by separation, if edge separation is enabled.
+    pub(crate) fn edge_index(&self) -> Option<EdgeIndex> {
+        self.edge_separation
+            .then(|| EdgeIndex::new(&self.nodes, self.topology, self.period().as_ref(), self.desired_separation))
+    }
+
+    // The sum of the pushes of the edges within `radius` of node `i`, like the sum of the
+    // separation of its neighbors. Edges of the node itself are skipped, as are edges whose
+    // closest point is one of their nodes, those push the node through separation already.
+    pub(crate) fn edge_separation_sum(&self, i: usize, edge_index: &EdgeIndex, radius: f64) -> Vector2<f64> {
+        let len: usize = self.nodes.len();
+        let position: Point2<f64> = self.nodes[i].position;
+        // The offset from the closest point of an edge to `image`, if it isn't one of its nodes.
+        let offset = |edge: usize, image: &Point2<f64>, start: &Point2<f64>, vector: &Vector2<f64>| {
+            if edge == i || self.topology.next(edge, len) == Some(i) {
+                return None;
+            }
+
+            let end: Point2<f64> = start + vector;
+            let (point, t): (Point2<f64>, f64) = geometry::closest_point_on_segment(image, start, &end);
+            (t > 0.0 && t < 1.0).then(|| image - point)
+        };
+        let mut sum: Vector2<f64> = Vector2::default();
+        let mut push = |offset: Vector2<f64>| {
+            let distance_sq: f64 = offset.norm_squared();
+            if distance_sq > 0.0 && distance_sq <= radius * radius {
+                sum.add_assign(offset / self.separation_kernel.divisor(distance_sq, radius));
+            }
+        };
+
+        let period: Period = match self.period() {
+            Some(period) => period,
+            None => {
+                // Every edge is a single segment, found at most once.
+                edge_index.for_each_near(&position, radius, |edge, start, vector| {
+                    if let Some(offset) = offset(edge, &position, start, vector) {
+                        push(offset);
+                    }
+                });
+                return sum;
+            }
+        };
+
+        // Across the boundary edges are found near several images and as two segments,
+        // only the closest offset to every edge counts.
+        let mut offsets: Vec<(usize, Vector2<f64>)> = Vec::new();
+        for image in period.images(&position, radius) {
+            edge_index.for_each_near(&image, radius, |edge, start, vector| {
+                if let Some(offset) = offset(edge, &image, start, vector) {
+                    offsets.push((edge, offset));
+                }
+            });
+        }
+        offsets.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(a.1.norm_squared().total_cmp(&b.1.norm_squared())));
+        offsets.dedup_by_key(|(edge, _)| *edge);
+        for (_, offset) in offsets {
+            push(offset);
+        }
+
+        sum
+    }
+}
@@ -0,0 +1,135 @@
+use nalgebra::Vector2;
+
+use crate::{differential_growth::DifferentialGrowth, periodic::Period, soa::NodeArrays, topology::Topology};
+
+/// How the endpoints of an open path behave. The cohesion at the ends
+/// decides whether open lines curl, fan out or stay straight.
+///
+/// Closed paths have no endpoints and ignore this.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{EndpointCondition, Topology};
+/// use nalgebra::Point2;
+///
+/// let starting_points: Vec<Point2<f64>> = (0..10).map(|i| Point2::new(i as f64 * 4.0, 0.0)).collect();
+/// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .topology(Topology::Open)
+///     .endpoint_condition(EndpointCondition::ClampedPosition)
+///     .build();
+///
+/// differential_growth.tick_n(50);
+/// let points = differential_growth.get_points();
+/// assert_eq!(points[0], Point2::new(0.0, 0.0));
+/// assert_eq!(points[points.len() - 1], Point2::new(36.0, 0.0));
+/// ```
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EndpointCondition {
+    /// Endpoints feel no cohesion and wander wherever the other forces push them. This is the default.
+    #[default]
+    Free,
+    /// Endpoints stay where they are, anchoring the line.
+    ClampedPosition,
+    /// Endpoints are pulled so the last edges point in the given directions, away from the path,
+    /// see [`DifferentialGrowth::endpoint_directions()`].
+    ClampedDirection {
+        /// The direction from the second node to the first node.
+        start: Vector2<f64>,
+        /// The direction from the second to last node to the last node.
+        end: Vector2<f64>,
+    },
+    /// Endpoints are pulled to the mirror image of the node two steps in, mirrored
+    /// through the node next to them, so the ends continue the line straight.
+    Mirrored,
+}
+
+impl EndpointCondition {
+    // Returns the offset from endpoint `i` to where its cohesion pulls it, or `None`
+    // when it is free or not an endpoint.
+    pub(crate) fn offset(
+        &self,
+        arrays: &NodeArrays,
+        i: usize,
+        topology: Topology,
+        period: Option<&Period>,
+    ) -> Option<Vector2<f64>> {
+        let len: usize = arrays.len();
+        if topology != Topology::Open || len < 3 || (i != 0 && i != len - 1) {
+            return None;
+        }
+
+        // The endpoint reaches its neighbors across a periodic boundary when that is shorter.
+        let origin: Vector2<f64> = Vector2::new(arrays.xs[i], arrays.ys[i]);
+        let position = |j: usize| match period {
+            Some(period) => origin + period.wrap(Vector2::new(arrays.xs[j], arrays.ys[j]) - origin),
+            None => Vector2::new(arrays.xs[j], arrays.ys[j]),
+        };
+        // The node next to the endpoint and the one after it, walking into the path.
+        let (inner, second): (usize, usize) = if i == 0 { (1, 2) } else { (len - 2, len - 3) };
+
+        match self {
+            EndpointCondition::Free | EndpointCondition::ClampedPosition => None,
+            EndpointCondition::ClampedDirection { start, end } => {
+                let direction: Vector2<f64> = if i == 0 { *start } else { *end };
+                let direction: Vector2<f64> = direction.try_normalize(0.0)?;
+                let edge_length: f64 = (position(i) - position(inner)).norm();
+                Some(position(inner) + direction * edge_length - position(i))
+            }
+            EndpointCondition::Mirrored => Some(position(inner) * 2.0 - position(second) - position(i)),
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Sets how the endpoints of an open path behave, see [`EndpointCondition`].
+    /// Defaults to [`EndpointCondition::Free`].
+    pub fn set_endpoint_condition(&mut self, endpoint_condition: EndpointCondition) {
+        self.endpoint_condition = endpoint_condition;
+    }
+
+    /// Returns the directions of the first and last edge of an open path, pointing away from
+    /// the path, to keep them with [`EndpointCondition::ClampedDirection`]. Returns `None`
+    /// for closed paths and paths with fewer than two nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{EndpointCondition, Topology};
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let starting_points: Vec<Point2<f64>> = (0..10).map(|i| Point2::new(i as f64 * 4.0, 0.0)).collect();
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .topology(Topology::Open)
+    ///     .build();
+    ///
+    /// let (start, end) = differential_growth.endpoint_directions().unwrap();
+    /// assert_eq!((start, end), (Vector2::new(-1.0, 0.0), Vector2::new(1.0, 0.0)));
+    /// differential_growth.set_endpoint_condition(EndpointCondition::ClampedDirection { start, end });
+    /// differential_growth.tick_n(10);
+    /// ```
+    ///
+    #[must_use]
+    pub fn endpoint_directions(&self) -> Option<(Vector2<f64>, Vector2<f64>)> {
+        let len: usize = self.nodes.len();
+        if self.topology != Topology::Open || len < 2 {
+            return None;
+        }
+
+        let start: Vector2<f64> = (self.nodes[0].position - self.nodes[1].position).try_normalize(0.0)?;
+        let end: Vector2<f64> = (self.nodes[len - 1].position - self.nodes[len - 2].position).try_normalize(0.0)?;
+        Some((start, end))
+    }
+
+    // The endpoints of an open path that don't move by force, like the leader.
+    pub(crate) fn clamped_endpoints(&self) -> Option<(usize, usize)> {
+        match (self.topology, self.endpoint_condition) {
+            (Topology::Open, EndpointCondition::ClampedPosition) if !self.nodes.is_empty() => Some((0, self.nodes.len() - 1)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::differential_growth::DifferentialGrowth;
+
+/// The forces on a single node, one by one, after scaling by the separation cohesion
+/// ratio, warmup, overlays and gradients. See [`DifferentialGrowth::explain_node()`].
+///
+/// Forces that aren't enabled are zero.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct NodeForces {
+    /// The force pushing the node away from nearby nodes.
+    pub separation: Vector2<f64>,
+    /// The force pulling the node towards its neighbors on the path.
+    pub cohesion: Vector2<f64>,
+    /// The force steering the node towards the velocity of nearby nodes.
+    pub alignment: Vector2<f64>,
+    /// The force along the normal from the curvature gain.
+    pub curvature: Vector2<f64>,
+    /// The force pushing the node away from parts of the path far away along it.
+    pub self_avoidance: Vector2<f64>,
+    /// The force pulling the node towards the guide path.
+    pub guide_path: Vector2<f64>,
+    /// The force steering the node along the trail map.
+    pub trail_map: Vector2<f64>,
+    /// The force pushing the node towards the aspect ratio of the aspect constraint.
+    pub aspect: Vector2<f64>,
+    /// The force pushing the node back in from the edges of a soft boundary.
+    pub boundary: Vector2<f64>,
+    /// The sum of the forces pushing the node out of the obstacles.
+    pub obstacles: Vector2<f64>,
+    /// The sum of the custom forces.
+    pub custom: Vector2<f64>,
+    /// The constant force, like gravity or wind.
+    pub constant: Vector2<f64>,
+    /// The external force applied for the next tick.
+    pub external: Vector2<f64>,
+}
+
+impl NodeForces {
+    /// Returns every force with its name, in the order of the fields.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Vector2<f64>)> {
+        [
+            ("separation", self.separation),
+            ("cohesion", self.cohesion),
+            ("alignment", self.alignment),
+            ("curvature", self.curvature),
+            ("self_avoidance", self.self_avoidance),
+            ("guide_path", self.guide_path),
+            ("trail_map", self.trail_map),
+            ("aspect", self.aspect),
+            ("boundary", self.boundary),
+            ("obstacles", self.obstacles),
+            ("custom", self.custom),
+            ("constant", self.constant),
+            ("external", self.external),
+        ]
+        .into_iter()
+    }
+
+    /// Returns the sum of all forces.
+    #[must_use]
+    pub fn total(&self) -> Vector2<f64> {
+        self.iter().map(|(_, force)| force).sum()
+    }
+
+    /// Returns the name and value of the strongest force, or `None` when all forces are zero.
+    #[must_use]
+    pub fn dominant(&self) -> Option<(&'static str, Vector2<f64>)> {
+        self.iter()
+            .filter(|(_, force)| force.magnitude() > 0.0)
+            .max_by(|a, b| a.1.magnitude().total_cmp(&b.1.magnitude()))
+    }
+}
+
+/// What holds a node in place or keeps it from moving by force.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodeConstraint {
+    /// The node is the leader and only moves along its trajectory.
+    Leader,
+    /// The node is an endpoint clamped by [`crate::EndpointCondition::ClampedPosition`].
+    ClampedEndpoint,
+    /// The node is frozen, see [`DifferentialGrowth::freeze_node()`].
+    Frozen,
+    /// The level of detail skips the node during the next tick.
+    LevelOfDetail,
+    /// The node sits on the edge of the boundary it was held back by.
+    Boundary,
+}
+
+/// Why a node moves the way it does, see [`DifferentialGrowth::explain_node()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeExplanation {
+    /// The index of the node.
+    pub index: usize,
+    /// The forces on the node.
+    pub forces: NodeForces,
+    /// The indices of the nodes within the desired separation, which push the node away.
+    pub neighbors: Vec<usize>,
+    /// What holds the node in place, nodes held by the leader, a clamp, freezing or the level
+    /// of detail ignore their forces.
+    pub constraints: Vec<NodeConstraint>,
+    /// The desired separation of the node, with its override, the field and oscillation.
+    pub desired_separation: f64,
+    /// The maximum force on the node.
+    pub max_force: f64,
+    /// The maximum magnitude of the velocity of the node.
+    pub max_speed: f64,
+}
+
+impl DifferentialGrowth {
+    /// Explains why the node with `id` moves the way it does: the forces on it one by one,
+    /// the nodes pushing it away and what holds it in place. Returns `None` when no node has `id`.
+    ///
+    /// Meant for tuning scenes where fields, zones and constraints pile up and it's hard to tell
+    /// which one dominates a misbehaving region. Only this node is evaluated, at the positions the
+    /// last tick left behind, so the forces are the ones the next tick starts from. Brownian noise
+    /// and scripts are left out, evaluating them would change the random state of the simulation.
+    ///
+    /// This takes `&mut self` because the spatial index is rebuilt first when
+    /// nodes were inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::NodeConstraint;
+    /// use nalgebra::Vector2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.set_constant_force(Vector2::new(0.0, -0.01));
+    /// differential_growth.tick_n(30);
+    ///
+    /// let id: usize = differential_growth.nodes()[3].id;
+    /// differential_growth.freeze_node(3, true);
+    /// let explanation = differential_growth.explain_node(id).unwrap();
+    ///
+    /// assert_eq!(explanation.index, 3);
+    /// assert_eq!(explanation.forces.constant, Vector2::new(0.0, -0.01));
+    /// assert!(explanation.forces.dominant().is_some());
+    /// assert!(!explanation.neighbors.contains(&3));
+    /// assert_eq!(explanation.constraints, vec![NodeConstraint::Frozen]);
+    /// assert!(differential_growth.explain_node(usize::MAX).is_none());
+    /// ```
+    ///
+    pub fn explain_node(&mut self, id: usize) -> Option<NodeExplanation> {
+        let index: usize = self.nodes.iter().position(|node| node.id == id)?;
+        let desired_separation: f64 = self.desired_separation_of(index);
+        let forces: NodeForces = self.node_forces(index);
+
+        let position: Point2<f64> = self.nodes[index].position;
+        let mut neighbors: Vec<usize> = self.nodes_within_radius(&position, desired_separation);
+        neighbors.retain(|&neighbor| neighbor != index);
+
+        Some(NodeExplanation {
+            index,
+            forces,
+            neighbors,
+            constraints: self.node_constraints(index),
+            desired_separation,
+            max_force: self.nodes[index].max_force,
+            max_speed: self.nodes[index].max_speed,
+        })
+    }
+}
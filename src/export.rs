@@ -0,0 +1,441 @@
+use core::fmt::Write as _;
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{
+    differential_growth::DifferentialGrowth,
+    node::Node,
+    topology::Topology,
+    units::{LengthUnit, Units},
+};
+
+/// How [`DifferentialGrowth::to_svg()`] renders the path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The width of the stroke. Defaults to `1.0`.
+    pub stroke_width: f64,
+    /// The stroke color, any SVG color. Defaults to `"black"`.
+    pub stroke: String,
+    /// The `viewBox` as `[min_x, min_y, width, height]`. Defaults to the
+    /// bounding box of the path, grown by the stroke width so nothing is clipped.
+    pub view_box: Option<[f64; 4]>,
+    /// What a world unit measures. When set, the document gets a physical
+    /// `width` and `height` in millimeters, so it prints or plots at the right size.
+    pub units: Option<Units>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            stroke_width: 1.0,
+            stroke: String::from("black"),
+            view_box: None,
+            units: None,
+        }
+    }
+}
+
+/// How [`DifferentialGrowth::to_gcode()`] drives the machine.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GcodeOptions {
+    /// The speed while drawing, in millimeters per minute. Defaults to `1000.0`.
+    pub feed_rate: f64,
+    /// The height of the pen or tool while moving without drawing. Defaults to `5.0`.
+    pub travel_height: f64,
+    /// The height of the pen or tool while drawing. Defaults to `0.0`.
+    pub draw_height: f64,
+    /// Millimeters per world unit. Defaults to `1.0`.
+    pub scale: f64,
+    /// Added to every scaled coordinate, to position the drawing on the machine bed.
+    /// Defaults to `[0.0, 0.0]`.
+    pub offset: [f64; 2],
+}
+
+impl Default for GcodeOptions {
+    fn default() -> Self {
+        GcodeOptions {
+            feed_rate: 1000.0,
+            travel_height: 5.0,
+            draw_height: 0.0,
+            scale: 1.0,
+            offset: [0.0, 0.0],
+        }
+    }
+}
+
+/// How the index buffer of [`IndexedGeometry`] connects the positions.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PrimitiveTopology {
+    /// Every index connects to the previous one. Closed paths repeat the first index at the end.
+    #[default]
+    LineStrip,
+    /// Every pair of indices is a separate edge.
+    LineList,
+}
+
+/// Node positions and an index buffer connecting them, in the formats
+/// GPU vertex and index buffers expect.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexedGeometry {
+    /// The position of every node.
+    pub positions: Vec<[f32; 2]>,
+    /// Indices into the positions, to be drawn with `primitive_topology`.
+    pub indices: Vec<u32>,
+    /// How the indices connect the positions.
+    pub primitive_topology: PrimitiveTopology,
+}
+
+impl DifferentialGrowth {
+    /// Returns the path data of the current nodes, to be used as the `d` attribute
+    /// of an SVG `<path>`. Closed paths end with `Z`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.to_svg_path(), "M0 0 L10 0 L0 10 Z");
+    /// ```
+    ///
+    pub fn to_svg_path(&self) -> String {
+        let mut data: String = String::new();
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let command: &str = if i == 0 { "M" } else { " L" };
+            // Writing to a String can't fail.
+            let _ = write!(data, "{}{} {}", command, node.position.x, node.position.y);
+        }
+
+        if self.topology == Topology::Closed && !self.nodes.is_empty() {
+            data.push_str(" Z");
+        }
+
+        data
+    }
+
+    /// Returns a standalone SVG document drawing the current path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let options = differential_growth::SvgOptions {
+    ///     stroke_width: 0.5,
+    ///     ..Default::default()
+    /// };
+    /// let svg: String = differential_growth.to_svg(&options);
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains("stroke-width=\"0.5\""));
+    ///
+    /// // Every world unit is a millimeter on paper.
+    /// let options = differential_growth::SvgOptions {
+    ///     view_box: Some([0.0, 0.0, 210.0, 297.0]),
+    ///     units: Some(differential_growth::Units::new(differential_growth::LengthUnit::Millimeters, 60.0)),
+    ///     ..Default::default()
+    /// };
+    /// assert!(differential_growth.to_svg(&options).contains("width=\"210mm\" height=\"297mm\""));
+    /// ```
+    ///
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let [min_x, min_y, width, height] = options.view_box.unwrap_or_else(|| self.fitting_view_box(options.stroke_width));
+
+        let size: String = match options.units {
+            Some(units) => format!(
+                " width=\"{}mm\" height=\"{}mm\"",
+                units.from_world(width, LengthUnit::Millimeters),
+                units.from_world(height, LengthUnit::Millimeters),
+            ),
+            None => String::new(),
+        };
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"{} viewBox=\"{} {} {} {}\">\n  <path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"round\" stroke-linecap=\"round\"/>\n</svg>\n",
+            size,
+            min_x,
+            min_y,
+            width,
+            height,
+            self.to_svg_path(),
+            options.stroke,
+            options.stroke_width,
+        )
+    }
+
+    /// Writes the SVG document of [`DifferentialGrowth::to_svg()`] to a file.
+    #[cfg(feature = "std")]
+    pub fn write_svg(&self, path: impl AsRef<Path>, options: &SvgOptions) -> io::Result<()> {
+        fs::write(path, self.to_svg(options))
+    }
+
+    /// Returns the current path as a GeoJSON geometry: a `Polygon` for closed paths
+    /// and a `LineString` for open paths, for loading into GIS tools.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(
+    ///     differential_growth.to_geojson(),
+    ///     r#"{"type":"Polygon","coordinates":[[[0,0],[10,0],[0,10],[0,0]]]}"#
+    /// );
+    /// ```
+    ///
+    pub fn to_geojson(&self) -> String {
+        let coordinates: Vec<String> = self
+            .ring()
+            .iter()
+            .map(|[x, y]| format!("[{},{}]", x, y))
+            .collect();
+
+        if self.is_polygon() {
+            format!(r#"{{"type":"Polygon","coordinates":[[{}]]}}"#, coordinates.join(","))
+        } else {
+            format!(r#"{{"type":"LineString","coordinates":[{}]}}"#, coordinates.join(","))
+        }
+    }
+
+    /// Returns the current path as Well-Known Text: a `POLYGON` for closed paths
+    /// and a `LINESTRING` for open paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert_eq!(differential_growth.to_wkt(), "POLYGON ((0 0, 10 0, 0 10, 0 0))");
+    ///
+    /// differential_growth.topology = Topology::Open;
+    /// assert_eq!(differential_growth.to_wkt(), "LINESTRING (0 0, 10 0, 0 10)");
+    /// ```
+    ///
+    pub fn to_wkt(&self) -> String {
+        let ring: Vec<[f64; 2]> = self.ring();
+        let geometry: &str = if self.is_polygon() { "POLYGON" } else { "LINESTRING" };
+
+        if ring.is_empty() {
+            return format!("{} EMPTY", geometry);
+        }
+
+        let coordinates: Vec<String> = ring.iter().map(|[x, y]| format!("{} {}", x, y)).collect();
+        if self.is_polygon() {
+            format!("{} (({}))", geometry, coordinates.join(", "))
+        } else {
+            format!("{} ({})", geometry, coordinates.join(", "))
+        }
+    }
+
+    /// Rasterizes the region enclosed by the path into a `width` by `height` grid of bytes,
+    /// `255` inside and `0` outside, ready to be used as a mask or stencil.
+    ///
+    /// The grid covers `bounds`, given as its minimum and maximum corner, for example
+    /// [`DifferentialGrowth::bounding_box()`]. Rows are stored one after the other, starting
+    /// at the minimum y. A pixel is inside when its center is, using the same even-odd rule
+    /// as [`DifferentialGrowth::contains()`]. Open paths enclose nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), Point2::new(4.0, 2.0), Point2::new(0.0, 2.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mask: Vec<u8> = differential_growth.occupancy_mask(4, 2, (Point2::new(-2.0, 0.0), Point2::new(6.0, 2.0)));
+    /// assert_eq!(mask, vec![0, 255, 255, 0, 0, 255, 255, 0]);
+    /// ```
+    ///
+    pub fn occupancy_mask(&self, width: usize, height: usize, bounds: (Point2<f64>, Point2<f64>)) -> Vec<u8> {
+        let mut mask: Vec<u8> = vec![0; width * height];
+        if self.topology == Topology::Open || self.nodes.len() < 3 {
+            return mask;
+        }
+
+        let (min, max) = bounds;
+        let pixel_width: f64 = (max.x - min.x) / width as f64;
+        let pixel_height: f64 = (max.y - min.y) / height as f64;
+        let mut crossings: Vec<f64> = Vec::new();
+
+        for row in 0..height {
+            let y: f64 = min.y + (row as f64 + 0.5) * pixel_height;
+
+            // The x coordinates where the path crosses the center line of this row.
+            crossings.clear();
+            for (i, j) in self.topology.edges(self.nodes.len()) {
+                let (a, b) = (&self.nodes[i].position, &self.nodes[j].position);
+                if (a.y > y) != (b.y > y) {
+                    crossings.push((b.x - a.x) * (y - a.y) / (b.y - a.y) + a.x);
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+
+            // Every pair of crossings encloses a span of the row.
+            for span in crossings.chunks_exact(2) {
+                // Pixels whose center lies within the span.
+                let first: f64 = ((span[0] - min.x) / pixel_width - 0.5).ceil().max(0.0);
+                let last: f64 = ((span[1] - min.x) / pixel_width - 0.5).floor().min(width as f64 - 1.0);
+                if first > last {
+                    continue;
+                }
+                for column in first as usize..=last as usize {
+                    mask[row * width + column] = 255;
+                }
+            }
+        }
+
+        mask
+    }
+
+    /// Returns G-code drawing the current path in a single stroke: travel to the
+    /// first node, lower the pen, draw through every node (back to the first one
+    /// for closed paths) and raise the pen again. Uses millimeters and absolute positioning.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let options = differential_growth::GcodeOptions {
+    ///     scale: 0.5,
+    ///     ..Default::default()
+    /// };
+    /// let gcode: String = differential_growth.to_gcode(&options);
+    /// assert!(gcode.contains("G0 X0.000 Y0.000"));
+    /// assert!(gcode.contains("G1 X5.000 Y0.000"));
+    /// assert!(gcode.trim_end().ends_with("G0 Z5.000"));
+    /// ```
+    ///
+    pub fn to_gcode(&self, options: &GcodeOptions) -> String {
+        let mut gcode: String = String::from("G21\nG90\n");
+        // Writing to a String can't fail.
+        let _ = writeln!(gcode, "G0 Z{:.3}", options.travel_height);
+
+        let transform = |node: &Node| {
+            (
+                node.position.x * options.scale + options.offset[0],
+                node.position.y * options.scale + options.offset[1],
+            )
+        };
+
+        if let Some(first) = self.nodes.first() {
+            let (x, y) = transform(first);
+            let _ = writeln!(gcode, "G0 X{:.3} Y{:.3}", x, y);
+            let _ = writeln!(gcode, "G1 Z{:.3} F{:.3}", options.draw_height, options.feed_rate);
+
+            let closing: Option<&Node> = match self.topology {
+                Topology::Closed if self.nodes.len() > 2 => Some(first),
+                _ => None,
+            };
+            for node in self.nodes.iter().skip(1).chain(closing) {
+                let (x, y) = transform(node);
+                let _ = writeln!(gcode, "G1 X{:.3} Y{:.3}", x, y);
+            }
+
+            let _ = writeln!(gcode, "G0 Z{:.3}", options.travel_height);
+        }
+
+        gcode
+    }
+
+    /// Writes the G-code of [`DifferentialGrowth::to_gcode()`] to a file.
+    #[cfg(feature = "std")]
+    pub fn write_gcode(&self, path: impl AsRef<Path>, options: &GcodeOptions) -> io::Result<()> {
+        fs::write(path, self.to_gcode(options))
+    }
+
+    /// Returns the current nodes as positions plus an index buffer, ready to be
+    /// uploaded as GPU vertex and index buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::PrimitiveTopology;
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let strip = differential_growth.indexed_geometry(PrimitiveTopology::LineStrip);
+    /// assert_eq!(strip.positions, vec![[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]]);
+    /// assert_eq!(strip.indices, vec![0, 1, 2, 0]);
+    ///
+    /// let list = differential_growth.indexed_geometry(PrimitiveTopology::LineList);
+    /// assert_eq!(list.indices, vec![0, 1, 1, 2, 2, 0]);
+    /// ```
+    ///
+    pub fn indexed_geometry(&self, primitive_topology: PrimitiveTopology) -> IndexedGeometry {
+        let len: usize = self.nodes.len();
+        let positions: Vec<[f32; 2]> = self
+            .nodes
+            .iter()
+            .map(|node| [node.position.x as f32, node.position.y as f32])
+            .collect();
+
+        let indices: Vec<u32> = match primitive_topology {
+            PrimitiveTopology::LineStrip => {
+                let mut indices: Vec<u32> = (0..len as u32).collect();
+                if self.topology == Topology::Closed && len > 1 {
+                    indices.push(0);
+                }
+                indices
+            }
+            PrimitiveTopology::LineList => self
+                .topology
+                .edges(len)
+                .flat_map(|(i, j)| [i as u32, j as u32])
+                .collect(),
+        };
+
+        IndexedGeometry {
+            positions,
+            indices,
+            primitive_topology,
+        }
+    }
+
+    // A closed path needs at least 3 nodes to be a valid polygon.
+    fn is_polygon(&self) -> bool {
+        self.topology == Topology::Closed && self.nodes.len() >= 3
+    }
+
+    // The coordinates of the nodes, repeating the first at the end for polygons.
+    fn ring(&self) -> Vec<[f64; 2]> {
+        let mut ring: Vec<[f64; 2]> = self.nodes.iter().map(|node| [node.position.x, node.position.y]).collect();
+        if self.is_polygon() {
+            ring.push(ring[0]);
+        }
+        ring
+    }
+
+    fn fitting_view_box(&self, margin: f64) -> [f64; 4] {
+        let (min, max) = match self.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return [0.0, 0.0, 0.0, 0.0],
+        };
+
+        [
+            min.x - margin,
+            min.y - margin,
+            max.x - min.x + 2.0 * margin,
+            max.y - min.y + 2.0 * margin,
+        ]
+    }
+}
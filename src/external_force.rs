@@ -0,0 +1,84 @@
+use core::ops::AddAssign;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::differential_growth::DifferentialGrowth;
+
+impl DifferentialGrowth {
+    /// Pushes the node at `index` with `force` during the next tick, like dragging it with the mouse.
+    ///
+    /// Forces applied before a tick add up and are applied once, on top of the forces of
+    /// the simulation, then forgotten. The sum on a node is capped at its maximum force, so
+    /// pushing hard can't fling nodes away. Nodes that don't move by force, like the leader,
+    /// ignore it. Does nothing when there is no node at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Vector2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let mut pushed = differential_growth.clone();
+    ///
+    /// pushed.apply_external_force(0, Vector2::new(100.0, 0.0));
+    /// differential_growth.tick();
+    /// pushed.tick();
+    ///
+    /// assert!(pushed.nodes()[0].position.x > differential_growth.nodes()[0].position.x);
+    /// assert!(pushed.nodes()[0].velocity.magnitude() <= pushed.max_speed + 1e-9);
+    /// ```
+    ///
+    pub fn apply_external_force(&mut self, index: usize, force: Vector2<f64>) {
+        if index >= self.nodes.len() {
+            return;
+        }
+
+        self.external_forces.resize(self.nodes.len(), Vector2::default());
+        self.external_forces[index].add_assign(force);
+    }
+
+    /// Pushes all nodes within `radius` of `center` with `force` during the next tick,
+    /// see [`DifferentialGrowth::apply_external_force()`].
+    ///
+    /// This takes `&mut self` because the spatial index is rebuilt first when
+    /// nodes were inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Blow the right side further to the right.
+    /// for _ in 0..20 {
+    ///     differential_growth.apply_force_in_radius(Point2::new(10.0, 0.0), 8.0, Vector2::new(0.5, 0.0));
+    ///     differential_growth.tick();
+    /// }
+    /// let right: f64 = differential_growth.get_points().iter().map(|point| point.x).fold(f64::MIN, f64::max);
+    /// assert!(right > 12.0);
+    /// ```
+    ///
+    pub fn apply_force_in_radius(&mut self, center: Point2<f64>, radius: f64, force: Vector2<f64>) {
+        for i in self.nodes_within_radius(&center, radius) {
+            self.apply_external_force(i, force);
+        }
+    }
+
+    // Adds the external forces to the moving nodes and forgets them.
+    pub(crate) fn apply_external_forces(&mut self, moving: &[usize]) {
+        if self.external_forces.is_empty() {
+            return;
+        }
+
+        for &i in moving {
+            if let Some(force) = self.external_forces.get(i) {
+                let node = &mut self.nodes[i];
+                node.apply_force(&force.cap_magnitude(node.max_force));
+            }
+        }
+        self.external_forces.clear();
+    }
+}
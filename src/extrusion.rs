@@ -0,0 +1,228 @@
+use core::fmt::Write as _;
+use std::collections::HashMap;
+use std::{fs, io, path::Path as FilePath};
+
+use lyon::{
+    math::point,
+    path::Path,
+    tessellation::{BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, TessellationError, VertexBuffers},
+};
+use nalgebra::{Point2, Point3, Vector3};
+
+use crate::{
+    differential_growth::DifferentialGrowth,
+    topology::Topology,
+    units::{LengthUnit, Units},
+};
+
+/// How [`DifferentialGrowth::extrude()`] turns the path into a solid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExtrusionOptions {
+    /// The height of the solid, in world units. Defaults to `10.0`.
+    pub height: f64,
+    /// The thickness of a wall along the inside of the path. Defaults to `None`,
+    /// which fills the whole inside. A wall too thick to leave a hole fills it as well.
+    pub wall_thickness: Option<f64>,
+    /// What a world unit measures. When set, the solid is scaled to millimeters,
+    /// the unit slicers assume for STL and OBJ files.
+    pub units: Option<Units>,
+}
+
+impl Default for ExtrusionOptions {
+    fn default() -> Self {
+        ExtrusionOptions {
+            height: 10.0,
+            wall_thickness: None,
+            units: None,
+        }
+    }
+}
+
+/// A closed triangle mesh, see [`DifferentialGrowth::extrude()`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Solid {
+    /// The positions of the vertices.
+    pub vertices: Vec<Point3<f64>>,
+    /// Triangles as indices into the vertices, in counter-clockwise order seen from outside.
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Solid {
+    /// Returns the solid as an ASCII STL file.
+    pub fn to_stl(&self) -> String {
+        let mut stl: String = String::from("solid differential_growth\n");
+        for face in &self.faces {
+            let [a, b, c]: [Point3<f64>; 3] = face.map(|index| self.vertices[index]);
+            let normal: Vector3<f64> = (b - a).cross(&(c - a)).try_normalize(0.0).unwrap_or_default();
+            let _ = writeln!(stl, "  facet normal {} {} {}", normal.x, normal.y, normal.z);
+            stl.push_str("    outer loop\n");
+            for vertex in [a, b, c] {
+                let _ = writeln!(stl, "      vertex {} {} {}", vertex.x, vertex.y, vertex.z);
+            }
+            stl.push_str("    endloop\n  endfacet\n");
+        }
+        stl.push_str("endsolid differential_growth\n");
+        stl
+    }
+
+    /// Returns the solid as a Wavefront OBJ file.
+    pub fn to_obj(&self) -> String {
+        let mut obj: String = String::new();
+        for vertex in &self.vertices {
+            let _ = writeln!(obj, "v {} {} {}", vertex.x, vertex.y, vertex.z);
+        }
+        // OBJ indices start at 1.
+        for [a, b, c] in &self.faces {
+            let _ = writeln!(obj, "f {} {} {}", a + 1, b + 1, c + 1);
+        }
+        obj
+    }
+
+    /// Writes the ASCII STL file of [`Solid::to_stl()`].
+    pub fn write_stl(&self, path: impl AsRef<FilePath>) -> io::Result<()> {
+        fs::write(path, self.to_stl())
+    }
+
+    /// Writes the Wavefront OBJ file of [`Solid::to_obj()`].
+    pub fn write_obj(&self, path: impl AsRef<FilePath>) -> io::Result<()> {
+        fs::write(path, self.to_obj())
+    }
+}
+
+impl DifferentialGrowth {
+    /// Extrudes the inside of the path into a watertight solid standing on the
+    /// xy-plane, to 3D print the grown shape with [`Solid::write_stl()`] or [`Solid::write_obj()`].
+    ///
+    /// The caps are tessellated like [`DifferentialGrowth::to_mesh()`] and share their
+    /// outlines with the side walls, so every edge belongs to exactly two triangles.
+    /// The inside of a wall is traced by [`DifferentialGrowth::offset_bands()`]. Open
+    /// paths have no inside to build a wall along and are always filled, as if their
+    /// ends were connected. Paths of less than 3 nodes, or a height that isn't
+    /// positive, give an empty solid.
+    ///
+    /// A path that crosses itself can't be watertight, the tessellation adds
+    /// vertices at the crossings that the side walls don't have.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    /// use std::collections::HashMap;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let options = differential_growth::ExtrusionOptions { height: 2.0, ..Default::default() };
+    /// let solid = differential_growth.extrude(&options).unwrap();
+    /// // 2 triangles for either cap and 2 for every side.
+    /// assert_eq!(solid.faces.len(), 12);
+    ///
+    /// // Watertight: every edge is used once in either direction.
+    /// let mut edges: HashMap<(usize, usize), i32> = HashMap::new();
+    /// for [a, b, c] in &solid.faces {
+    ///     for (from, to) in [(*a, *b), (*b, *c), (*c, *a)] {
+    ///         *edges.entry((from.min(to), from.max(to))).or_default() += if from < to { 1 } else { -1 };
+    ///     }
+    /// }
+    /// assert!(edges.values().all(|&balance| balance == 0));
+    ///
+    /// assert!(solid.to_stl().starts_with("solid differential_growth"));
+    /// assert_eq!(solid.to_obj().lines().filter(|line| line.starts_with("f ")).count(), 12);
+    /// ```
+    ///
+    pub fn extrude(&self, options: &ExtrusionOptions) -> Result<Solid, TessellationError> {
+        if self.nodes.len() < 3 || options.height <= 0.0 {
+            return Ok(Solid::default());
+        }
+
+        // Side walls face outward when the outline runs counter-clockwise and the holes clockwise.
+        let mut rings: Vec<Vec<Point2<f64>>> = vec![oriented(self.get_unwrapped_points(), true)];
+        if let (Some(thickness), Topology::Closed) = (options.wall_thickness, self.topology) {
+            if let Some(band) = self.offset_bands(1, thickness).into_iter().find(|band| band.offset < 0.0) {
+                rings.extend(band.contours.into_iter().filter(|contour| contour.len() > 2).map(|contour| oriented(contour, false)));
+            }
+        }
+
+        let mut builder = Path::builder();
+        for ring in &rings {
+            builder.begin(point(ring[0].x as f32, ring[0].y as f32));
+            for position in &ring[1..] {
+                builder.line_to(point(position.x as f32, position.y as f32));
+            }
+            builder.end(true);
+        }
+        let path: Path = builder.build();
+
+        let mut buffers: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+        FillTessellator::new().tessellate_path(
+            &path,
+            &FillOptions::default().with_fill_rule(FillRule::EvenOdd),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position().to_array()),
+        )?;
+
+        // The tessellation has its own vertices, they are matched to the outlines by their
+        // exact position so the caps and the side walls share their edges.
+        let mut outline: Vec<Point2<f64>> = rings.iter().flatten().copied().collect();
+        let mut outline_index: HashMap<[u32; 2], usize> = HashMap::new();
+        for (i, position) in outline.iter().enumerate() {
+            outline_index.entry([(position.x as f32).to_bits(), (position.y as f32).to_bits()]).or_insert(i);
+        }
+        let cap_vertices: Vec<usize> = buffers
+            .vertices
+            .iter()
+            .map(|[x, y]| {
+                *outline_index.entry([x.to_bits(), y.to_bits()]).or_insert_with(|| {
+                    outline.push(Point2::new(f64::from(*x), f64::from(*y)));
+                    outline.len() - 1
+                })
+            })
+            .collect();
+
+        let count: usize = outline.len();
+        let mut faces: Vec<[usize; 3]> = Vec::new();
+        for triangle in buffers.indices.chunks_exact(3) {
+            let [a, b, c]: [usize; 3] = [0, 1, 2].map(|k| cap_vertices[triangle[k] as usize]);
+            let turn: f64 = (outline[b] - outline[a]).perp(&(outline[c] - outline[a]));
+            if turn == 0.0 {
+                continue;
+            }
+            let [a, b, c]: [usize; 3] = if turn > 0.0 { [a, b, c] } else { [a, c, b] };
+            faces.push([a, c, b]);
+            faces.push([a + count, b + count, c + count]);
+        }
+
+        let mut start: usize = 0;
+        for ring in &rings {
+            for k in 0..ring.len() {
+                let (a, b): (usize, usize) = (start + k, start + (k + 1) % ring.len());
+                faces.push([a, b, b + count]);
+                faces.push([a, b + count, a + count]);
+            }
+            start += ring.len();
+        }
+
+        let scale: f64 = match options.units {
+            Some(units) => units.from_world(1.0, LengthUnit::Millimeters),
+            None => 1.0,
+        };
+        let vertices: Vec<Point3<f64>> = [0.0, options.height]
+            .iter()
+            .flat_map(|&z| outline.iter().map(move |position| Point3::new(position.x, position.y, z) * scale))
+            .collect();
+
+        Ok(Solid { vertices, faces })
+    }
+}
+
+// Reverses the ring when it doesn't run in the requested direction.
+fn oriented(mut ring: Vec<Point2<f64>>, counter_clockwise: bool) -> Vec<Point2<f64>> {
+    let twice_area: f64 = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.coords.perp(&b.coords))
+        .sum();
+    if (twice_area > 0.0) != counter_clockwise {
+        ring.reverse();
+    }
+    ring
+}
@@ -0,0 +1,131 @@
+use nalgebra::Point2;
+use ttf_parser::{Face, FaceParsingError, GlyphId, OutlineBuilder};
+
+use crate::prelude::*;
+use crate::text::{Font, Glyph};
+
+/// A TrueType or OpenType font, parsed with `ttf-parser`, as a [`Font`] for
+/// [`crate::TextLayout`] and [`crate::points_from_text()`]. Kerning isn't applied.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use differential_growth::TtfFont;
+///
+/// let data: Vec<u8> = std::fs::read("font.ttf").unwrap();
+/// let font = TtfFont::parse(&data).unwrap();
+///
+/// let loops = differential_growth::points_from_text("grow", &font, 100.0, 2.0);
+/// ```
+///
+pub struct TtfFont<'a> {
+    face: Face<'a>,
+}
+
+impl<'a> TtfFont<'a> {
+    /// Parses the first font in `data`, the contents of a `.ttf` or `.otf` file.
+    pub fn parse(data: &'a [u8]) -> Result<TtfFont<'a>, FaceParsingError> {
+        Ok(TtfFont {
+            face: Face::parse(data, 0)?,
+        })
+    }
+
+    /// Returns the parsed font, for everything [`Font`] doesn't cover.
+    pub fn face(&self) -> &Face<'a> {
+        &self.face
+    }
+}
+
+impl<'a> Font for TtfFont<'a> {
+    fn glyph(&self, character: char) -> Option<Glyph> {
+        let id: GlyphId = self.face.glyph_index(character)?;
+        let units_per_em: f64 = f64::from(self.face.units_per_em());
+
+        let mut outline: Outline = Outline {
+            scale: 1.0 / units_per_em,
+            contours: Vec::new(),
+        };
+        // Glyphs without an outline, like spaces, still advance the pen.
+        self.face.outline_glyph(id, &mut outline);
+
+        Some(Glyph {
+            contours: outline.contours,
+            advance: f64::from(self.face.glyph_hor_advance(id).unwrap_or_default()) / units_per_em,
+        })
+    }
+}
+
+// The amount of straight segments a curve of an outline is flattened into.
+const CURVE_SEGMENTS: usize = 8;
+
+// Collects the contours of a glyph outline in ems, curves flattened.
+struct Outline {
+    scale: f64,
+    contours: Vec<Vec<Point2<f64>>>,
+}
+
+impl Outline {
+    fn point(&self, x: f32, y: f32) -> Point2<f64> {
+        Point2::new(f64::from(x), f64::from(y)) * self.scale
+    }
+
+    fn current(&self) -> Point2<f64> {
+        self.contours
+            .last()
+            .and_then(|contour| contour.last())
+            .copied()
+            .unwrap_or_else(Point2::origin)
+    }
+
+    fn push(&mut self, point: Point2<f64>) {
+        if let Some(contour) = self.contours.last_mut() {
+            contour.push(point);
+        }
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point: Point2<f64> = self.point(x, y);
+        self.contours.push(vec![point]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point: Point2<f64> = self.point(x, y);
+        self.push(point);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (start, control, end): (Point2<f64>, Point2<f64>, Point2<f64>) =
+            (self.current(), self.point(x1, y1), self.point(x, y));
+        for i in 1..=CURVE_SEGMENTS {
+            let t: f64 = i as f64 / CURVE_SEGMENTS as f64;
+            let u: f64 = 1.0 - t;
+            self.push(Point2::from(start.coords * (u * u) + control.coords * (2.0 * u * t) + end.coords * (t * t)));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (start, first, second, end): (Point2<f64>, Point2<f64>, Point2<f64>, Point2<f64>) =
+            (self.current(), self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        for i in 1..=CURVE_SEGMENTS {
+            let t: f64 = i as f64 / CURVE_SEGMENTS as f64;
+            let u: f64 = 1.0 - t;
+            self.push(Point2::from(
+                start.coords * (u * u * u)
+                    + first.coords * (3.0 * u * u * t)
+                    + second.coords * (3.0 * u * t * t)
+                    + end.coords * (t * t * t),
+            ));
+        }
+    }
+
+    // Contours are always closed, only a repeated first point is dropped.
+    fn close(&mut self) {
+        if let Some(contour) = self.contours.last_mut() {
+            if contour.len() > 1 && contour.last() == contour.first() {
+                contour.pop();
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use nalgebra::Vector2;
+
+use crate::node::Node;
+
+/// An additional force pass, run every time the forces of a tick are computed,
+/// to extend the simulation without changing the crate.
+///
+/// A force adds to `out`, which holds the force on every node. Every registered
+/// force adds to the same buffer, in the order they were added. Like the built-in
+/// extra forces it is ramped up during warmup and nodes that don't move by force,
+/// like the leader or nodes skipped by the level of detail, ignore it.
+///
+/// Any closure taking the nodes and the buffer is a force.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Force, Node};
+/// use nalgebra::{Point2, Vector2};
+///
+/// // Pulls every node towards the origin, harder the further away it is.
+/// struct Spring {
+///     stiffness: f64,
+/// }
+///
+/// impl Force for Spring {
+///     fn accumulate(&self, nodes: &[Node], out: &mut [Vector2<f64>]) {
+///         for (node, force) in nodes.iter().zip(out.iter_mut()) {
+///             *force -= node.position.coords * self.stiffness;
+///         }
+///     }
+/// }
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.add_force(Spring { stiffness: 0.01 });
+/// // Closures work too.
+/// differential_growth.add_force(|nodes: &[Node], out: &mut [Vector2<f64>]| {
+///     for force in out.iter_mut().take(nodes.len()) {
+///         force.y -= 0.1;
+///     }
+/// });
+///
+/// differential_growth.tick_n(20);
+/// let points = differential_growth.get_points();
+/// let center_y: f64 = points.iter().map(|point| point.y).sum::<f64>() / points.len() as f64;
+/// assert!(center_y < 0.0);
+/// ```
+///
+pub trait Force: Send + Sync {
+    /// Adds the force on every node to `out`, which has one entry per node.
+    fn accumulate(&self, nodes: &[Node], out: &mut [Vector2<f64>]);
+}
+
+impl<F: Fn(&[Node], &mut [Vector2<f64>]) + Send + Sync> Force for F {
+    fn accumulate(&self, nodes: &[Node], out: &mut [Vector2<f64>]) {
+        self(nodes, out)
+    }
+}
@@ -0,0 +1,144 @@
+use core::ops::{AddAssign, SubAssign};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+
+/// Returns the point on segment `a`-`b` closest to `point`,
+/// together with how far along the segment it lies, between 0 and 1.
+pub(crate) fn closest_point_on_segment(point: &Point2<f64>, a: &Point2<f64>, b: &Point2<f64>) -> (Point2<f64>, f64) {
+    let ab: Vector2<f64> = b - a;
+    let length_sq: f64 = ab.norm_squared();
+
+    // Degenerate segments are a single point.
+    if length_sq == 0.0 {
+        return (*a, 0.0);
+    }
+
+    let t: f64 = ((point - a).dot(&ab) / length_sq).clamp(0.0, 1.0);
+    (a + ab * t, t)
+}
+
+/// Returns the signed curvature at `b` of the circle through `a`, `b` and `c`,
+/// positive for counter-clockwise turns. Collinear or coinciding points have no curvature.
+pub(crate) fn signed_curvature(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>) -> f64 {
+    let ab: Vector2<f64> = b - a;
+    let bc: Vector2<f64> = c - b;
+    let denominator: f64 = ab.norm() * bc.norm() * (c - a).norm();
+
+    if denominator == 0.0 {
+        return 0.0;
+    }
+
+    2.0 * ab.perp(&bc) / denominator
+}
+
+/// Returns where segment `a`-`b` crosses segment `c`-`d`, together with how far along `a`-`b`
+/// it lies, between 0 and 1. Parallel segments never cross.
+pub(crate) fn segment_intersection(
+    a: &Point2<f64>,
+    b: &Point2<f64>,
+    c: &Point2<f64>,
+    d: &Point2<f64>,
+) -> Option<(Point2<f64>, f64)> {
+    let ab: Vector2<f64> = b - a;
+    let cd: Vector2<f64> = d - c;
+    let denominator: f64 = ab.perp(&cd);
+
+    if denominator == 0.0 {
+        return None;
+    }
+
+    let ac: Vector2<f64> = c - a;
+    let t: f64 = ac.perp(&cd) / denominator;
+    let u: f64 = ac.perp(&ab) / denominator;
+
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        Some((a + ab * t, t))
+    } else {
+        None
+    }
+}
+
+/// Marks the points between `start` and `end` that the Ramer–Douglas–Peucker algorithm
+/// keeps: the ones further than `epsilon` from the simplified line. Works with an explicit
+/// stack so long paths can't overflow the call stack.
+pub(crate) fn douglas_peucker(points: &[Point2<f64>], start: usize, end: usize, epsilon: f64, keep: &mut [bool]) {
+    let mut stack: Vec<(usize, usize)> = vec![(start, end)];
+
+    while let Some((start, end)) = stack.pop() {
+        let farthest: Option<(usize, f64)> = (start + 1..end)
+            .map(|i| {
+                let closest: Point2<f64> = closest_point_on_segment(&points[i], &points[start], &points[end]).0;
+                (i, nalgebra::distance(&points[i], &closest))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((i, distance)) = farthest {
+            if distance > epsilon {
+                keep[i] = true;
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+        }
+    }
+}
+
+/// Returns whether `point` lies inside the closed `polygon`, using the even-odd rule.
+pub(crate) fn polygon_contains(polygon: &[Point2<f64>], point: &Point2<f64>) -> bool {
+    // Counts the edges crossed by a ray from the point towards positive x.
+    let mut inside: bool = false;
+    for (i, a) in polygon.iter().enumerate() {
+        let b: &Point2<f64> = &polygon[(i + 1) % polygon.len()];
+        if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Returns points evenly spaced along the polyline through `points`, as close to `spacing`
+/// apart as fits a whole number of times into its length. Open polylines keep both ends,
+/// closed ones get the edge back to the first point too and don't repeat it.
+pub(crate) fn resample_evenly(points: &[Point2<f64>], closed: bool, spacing: f64) -> Vec<Point2<f64>> {
+    let edges = || {
+        let len: usize = points.len();
+        (0..len - 1).map(|i| (i, i + 1)).chain((closed && len > 2).then_some((len - 1, 0)))
+    };
+    if points.len() < 2 || !(spacing > 0.0 && spacing.is_finite()) {
+        return points.to_vec();
+    }
+
+    let length: f64 = edges().map(|(i, j)| nalgebra::distance(&points[i], &points[j])).sum();
+    let count: usize = ((length / spacing).round() as usize).max(if closed { 3 } else { 1 });
+    if length == 0.0 {
+        return vec![points[0]];
+    }
+    let spacing: f64 = length / count as f64;
+
+    let mut result: Vec<Point2<f64>> = Vec::with_capacity(count + 1);
+    result.push(points[0]);
+    // The arc length left to walk before the next point.
+    let mut remaining: f64 = spacing;
+    for (i, j) in edges() {
+        let (start, end): (Point2<f64>, Point2<f64>) = (points[i], points[j]);
+        let edge_length: f64 = nalgebra::distance(&start, &end);
+        let mut walked: f64 = 0.0;
+
+        while edge_length - walked >= remaining && result.len() < count {
+            walked.add_assign(remaining);
+            result.push(start + (end - start) * (walked / edge_length));
+            remaining = spacing;
+        }
+        remaining.sub_assign(edge_length - walked);
+    }
+
+    // Rounding can leave the last point short of the end.
+    if !closed {
+        result.truncate(count);
+        result.push(points[points.len() - 1]);
+    }
+
+    result
+}
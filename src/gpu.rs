@@ -0,0 +1,366 @@
+use std::{borrow::Cow, error::Error, fmt};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::{differential_growth::DifferentialGrowth, node::Node};
+
+// Must match `workgroup_size` in the shader.
+const WORKGROUP_SIZE: usize = 64;
+
+// The amount of nodes a bucket of the grid holds at first. Buckets grow when they fill up.
+const INITIAL_BUCKET_CAPACITY: u32 = 16;
+
+/// Error returned when no GPU can be used, see [`GpuDifferentialGrowth::new()`].
+#[derive(Debug)]
+pub enum GpuError {
+    /// No adapter supports compute shaders.
+    NoAdapter,
+    /// The adapter refused to hand out a device.
+    RequestDevice(wgpu::RequestDeviceError),
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuError::NoAdapter => write!(f, "no GPU adapter found"),
+            GpuError::RequestDevice(error) => write!(f, "failed to request a GPU device: {}", error),
+        }
+    }
+}
+
+impl Error for GpuError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GpuError::NoAdapter => None,
+            GpuError::RequestDevice(error) => Some(error),
+        }
+    }
+}
+
+/// A [`DifferentialGrowth`] that computes the separation forces in a compute shader.
+///
+/// For paths of 100k nodes and more, rebuilding the kd-tree and looping over the neighbors of
+/// every node take most of a tick, even with the `rayon` feature. On the GPU the nodes are binned
+/// into a uniform grid with cells as large as the desired separation and every node sums up the
+/// nodes in the 3x3 cells around it. Everything else still runs on the CPU.
+///
+/// The GPU computes in `f32`, so a run drifts apart from the same run on the CPU over time.
+/// The CPU takes over for a tick when a cell of the grid overflows, when nodes were inserted
+/// since the sums were computed and on a [`crate::BoundaryBehavior::Periodic`] boundary.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use differential_growth::GpuDifferentialGrowth;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// let mut gpu_differential_growth = GpuDifferentialGrowth::new(differential_growth).unwrap();
+///
+/// gpu_differential_growth.tick_n(100);
+/// let points = gpu_differential_growth.get_points();
+/// ```
+///
+pub struct GpuDifferentialGrowth {
+    differential_growth: DifferentialGrowth,
+    separation: GpuSeparation,
+}
+
+impl GpuDifferentialGrowth {
+    /// Returns a GpuDifferentialGrowth running `differential_growth` on the default GPU.
+    /// Returns an error when there is no GPU to run on.
+    pub fn new(differential_growth: DifferentialGrowth) -> Result<GpuDifferentialGrowth, GpuError> {
+        Ok(GpuDifferentialGrowth {
+            differential_growth,
+            separation: GpuSeparation::new()?,
+        })
+    }
+
+    /// Advances the simulation by one iteration, see [`DifferentialGrowth::tick()`].
+    pub fn tick(&mut self) {
+        if self.differential_growth.period().is_none() {
+            self.differential_growth.separation_sums = self.separation.sums(&self.differential_growth);
+        }
+        self.differential_growth.tick();
+    }
+
+    /// Advances the simulation by `n` iterations.
+    pub fn tick_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    /// Returns the positions of the nodes, see [`DifferentialGrowth::get_points()`].
+    #[must_use]
+    pub fn get_points(&self) -> Vec<Point2<f64>> {
+        self.differential_growth.get_points()
+    }
+
+    /// Returns the nodes of the path.
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        self.differential_growth.nodes()
+    }
+
+    /// Returns the simulation running on the GPU.
+    #[must_use]
+    pub fn differential_growth(&self) -> &DifferentialGrowth {
+        &self.differential_growth
+    }
+
+    /// Returns the simulation running on the GPU, to change its parameters between ticks.
+    pub fn differential_growth_mut(&mut self) -> &mut DifferentialGrowth {
+        &mut self.differential_growth
+    }
+
+    /// Returns the simulation, to continue on the CPU.
+    #[must_use]
+    pub fn into_inner(self) -> DifferentialGrowth {
+        self.differential_growth
+    }
+}
+
+// The device and the compute pipelines of the separation shader.
+struct GpuSeparation {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    bind_group_layout: wgpu::BindGroupLayout,
+    insert: wgpu::ComputePipeline,
+    separate: wgpu::ComputePipeline,
+    bucket_capacity: u32,
+    buffers: Option<GpuBuffers>,
+}
+
+// The buffers for up to `capacity` nodes, recreated when the path outgrows them.
+struct GpuBuffers {
+    capacity: usize,
+    bucket_count: u32,
+    bucket_capacity: u32,
+    params: wgpu::Buffer,
+    nodes: wgpu::Buffer,
+    bucket_lengths: wgpu::Buffer,
+    // Only read by the shader, kept for as long as the bind group uses it.
+    _buckets: wgpu::Buffer,
+    sums: wgpu::Buffer,
+    overflow: wgpu::Buffer,
+    // The sums followed by the overflow flag, read back by the CPU.
+    readback: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl GpuSeparation {
+    fn new() -> Result<GpuSeparation, GpuError> {
+        let instance: wgpu::Instance = wgpu::Instance::new(wgpu::Backends::all());
+        let adapter: wgpu::Adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or(GpuError::NoAdapter)?;
+        let (device, queue): (wgpu::Device, wgpu::Queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("differential-growth"),
+                features: wgpu::Features::empty(),
+                limits: wgpu::Limits::default(),
+            },
+            None,
+        ))
+        .map_err(GpuError::RequestDevice)?;
+
+        let shader: wgpu::ShaderModule = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: Some("separation"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("gpu_separation.wgsl"))),
+        });
+
+        let storage = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout: wgpu::BindGroupLayout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("separation"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage(1, true),
+                storage(2, false),
+                storage(3, false),
+                storage(4, false),
+                storage(5, false),
+            ],
+        });
+        let pipeline_layout: wgpu::PipelineLayout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("separation"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point,
+            })
+        };
+        let insert: wgpu::ComputePipeline = pipeline("insert");
+        let separate: wgpu::ComputePipeline = pipeline("separate");
+
+        Ok(GpuSeparation {
+            device,
+            queue,
+            bind_group_layout,
+            insert,
+            separate,
+            bucket_capacity: INITIAL_BUCKET_CAPACITY,
+            buffers: None,
+        })
+    }
+
+    // The separation sums of every node, or `None` when a bucket overflowed and the sums are incomplete.
+    fn sums(&mut self, differential_growth: &DifferentialGrowth) -> Option<Vec<Vector2<f64>>> {
+        let len: usize = differential_growth.nodes().len();
+        let nodes: Vec<[f32; 4]> = differential_growth
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let desired_separation: f64 = differential_growth.desired_separation_of(i);
+                [node.position.x as f32, node.position.y as f32, desired_separation as f32, 0.0]
+            })
+            .collect();
+        // Cells as large as the largest separation find every neighbor in the 3x3 cells around a node.
+        let cell_size: f32 = nodes.iter().map(|node| node[2]).fold(0.0, f32::max);
+        if len == 0 || !(cell_size > 0.0 && cell_size.is_finite()) {
+            return None;
+        }
+
+        if !self.reserve(len) {
+            return None;
+        }
+        let buffers: &GpuBuffers = self.buffers.as_ref()?;
+        let params: [u32; 4] = [len as u32, buffers.bucket_count, buffers.bucket_capacity, cell_size.to_bits()];
+        self.queue.write_buffer(&buffers.params, 0, bytemuck::cast_slice(&params));
+        self.queue.write_buffer(&buffers.nodes, 0, bytemuck::cast_slice(&nodes));
+
+        let sums_size: u64 = (len * 8) as u64;
+        let workgroups: u32 = len.div_ceil(WORKGROUP_SIZE) as u32;
+        let mut encoder: wgpu::CommandEncoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("separation") });
+        encoder.clear_buffer(&buffers.bucket_lengths, 0, None);
+        encoder.clear_buffer(&buffers.overflow, 0, None);
+        {
+            let mut pass: wgpu::ComputePass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("separation") });
+            pass.set_bind_group(0, &buffers.bind_group, &[]);
+            pass.set_pipeline(&self.insert);
+            pass.dispatch(workgroups, 1, 1);
+            pass.set_pipeline(&self.separate);
+            pass.dispatch(workgroups, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&buffers.sums, 0, &buffers.readback, 0, sums_size);
+        encoder.copy_buffer_to_buffer(&buffers.overflow, 0, &buffers.readback, sums_size, 4);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice: wgpu::BufferSlice = buffers.readback.slice(..sums_size + 4);
+        let mapping = slice.map_async(wgpu::MapMode::Read);
+        self.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(mapping).ok()?;
+
+        let data: wgpu::BufferView = slice.get_mapped_range();
+        let word = |offset: usize| u32::from_ne_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
+        let overflowed: bool = word(len * 8) != 0;
+        let sums: Vec<Vector2<f64>> = (0..len)
+            .map(|i| Vector2::new(f32::from_bits(word(i * 8)) as f64, f32::from_bits(word(i * 8 + 4)) as f64))
+            .collect();
+        drop(data);
+        buffers.readback.unmap();
+
+        if overflowed {
+            // Crowded buckets get room for twice as many nodes from the next tick on.
+            self.bucket_capacity = self.bucket_capacity.saturating_mul(2);
+            return None;
+        }
+        Some(sums)
+    }
+
+    // Makes sure there are buffers for `len` nodes. Returns false when the buckets don't fit on the device.
+    fn reserve(&mut self, len: usize) -> bool {
+        let outgrown: bool = match &self.buffers {
+            Some(buffers) => buffers.capacity < len || buffers.bucket_capacity != self.bucket_capacity,
+            None => true,
+        };
+        if outgrown {
+            let capacity: usize = len.next_power_of_two();
+            let bucket_count: u32 = capacity as u32;
+            let buckets_size: u64 = bucket_count as u64 * self.bucket_capacity as u64 * 4;
+            if buckets_size > self.device.limits().max_storage_buffer_binding_size as u64 {
+                self.buffers = None;
+                return false;
+            }
+
+            let buffer = |label: &str, size: u64, usage: wgpu::BufferUsages| {
+                self.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some(label),
+                    size,
+                    usage,
+                    mapped_at_creation: false,
+                })
+            };
+            let storage: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC;
+            let params: wgpu::Buffer = buffer("params", 16, wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST);
+            let nodes: wgpu::Buffer = buffer("nodes", capacity as u64 * 16, storage);
+            let bucket_lengths: wgpu::Buffer = buffer("bucket lengths", bucket_count as u64 * 4, storage);
+            let buckets: wgpu::Buffer = buffer("buckets", buckets_size, storage);
+            let sums: wgpu::Buffer = buffer("sums", capacity as u64 * 8, storage);
+            let overflow: wgpu::Buffer = buffer("overflow", 4, storage);
+            let readback: wgpu::Buffer = buffer(
+                "readback",
+                capacity as u64 * 8 + 4,
+                wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            );
+
+            let bind_group: wgpu::BindGroup = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("separation"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 1, resource: nodes.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 2, resource: bucket_lengths.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 3, resource: buckets.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 4, resource: sums.as_entire_binding() },
+                    wgpu::BindGroupEntry { binding: 5, resource: overflow.as_entire_binding() },
+                ],
+            });
+
+            self.buffers = Some(GpuBuffers {
+                capacity,
+                bucket_count,
+                bucket_capacity: self.bucket_capacity,
+                params,
+                nodes,
+                bucket_lengths,
+                _buckets: buckets,
+                sums,
+                overflow,
+                readback,
+                bind_group,
+            });
+        }
+
+        true
+    }
+}
@@ -0,0 +1,101 @@
+// The separation sums of every node, see `GpuSeparation` in gpu.rs.
+//
+// `insert` bins the nodes into a hashed uniform grid with cells as large as the largest
+// desired separation, `separate` then sums the differences to the nodes in the 3x3 cells
+// around every node divided by their squared distances.
+
+struct Params {
+    node_count: u32;
+    // A power of two.
+    bucket_count: u32;
+    bucket_capacity: u32;
+    cell_size: f32;
+};
+
+// Storage buffers have to be structs.
+struct Nodes {
+    data: array<vec4<f32>>;
+};
+
+struct Counters {
+    data: array<atomic<u32>>;
+};
+
+struct Indices {
+    data: array<u32>;
+};
+
+struct Sums {
+    data: array<vec2<f32>>;
+};
+
+struct Flag {
+    value: atomic<u32>;
+};
+
+[[group(0), binding(0)]] var<uniform> params: Params;
+// The position of every node in `xy` and its desired separation in `z`.
+[[group(0), binding(1)]] var<storage, read> nodes: Nodes;
+[[group(0), binding(2)]] var<storage, read_write> bucket_lengths: Counters;
+[[group(0), binding(3)]] var<storage, read_write> buckets: Indices;
+[[group(0), binding(4)]] var<storage, read_write> sums: Sums;
+// Set when a bucket is full, the sums are incomplete then.
+[[group(0), binding(5)]] var<storage, read_write> overflow: Flag;
+
+fn cell_of(position: vec2<f32>) -> vec2<i32> {
+    return vec2<i32>(floor(position / params.cell_size));
+}
+
+fn bucket_of(cell: vec2<i32>) -> u32 {
+    return ((bitcast<u32>(cell.x) * 73856093u) ^ (bitcast<u32>(cell.y) * 19349663u)) & (params.bucket_count - 1u);
+}
+
+[[stage(compute), workgroup_size(64)]]
+fn insert([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    let i: u32 = id.x;
+    if (i >= params.node_count) {
+        return;
+    }
+
+    let bucket: u32 = bucket_of(cell_of(nodes.data[i].xy));
+    let slot: u32 = atomicAdd(&bucket_lengths.data[bucket], 1u);
+    if (slot < params.bucket_capacity) {
+        buckets.data[bucket * params.bucket_capacity + slot] = i;
+    } else {
+        atomicStore(&overflow.value, 1u);
+    }
+}
+
+[[stage(compute), workgroup_size(64)]]
+fn separate([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    let i: u32 = id.x;
+    if (i >= params.node_count) {
+        return;
+    }
+
+    let position: vec2<f32> = nodes.data[i].xy;
+    let radius_sq: f32 = nodes.data[i].z * nodes.data[i].z;
+    let cell: vec2<i32> = cell_of(position);
+    var sum: vec2<f32> = vec2<f32>(0.0, 0.0);
+    for (var dx: i32 = -1; dx <= 1; dx = dx + 1) {
+        for (var dy: i32 = -1; dy <= 1; dy = dy + 1) {
+            let neighbor_cell: vec2<i32> = cell + vec2<i32>(dx, dy);
+            let bucket: u32 = bucket_of(neighbor_cell);
+            let bucket_length: u32 = min(atomicLoad(&bucket_lengths.data[bucket]), params.bucket_capacity);
+            for (var k: u32 = 0u; k < bucket_length; k = k + 1u) {
+                let j: u32 = buckets.data[bucket * params.bucket_capacity + k];
+                let other: vec2<f32> = nodes.data[j].xy;
+                // Other cells can share the bucket, only count the nodes of this cell.
+                if (any(cell_of(other) != neighbor_cell)) {
+                    continue;
+                }
+                let difference: vec2<f32> = position - other;
+                let distance_sq: f32 = dot(difference, difference);
+                if (distance_sq > 0.0 && distance_sq <= radius_sq) {
+                    sum = sum + difference / distance_sq;
+                }
+            }
+        }
+    }
+    sums.data[i] = sum;
+}
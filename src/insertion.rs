@@ -0,0 +1,136 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{node::Node, rng::Rng, topology::Topology};
+
+/// Decides where a new node is placed when an edge grows longer
+/// than the maximum edge length and gets split.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{InsertionStrategy, Node, Topology};
+/// use nalgebra::Point2;
+///
+/// // Always inserts at one third of the edge.
+/// struct OneThird;
+///
+/// impl InsertionStrategy for OneThird {
+///     fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, _topology: Topology) -> Point2<f64> {
+///         nodes[i].position + (nodes[j].position - nodes[i].position) / 3.0
+///     }
+/// }
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_insertion_strategy(OneThird);
+/// differential_growth.tick();
+/// ```
+///
+pub trait InsertionStrategy: Send + Sync {
+    /// Returns the position of the node inserted on the edge from node `i` to node `j`.
+    fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, topology: Topology) -> Point2<f64>;
+}
+
+impl<T: InsertionStrategy + ?Sized> InsertionStrategy for Box<T> {
+    fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, topology: Topology) -> Point2<f64> {
+        (**self).insertion_point(nodes, i, j, topology)
+    }
+}
+
+/// Inserts new nodes at the exact middle of the edge. This is the default.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Midpoint;
+
+impl InsertionStrategy for Midpoint {
+    fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, _topology: Topology) -> Point2<f64> {
+        nalgebra::center(&nodes[i].position, &nodes[j].position)
+    }
+}
+
+/// Inserts new nodes at the middle of the edge, displaced by a random offset.
+///
+/// # Examples
+///
+/// ```rust
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_insertion_strategy(differential_growth::JitteredMidpoint::new(0.5, 42));
+/// differential_growth.tick();
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct JitteredMidpoint {
+    /// The maximum distance the inserted node is moved away from the midpoint.
+    pub jitter: f64,
+    rng: Rng,
+}
+
+impl JitteredMidpoint {
+    /// Returns a JitteredMidpoint strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `jitter` - The maximum distance the inserted node is moved away from the midpoint.
+    /// * `seed` - The same seed always produces the same offsets.
+    ///
+    pub fn new(jitter: f64, seed: u64) -> JitteredMidpoint {
+        JitteredMidpoint {
+            jitter,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl InsertionStrategy for JitteredMidpoint {
+    fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, _topology: Topology) -> Point2<f64> {
+        let middle: Point2<f64> = nalgebra::center(&nodes[i].position, &nodes[j].position);
+
+        // Uniformly distributed within a disc of radius jitter.
+        let angle: f64 = self.rng.range(0.0, core::f64::consts::TAU);
+        let radius: f64 = self.jitter * self.rng.next_f64().sqrt();
+
+        middle + Vector2::new(angle.cos(), angle.sin()) * radius
+    }
+}
+
+/// Inserts new nodes on a smooth curve through the surrounding nodes instead of
+/// on the straight edge, so bends keep their curvature as they are refined.
+///
+/// With a weight of `1.0` this is the four-point subdivision scheme:
+/// `9/16 * (p1 + p2) - 1/16 * (p0 + p3)`. A weight of `0.0` is the midpoint,
+/// larger weights exaggerate the curvature.
+#[derive(Copy, Clone, Debug)]
+pub struct CurvatureWeighted {
+    /// How strongly the local curvature displaces the inserted node.
+    pub weight: f64,
+}
+
+impl CurvatureWeighted {
+    /// Returns a CurvatureWeighted strategy with the given weight.
+    pub fn new(weight: f64) -> CurvatureWeighted {
+        CurvatureWeighted { weight }
+    }
+}
+
+impl Default for CurvatureWeighted {
+    fn default() -> Self {
+        CurvatureWeighted { weight: 1.0 }
+    }
+}
+
+impl InsertionStrategy for CurvatureWeighted {
+    fn insertion_point(&mut self, nodes: &[Node], i: usize, j: usize, topology: Topology) -> Point2<f64> {
+        let p1: Vector2<f64> = nodes[i].position.coords;
+        let p2: Vector2<f64> = nodes[j].position.coords;
+        // Endpoints of an open path use the edge itself as outer neighbor,
+        // which locally straightens the curve.
+        let p0: Vector2<f64> = topology.prev(i, nodes.len()).map_or(p1, |prev| nodes[prev].position.coords);
+        let p3: Vector2<f64> = topology.next(j, nodes.len()).map_or(p2, |next| nodes[next].position.coords);
+
+        let middle: Vector2<f64> = (p1 + p2) / 2.0;
+        let outer_middle: Vector2<f64> = (p0 + p3) / 2.0;
+
+        Point2::from(middle + (middle - outer_middle) * (self.weight / 8.0))
+    }
+}
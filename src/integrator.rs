@@ -0,0 +1,138 @@
+use core::ops::MulAssign;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, observer::AppliedForces};
+
+/// How the forces on the nodes are integrated into their movement every tick.
+///
+/// Euler is the cheapest but gets jittery when `max_force` is large compared to
+/// `max_speed`. Verlet and Midpoint evaluate the forces twice per tick, which makes
+/// a tick roughly twice as expensive, in exchange for smoother and more stable motion.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Integrator;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut euler = differential_growth::DifferentialGrowth::new(starting_points.clone(), 10.0, 1.0, 14.0, 1.1, 5.0);
+/// let mut verlet = differential_growth::DifferentialGrowth::new(starting_points, 10.0, 1.0, 14.0, 1.1, 5.0);
+/// verlet.set_integrator(Integrator::Verlet);
+///
+/// for _ in 0..20 {
+///     euler.tick();
+///     verlet.tick();
+/// }
+///
+/// // Both move the nodes by the same forces, just along a slightly different path.
+/// assert!(!euler.diff(&verlet).is_identical());
+/// assert!(verlet.nodes().iter().all(|node| node.velocity.norm() <= 1.0 + 1e-9));
+/// ```
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Integrator {
+    /// Explicit Euler: the forces change the velocity, then the velocity moves the node.
+    #[default]
+    Euler,
+    /// Velocity Verlet: the node moves by its velocity and the forces, then the velocity
+    /// changes by the average of the forces before and after the move.
+    Verlet,
+    /// Midpoint, also known as RK2: the forces are evaluated halfway through the tick
+    /// and move the node for the whole tick.
+    Midpoint,
+}
+
+impl DifferentialGrowth {
+    /// Sets how the forces are integrated into the movement of the nodes, see [`Integrator`].
+    /// Defaults to [`Integrator::Euler`].
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.integrator = integrator;
+    }
+
+    // Moves the nodes at the given indices by the forces acting on them, over `dt` ticks.
+    // Returns the forces of the last evaluation when there are observers to pass them to.
+    pub(crate) fn integrate(&mut self, moving: &[usize], dt: f64) -> Option<AppliedForces> {
+        if self.damping > 0.0 {
+            let remaining: f64 = (1.0 - self.damping.min(1.0)).powf(dt);
+            for &i in moving {
+                self.nodes[i].velocity.mul_assign(remaining);
+            }
+        }
+
+        match self.integrator {
+            Integrator::Euler => {
+                let applied_forces: Option<AppliedForces> = self.accumulate_forces(moving);
+                for &i in moving {
+                    self.nodes[i].update_dt(dt);
+                }
+                applied_forces
+            }
+            Integrator::Verlet => {
+                let (start, external) = self.first_evaluation(moving);
+
+                // Move by the forces at the start and evaluate them again at the new position.
+                for (&i, &(position, velocity, acceleration)) in moving.iter().zip(&start) {
+                    let node = &mut self.nodes[i];
+                    let displacement: Vector2<f64> = (velocity + acceleration * (dt / 2.0)).cap_magnitude(node.max_speed);
+                    node.position = position + displacement * dt;
+                    node.velocity = (velocity + acceleration * dt).cap_magnitude(node.max_speed);
+                }
+                let applied_forces: Option<AppliedForces> = self.second_evaluation(moving, &external);
+
+                for (&i, &(_, velocity, acceleration)) in moving.iter().zip(&start) {
+                    let node = &mut self.nodes[i];
+                    let average: Vector2<f64> = (acceleration + node.acceleration) / 2.0;
+                    node.velocity = (velocity + average * dt).cap_magnitude(node.max_speed);
+                    node.acceleration = Vector2::default();
+                }
+                applied_forces
+            }
+            Integrator::Midpoint => {
+                let (start, external) = self.first_evaluation(moving);
+
+                // Evaluate the forces halfway through the tick.
+                for (&i, &(position, velocity, acceleration)) in moving.iter().zip(&start) {
+                    let node = &mut self.nodes[i];
+                    node.position = position + velocity * (dt / 2.0);
+                    node.velocity = (velocity + acceleration * (dt / 2.0)).cap_magnitude(node.max_speed);
+                }
+                let applied_forces: Option<AppliedForces> = self.second_evaluation(moving, &external);
+
+                for (&i, &(position, velocity, _)) in moving.iter().zip(&start) {
+                    let node = &mut self.nodes[i];
+                    let halfway_velocity: Vector2<f64> = node.velocity;
+                    node.velocity = (velocity + node.acceleration * dt).cap_magnitude(node.max_speed);
+                    node.position = position + halfway_velocity * dt;
+                    node.acceleration = Vector2::default();
+                }
+                applied_forces
+            }
+        }
+    }
+
+    // Evaluates the forces at the start of the tick. Returns the position, velocity and
+    // acceleration every moving node started with and the acceleration applied from
+    // outside the simulation, like the forces between the paths of a MultiPathGrowth.
+    #[allow(clippy::type_complexity)]
+    fn first_evaluation(&mut self, moving: &[usize]) -> (Vec<(Point2<f64>, Vector2<f64>, Vector2<f64>)>, Vec<Vector2<f64>>) {
+        let external: Vec<Vector2<f64>> = moving.iter().map(|&i| self.nodes[i].acceleration).collect();
+        self.accumulate_forces(moving);
+        let start = moving
+            .iter()
+            .map(|&i| (self.nodes[i].position, self.nodes[i].velocity, self.nodes[i].acceleration))
+            .collect();
+
+        (start, external)
+    }
+
+    // Evaluates the forces again at the current positions and velocities.
+    fn second_evaluation(&mut self, moving: &[usize], external: &[Vector2<f64>]) -> Option<AppliedForces> {
+        for (&i, acceleration) in moving.iter().zip(external) {
+            self.nodes[i].acceleration = *acceleration;
+        }
+        self.accumulate_forces(moving)
+    }
+}
@@ -0,0 +1,172 @@
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, geometry};
+
+/// A point where the path crosses itself.
+///
+/// Edges are identified by the index of the node they start from,
+/// edge `i` runs from node `i` to the next node.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfIntersection {
+    /// The edge starting at the lowest node index.
+    pub first: usize,
+    /// The other edge.
+    pub second: usize,
+    /// Where the edges cross.
+    pub point: Point2<f64>,
+}
+
+impl DifferentialGrowth {
+    /// Returns every pair of edges that cross eachother, ordered by their first edge.
+    /// Edges sharing a node never count as crossing.
+    ///
+    /// A sweep line over the x coordinate only tests edges whose horizontal
+    /// extents overlap, so this stays fast for paths with many nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// // A bow tie crossing itself in the middle.
+    /// let starting_points = vec![
+    ///     Point2::new(0.0, 0.0),
+    ///     Point2::new(10.0, 10.0),
+    ///     Point2::new(10.0, 0.0),
+    ///     Point2::new(0.0, 10.0),
+    /// ];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let intersections = differential_growth.self_intersections();
+    /// assert_eq!(intersections.len(), 1);
+    /// assert_eq!((intersections[0].first, intersections[0].second), (0, 2));
+    /// assert_eq!(intersections[0].point, Point2::new(5.0, 5.0));
+    /// ```
+    ///
+    #[must_use]
+    pub fn self_intersections(&self) -> Vec<SelfIntersection> {
+        let len: usize = self.nodes.len();
+        let edges: Vec<(usize, usize)> = self.topology.edges(len).collect();
+
+        // Edges sorted by where they start along the sweep direction.
+        let min_x = |&(i, j): &(usize, usize)| self.nodes[i].position.x.min(self.nodes[j].position.x);
+        let max_x = |&(i, j): &(usize, usize)| self.nodes[i].position.x.max(self.nodes[j].position.x);
+        let mut order: Vec<usize> = (0..edges.len()).collect();
+        order.sort_by(|&a, &b| min_x(&edges[a]).total_cmp(&min_x(&edges[b])));
+
+        let mut active: Vec<usize> = Vec::new();
+        let mut intersections: Vec<SelfIntersection> = Vec::new();
+
+        for edge in order {
+            let (i, j): (usize, usize) = edges[edge];
+            let sweep_x: f64 = min_x(&edges[edge]);
+            // Edges ending before the sweep line can't cross anything anymore.
+            active.retain(|&other| max_x(&edges[other]) >= sweep_x);
+
+            for &other in &active {
+                let (k, l): (usize, usize) = edges[other];
+                if i == k || i == l || j == k || j == l {
+                    continue;
+                }
+
+                if let Some((point, _)) = geometry::segment_intersection(
+                    &self.nodes[i].position,
+                    &self.nodes[j].position,
+                    &self.nodes[k].position,
+                    &self.nodes[l].position,
+                ) {
+                    intersections.push(SelfIntersection {
+                        first: edge.min(other),
+                        second: edge.max(other),
+                        point,
+                    });
+                }
+            }
+
+            active.push(edge);
+        }
+
+        intersections.sort_by_key(|intersection| (intersection.first, intersection.second));
+        intersections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use super::SelfIntersection;
+    use crate::prelude::*;
+    use crate::{differential_growth::DifferentialGrowth, geometry, rng::Rng, topology::Topology};
+
+    // Every pair of edges that don't share a node, tested against eachother.
+    fn brute_force(differential_growth: &DifferentialGrowth) -> Vec<(usize, usize, Point2<f64>)> {
+        let nodes = differential_growth.nodes();
+        let edges: Vec<(usize, usize)> = differential_growth.topology.edges(nodes.len()).collect();
+        let mut intersections: Vec<(usize, usize, Point2<f64>)> = Vec::new();
+        for (a, &(i, j)) in edges.iter().enumerate() {
+            for (b, &(k, l)) in edges.iter().enumerate().skip(a + 1) {
+                if i == k || i == l || j == k || j == l {
+                    continue;
+                }
+                if let Some((point, _)) =
+                    geometry::segment_intersection(&nodes[i].position, &nodes[j].position, &nodes[k].position, &nodes[l].position)
+                {
+                    intersections.push((a, b, point));
+                }
+            }
+        }
+        intersections
+    }
+
+    #[test]
+    fn sweep_matches_brute_force() {
+        let mut rng: Rng = Rng::new(7);
+        for topology in [Topology::Closed, Topology::Open] {
+            for len in [3, 4, 10, 40] {
+                let starting_points: Vec<Point2<f64>> = (0..len)
+                    .map(|_| Point2::new(rng.range(-20.0, 20.0), rng.range(-20.0, 20.0)))
+                    .collect();
+                let mut differential_growth = DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+                differential_growth.topology = topology;
+
+                let intersections: Vec<SelfIntersection> = differential_growth.self_intersections();
+                let expected: Vec<(usize, usize, Point2<f64>)> = brute_force(&differential_growth);
+                assert_eq!(intersections.len(), expected.len());
+                for (intersection, &(first, second, point)) in intersections.iter().zip(&expected) {
+                    assert_eq!((intersection.first, intersection.second), (first, second));
+                    // Computed from the other edge, the point can differ in the last bits.
+                    assert!(nalgebra::distance(&intersection.point, &point) < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn touching_and_collinear_edges() {
+        // The last edge runs back over the first one, the third touches it at its end.
+        let starting_points: Vec<Point2<f64>> = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 5.0),
+            Point2::new(5.0, 0.0),
+            Point2::new(5.0, -5.0),
+        ];
+        let mut differential_growth = DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+        for topology in [Topology::Closed, Topology::Open] {
+            differential_growth.topology = topology;
+            let intersections: Vec<(usize, usize)> = differential_growth
+                .self_intersections()
+                .iter()
+                .map(|intersection| (intersection.first, intersection.second))
+                .collect();
+            let expected: Vec<(usize, usize)> = brute_force(&differential_growth)
+                .iter()
+                .map(|&(first, second, _)| (first, second))
+                .collect();
+            assert_eq!(intersections, expected);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use nalgebra::{distance, Point2};
+
+use crate::prelude::*;
+
+/// The path the leading endpoint of an open path follows over time.
+///
+/// Any closure taking the current tick and returning a position is a trajectory.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Topology, Trajectory};
+/// use nalgebra::Point2;
+///
+/// let mut spiral = |tick: usize| {
+///     let angle: f64 = tick as f64 * 0.05;
+///     Point2::new(angle.cos() * angle * 10.0, angle.sin() * angle * 10.0)
+/// };
+/// assert_eq!(spiral.position(0), Point2::new(0.0, 0.0));
+///
+/// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)];
+/// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .topology(Topology::Open)
+///     .build();
+/// differential_growth.set_leader(spiral);
+/// differential_growth.tick();
+/// ```
+///
+pub trait Trajectory: Send + Sync {
+    /// Returns where the leader should be at `tick`.
+    fn position(&mut self, tick: usize) -> Point2<f64>;
+}
+
+impl<F: FnMut(usize) -> Point2<f64> + Send + Sync> Trajectory for F {
+    fn position(&mut self, tick: usize) -> Point2<f64> {
+        self(tick)
+    }
+}
+
+/// Moves along a polyline at a constant speed, stopping at its last point.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{PolylineTrajectory, Trajectory};
+/// use nalgebra::Point2;
+///
+/// let mut trajectory = PolylineTrajectory::new(vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0)], 2.0);
+/// assert_eq!(trajectory.position(3), Point2::new(6.0, 0.0));
+/// assert_eq!(trajectory.position(7), Point2::new(10.0, 4.0));
+/// assert_eq!(trajectory.position(100), Point2::new(10.0, 10.0));
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolylineTrajectory {
+    /// The points of the polyline that is followed.
+    pub points: Vec<Point2<f64>>,
+    /// The distance travelled along the polyline every tick.
+    pub speed: f64,
+}
+
+impl PolylineTrajectory {
+    /// Returns a PolylineTrajectory.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points of the polyline that is followed.
+    /// * `speed` - The distance travelled along the polyline every tick.
+    ///
+    pub fn new(points: Vec<Point2<f64>>, speed: f64) -> PolylineTrajectory {
+        PolylineTrajectory { points, speed }
+    }
+}
+
+impl Trajectory for PolylineTrajectory {
+    fn position(&mut self, tick: usize) -> Point2<f64> {
+        let mut remaining: f64 = tick as f64 * self.speed;
+
+        for segment in self.points.windows(2) {
+            let length: f64 = distance(&segment[0], &segment[1]);
+            if remaining <= length {
+                // Degenerate segments are skipped over.
+                if length == 0.0 {
+                    return segment[0];
+                }
+                return segment[0] + (segment[1] - segment[0]) * (remaining / length);
+            }
+            remaining -= length;
+        }
+
+        self.points.last().copied().unwrap_or_else(Point2::origin)
+    }
+}
@@ -20,7 +20,7 @@
 //! 
 //! # Example
 //! 
-//! The best way to understand this crate is by taking a look at `/example` folder. You can run it on any platform running `cargo run --example example`.
+//! The best way to understand this crate is by taking a look at `/example` folder. You can run it on any platform running `cargo run --example example --features nannou`.
 //! 
 //! Otherwise, below is a quick reference on how to use this crate.
 //! 
@@ -53,12 +53,220 @@
 //! // - drawing a line between the first and the last element.
 //! ```
 //! 
+//! # `no_std`
+//! 
+//! The algorithm itself only needs `alloc`. Disable the default `std` feature to use it without
+//! the standard library, the float math goes through `libm` then. The kd-tree spatial index,
+//! writing files, [`Sandbox`] and the 3D growth need `std`, [`GridIndex`] is the spatial index then.
+//! 
+//! # Determinism
+//! 
+//! A simulation only uses randomness from its seed, see [`DifferentialGrowth::set_seed()`], and
+//! updates its nodes in a fixed order, also on several threads with the `rayon` feature.
+//! The same starting points, parameters, seed and ticks give bit-identical results on every run.
+//! To reproduce a result on other machines and versions of the dependencies as well:
+//! 
+//! - Enable [`DifferentialGrowth::set_deterministic()`], so a different spatial index or traversal
+//!   order of the kd-tree doesn't change the sums of the neighbor forces.
+//! - Tick with a fixed time step, [`DifferentialGrowth::tick()`] or [`DifferentialGrowth::tick_dt()`]
+//!   with a constant `dt`, not the time a frame took.
+//! - Don't rely on time limits like those of [`Sandbox`], or on the `gpu` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+mod access;
+#[cfg(feature = "gif")]
+mod animation;
+#[cfg(feature = "std")]
+mod batch;
+mod brightness;
+mod bootstrap;
+mod builder;
+mod changes;
+mod cleanup;
+mod cohesion;
+mod constraints;
+#[cfg(feature = "std")]
+mod csv;
+mod diff;
 mod differential_growth;
+#[cfg(feature = "std")]
+mod differential_growth_3d;
+mod division;
+#[cfg(feature = "nannou")]
+mod drawing;
+#[cfg(feature = "dxf")]
+mod dxf;
+mod edge_separation;
+mod endpoints;
+mod explain;
+mod export;
+#[cfg(feature = "lyon")]
+mod extrusion;
+mod external_force;
+#[cfg(feature = "font")]
+mod font;
+mod force;
+mod geometry;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod insertion;
+mod integrator;
+mod intersections;
+mod leader;
+mod lod;
+#[cfg(feature = "lsystem")]
+mod lsystem;
+mod manipulation;
+#[cfg(not(feature = "std"))]
+mod math;
+mod merging;
+#[cfg(feature = "mesh")]
+mod mesh;
+mod multi_path;
 mod node;
+mod node_data;
+mod node_params;
+mod node_view;
+mod observer;
+mod offset;
+mod oscillation;
+mod overlay;
+mod parallel;
+mod param_field;
+mod params;
+mod periodic;
 #[cfg(feature = "point_generators")]
 mod point_generators;
+mod prelude;
+mod profile;
+#[cfg(feature = "python")]
+mod python;
+mod quality;
+#[cfg(feature = "raster")]
+mod raster;
+mod recording;
+mod region;
+mod registry;
+mod renormalization;
+mod rng;
+#[cfg(feature = "std")]
+mod sandbox;
+mod scene;
+mod scratch;
+mod self_contact;
+mod separation;
+#[cfg(feature = "rhai")]
+mod scripting;
+#[cfg(feature = "std")]
+mod shared;
+mod soa;
+mod spatial;
+mod spline;
+mod state;
+mod stats;
+#[cfg(feature = "svg_path")]
+mod svg_path;
+#[cfg(feature = "lyon")]
+mod tessellation;
+mod text;
+mod topology;
+mod trail;
+mod units;
+mod validation;
+mod velocity;
+mod watchdog;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub use crate::access::*;
+#[cfg(feature = "gif")]
+pub use crate::animation::*;
+#[cfg(feature = "std")]
+pub use crate::batch::*;
+pub use crate::brightness::*;
+pub use crate::builder::*;
+pub use crate::changes::{ChangeKind, ChangedRange, NodeEvent};
+pub use crate::cohesion::*;
+pub use crate::constraints::*;
+#[cfg(feature = "std")]
+pub use crate::csv::*;
+pub use crate::diff::*;
 pub use crate::differential_growth::*;
+#[cfg(feature = "std")]
+pub use crate::differential_growth_3d::*;
+pub use crate::division::*;
+#[cfg(feature = "nannou")]
+pub use crate::drawing::*;
+pub use crate::endpoints::*;
+pub use crate::explain::*;
+pub use crate::export::*;
+#[cfg(feature = "lyon")]
+pub use crate::extrusion::*;
+#[cfg(feature = "font")]
+pub use crate::font::*;
+pub use crate::force::*;
+#[cfg(feature = "gpu")]
+pub use crate::gpu::*;
+pub use crate::insertion::*;
+pub use crate::integrator::*;
+pub use crate::intersections::*;
+pub use crate::leader::*;
+pub use crate::lod::*;
+#[cfg(feature = "lsystem")]
+pub use crate::lsystem::*;
+pub use crate::merging::*;
+#[cfg(feature = "mesh")]
+pub use crate::mesh::*;
+pub use crate::multi_path::*;
+pub use crate::node::*;
+pub use crate::node_params::*;
+pub use crate::node_view::*;
+pub use crate::observer::*;
+pub use crate::offset::*;
+pub use crate::oscillation::*;
+pub use crate::overlay::{Falloff, ParamOverlay, RadialGradient};
+pub use crate::params::*;
 #[cfg(feature = "point_generators")]
 pub use crate::point_generators::*;
+#[cfg(feature = "profiling")]
+pub use crate::profile::*;
+#[cfg(feature = "python")]
+pub use crate::python::*;
+pub use crate::quality::*;
+#[cfg(feature = "raster")]
+pub use crate::raster::*;
+pub use crate::recording::*;
+pub use crate::region::*;
+pub use crate::registry::*;
+pub use crate::renormalization::*;
+#[cfg(feature = "std")]
+pub use crate::sandbox::*;
+pub use crate::scene::*;
+pub use crate::self_contact::*;
+pub use crate::separation::*;
+#[cfg(feature = "rhai")]
+pub use crate::scripting::*;
+#[cfg(feature = "std")]
+pub use crate::shared::*;
+#[cfg(feature = "std")]
+pub use crate::spatial::KdTreeIndex;
+pub use crate::spatial::{GridIndex, RebuildPolicy, SpatialIndex};
+pub use crate::spline::*;
+pub use crate::state::*;
+pub use crate::stats::*;
+#[cfg(feature = "svg_path")]
+pub use crate::svg_path::*;
+#[cfg(feature = "lyon")]
+pub use crate::tessellation::*;
+pub use crate::text::*;
+pub use crate::topology::*;
+pub use crate::trail::*;
+pub use crate::units::*;
+pub use crate::validation::*;
+pub use crate::velocity::*;
+pub use crate::watchdog::{BlowUp, Watchdog, WatchdogEvent};
+#[cfg(feature = "wasm")]
+pub use crate::wasm::*;
@@ -0,0 +1,76 @@
+use nalgebra::{distance, Point2};
+
+use crate::prelude::*;
+
+/// Simulates the nodes far away from a point of focus, like the camera of an
+/// installation, less often than the nodes close to it.
+///
+/// Nodes within `radius` of the focus move every tick. Further out the interval
+/// between updates grows linearly over the `blend` distance to `interval` ticks,
+/// so there is no visible seam where the detail drops. Skipped nodes don't move,
+/// so time runs slower in the background and it grows slower than the foreground.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::LevelOfDetail;
+/// use nalgebra::Point2;
+///
+/// let level_of_detail = LevelOfDetail::new(Point2::new(0.0, 0.0), 50.0, 25.0, 4);
+/// assert_eq!(level_of_detail.interval_at(&Point2::new(10.0, 0.0)), 1);
+/// assert_eq!(level_of_detail.interval_at(&Point2::new(62.5, 0.0)), 3);
+/// assert_eq!(level_of_detail.interval_at(&Point2::new(500.0, 0.0)), 4);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_level_of_detail(level_of_detail);
+/// differential_growth.tick();
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelOfDetail {
+    /// The point around which the path is simulated at full detail.
+    pub focus: Point2<f64>,
+    /// Nodes within this distance of the focus are updated every tick.
+    pub radius: f64,
+    /// The distance beyond the radius over which the detail drops to the background interval.
+    pub blend: f64,
+    /// The amount of ticks between updates of background nodes.
+    pub interval: usize,
+}
+
+impl LevelOfDetail {
+    /// Returns a LevelOfDetail with its full detail region around `focus`.
+    pub fn new(focus: Point2<f64>, radius: f64, blend: f64, interval: usize) -> LevelOfDetail {
+        LevelOfDetail {
+            focus,
+            radius,
+            blend,
+            interval,
+        }
+    }
+
+    /// Returns the amount of ticks between updates of a node at `position`, at least 1.
+    pub fn interval_at(&self, position: &Point2<f64>) -> usize {
+        let interval: usize = self.interval.max(1);
+        let distance: f64 = distance(&self.focus, position) - self.radius;
+
+        if distance <= 0.0 {
+            1
+        } else if distance >= self.blend {
+            interval
+        } else {
+            1 + ((interval - 1) as f64 * distance / self.blend).round() as usize
+        }
+    }
+
+    // Returns which nodes are updated during this iteration.
+    pub(crate) fn active(&self, positions: impl Iterator<Item = Point2<f64>>, iteration: usize) -> Vec<bool> {
+        // All nodes with the same interval update on the same ticks, so neighbors
+        // move together instead of tugging at eachother.
+        positions
+            .map(|position| iteration % self.interval_at(&position) == 0)
+            .collect()
+    }
+}
@@ -0,0 +1,161 @@
+use nalgebra::{Point2, Vector2};
+use core::{f64::consts::PI, ops::AddAssign};
+
+use crate::prelude::*;
+
+/// A Lindenmayer system that can be interpreted with turtle graphics
+/// to generate a seed polyline.
+///
+/// The turtle understands the following symbols:
+/// * `F` and `G` - Move forward by the step length and emit a point.
+/// * `f` - Move forward by the step length without emitting a point.
+/// * `+` - Turn left by the angle.
+/// * `-` - Turn right by the angle.
+/// * `|` - Turn around.
+/// * `[` - Push the current position and heading on the stack.
+/// * `]` - Pop a position and heading from the stack.
+///
+/// All other symbols are ignored by the turtle but can still be used in rules.
+///
+/// Since the simulation works on a single connected path, a popped state or
+/// a `f` move is connected to the previous point by a straight edge.
+///
+/// # Examples
+///
+/// ```rust
+/// // Koch snowflake.
+/// let mut lsystem = differential_growth::LSystem::new("F--F--F", 60.0, 10.0);
+/// lsystem.add_rule('F', "F+F--F+F");
+/// let points = lsystem.generate_points(0.0, 0.0, 2);
+/// assert_eq!(points.len(), 48);
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct LSystem {
+    /// The starting string.
+    pub axiom: String,
+    /// Production rules, each symbol is replaced by its successor on every iteration.
+    pub rules: HashMap<char, String>,
+    /// The angle in degrees the turtle turns on `+` and `-`.
+    pub angle: f64,
+    /// The distance the turtle moves on `F`, `G` and `f`.
+    pub step_length: f64,
+}
+
+impl LSystem {
+    /// Returns an LSystem without any rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `axiom` - The starting string.
+    /// * `angle` - The angle in degrees the turtle turns on `+` and `-`.
+    /// * `step_length` - The distance the turtle moves on `F`, `G` and `f`.
+    ///
+    pub fn new(axiom: &str, angle: f64, step_length: f64) -> LSystem {
+        LSystem {
+            axiom: axiom.to_string(),
+            rules: HashMap::new(),
+            angle,
+            step_length,
+        }
+    }
+
+    /// Adds a production rule replacing `predecessor` by `successor`.
+    /// Adding a rule for a symbol that already has one replaces it.
+    pub fn add_rule(&mut self, predecessor: char, successor: &str) -> &mut LSystem {
+        self.rules.insert(predecessor, successor.to_string());
+        self
+    }
+
+    /// Applies the production rules `iterations` times to the axiom.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut lsystem = differential_growth::LSystem::new("A", 90.0, 1.0);
+    /// lsystem.add_rule('A', "AB").add_rule('B', "A");
+    /// assert_eq!(lsystem.expand(3), "ABAAB");
+    /// ```
+    ///
+    pub fn expand(&self, iterations: usize) -> String {
+        let mut current: String = self.axiom.clone();
+
+        for _ in 0..iterations {
+            let mut next: String = String::with_capacity(current.len() * 2);
+            for symbol in current.chars() {
+                match self.rules.get(&symbol) {
+                    Some(successor) => next.push_str(successor),
+                    None => next.push(symbol),
+                }
+            }
+            current = next;
+        }
+
+        current
+    }
+
+    /// Expands the system and interprets the result with turtle graphics,
+    /// returning the points the turtle emitted.
+    ///
+    /// The turtle starts at `(origin_x, origin_y)`, heading along the positive x axis.
+    /// Consecutive duplicate points are skipped and when the path ends where it
+    /// started the last point is dropped, since the path is closed anyway.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin_x` - The x coordinate the turtle starts at.
+    /// * `origin_y` - The y coordinate the turtle starts at.
+    /// * `iterations` - The amount of times the production rules are applied.
+    ///
+    pub fn generate_points(&self, origin_x: f64, origin_y: f64, iterations: usize) -> Vec<Point2<f64>> {
+        let instructions: String = self.expand(iterations);
+        let turn: f64 = self.angle * PI / 180.0;
+
+        let mut points: Vec<Point2<f64>> = vec![Point2::new(origin_x, origin_y)];
+        let mut position: Point2<f64> = Point2::new(origin_x, origin_y);
+        let mut heading: f64 = 0.0;
+        let mut stack: Vec<(Point2<f64>, f64)> = Vec::new();
+
+        for symbol in instructions.chars() {
+            match symbol {
+                'F' | 'G' => {
+                    position.add_assign(Vector2::new(heading.cos(), heading.sin()) * self.step_length);
+                    push_distinct(&mut points, position);
+                }
+                'f' => {
+                    position.add_assign(Vector2::new(heading.cos(), heading.sin()) * self.step_length);
+                }
+                '+' => heading += turn,
+                '-' => heading -= turn,
+                '|' => heading += PI,
+                '[' => stack.push((position, heading)),
+                ']' => {
+                    if let Some((popped_position, popped_heading)) = stack.pop() {
+                        position = popped_position;
+                        heading = popped_heading;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Closed figures end on the starting point which would create a zero-length edge.
+        if points.len() > 1 && is_same_point(&points[0], &points[points.len() - 1]) {
+            points.pop();
+        }
+
+        points
+    }
+}
+
+fn push_distinct(points: &mut Vec<Point2<f64>>, point: Point2<f64>) {
+    match points.last() {
+        Some(last) if is_same_point(last, &point) => {}
+        _ => points.push(point),
+    }
+}
+
+// Turtle positions accumulate rounding errors, so exact comparison is not enough.
+fn is_same_point(p1: &Point2<f64>, p2: &Point2<f64>) -> bool {
+    nalgebra::distance(p1, p2) < 1e-9
+}
@@ -0,0 +1,117 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::differential_growth::DifferentialGrowth;
+
+impl DifferentialGrowth {
+    /// Moves the node at `index` to `position`, like dragging it with the mouse.
+    ///
+    /// The node comes to rest there: its velocity and the forces it gathered are dropped,
+    /// so it doesn't shoot back or fly off once it's let go. Its neighbors follow through the
+    /// forces of the next ticks. Does nothing when there is no node at `index` or `position`
+    /// isn't finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick_n(5);
+    ///
+    /// // Drag the node under the cursor.
+    /// let cursor: Point2<f64> = Point2::new(11.0, 1.0);
+    /// let (index, _) = differential_growth.nearest_node(&cursor).unwrap();
+    /// differential_growth.move_node(index, Point2::new(20.0, 0.0));
+    ///
+    /// assert_eq!(differential_growth.nodes()[index].position, Point2::new(20.0, 0.0));
+    /// assert_eq!(differential_growth.nodes()[index].velocity.magnitude(), 0.0);
+    /// assert_eq!(differential_growth.nearest_node(&Point2::new(19.0, 0.0)).unwrap().0, index);
+    /// ```
+    ///
+    pub fn move_node(&mut self, index: usize, position: Point2<f64>) {
+        if !(position.x.is_finite() && position.y.is_finite()) {
+            return;
+        }
+
+        if let Some(node) = self.nodes.get_mut(index) {
+            node.position = position;
+            node.velocity = Vector2::default();
+            node.acceleration = Vector2::default();
+            self.neighbor_search.invalidate();
+        }
+    }
+
+    /// Sets the velocity of the node at `index`, like flicking it. The velocity is capped
+    /// at the maximum speed of the node. Does nothing when there is no node at `index`,
+    /// the node is frozen or `velocity` isn't finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Vector2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// differential_growth.set_node_velocity(0, Vector2::new(100.0, 0.0));
+    /// assert_eq!(differential_growth.nodes()[0].velocity, Vector2::new(1.0, 0.0));
+    /// ```
+    ///
+    pub fn set_node_velocity(&mut self, index: usize, velocity: Vector2<f64>) {
+        if self.is_node_frozen(index) || !(velocity.x.is_finite() && velocity.y.is_finite()) {
+            return;
+        }
+
+        if let Some(node) = self.nodes.get_mut(index) {
+            node.velocity = velocity.cap_magnitude(node.max_speed);
+        }
+    }
+
+    /// Pins the node at `index` in place when `frozen` is `true`, or lets it move again.
+    ///
+    /// Frozen nodes ignore every force, including the ones from
+    /// [`DifferentialGrowth::apply_external_force()`], but can still be moved with
+    /// [`DifferentialGrowth::move_node()`] and still push other nodes away. Edges between
+    /// frozen nodes still split, the nodes inserted between two frozen nodes are frozen too.
+    /// Does nothing when there is no node at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let pinned: Point2<f64> = differential_growth.nodes()[0].position;
+    ///
+    /// differential_growth.freeze_node(0, true);
+    /// differential_growth.tick_n(20);
+    /// assert_eq!(differential_growth.nodes()[0].position, pinned);
+    /// assert!(differential_growth.is_node_frozen(0));
+    ///
+    /// differential_growth.freeze_node(0, false);
+    /// differential_growth.tick_n(20);
+    /// assert_ne!(differential_growth.nodes()[0].position, pinned);
+    /// ```
+    ///
+    pub fn freeze_node(&mut self, index: usize, frozen: bool) {
+        if index >= self.nodes.len() || (!frozen && self.frozen.is_empty()) {
+            return;
+        }
+
+        self.frozen.resize(self.nodes.len(), false);
+        self.frozen[index] = frozen;
+        if frozen {
+            let node = &mut self.nodes[index];
+            node.velocity = Vector2::default();
+            node.acceleration = Vector2::default();
+        }
+    }
+
+    /// Returns whether the node at `index` is frozen, see [`DifferentialGrowth::freeze_node()`].
+    #[must_use]
+    pub fn is_node_frozen(&self, index: usize) -> bool {
+        self.frozen.get(index).copied().unwrap_or(false)
+    }
+}
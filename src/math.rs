@@ -0,0 +1,77 @@
+/// The float methods of the standard library this crate uses, implemented with `libm` for
+/// builds without `std`.
+pub(crate) trait Float {
+    fn sqrt(self) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn ln(self) -> f64;
+    fn exp(self) -> f64;
+    fn hypot(self, other: f64) -> f64;
+    #[cfg_attr(not(feature = "svg_path"), allow(dead_code))]
+    fn atan2(self, other: f64) -> f64;
+    fn floor(self) -> f64;
+    fn ceil(self) -> f64;
+    fn round(self) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn hypot(self, other: f64) -> f64 {
+        libm::hypot(self, other)
+    }
+
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder: f64 = self % rhs;
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use nalgebra::{distance, Point2};
+
+use crate::prelude::*;
+use crate::{
+    changes::NodeEvent,
+    differential_growth::DifferentialGrowth,
+    node::Node,
+    node_data::Source,
+    spatial::{DefaultIndex, SpatialIndex},
+    topology::Topology,
+};
+
+/// When the paths of a [`crate::MultiPathGrowth`] merge as they collide, see
+/// [`DifferentialGrowth::merge_at_contact()`].
+///
+/// Paths that just divided are still close where they pinched, keep the threshold
+/// well below the one of [`crate::Division`] to keep them from merging right back.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{DifferentialGrowth, Interaction, Merging, MultiPathGrowth};
+///
+/// let left = differential_growth::generate_points_on_circle(-10.5, 0.0, 10.0, 20);
+/// let right = differential_growth::generate_points_on_circle(10.5, 0.0, 10.0, 20);
+///
+/// let mut multi_path_growth = MultiPathGrowth::new(Interaction::Independent);
+/// multi_path_growth.add_path(DifferentialGrowth::builder().points(left).build(), 0);
+/// multi_path_growth.add_path(DifferentialGrowth::builder().points(right).build(), 0);
+/// multi_path_growth.merging = Some(Merging { threshold: 2.0 });
+///
+/// multi_path_growth.tick();
+/// assert_eq!(multi_path_growth.paths().len(), 1);
+/// assert!(multi_path_growth.paths()[0].nodes().len() >= 40);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Merging {
+    /// Two paths with nodes closer than this merge. Defaults to `1.0`.
+    pub threshold: f64,
+}
+
+impl Default for Merging {
+    fn default() -> Self {
+        Merging { threshold: 1.0 }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Merges `other` into this path where their nodes come closest, if closer than `threshold`.
+    /// Returns whether they merged.
+    ///
+    /// The path is cut open after its node at the contact and `other` is spliced in, starting
+    /// from its node at the contact and going around in the direction that keeps the new edges
+    /// short, so the merged path doesn't cross itself there. A closed path can be spliced into
+    /// any path. An open path can only take in a closed one or be taken in by one, which opens
+    /// the merged path. Two open paths never merge.
+    ///
+    /// The parameters of this path apply to the merged path. The nodes of `other` keep their
+    /// birth ticks, get new ids and default node data, see [`crate::NodeEvent::Reset`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// let left = differential_growth::generate_points_on_circle(-10.5, 0.0, 10.0, 20);
+    /// let right = differential_growth::generate_points_on_circle(10.5, 0.0, 10.0, 20);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(left, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// let other = differential_growth::DifferentialGrowth::new(right, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// assert!(!differential_growth.merge_at_contact(&other, 0.5));
+    /// assert!(differential_growth.merge_at_contact(&other, 1.5));
+    /// assert_eq!(differential_growth.nodes().len(), 40);
+    /// assert_eq!(differential_growth.topology, Topology::Closed);
+    ///
+    /// // The contact is bridged by two short edges, not by a long one across the circle.
+    /// let points = differential_growth.get_points();
+    /// let longest: f64 = (0..40).map(|i| nalgebra::distance(&points[i], &points[(i + 1) % 40])).fold(0.0, f64::max);
+    /// assert!(longest < 4.0);
+    /// ```
+    ///
+    pub fn merge_at_contact(&mut self, other: &DifferentialGrowth, threshold: f64) -> bool {
+        if self.topology == Topology::Open && other.topology == Topology::Open {
+            return false;
+        }
+
+        let (a, b): (usize, usize) = match self.closest_contact(other, threshold) {
+            Some(contact) => contact,
+            None => return false,
+        };
+
+        // Only closed paths can be spliced in, an open one takes this path in instead.
+        let (host, guest, host_contact, guest_contact, host_is_self): (&[Node], &[Node], usize, usize, bool) =
+            if other.topology == Topology::Closed {
+                (&self.nodes, &other.nodes, a, b, true)
+            } else {
+                (&other.nodes, &self.nodes, b, a, false)
+            };
+
+        let guest_len: usize = guest.len();
+        let host_next: Option<Point2<f64>> = match host_contact + 1 < host.len() {
+            true => Some(host[host_contact + 1].position),
+            false if host_is_self && self.topology == Topology::Closed => Some(host[0].position),
+            false => None,
+        };
+        // Going around backwards ends the guest at the node after its contact instead of the one before.
+        let backwards: bool = match host_next {
+            Some(next) => {
+                let forwards_end: Point2<f64> = guest[(guest_contact + guest_len - 1) % guest_len].position;
+                let backwards_end: Point2<f64> = guest[(guest_contact + 1) % guest_len].position;
+                distance(&backwards_end, &next) < distance(&forwards_end, &next)
+            }
+            None => false,
+        };
+        let guest_order: Vec<usize> = (0..guest_len)
+            .map(|k| match backwards {
+                true => (guest_contact + guest_len - k) % guest_len,
+                false => (guest_contact + k) % guest_len,
+            })
+            .collect();
+
+        // The host up to its contact, the guest all the way around and the rest of the host.
+        let order: Vec<(bool, usize)> = (0..=host_contact)
+            .map(|i| (true, i))
+            .chain(guest_order.into_iter().map(|i| (false, i)))
+            .chain((host_contact + 1..host.len()).map(|i| (true, i)))
+            .collect();
+        let nodes: Vec<Node> = order
+            .iter()
+            .map(|&(of_host, i)| if of_host { host[i] } else { guest[i] })
+            .collect();
+        // Nodes of this path keep their values, the nodes of the other path start out fresh.
+        let sources: Vec<Source> = order
+            .iter()
+            .map(|&(of_host, i)| if of_host == host_is_self { Source::Node(i) } else { Source::New })
+            .collect();
+
+        let own_ids: Vec<bool> = sources.iter().map(|source| *source != Source::New).collect();
+        self.nodes = nodes;
+        for (i, own_id) in own_ids.into_iter().enumerate() {
+            if !own_id {
+                self.nodes[i].id = self.take_node_id();
+            }
+        }
+        if !host_is_self {
+            self.topology = Topology::Open;
+        }
+
+        self.neighbor_search.invalidate();
+        if self.has_node_values() {
+            self.remap_node_values(&sources);
+        }
+        self.push_node_event(NodeEvent::Reset);
+        true
+    }
+
+    // The closest pair of a node of this path and a node of `other` closer than `threshold`.
+    fn closest_contact(&self, other: &DifferentialGrowth, threshold: f64) -> Option<(usize, usize)> {
+        let positions: Vec<Point2<f64>> = self.get_points();
+        let mut index: DefaultIndex = DefaultIndex::default();
+        index.rebuild(&positions);
+
+        let mut close_nodes: Vec<usize> = Vec::new();
+        let mut closest: Option<(usize, usize, f64)> = None;
+        for (b, node) in other.nodes.iter().enumerate() {
+            close_nodes.clear();
+            index.query_radius(&node.position, threshold, &mut close_nodes);
+            for &a in &close_nodes {
+                let length: f64 = distance(&positions[a], &node.position);
+                if length < threshold && closest.map_or(true, |(_, _, closest)| length < closest) {
+                    closest = Some((a, b, length));
+                }
+            }
+        }
+
+        closest.map(|(a, b, _)| (a, b))
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::ops::{AddAssign, MulAssign, SubAssign};
+
+use kd_tree::KdTree3;
+use nalgebra::{distance, Point3, Vector3};
+
+use crate::{rng::Rng, spatial::IndexedPoint3};
+
+/// Differential growth of a triangulated surface. **Experimental.**
+///
+/// The same ideas as [`crate::DifferentialGrowth`] applied to a triangle mesh:
+/// vertices repel each other, are pulled towards the average of their neighbors
+/// and edges that grow too long are split. Edges are flipped to keep triangles
+/// well shaped. Since the surface keeps growing within the same space, it
+/// buckles and wrinkles out of its plane.
+///
+/// New vertices are displaced by a small random offset, otherwise a perfectly
+/// flat mesh has no reason to ever leave its plane.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{generate_hexagon_mesh, MeshGrowth};
+///
+/// let (vertices, faces) = generate_hexagon_mesh(0.0, 0.0, 10.0);
+/// let mut mesh_growth = MeshGrowth::new(vertices, faces, 1.5, 1.0, 14.0, 1.1, 5.0);
+///
+/// for _ in 0..20 {
+///     mesh_growth.tick();
+/// }
+///
+/// assert!(mesh_growth.vertices.len() > 7);
+/// assert!(mesh_growth.faces.iter().all(|[a, b, c]| a != b && b != c && c != a));
+/// ```
+///
+pub struct MeshGrowth {
+    /// The positions of the vertices.
+    pub vertices: Vec<Point3<f64>>,
+    /// The velocities of the vertices.
+    pub velocities: Vec<Vector3<f64>>,
+    /// Triangles as indices into the vertices, in counter-clockwise order.
+    pub faces: Vec<[usize; 3]>,
+    /// The maximum force vertices can exert on eachother.
+    pub max_force: f64,
+    /// The maximum magnitude of a vertex's velocity.
+    pub max_speed: f64,
+    /// The desired separation between vertices.
+    pub desired_separation: f64,
+    /// The ratio between separation and cohesion forces.
+    pub separation_cohesion_ratio: f64,
+    /// The maximum length of an edge before it is split.
+    pub max_edge_length: f64,
+    /// The maximum distance a new vertex is displaced from the middle of the split edge.
+    pub insertion_jitter: f64,
+    rng: Rng,
+    iteration: usize,
+}
+
+impl MeshGrowth {
+    /// Returns a MeshGrowth instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - The starting positions of the vertices.
+    /// * `faces` - Triangles as indices into the vertices, in counter-clockwise order.
+    /// * `max_force` - The maximum force vertices can exert on eachother.
+    /// * `max_speed` - The maximum magnitude of a vertex's velocity.
+    /// * `desired_separation` - The desired separation between vertices.
+    /// * `separation_cohesion_ratio` - The ratio between separation and cohesion forces.
+    /// * `max_edge_length` - The maximum length of an edge before it is split.
+    ///
+    pub fn new(
+        vertices: Vec<Point3<f64>>,
+        faces: Vec<[usize; 3]>,
+        max_force: f64,
+        max_speed: f64,
+        desired_separation: f64,
+        separation_cohesion_ratio: f64,
+        max_edge_length: f64,
+    ) -> MeshGrowth {
+        let velocities: Vec<Vector3<f64>> = vec![Vector3::default(); vertices.len()];
+        MeshGrowth {
+            vertices,
+            velocities,
+            faces,
+            max_force,
+            max_speed,
+            desired_separation,
+            separation_cohesion_ratio,
+            max_edge_length,
+            insertion_jitter: max_edge_length * 0.05,
+            rng: Rng::new(0),
+            iteration: 0,
+        }
+    }
+
+    /// Reseeds the random number generator used to displace new vertices.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Advances the algorithm by 1 iteration.
+    pub fn tick(&mut self) {
+        self.differentiate();
+        self.split_edges();
+        self.flip_edges();
+        self.iteration.add_assign(1);
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    #[must_use]
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    fn differentiate(&mut self) {
+        let indexed_points: Vec<IndexedPoint3> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(index, position)| IndexedPoint3 { position: *position, index })
+            .collect();
+        let tree: KdTree3<IndexedPoint3> = KdTree3::build_by_ordered_float(indexed_points);
+        let neighbors: Vec<Vec<usize>> = self.vertex_neighbors();
+
+        let forces: Vec<Vector3<f64>> = (0..self.vertices.len())
+            .map(|i| {
+                let mut separation: Vector3<f64> = self.separation_force(&tree, i);
+                separation.mul_assign(self.separation_cohesion_ratio);
+                separation + self.cohesion_force(&neighbors[i], i)
+            })
+            .collect();
+
+        for ((position, velocity), force) in self.vertices.iter_mut().zip(self.velocities.iter_mut()).zip(forces) {
+            velocity.add_assign(force);
+            *velocity = velocity.cap_magnitude(self.max_speed);
+            position.add_assign(*velocity);
+        }
+    }
+
+    fn separation_force(&self, tree: &KdTree3<IndexedPoint3>, i: usize) -> Vector3<f64> {
+        let query: IndexedPoint3 = IndexedPoint3 { position: self.vertices[i], index: i };
+        let mut separate_force: Vector3<f64> = Vector3::default();
+
+        for close in tree.within_radius(&query, self.desired_separation) {
+            let diff: Vector3<f64> = self.vertices[i] - self.vertices[close.index];
+            let distance_sq: f64 = diff.norm_squared();
+            if distance_sq > 0.0 {
+                separate_force.add_assign(diff / distance_sq);
+            }
+        }
+
+        steer(separate_force, self.velocities[i], self.max_speed, self.max_force)
+    }
+
+    fn cohesion_force(&self, neighbors: &[usize], i: usize) -> Vector3<f64> {
+        if neighbors.is_empty() {
+            return Vector3::default();
+        }
+
+        let mut target: Vector3<f64> = Vector3::default();
+        for &neighbor in neighbors {
+            target.add_assign(self.vertices[neighbor].coords);
+        }
+        target /= neighbors.len() as f64;
+
+        steer(target - self.vertices[i].coords, self.velocities[i], self.max_speed, self.max_force)
+    }
+
+    // The vertices sharing an edge with each vertex.
+    fn vertex_neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        for face in &self.faces {
+            for k in 0..3 {
+                let (a, b) = (face[k], face[(k + 1) % 3]);
+                if !neighbors[a].contains(&b) {
+                    neighbors[a].push(b);
+                }
+                if !neighbors[b].contains(&a) {
+                    neighbors[b].push(a);
+                }
+            }
+        }
+        neighbors
+    }
+
+    // The faces adjacent to every edge, keyed by the sorted vertex indices.
+    fn edge_faces(&self) -> HashMap<(usize, usize), Vec<usize>> {
+        let mut edge_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            for k in 0..3 {
+                edge_faces.entry(edge_key(face[k], face[(k + 1) % 3])).or_default().push(f);
+            }
+        }
+        edge_faces
+    }
+
+    fn split_edges(&mut self) {
+        let edge_faces: HashMap<(usize, usize), Vec<usize>> = self.edge_faces();
+        let mut long_edges: Vec<(usize, usize)> = edge_faces
+            .keys()
+            .copied()
+            .filter(|&(a, b)| distance(&self.vertices[a], &self.vertices[b]) > self.max_edge_length)
+            .collect();
+        // HashMap iteration order is random, sorting keeps runs reproducible.
+        long_edges.sort_unstable();
+
+        // Faces that were split already have outdated adjacency,
+        // their other long edges are split on the next tick.
+        let mut touched: Vec<bool> = vec![false; self.faces.len()];
+
+        for (a, b) in long_edges {
+            let faces: &Vec<usize> = &edge_faces[&(a, b)];
+            if faces.len() > 2 || faces.iter().any(|&f| touched[f]) {
+                continue;
+            }
+
+            let middle: Point3<f64> = nalgebra::center(&self.vertices[a], &self.vertices[b]) + self.random_offset();
+            let m: usize = self.vertices.len();
+            self.vertices.push(middle);
+            self.velocities.push((self.velocities[a] + self.velocities[b]) / 2.0);
+
+            for &f in faces {
+                let [x, y, z] = rotate_to_edge(self.faces[f], a, b);
+                // Replaces x-y-z with x-m-z and m-y-z, keeping the orientation.
+                self.faces[f] = [x, m, z];
+                self.faces.push([m, y, z]);
+                touched[f] = true;
+            }
+        }
+    }
+
+    fn flip_edges(&mut self) {
+        let edge_faces: HashMap<(usize, usize), Vec<usize>> = self.edge_faces();
+        let mut edges: Vec<(usize, usize)> = edge_faces
+            .iter()
+            .filter(|(_, faces)| faces.len() == 2)
+            .map(|(edge, _)| *edge)
+            .collect();
+        edges.sort_unstable();
+
+        let mut touched: Vec<bool> = vec![false; self.faces.len()];
+
+        for (a, b) in edges {
+            let (f1, f2) = (edge_faces[&(a, b)][0], edge_faces[&(a, b)][1]);
+            if touched[f1] || touched[f2] {
+                continue;
+            }
+
+            let [a, b, c] = rotate_to_edge(self.faces[f1], a, b);
+            let d: usize = match self.faces[f2].iter().copied().find(|&v| v != a && v != b) {
+                Some(d) => d,
+                None => continue,
+            };
+
+            // Flipping onto an existing edge would make the mesh non-manifold.
+            if c == d || edge_faces.contains_key(&edge_key(c, d)) {
+                continue;
+            }
+
+            // Delaunay criterion: flip when the opposite angles add up to more than 180°.
+            let angle_c: f64 = (self.vertices[a] - self.vertices[c]).angle(&(self.vertices[b] - self.vertices[c]));
+            let angle_d: f64 = (self.vertices[a] - self.vertices[d]).angle(&(self.vertices[b] - self.vertices[d]));
+            if angle_c + angle_d > std::f64::consts::PI {
+                self.faces[f1] = [c, a, d];
+                self.faces[f2] = [d, b, c];
+                touched[f1] = true;
+                touched[f2] = true;
+            }
+        }
+    }
+
+    fn random_offset(&mut self) -> Vector3<f64> {
+        if self.insertion_jitter == 0.0 {
+            return Vector3::default();
+        }
+
+        let offset: Vector3<f64> = Vector3::new(
+            self.rng.range(-1.0, 1.0),
+            self.rng.range(-1.0, 1.0),
+            self.rng.range(-1.0, 1.0),
+        );
+        offset * self.insertion_jitter
+    }
+}
+
+/// Helper function that returns a flat hexagon of 6 triangles around a center vertex,
+/// a good starting point for [`MeshGrowth`].
+///
+/// # Examples
+///
+/// ```rust
+/// let (vertices, faces) = differential_growth::generate_hexagon_mesh(0.0, 0.0, 10.0);
+/// assert_eq!(vertices.len(), 7);
+/// assert_eq!(faces.len(), 6);
+/// ```
+///
+pub fn generate_hexagon_mesh(origin_x: f64, origin_y: f64, radius: f64) -> (Vec<Point3<f64>>, Vec<[usize; 3]>) {
+    let mut vertices: Vec<Point3<f64>> = vec![Point3::new(origin_x, origin_y, 0.0)];
+    let mut faces: Vec<[usize; 3]> = Vec::with_capacity(6);
+
+    for i in 0..6 {
+        let theta: f64 = i as f64 * std::f64::consts::TAU / 6.0;
+        vertices.push(Point3::new(origin_x + radius * theta.cos(), origin_y + radius * theta.sin(), 0.0));
+        faces.push([0, 1 + i, 1 + (i + 1) % 6]);
+    }
+
+    (vertices, faces)
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+// Rotates a face so it starts with the edge between `a` and `b`, in whatever direction the face has it.
+fn rotate_to_edge(face: [usize; 3], a: usize, b: usize) -> [usize; 3] {
+    let mut face: [usize; 3] = face;
+    for _ in 0..3 {
+        if (face[0] == a && face[1] == b) || (face[0] == b && face[1] == a) {
+            break;
+        }
+        face.rotate_left(1);
+    }
+    face
+}
+
+// Steers towards `desired` at full speed, the same way nodes of a path do.
+fn steer(desired: Vector3<f64>, velocity: Vector3<f64>, max_speed: f64, max_force: f64) -> Vector3<f64> {
+    let mut steer: Vector3<f64> = desired;
+    let magnitude: f64 = steer.magnitude();
+    if magnitude > 0.0 {
+        steer.mul_assign(max_speed / magnitude);
+    }
+    steer.sub_assign(velocity);
+    steer.cap_magnitude(max_force)
+}
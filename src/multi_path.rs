@@ -0,0 +1,510 @@
+use core::ops::AddAssign;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    constraints::{Attractor, Obstacle},
+    differential_growth::DifferentialGrowth,
+    division::Division,
+    merging::Merging,
+    overlay::ParamOverlay,
+    params::ParamSet,
+    spatial::{DefaultIndex, SpatialIndex},
+    topology::Topology,
+};
+
+/// How the paths of a [`MultiPathGrowth`] act on eachother.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interaction {
+    /// Every path only feels its own nodes, paths grow through eachother.
+    Independent,
+    /// Nodes are pushed away from the nodes of every other path.
+    #[default]
+    RepelAll,
+    /// Nodes are only pushed away from paths in another collision group,
+    /// paths in the same group grow through eachother.
+    RepelOtherGroups,
+}
+
+/// New parameters for every path, applied at the start of the given tick.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamChange {
+    /// The tick at which the parameters are applied.
+    pub tick: usize,
+    /// The parameters applied to every path.
+    pub params: ParamSet,
+}
+
+/// A structural change of a [`MultiPathGrowth`], see [`MultiPathGrowth::schedule()`].
+///
+/// Operations on a path index that doesn't exist when they run do nothing.
+#[derive(Clone)]
+pub enum Operation {
+    /// Adds a path in the given collision group.
+    AddPath { path: Box<DifferentialGrowth>, group: u32 },
+    /// Freezes the path at the given index, see [`MultiPathGrowth::freeze()`].
+    Freeze(usize),
+    /// Lets the frozen path at the given index grow again.
+    Unfreeze(usize),
+    /// Freezes every path added so far.
+    FreezeAll,
+    /// Opens or closes the path at the given index.
+    SetTopology { path: usize, topology: Topology },
+    /// Adds an attractor acting on every path.
+    AddAttractor(Attractor),
+    /// Adds an obstacle acting on every path.
+    AddObstacle(Obstacle),
+    /// Adds a region with a different growth rate and force magnitude to every path.
+    AddZone(ParamOverlay),
+    /// Applies the same force to every node of every path, see [`DifferentialGrowth::set_constant_force()`].
+    SetConstantForce(Vector2<f64>),
+}
+
+/// Several paths growing side by side, each in their own [`DifferentialGrowth`],
+/// pushing eachother away according to their [`Interaction`].
+///
+/// Attractors and obstacles act on the nodes of every path.
+///
+/// Paths can be frozen: they stop ticking but keep pushing the other paths away.
+/// Freezing the finished paths before adding the next ones composes a piece
+/// layer by layer, with every layer growing around the ones baked before it.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{DifferentialGrowth, Interaction, MultiPathGrowth};
+///
+/// let left = differential_growth::generate_points_on_circle(-12.0, 0.0, 10.0, 10);
+/// let right = differential_growth::generate_points_on_circle(12.0, 0.0, 10.0, 10);
+///
+/// let mut multi_path_growth = MultiPathGrowth::new(Interaction::RepelAll);
+/// multi_path_growth.add_path(DifferentialGrowth::builder().points(left).build(), 0);
+/// multi_path_growth.add_path(DifferentialGrowth::builder().points(right).build(), 0);
+///
+/// for _ in 0..10 {
+///     multi_path_growth.tick();
+/// }
+/// assert_eq!(multi_path_growth.paths().len(), 2);
+/// assert_eq!(multi_path_growth.iteration(), 10);
+/// ```
+///
+pub struct MultiPathGrowth {
+    /// How the paths act on eachother.
+    pub interaction: Interaction,
+    /// Points pulling nodes of every path towards them.
+    pub attractors: Vec<Attractor>,
+    /// Shapes pushing nodes of every path out.
+    pub obstacles: Vec<Obstacle>,
+    /// Splits paths in two where they pinch after every tick, the new path joins the
+    /// collision group of the path it split from. See [`Division`].
+    pub division: Option<Division>,
+    /// Merges paths where they touch after every tick, before dividing. The merged path
+    /// keeps the index, collision group and parameters of the first of the two, the paths
+    /// after the second move down one index. See [`Merging`].
+    pub merging: Option<Merging>,
+    paths: Vec<DifferentialGrowth>,
+    groups: Vec<u32>,
+    frozen: Vec<bool>,
+    param_changes: Vec<ParamChange>,
+    operations: Vec<(usize, Operation)>,
+    index: DefaultIndex,
+    iteration: usize,
+}
+
+impl Default for MultiPathGrowth {
+    /// Returns a MultiPathGrowth without any paths where every path repels the others.
+    fn default() -> Self {
+        MultiPathGrowth::new(Interaction::default())
+    }
+}
+
+impl MultiPathGrowth {
+    /// Returns a MultiPathGrowth without any paths.
+    pub fn new(interaction: Interaction) -> MultiPathGrowth {
+        MultiPathGrowth {
+            interaction,
+            attractors: Vec::new(),
+            obstacles: Vec::new(),
+            division: None,
+            merging: None,
+            paths: Vec::new(),
+            groups: Vec::new(),
+            frozen: Vec::new(),
+            param_changes: Vec::new(),
+            operations: Vec::new(),
+            index: DefaultIndex::default(),
+            iteration: 0,
+        }
+    }
+
+    /// Adds a path in the given collision group and returns its index.
+    pub fn add_path(&mut self, path: DifferentialGrowth, group: u32) -> usize {
+        self.paths.push(path);
+        self.groups.push(group);
+        self.frozen.push(false);
+        self.paths.len() - 1
+    }
+
+    /// Freezes a path: it no longer ticks and only acts as a static shape
+    /// pushing the other paths away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{DifferentialGrowth, Interaction, MultiPathGrowth};
+    ///
+    /// let mut multi_path_growth = MultiPathGrowth::new(Interaction::RepelAll);
+    /// let first_layer = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// multi_path_growth.add_path(DifferentialGrowth::builder().points(first_layer).build(), 0);
+    /// for _ in 0..20 {
+    ///     multi_path_growth.tick();
+    /// }
+    ///
+    /// // Bake the first layer and grow the next one around it.
+    /// multi_path_growth.freeze_all();
+    /// let baked = multi_path_growth.get_points();
+    /// let second_layer = differential_growth::generate_points_on_circle(0.0, 0.0, 40.0, 20);
+    /// multi_path_growth.add_path(DifferentialGrowth::builder().points(second_layer).build(), 0);
+    /// for _ in 0..20 {
+    ///     multi_path_growth.tick();
+    /// }
+    ///
+    /// assert_eq!(multi_path_growth.get_points()[0], baked[0]);
+    /// assert!(multi_path_growth.is_frozen(0));
+    /// assert!(!multi_path_growth.is_frozen(1));
+    /// ```
+    ///
+    pub fn freeze(&mut self, path: usize) {
+        self.frozen[path] = true;
+    }
+
+    /// Lets a frozen path grow again.
+    pub fn unfreeze(&mut self, path: usize) {
+        self.frozen[path] = false;
+    }
+
+    /// Freezes every path added so far.
+    pub fn freeze_all(&mut self) {
+        self.frozen.iter_mut().for_each(|frozen| *frozen = true);
+    }
+
+    /// Whether a path is frozen.
+    #[must_use]
+    pub fn is_frozen(&self, path: usize) -> bool {
+        self.frozen[path]
+    }
+
+    /// Returns the paths.
+    #[must_use]
+    pub fn paths(&self) -> &[DifferentialGrowth] {
+        &self.paths
+    }
+
+    /// Returns the paths to change their parameters or nodes.
+    pub fn paths_mut(&mut self) -> &mut [DifferentialGrowth] {
+        &mut self.paths
+    }
+
+    /// Returns the collision group of every path.
+    #[must_use]
+    pub fn groups(&self) -> &[u32] {
+        &self.groups
+    }
+
+    /// Applies `params` to every path at the start of `tick`.
+    pub fn schedule_params(&mut self, tick: usize, params: ParamSet) {
+        self.param_changes.push(ParamChange { tick, params });
+    }
+
+    /// Runs `operation` at the start of `tick`, before the parameter changes of that tick,
+    /// so an evolving composition runs unattended. Operations scheduled for a tick that
+    /// already passed run at the start of the next tick, operations for the same tick
+    /// run in the order they were scheduled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{DifferentialGrowth, Interaction, MultiPathGrowth, Operation};
+    ///
+    /// let mut multi_path_growth = MultiPathGrowth::new(Interaction::RepelAll);
+    /// let inner = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// multi_path_growth.add_path(DifferentialGrowth::builder().points(inner).build(), 0);
+    ///
+    /// // After 20 ticks the inner path is baked and an outer path starts growing around it.
+    /// let outer = differential_growth::generate_points_on_circle(0.0, 0.0, 60.0, 30);
+    /// let path = Box::new(DifferentialGrowth::builder().points(outer).build());
+    /// multi_path_growth.schedule(20, Operation::FreezeAll);
+    /// multi_path_growth.schedule(20, Operation::AddPath { path, group: 0 });
+    ///
+    /// for _ in 0..30 {
+    ///     multi_path_growth.tick();
+    /// }
+    /// assert_eq!(multi_path_growth.paths().len(), 2);
+    /// assert!(multi_path_growth.is_frozen(0) && !multi_path_growth.is_frozen(1));
+    /// ```
+    ///
+    pub fn schedule(&mut self, tick: usize, operation: Operation) {
+        self.operations.push((tick, operation));
+    }
+
+    fn run_operations(&mut self) {
+        if self.operations.is_empty() {
+            return;
+        }
+
+        let (due, pending): (Vec<_>, Vec<_>) = core::mem::take(&mut self.operations)
+            .into_iter()
+            .partition(|(tick, _)| *tick <= self.iteration);
+        self.operations = pending;
+
+        for (_, operation) in due {
+            match operation {
+                Operation::AddPath { path, group } => {
+                    self.add_path(*path, group);
+                }
+                Operation::Freeze(path) => {
+                    if let Some(frozen) = self.frozen.get_mut(path) {
+                        *frozen = true;
+                    }
+                }
+                Operation::Unfreeze(path) => {
+                    if let Some(frozen) = self.frozen.get_mut(path) {
+                        *frozen = false;
+                    }
+                }
+                Operation::FreezeAll => self.freeze_all(),
+                Operation::SetTopology { path, topology } => {
+                    if let Some(path) = self.paths.get_mut(path) {
+                        path.topology = topology;
+                    }
+                }
+                Operation::AddAttractor(attractor) => self.attractors.push(attractor),
+                Operation::AddObstacle(obstacle) => self.obstacles.push(obstacle),
+                Operation::AddZone(zone) => {
+                    for path in self.paths.iter_mut() {
+                        path.add_overlay(zone);
+                    }
+                }
+                Operation::SetConstantForce(force) => {
+                    for path in self.paths.iter_mut() {
+                        path.set_constant_force(force);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    #[must_use]
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Get the positions of the current state of the nodes of every path.
+    #[must_use]
+    pub fn get_points(&self) -> Vec<Vec<Point2<f64>>> {
+        self.paths.iter().map(|path| path.get_points()).collect()
+    }
+
+    /// Returns the minimum and maximum corner of the axis aligned box around the nodes
+    /// of every path, or `None` if there are no nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{DifferentialGrowth, Interaction, MultiPathGrowth};
+    /// use nalgebra::Point2;
+    ///
+    /// let mut multi_path_growth = MultiPathGrowth::new(Interaction::RepelAll);
+    /// assert_eq!(multi_path_growth.bounding_box(), None);
+    ///
+    /// let left = vec![Point2::new(-20.0, 0.0), Point2::new(-10.0, 5.0), Point2::new(-10.0, -5.0)];
+    /// let right = vec![Point2::new(20.0, 0.0), Point2::new(10.0, 8.0), Point2::new(10.0, -5.0)];
+    /// multi_path_growth.add_path(DifferentialGrowth::builder().points(left).build(), 0);
+    /// multi_path_growth.add_path(DifferentialGrowth::builder().points(right).build(), 0);
+    ///
+    /// assert_eq!(multi_path_growth.bounding_box(), Some((Point2::new(-20.0, -5.0), Point2::new(20.0, 8.0))));
+    /// ```
+    ///
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Point2<f64>, Point2<f64>)> {
+        self.paths
+            .iter()
+            .filter_map(|path| path.bounding_box())
+            .reduce(|(min, max), (path_min, path_max)| (min.inf(&path_min), max.sup(&path_max)))
+    }
+
+    /// Advances every path by 1 iteration.
+    pub fn tick(&mut self) {
+        self.tick_dt(1.0);
+    }
+
+    /// Advances every path that isn't frozen by one iteration covering a time step of
+    /// `dt` ticks, see [`DifferentialGrowth::tick_dt()`].
+    pub fn tick_dt(&mut self, dt: f64) {
+        self.run_operations();
+
+        for change in self.param_changes.iter().filter(|change| change.tick == self.iteration) {
+            for path in self.paths.iter_mut() {
+                change.params.apply(path);
+            }
+        }
+
+        let forces: Vec<Vec<Vector2<f64>>> = self.external_forces();
+        for ((path, forces), frozen) in self.paths.iter_mut().zip(forces).zip(&self.frozen) {
+            if *frozen {
+                continue;
+            }
+
+            // Forces accumulate on the nodes until the tick integrates them.
+            for (node, force) in path.nodes.iter_mut().zip(forces) {
+                node.apply_force(&force);
+            }
+            path.tick_dt(dt);
+
+            // Polygons keep the nodes of every path out, like they do for a single path.
+            for node in path.nodes.iter_mut() {
+                for obstacle in &self.obstacles {
+                    obstacle.keep_out(node);
+                }
+            }
+        }
+
+        self.merge();
+        self.divide();
+        self.iteration.add_assign(1);
+    }
+
+    // Merges the paths that aren't frozen where they touch, until none touch anymore.
+    fn merge(&mut self) {
+        let merging: Merging = match self.merging {
+            Some(merging) => merging,
+            None => return,
+        };
+
+        let mut first: usize = 0;
+        while first < self.paths.len() {
+            let mut second: usize = first + 1;
+            while second < self.paths.len() {
+                if self.frozen[first] || self.frozen[second] || !self.boxes_touch(first, second, merging.threshold) {
+                    second.add_assign(1);
+                    continue;
+                }
+                let (head, tail) = self.paths.split_at_mut(second);
+                match head[first].merge_at_contact(&tail[0], merging.threshold) {
+                    true => {
+                        self.paths.remove(second);
+                        self.groups.remove(second);
+                        self.frozen.remove(second);
+                        // The merged path may touch paths it was checked against before.
+                        second = first + 1;
+                    }
+                    false => second.add_assign(1),
+                }
+            }
+            first.add_assign(1);
+        }
+    }
+
+    // Whether the bounding boxes of two paths are closer than `distance`.
+    fn boxes_touch(&self, a: usize, b: usize, distance: f64) -> bool {
+        match (self.paths[a].bounding_box(), self.paths[b].bounding_box()) {
+            (Some((min_a, max_a)), Some((min_b, max_b))) => {
+                (0..2).all(|axis| min_a[axis] - distance <= max_b[axis] && min_b[axis] - distance <= max_a[axis])
+            }
+            _ => false,
+        }
+    }
+
+    // Splits the paths that aren't frozen where they pinch, until none pinch anymore.
+    fn divide(&mut self) {
+        let division: Division = match self.division {
+            Some(division) => division,
+            None => return,
+        };
+
+        let mut path: usize = 0;
+        while path < self.paths.len() {
+            if self.frozen[path] {
+                path.add_assign(1);
+                continue;
+            }
+            match self.paths[path].split_at_pinch(division.threshold, division.min_nodes) {
+                Some(piece) => {
+                    let group: u32 = self.groups[path];
+                    self.add_path(piece, group);
+                }
+                None => path.add_assign(1),
+            }
+        }
+    }
+
+    fn interacts(&self, a: usize, b: usize) -> bool {
+        match self.interaction {
+            Interaction::Independent => false,
+            Interaction::RepelAll => a != b,
+            Interaction::RepelOtherGroups => self.groups[a] != self.groups[b],
+        }
+    }
+
+    // Returns the force of other paths, attractors and obstacles on every node.
+    fn external_forces(&mut self) -> Vec<Vec<Vector2<f64>>> {
+        let mut positions: Vec<Point2<f64>> = Vec::new();
+        let mut owners: Vec<usize> = Vec::new();
+        if self.interaction != Interaction::Independent && self.paths.len() > 1 {
+            for (owner, path) in self.paths.iter().enumerate() {
+                positions.extend(path.nodes.iter().map(|node| node.position));
+                owners.resize(owners.len() + path.nodes.len(), owner);
+            }
+            self.index.rebuild(&positions);
+        }
+
+        let mut neighbors: Vec<usize> = Vec::new();
+        let mut forces: Vec<Vec<Vector2<f64>>> = Vec::with_capacity(self.paths.len());
+
+        for (owner, path) in self.paths.iter().enumerate() {
+            let mut path_forces: Vec<Vector2<f64>> = Vec::with_capacity(path.nodes.len());
+            if self.frozen[owner] {
+                forces.push(path_forces);
+                continue;
+            }
+
+            for node in &path.nodes {
+                let mut force: Vector2<f64> = Vector2::default();
+
+                if !positions.is_empty() {
+                    let mut repulsion: Vector2<f64> = Vector2::default();
+                    neighbors.clear();
+                    self.index.query_radius(&node.position, path.desired_separation, &mut neighbors);
+                    for &neighbor in neighbors.iter() {
+                        if !self.interacts(owner, owners[neighbor]) {
+                            continue;
+                        }
+                        let away: Vector2<f64> = node.position - positions[neighbor];
+                        let distance: f64 = away.norm();
+                        if distance > 0.0 && distance < path.desired_separation {
+                            repulsion.add_assign(away / distance * (1.0 - distance / path.desired_separation));
+                        }
+                    }
+                    force.add_assign(repulsion.cap_magnitude(1.0) * path.max_force);
+                }
+
+                for attractor in &self.attractors {
+                    force.add_assign(attractor.force(&node.position));
+                }
+                for obstacle in &self.obstacles {
+                    force.add_assign(obstacle.force(&node.position));
+                }
+
+                path_forces.push(force);
+            }
+
+            forces.push(path_forces);
+        }
+
+        forces
+    }
+}
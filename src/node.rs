@@ -1,15 +1,28 @@
-use std::{fmt, ops::{AddAssign, MulAssign, Sub}};
+use core::{fmt, ops::{AddAssign, MulAssign, Sub}};
 
+#[cfg(feature = "std")]
 use kd_tree::KdPoint;
 use nalgebra::{Point2, Vector2};
 
-#[derive(Copy, Clone)]
+/// A single point of the path, moved around by the forces acting on it.
+#[derive(Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     pub position: Point2<f64>,
     pub velocity: Vector2<f64>,
     pub acceleration: Vector2<f64>,
     pub max_force: f64,
     pub max_speed: f64,
+    /// The iteration of the simulation the node was created at, 0 for starting points.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub birth_tick: usize,
+    /// Identifies the node for as long as it exists, unlike its index which shifts
+    /// when nodes are inserted or removed. Unique among the nodes a simulation creates.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: usize,
+    /// Divides the forces applied to the node, so heavier nodes react more sluggishly. Defaults to 1.
+    #[cfg_attr(feature = "serde", serde(default = "default_mass"))]
+    pub mass: f64,
 }
 
 impl Node {
@@ -20,20 +33,57 @@ impl Node {
             acceleration: Vector2::default(),
             max_speed,
             max_force,
+            birth_tick: 0,
+            id: 0,
+            mass: 1.0,
         }
     }
 
     pub fn apply_force(&mut self, force: &Vector2<f64>) {
-        self.acceleration.add_assign(force);
+        self.acceleration.add_assign(force / self.mass);
     }
 
     pub fn update(&mut self) {
-        self.velocity.add_assign(self.acceleration);
+        self.update_dt(1.0);
+    }
+
+    /// Integrates the accumulated forces over a time step of `dt` ticks.
+    pub fn update_dt(&mut self, dt: f64) {
+        self.velocity.add_assign(self.acceleration * dt);
         self.velocity = self.velocity.cap_magnitude(self.max_speed);
-        self.position.add_assign(self.velocity);
+        self.position.add_assign(self.velocity * dt);
         self.acceleration.mul_assign(0.0);
     }
 
+    /// Returns whether both nodes are equal, except that their positions, velocities
+    /// and accelerations only need to be within `epsilon` of each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Node;
+    /// use nalgebra::Point2;
+    ///
+    /// let node = Node::new(Point2::new(1.0, 2.0), 1.5, 1.0);
+    /// let nudged = Node::new(Point2::new(1.0 + 1e-12, 2.0), 1.5, 1.0);
+    /// assert!(node != nudged);
+    /// assert!(node.approx_eq(&nudged, 1e-9));
+    /// assert!(!node.approx_eq(&Node::new(Point2::new(1.0, 2.0), 2.0, 1.0), 1e-9));
+    /// ```
+    ///
+    #[must_use]
+    pub fn approx_eq(&self, other: &Node, epsilon: f64) -> bool {
+        let close = |a: Vector2<f64>, b: Vector2<f64>| (a - b).abs().max() <= epsilon;
+        close(self.position.coords, other.position.coords)
+            && close(self.velocity, other.velocity)
+            && close(self.acceleration, other.acceleration)
+            && self.max_force == other.max_force
+            && self.max_speed == other.max_speed
+            && self.birth_tick == other.birth_tick
+            && self.id == other.id
+            && self.mass == other.mass
+    }
+
     pub fn seek(&self, target: &Vector2<f64>) -> Vector2<f64> {
         let mut desired: Vector2<f64> = target.sub(self.position.coords);
         if desired.magnitude() != 0.0 {
@@ -44,6 +94,11 @@ impl Node {
     }
 }
 
+#[cfg(feature = "serde")]
+fn default_mass() -> f64 {
+    1.0
+}
+
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Point")
@@ -55,6 +110,7 @@ impl fmt::Debug for Node {
 }
 
 // Somehow the nalgebra feature of kd-tree doesn't work so doing it manually.
+#[cfg(feature = "std")]
 impl KdPoint for Node {
     type Scalar = f64;
     type Dim = typenum::U2; // 2 dimensional tree.
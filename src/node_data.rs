@@ -0,0 +1,183 @@
+use alloc::sync::Arc;
+use core::{any::Any, fmt};
+
+use nalgebra::Vector2;
+
+use crate::prelude::*;
+use crate::differential_growth::DifferentialGrowth;
+
+/// Where the value of a node comes from after nodes were inserted, merged or appended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Source {
+    /// The value of the node that was at this index before.
+    Node(usize),
+    /// A value interpolated between two nodes, for inserted and merged nodes.
+    Between(usize, usize),
+    /// The default value, for nodes that didn't exist before.
+    New,
+}
+
+// Type erased storage for one value per node, so DifferentialGrowth
+// doesn't need a type parameter for the data users attach to it.
+pub(crate) trait NodeData: Send + Sync {
+    // Rebuilds the values from where every node came from.
+    fn remap(&mut self, sources: &[Source]);
+    fn clone_box(&self) -> Box<dyn NodeData>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl Clone for Box<dyn NodeData> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl fmt::Debug for dyn NodeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NodeData")
+    }
+}
+
+// Returns the value of every node from where it came from.
+pub(crate) fn remap<T: Clone + Default>(values: &[T], sources: &[Source], interpolate: impl Fn(&T, &T) -> T) -> Vec<T> {
+    // Sources outside the values, after the nodes were changed by hand, get the default.
+    let value = |i: usize| values.get(i).cloned().unwrap_or_default();
+
+    sources
+        .iter()
+        .map(|source| match *source {
+            Source::Node(i) => value(i),
+            Source::Between(i, j) => match (values.get(i), values.get(j)) {
+                (Some(a), Some(b)) => interpolate(a, b),
+                _ => T::default(),
+            },
+            Source::New => T::default(),
+        })
+        .collect()
+}
+
+type Interpolate<T> = Arc<dyn Fn(&T, &T) -> T + Send + Sync>;
+
+struct Column<T> {
+    values: Vec<T>,
+    interpolate: Interpolate<T>,
+}
+
+impl<T: Clone + Default + Send + Sync + 'static> NodeData for Column<T> {
+    fn remap(&mut self, sources: &[Source]) {
+        self.values = remap(&self.values, sources, self.interpolate.as_ref());
+    }
+
+    fn clone_box(&self) -> Box<dyn NodeData> {
+        Box::new(Column {
+            values: self.values.clone(),
+            interpolate: self.interpolate.clone(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl DifferentialGrowth {
+    /// Attaches a value to every node, like a color, a stroke width or an ID,
+    /// replacing any data attached before.
+    ///
+    /// The values stay aligned with the nodes while the path grows. Inserted nodes
+    /// get the value `interpolate` returns for the nodes on both ends of the split edge,
+    /// merged nodes get the value it returns for the two merged nodes and nodes added by
+    /// [`DifferentialGrowth::extend_path()`] copy the value of the last node.
+    ///
+    /// Missing values are filled up with `T::default()` and extra values are dropped.
+    /// Changes through [`DifferentialGrowth::with_nodes_mut()`] keep the values aligned by node id.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - One value per node, in the order of the nodes.
+    /// * `interpolate` - Returns the value of a node created from the two given values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Stroke widths from 1 to 10, new nodes get the average of their neighbors.
+    /// let widths: Vec<f64> = (1..=10).map(|width| width as f64).collect();
+    /// differential_growth.set_node_data(widths, |a: &f64, b: &f64| (a + b) / 2.0);
+    ///
+    /// for _ in 0..20 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// let widths: &[f64] = differential_growth.node_data().unwrap();
+    /// assert_eq!(widths.len(), differential_growth.nodes().len());
+    /// assert!(widths.iter().all(|width| (1.0..=10.0).contains(width)));
+    /// assert!(differential_growth.node_data::<u32>().is_none());
+    /// ```
+    ///
+    pub fn set_node_data<T: Clone + Default + Send + Sync + 'static>(
+        &mut self,
+        mut values: Vec<T>,
+        interpolate: impl Fn(&T, &T) -> T + Send + Sync + 'static,
+    ) {
+        values.resize(self.nodes.len(), T::default());
+        self.node_data = Some(Box::new(Column {
+            values,
+            interpolate: Arc::new(interpolate),
+        }));
+    }
+
+    /// Returns the values attached with [`DifferentialGrowth::set_node_data()`], one per node,
+    /// or `None` when nothing or data of another type is attached.
+    #[must_use]
+    pub fn node_data<T: 'static>(&self) -> Option<&[T]> {
+        self.node_data
+            .as_ref()?
+            .as_any()
+            .downcast_ref::<Column<T>>()
+            .map(|column| column.values.as_slice())
+    }
+
+    /// Returns the values attached with [`DifferentialGrowth::set_node_data()`] to change them
+    /// in place, or `None` when nothing or data of another type is attached.
+    pub fn node_data_mut<T: 'static>(&mut self) -> Option<&mut [T]> {
+        self.node_data
+            .as_mut()?
+            .as_any_mut()
+            .downcast_mut::<Column<T>>()
+            .map(|column| column.values.as_mut_slice())
+    }
+
+    /// Removes the data attached with [`DifferentialGrowth::set_node_data()`].
+    pub fn clear_node_data(&mut self) {
+        self.node_data = None;
+    }
+
+    // Whether anything attached to the nodes has to follow them when they change.
+    pub(crate) fn has_node_values(&self) -> bool {
+        self.node_data.is_some() || self.node_params.is_some() || !self.external_forces.is_empty() || !self.frozen.is_empty()
+    }
+
+    // Keeps everything attached to the nodes aligned with them, from where every node came from.
+    pub(crate) fn remap_node_values(&mut self, sources: &[Source]) {
+        if let Some(node_data) = self.node_data.as_mut() {
+            node_data.remap(sources);
+        }
+        self.remap_node_params(sources);
+        if !self.external_forces.is_empty() {
+            // Nodes that didn't exist yet weren't pushed.
+            self.external_forces = remap(&self.external_forces, sources, |_, _| Vector2::default());
+        }
+        if !self.frozen.is_empty() {
+            // Nodes between two frozen nodes are frozen too.
+            self.frozen = remap(&self.frozen, sources, |a, b| *a && *b);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use crate::{
+    differential_growth::DifferentialGrowth,
+    node_data::{self, Source},
+};
+
+/// Parameters of a single node, overriding the ones of the simulation, to make parts
+/// of a path stiffer or denser than the rest. See [`DifferentialGrowth::set_node_params()`].
+///
+/// Nodes inserted between two nodes that both override a parameter get the average of
+/// both, otherwise they use the parameter of the simulation.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeParams {
+    /// The distance within which other nodes push this node away.
+    pub desired_separation: Option<f64>,
+    /// The maximum force on this node.
+    pub max_force: Option<f64>,
+    /// The maximum magnitude of the velocity of this node.
+    pub max_speed: Option<f64>,
+}
+
+impl NodeParams {
+    fn interpolate(a: &NodeParams, b: &NodeParams) -> NodeParams {
+        let average = |a: Option<f64>, b: Option<f64>| a.zip(b).map(|(a, b)| (a + b) / 2.0);
+        NodeParams {
+            desired_separation: average(a.desired_separation, b.desired_separation),
+            max_force: average(a.max_force, b.max_force),
+            max_speed: average(a.max_speed, b.max_speed),
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Overrides the parameters of the node at `index`, replacing any override set before.
+    /// Parameters left at `None` use the ones of the simulation again.
+    ///
+    /// The overrides stay with the node while the path grows, also when the parameters
+    /// of the simulation change. Does nothing when there is no node at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::NodeParams;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Half of the path barely moves.
+    /// let stiff = NodeParams { max_speed: Some(0.1), max_force: Some(0.05), ..Default::default() };
+    /// for index in 0..5 {
+    ///     differential_growth.set_node_params(index, stiff);
+    /// }
+    /// differential_growth.set_max_speed(2.0);
+    /// assert_eq!(differential_growth.nodes()[0].max_speed, 0.1);
+    /// assert_eq!(differential_growth.nodes()[5].max_speed, 2.0);
+    ///
+    /// differential_growth.tick_n(30);
+    /// let stiff_nodes: usize = (0..differential_growth.nodes().len())
+    ///     .filter(|&index| differential_growth.node_params(index) == stiff)
+    ///     .count();
+    /// assert!(stiff_nodes >= 5);
+    /// assert!(differential_growth.nodes().iter().all(|node| node.velocity.magnitude() <= node.max_speed + 1e-9));
+    /// ```
+    ///
+    pub fn set_node_params(&mut self, index: usize, node_params: NodeParams) {
+        if index >= self.nodes.len() {
+            return;
+        }
+
+        let len: usize = self.nodes.len();
+        let all_node_params: &mut Vec<NodeParams> = self.node_params.get_or_insert_with(Vec::new);
+        all_node_params.resize(len, NodeParams::default());
+        all_node_params[index] = node_params;
+
+        let node = &mut self.nodes[index];
+        node.max_force = node_params.max_force.unwrap_or(self.max_force);
+        node.max_speed = node_params.max_speed.unwrap_or(self.max_speed);
+    }
+
+    /// Returns the parameters the node at `index` overrides, all `None` when it overrides none.
+    #[must_use]
+    pub fn node_params(&self, index: usize) -> NodeParams {
+        self.node_params
+            .as_ref()
+            .and_then(|node_params| node_params.get(index).copied())
+            .unwrap_or_default()
+    }
+
+    /// Removes the overrides of all nodes, they use the parameters of the simulation again.
+    pub fn clear_node_params(&mut self) {
+        self.node_params = None;
+        for node in self.nodes.iter_mut() {
+            node.max_force = self.max_force;
+            node.max_speed = self.max_speed;
+        }
+    }
+
+    // The desired separation of the node at `i`, with its override or the field at the node,
+    // and its oscillation.
+    pub(crate) fn desired_separation_of(&self, i: usize) -> f64 {
+        let desired_separation: f64 = self
+            .node_params(i)
+            .desired_separation
+            .or_else(|| self.field_params_of(i).map(|field_params| field_params.desired_separation))
+            .unwrap_or(self.desired_separation);
+        desired_separation * self.oscillation_factor(i)
+    }
+
+    pub(crate) fn remap_node_params(&mut self, sources: &[Source]) {
+        let node_params: Vec<NodeParams> = match &self.node_params {
+            Some(node_params) => node_data::remap(node_params, sources, NodeParams::interpolate),
+            None => return,
+        };
+
+        // New nodes start out with the limits of the simulation.
+        for (node, node_params) in self.nodes.iter_mut().zip(&node_params) {
+            if let Some(max_force) = node_params.max_force {
+                node.max_force = max_force;
+            }
+            if let Some(max_speed) = node_params.max_speed {
+                node.max_speed = max_speed;
+            }
+        }
+        self.node_params = Some(node_params);
+    }
+}
@@ -0,0 +1,60 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::{differential_growth::DifferentialGrowth, node::Node};
+
+/// A read-only view of a node together with the data derived from its neighbors,
+/// see [`DifferentialGrowth::node_views()`].
+#[derive(Copy, Clone, Debug)]
+pub struct NodeView<'a> {
+    /// The index of the node in [`DifferentialGrowth::nodes()`].
+    pub index: usize,
+    /// The identity of the node, stable while it exists. See [`Node::id`].
+    pub id: usize,
+    pub position: Point2<f64>,
+    pub velocity: Vector2<f64>,
+    /// The amount of ticks since the node was created.
+    pub age: usize,
+    /// The signed curvature at the node, see [`DifferentialGrowth::curvatures()`].
+    pub curvature: f64,
+    /// The node itself.
+    pub node: &'a Node,
+}
+
+impl DifferentialGrowth {
+    /// Iterates over all nodes in order, with everything a renderer needs
+    /// about every node in one place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// for _ in 0..10 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// for view in differential_growth.node_views() {
+    ///     let _stroke_width: f64 = 1.0 + view.age as f64 * 0.1 + view.curvature.abs();
+    ///     assert_eq!(view.position, differential_growth.nodes()[view.index].position);
+    /// }
+    ///
+    /// // Ids stay unique while nodes are inserted.
+    /// let mut ids: Vec<usize> = differential_growth.node_views().map(|view| view.id).collect();
+    /// ids.sort();
+    /// ids.dedup();
+    /// assert_eq!(ids.len(), differential_growth.nodes().len());
+    /// ```
+    ///
+    pub fn node_views(&self) -> impl Iterator<Item = NodeView<'_>> + '_ {
+        self.nodes.iter().enumerate().map(move |(index, node)| NodeView {
+            index,
+            id: node.id,
+            position: node.position,
+            velocity: node.velocity,
+            age: self.iteration().saturating_sub(node.birth_tick),
+            curvature: self.curvature_at(index),
+            node,
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use nalgebra::Vector2;
+
+use crate::prelude::*;
+use crate::differential_growth::DifferentialGrowth;
+
+/// The forces applied to every node during a tick, after scaling by the
+/// separation cohesion ratio, warmup and overlays.
+///
+/// Nodes that didn't move by force, like the leader or nodes skipped by the
+/// level of detail, have zero forces.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppliedForces {
+    /// The force pushing every node away from nearby nodes.
+    pub separation: Vec<Vector2<f64>>,
+    /// The force pulling every node towards its neighbors on the path.
+    pub cohesion: Vec<Vector2<f64>>,
+    /// The force steering every node towards the velocity of nearby nodes, zero without alignment.
+    pub alignment: Vec<Vector2<f64>>,
+}
+
+/// Callbacks during a tick with read access to the simulation, to draw force vectors,
+/// log diagnostics or decide when to stop without changing the crate.
+///
+/// Every callback does nothing by default, implement the ones you need.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{AppliedForces, DifferentialGrowth, Observer};
+///
+/// // Stops once the strongest separation force gets weak, when the path has relaxed.
+/// struct Relaxed {
+///     strongest: f64,
+/// }
+///
+/// impl Observer for Relaxed {
+///     fn after_differentiate(&mut self, _differential_growth: &DifferentialGrowth, forces: &AppliedForces) {
+///         self.strongest = forces.separation.iter().map(|force| force.norm()).fold(0.0, f64::max);
+///     }
+///
+///     fn should_stop(&self) -> bool {
+///         self.strongest < 0.01
+///     }
+/// }
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.add_observer(Relaxed { strongest: f64::INFINITY });
+///
+/// while !differential_growth.should_stop() && differential_growth.iteration() < 10 {
+///     differential_growth.tick();
+/// }
+/// ```
+///
+#[allow(unused_variables)]
+pub trait Observer: Send + Sync {
+    /// Called before the forces are computed and the nodes move.
+    fn before_differentiate(&mut self, differential_growth: &DifferentialGrowth) {}
+
+    /// Called after the nodes moved, with the forces that moved them.
+    fn after_differentiate(&mut self, differential_growth: &DifferentialGrowth, forces: &AppliedForces) {}
+
+    /// Called before long edges are split.
+    fn before_growth(&mut self, differential_growth: &DifferentialGrowth) {}
+
+    /// Called after long edges were split, with whether every node was inserted just now.
+    fn after_growth(&mut self, differential_growth: &DifferentialGrowth, inserted: &[bool]) {}
+
+    /// Returns whether the simulation should stop, see [`DifferentialGrowth::should_stop()`].
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
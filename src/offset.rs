@@ -0,0 +1,265 @@
+use nalgebra::{distance, Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    differential_growth::DifferentialGrowth,
+    geometry, parallel,
+    spatial::{DefaultIndex, SpatialIndex},
+    topology::Topology,
+};
+
+/// The grid the offsets are traced on has cells this many times smaller than the spacing.
+const CELLS_PER_SPACING: f64 = 4.0;
+
+/// The curves at a fixed distance from the path, see [`DifferentialGrowth::offset_bands()`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetBand {
+    /// The distance from the path, negative inside a closed path and positive outside.
+    pub offset: f64,
+    /// The closed curves at the offset. Inward offsets split into several curves where the
+    /// shape narrows and have none left where it is thinner than twice the distance.
+    pub contours: Vec<Vec<Point2<f64>>>,
+}
+
+impl DifferentialGrowth {
+    /// Returns `count` offsets of the path on either side, `spacing` apart, for the
+    /// topographic look of concentric outlines.
+    ///
+    /// The offsets are traced on a grid from the distance to the path, so they never
+    /// cross themselves or each other. Where an inward offset doesn't fit into the shape
+    /// anymore it splits or disappears instead of turning inside out. Closed paths get
+    /// the inward offsets first, from the innermost, then the outward ones. Open paths
+    /// have no inside, their offsets are the closed outlines at every distance.
+    ///
+    /// Returns nothing when `count` is 0, `spacing` isn't positive or there are fewer than 2 nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The amount of offsets on either side of the path.
+    /// * `spacing` - The distance between consecutive offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 60);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let bands = differential_growth.offset_bands(3, 4.0);
+    /// let offsets: Vec<f64> = bands.iter().map(|band| band.offset).collect();
+    /// assert_eq!(offsets, vec![-12.0, -8.0, -4.0, 4.0, 8.0, 12.0]);
+    ///
+    /// // The circle of radius 10 has room for the offsets at 4 and 8 inside, not for 12.
+    /// assert!(bands[0].contours.is_empty());
+    /// assert_eq!(bands[2].contours.len(), 1);
+    /// for point in &bands[5].contours[0] {
+    ///     assert!((point.coords.magnitude() - 22.0).abs() < 0.5);
+    /// }
+    /// ```
+    ///
+    #[must_use]
+    pub fn offset_bands(&self, count: usize, spacing: f64) -> Vec<OffsetBand> {
+        let positions: Vec<Point2<f64>> = self.get_points();
+        if count == 0 || !(spacing > 0.0 && spacing.is_finite()) || positions.len() < 2 {
+            return Vec::new();
+        }
+        let (min, max): (Point2<f64>, Point2<f64>) = match self.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return Vec::new(),
+        };
+
+        let max_offset: f64 = count as f64 * spacing;
+        let cell: f64 = spacing / CELLS_PER_SPACING;
+        // A margin of a few cells beyond the largest offset keeps every contour closed.
+        let origin: Point2<f64> = min - Vector2::repeat(max_offset + 2.0 * cell);
+        let columns: usize = ((max.x - min.x + 2.0 * max_offset) / cell).ceil() as usize + 5;
+        let rows: usize = ((max.y - min.y + 2.0 * max_offset) / cell).ceil() as usize + 5;
+        let grid_point = |column: usize, row: usize| Point2::new(origin.x + column as f64 * cell, origin.y + row as f64 * cell);
+
+        let distances: Vec<f64> = self.distance_field(&positions, columns, rows, &grid_point, max_offset + cell);
+        let is_closed: bool = self.topology == Topology::Closed && positions.len() >= 3;
+        let signed: Vec<f64> = if is_closed {
+            let inside: Vec<bool> = scanline_inside(&positions, columns, rows, &grid_point);
+            distances.iter().zip(inside).map(|(d, inside)| if inside { -d } else { *d }).collect()
+        } else {
+            distances
+        };
+
+        let offsets = (1..=count).map(|k| k as f64 * spacing);
+        let offsets: Vec<f64> = if is_closed {
+            offsets.clone().rev().map(|offset| -offset).chain(offsets).collect()
+        } else {
+            offsets.collect()
+        };
+
+        offsets
+            .into_iter()
+            .map(|offset| OffsetBand {
+                offset,
+                contours: trace_contours(&signed, columns, rows, offset, &grid_point),
+            })
+            .collect()
+    }
+
+    // The distance from every grid point to the path, capped at `limit`.
+    fn distance_field(
+        &self,
+        positions: &[Point2<f64>],
+        columns: usize,
+        rows: usize,
+        grid_point: &(dyn Fn(usize, usize) -> Point2<f64> + Sync),
+        limit: f64,
+    ) -> Vec<f64> {
+        let mut index: DefaultIndex = DefaultIndex::default();
+        index.rebuild(positions);
+        let len: usize = positions.len();
+        let edges: Vec<(usize, usize)> = self.topology.edges(len).collect();
+        let longest_edge: f64 = edges
+            .iter()
+            .map(|&(i, j)| distance(&positions[i], &positions[j]))
+            .fold(0.0, f64::max);
+
+        parallel::map(columns * rows, |k| {
+            let point: Point2<f64> = grid_point(k % columns, k / columns);
+            // An edge within the limit has both ends within the limit plus its length.
+            let mut close_nodes: Vec<usize> = Vec::new();
+            index.query_radius(&point, limit + longest_edge, &mut close_nodes);
+
+            let mut closest: f64 = limit;
+            for i in close_nodes {
+                for (a, b) in [(self.topology.prev(i, len), Some(i)), (Some(i), self.topology.next(i, len))] {
+                    if let (Some(a), Some(b)) = (a, b) {
+                        let (on_edge, _) = geometry::closest_point_on_segment(&point, &positions[a], &positions[b]);
+                        closest = closest.min(distance(&point, &on_edge));
+                    }
+                }
+            }
+            closest
+        })
+    }
+}
+
+// Whether every grid point lies inside the closed path, with the even-odd rule, row by row.
+fn scanline_inside(positions: &[Point2<f64>], columns: usize, rows: usize, grid_point: &dyn Fn(usize, usize) -> Point2<f64>) -> Vec<bool> {
+    let len: usize = positions.len();
+    let mut inside: Vec<bool> = Vec::with_capacity(columns * rows);
+    let mut crossings: Vec<f64> = Vec::new();
+
+    for row in 0..rows {
+        let y: f64 = grid_point(0, row).y;
+        crossings.clear();
+        for i in 0..len {
+            let (a, b): (&Point2<f64>, &Point2<f64>) = (&positions[i], &positions[(i + 1) % len]);
+            if (a.y <= y) != (b.y <= y) {
+                crossings.push(a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x));
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+
+        let mut crossed: usize = 0;
+        for column in 0..columns {
+            let x: f64 = grid_point(column, row).x;
+            while crossed < crossings.len() && crossings[crossed] < x {
+                crossed += 1;
+            }
+            inside.push(crossed % 2 == 1);
+        }
+    }
+
+    inside
+}
+
+// Traces the closed curves where `field` equals `level` with marching squares.
+fn trace_contours(
+    field: &[f64],
+    columns: usize,
+    rows: usize,
+    level: f64,
+    grid_point: &dyn Fn(usize, usize) -> Point2<f64>,
+) -> Vec<Vec<Point2<f64>>> {
+    let value = |column: usize, row: usize| field[row * columns + column] - level;
+    // Crossings are identified by the grid edge they lie on: even for the edge to the
+    // right of a grid point, odd for the edge above it.
+    let horizontal = |column: usize, row: usize| 2 * (row * columns + column);
+    let vertical = |column: usize, row: usize| 2 * (row * columns + column) + 1;
+
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+    for row in 0..rows - 1 {
+        for column in 0..columns - 1 {
+            let corners: [f64; 4] = [
+                value(column, row),
+                value(column + 1, row),
+                value(column + 1, row + 1),
+                value(column, row + 1),
+            ];
+            let case: usize = corners
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| **value < 0.0)
+                .map(|(bit, _)| 1 << bit)
+                .sum();
+            let bottom: usize = horizontal(column, row);
+            let right: usize = vertical(column + 1, row);
+            let top: usize = horizontal(column, row + 1);
+            let left: usize = vertical(column, row);
+            // Saddles are resolved by the value in the middle of the cell.
+            let center_below: bool = corners.iter().sum::<f64>() < 0.0;
+
+            match case {
+                1 | 14 => segments.push((left, bottom)),
+                2 | 13 => segments.push((bottom, right)),
+                3 | 12 => segments.push((left, right)),
+                4 | 11 => segments.push((right, top)),
+                6 | 9 => segments.push((bottom, top)),
+                7 | 8 => segments.push((left, top)),
+                5 if center_below => segments.extend([(bottom, right), (top, left)]),
+                5 => segments.extend([(left, bottom), (right, top)]),
+                10 if center_below => segments.extend([(left, bottom), (right, top)]),
+                10 => segments.extend([(bottom, right), (top, left)]),
+                _ => {}
+            }
+        }
+    }
+
+    let crossing = |edge: usize| -> Point2<f64> {
+        let (column, row): (usize, usize) = ((edge / 2) % columns, (edge / 2) / columns);
+        let (other_column, other_row): (usize, usize) = if edge % 2 == 0 { (column + 1, row) } else { (column, row + 1) };
+        let (a, b): (f64, f64) = (value(column, row), value(other_column, other_row));
+        let t: f64 = if a == b { 0.5 } else { a / (a - b) };
+        let (start, end): (Point2<f64>, Point2<f64>) = (grid_point(column, row), grid_point(other_column, other_row));
+        start + (end - start) * t
+    };
+
+    // Every crossing is shared by the two cells on either side of its grid edge,
+    // so following the segments from crossing to crossing closes every curve.
+    let mut segments_at: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &(a, b)) in segments.iter().enumerate() {
+        segments_at.entry(a).or_default().push(i);
+        segments_at.entry(b).or_default().push(i);
+    }
+
+    let mut used: Vec<bool> = vec![false; segments.len()];
+    let mut contours: Vec<Vec<Point2<f64>>> = Vec::new();
+    for first in 0..segments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let (start, mut current): (usize, usize) = segments[first];
+        let mut contour: Vec<Point2<f64>> = vec![crossing(start)];
+        while current != start {
+            contour.push(crossing(current));
+            let next: usize = match segments_at[&current].iter().copied().find(|&segment| !used[segment]) {
+                Some(next) => next,
+                None => break,
+            };
+            used[next] = true;
+            let (a, b): (usize, usize) = segments[next];
+            current = if a == current { b } else { a };
+        }
+        contours.push(contour);
+    }
+
+    contours
+}
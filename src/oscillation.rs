@@ -0,0 +1,105 @@
+use core::f64::consts::TAU;
+
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+use crate::{differential_growth::DifferentialGrowth, rng::Rng};
+
+/// Makes the desired separation of every node wobble over time, each node at its own
+/// phase, for a subtle shimmer in animated pieces.
+///
+/// The phase of a node is derived from the seed and the id of the node, so it stays
+/// with the node while the path grows and the same seed wobbles the same way on every
+/// run. See [`DifferentialGrowth::node_phase()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Oscillation;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// let mut still = differential_growth.clone();
+/// differential_growth.set_oscillation(Oscillation { amplitude: 0.3, period: 40.0, seed: 7 });
+///
+/// differential_growth.tick_n(100);
+/// still.tick_n(100);
+/// assert!(!differential_growth.diff(&still).is_identical());
+///
+/// // Every node has its own phase.
+/// let phases: Vec<f64> = (0..10).map(|index| differential_growth.node_phase(index).unwrap()).collect();
+/// assert!(phases.iter().all(|phase| (0.0..core::f64::consts::TAU).contains(phase)));
+/// assert_ne!(phases[0], phases[1]);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oscillation {
+    /// How far the desired separation swings either way, as a fraction of it. Defaults to `0.1`.
+    pub amplitude: f64,
+    /// The amount of ticks of one full swing. Defaults to `120.0`.
+    pub period: f64,
+    /// Decides the phase of every node. Defaults to `0`.
+    pub seed: u64,
+}
+
+impl Default for Oscillation {
+    fn default() -> Self {
+        Oscillation {
+            amplitude: 0.1,
+            period: 120.0,
+            seed: 0,
+        }
+    }
+}
+
+impl Oscillation {
+    // The phase of the node with `id`, in [0, 2π).
+    fn phase(&self, id: usize) -> f64 {
+        // Mixes the id into the seed so neighboring ids get unrelated phases.
+        let mut rng: Rng = Rng::new(self.seed ^ (id as u64).wrapping_mul(0xD1B5_4A32_D192_ED03));
+        rng.next_f64() * TAU
+    }
+
+    // What the desired separation of the node with `id` is multiplied by at `iteration`.
+    fn factor(&self, id: usize, iteration: usize) -> f64 {
+        if !(self.period > 0.0 && self.period.is_finite()) {
+            return 1.0;
+        }
+        1.0 + self.amplitude * (TAU * iteration as f64 / self.period + self.phase(id)).sin()
+    }
+}
+
+impl DifferentialGrowth {
+    /// Makes the desired separation of the nodes wobble, see [`Oscillation`].
+    pub fn set_oscillation(&mut self, oscillation: Oscillation) {
+        self.oscillation = Some(oscillation);
+    }
+
+    /// Returns the oscillation, if any.
+    #[must_use]
+    pub fn oscillation(&self) -> Option<&Oscillation> {
+        self.oscillation.as_ref()
+    }
+
+    /// Stops the oscillation, the nodes use their desired separation again.
+    pub fn clear_oscillation(&mut self) {
+        self.oscillation = None;
+    }
+
+    /// Returns the phase of the node at `index` in the oscillation, between 0 and 2π,
+    /// to animate other things along with it, like colors. Returns `None` without an
+    /// oscillation or when there is no node at `index`.
+    #[must_use]
+    pub fn node_phase(&self, index: usize) -> Option<f64> {
+        let oscillation: &Oscillation = self.oscillation.as_ref()?;
+        self.nodes.get(index).map(|node| oscillation.phase(node.id))
+    }
+
+    // What the desired separation of node `i` is multiplied by this tick.
+    pub(crate) fn oscillation_factor(&self, i: usize) -> f64 {
+        match &self.oscillation {
+            Some(oscillation) => oscillation.factor(self.nodes[i].id, self.iteration()),
+            None => 1.0,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use nalgebra::{distance, Point2};
+
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+/// A temporary change of the growth rate and force magnitudes within a circular region.
+///
+/// The effect is strongest at the center and falls off linearly to no effect at the radius.
+/// Factors of `1.0` leave the simulation unchanged, larger factors boost and smaller
+/// factors suppress. Overlays are removed once their remaining ticks reach zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamOverlay {
+    /// The center of the affected region.
+    pub center: Point2<f64>,
+    /// The radius of the affected region.
+    pub radius: f64,
+    /// Multiplies how eagerly edges are split at the center of the region.
+    pub growth_factor: f64,
+    /// Multiplies the forces acting on nodes at the center of the region.
+    pub force_factor: f64,
+    /// The amount of ticks the overlay was created for.
+    pub duration: usize,
+    /// The amount of ticks the overlay still stays active.
+    pub remaining_ticks: usize,
+    /// Whether the effect fades out linearly over its duration instead of stopping at once.
+    pub decays: bool,
+}
+
+impl ParamOverlay {
+    /// Returns an overlay that stays active for `duration` ticks.
+    pub fn new(center: Point2<f64>, radius: f64, growth_factor: f64, force_factor: f64, duration: usize) -> ParamOverlay {
+        ParamOverlay {
+            center,
+            radius,
+            growth_factor,
+            force_factor,
+            duration,
+            remaining_ticks: duration,
+            decays: false,
+        }
+    }
+
+    /// Returns how much of the effect applies at `point`, between 0 and 1.
+    pub fn weight_at(&self, point: &Point2<f64>) -> f64 {
+        if self.radius <= 0.0 || self.remaining_ticks == 0 {
+            return 0.0;
+        }
+
+        let falloff: f64 = (1.0 - distance(&self.center, point) / self.radius).max(0.0);
+
+        if self.decays && self.duration > 0 {
+            falloff * self.remaining_ticks as f64 / self.duration as f64
+        } else {
+            falloff
+        }
+    }
+
+    /// Returns the growth multiplier at `point`.
+    pub fn growth_multiplier_at(&self, point: &Point2<f64>) -> f64 {
+        1.0 + (self.growth_factor - 1.0) * self.weight_at(point)
+    }
+
+    /// Returns the force multiplier at `point`.
+    pub fn force_multiplier_at(&self, point: &Point2<f64>) -> f64 {
+        1.0 + (self.force_factor - 1.0) * self.weight_at(point)
+    }
+}
+
+// Overlapping overlays compound.
+pub(crate) fn growth_multiplier(overlays: &[ParamOverlay], point: &Point2<f64>) -> f64 {
+    overlays.iter().map(|overlay| overlay.growth_multiplier_at(point)).product()
+}
+
+pub(crate) fn force_multiplier(overlays: &[ParamOverlay], point: &Point2<f64>) -> f64 {
+    overlays.iter().map(|overlay| overlay.force_multiplier_at(point)).product()
+}
+
+/// How a [`RadialGradient`] blends from its center value to its edge value.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Falloff {
+    /// Changes at the same rate from the center to the radius.
+    #[default]
+    Linear,
+    /// Eases in at the center and out at the radius.
+    Smooth,
+    /// Raises the relative distance to the given exponent, above 1 keeps the
+    /// center value further out, below 1 reaches the edge value sooner.
+    Power(f64),
+}
+
+impl Falloff {
+    /// Returns how far the blend has progressed at `t`, the distance relative to the radius between 0 and 1.
+    pub fn apply(&self, t: f64) -> f64 {
+        let t: f64 = t.clamp(0.0, 1.0);
+        match *self {
+            Falloff::Linear => t,
+            Falloff::Smooth => t * t * (3.0 - 2.0 * t),
+            Falloff::Power(exponent) => t.powf(exponent),
+        }
+    }
+}
+
+/// Scales growth and separation with the distance from a center point, for the
+/// popular look of a dense core turning into a feathery edge.
+///
+/// Both factors multiply the normal parameters like those of a [`ParamOverlay`],
+/// blending from their value at the center to their value at the radius and beyond.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Falloff, RadialGradient};
+/// use nalgebra::Point2;
+///
+/// // Grows fast and packed in the middle, slow and airy at the edge.
+/// let gradient = RadialGradient {
+///     growth_at_center: 2.0,
+///     separation_at_center: 0.5,
+///     falloff: Falloff::Smooth,
+///     ..RadialGradient::new(Point2::new(0.0, 0.0), 100.0)
+/// };
+/// assert_eq!(gradient.growth_multiplier_at(&Point2::new(0.0, 0.0)), 2.0);
+/// assert_eq!(gradient.growth_multiplier_at(&Point2::new(50.0, 0.0)), 1.5);
+/// assert_eq!(gradient.separation_multiplier_at(&Point2::new(200.0, 0.0)), 1.0);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_radial_gradient(gradient);
+/// differential_growth.tick();
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialGradient {
+    pub center: Point2<f64>,
+    /// The distance from the center at which the edge values are reached.
+    pub radius: f64,
+    /// Multiplies how eagerly edges are split at the center.
+    pub growth_at_center: f64,
+    /// Multiplies how eagerly edges are split at the radius and beyond.
+    pub growth_at_edge: f64,
+    /// Multiplies the separation force at the center.
+    pub separation_at_center: f64,
+    /// Multiplies the separation force at the radius and beyond.
+    pub separation_at_edge: f64,
+    pub falloff: Falloff,
+}
+
+impl RadialGradient {
+    /// Returns a gradient with all factors at `1.0` and a linear falloff, which changes nothing yet.
+    pub fn new(center: Point2<f64>, radius: f64) -> RadialGradient {
+        RadialGradient {
+            center,
+            radius,
+            growth_at_center: 1.0,
+            growth_at_edge: 1.0,
+            separation_at_center: 1.0,
+            separation_at_edge: 1.0,
+            falloff: Falloff::Linear,
+        }
+    }
+
+    /// Returns how far `point` is along the gradient, 0 at the center and 1 at the radius.
+    pub fn blend_at(&self, point: &Point2<f64>) -> f64 {
+        if self.radius <= 0.0 {
+            return 1.0;
+        }
+
+        self.falloff.apply(distance(&self.center, point) / self.radius)
+    }
+
+    /// Returns the growth multiplier at `point`.
+    pub fn growth_multiplier_at(&self, point: &Point2<f64>) -> f64 {
+        let blend: f64 = self.blend_at(point);
+        self.growth_at_center + (self.growth_at_edge - self.growth_at_center) * blend
+    }
+
+    /// Returns the separation force multiplier at `point`.
+    pub fn separation_multiplier_at(&self, point: &Point2<f64>) -> f64 {
+        let blend: f64 = self.blend_at(point);
+        self.separation_at_center + (self.separation_at_edge - self.separation_at_center) * blend
+    }
+}
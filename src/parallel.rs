@@ -0,0 +1,52 @@
+// Work spread over threads with the `rayon` feature, in fixed chunks so the results
+// are bit-identical to the serial ones whatever the amount of threads.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::prelude::*;
+
+// The amount of items every task handles. Sums are added up per chunk first, also
+// without rayon, so the order of the additions never depends on the threads.
+const CHUNK_SIZE: usize = 256;
+
+// Returns `f` for every index below `len`. Every item is computed on its own, so there is
+// nothing to reduce and only the speed depends on the threads.
+pub(crate) fn map<T, F>(len: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    let items: Vec<T> = (0..len).into_par_iter().with_min_len(CHUNK_SIZE).map(f).collect();
+    #[cfg(not(feature = "rayon"))]
+    let items: Vec<T> = (0..len).map(f).collect();
+
+    items
+}
+
+// Replaces the contents of `out` with `f` for every index below `len`, like `map()`
+// but reusing the allocation of `out`.
+pub(crate) fn map_into<T, F>(len: usize, f: F, out: &mut Vec<T>)
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    out.clear();
+    #[cfg(feature = "rayon")]
+    out.par_extend((0..len).into_par_iter().with_min_len(CHUNK_SIZE).map(f));
+    #[cfg(not(feature = "rayon"))]
+    out.extend((0..len).map(f));
+}
+
+// Returns the sum of `values`, adding up every chunk from front to back and then the
+// sums of the chunks in order. Floating point addition isn't associative, so both the
+// chunks and the order are fixed.
+pub(crate) fn ordered_sum(values: &[f64]) -> f64 {
+    #[cfg(feature = "rayon")]
+    let chunk_sums: Vec<f64> = values.par_chunks(CHUNK_SIZE).map(|chunk| chunk.iter().sum()).collect();
+    #[cfg(not(feature = "rayon"))]
+    let chunk_sums: Vec<f64> = values.chunks(CHUNK_SIZE).map(|chunk| chunk.iter().sum()).collect();
+
+    chunk_sums.iter().sum()
+}
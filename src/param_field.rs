@@ -0,0 +1,89 @@
+use alloc::sync::Arc;
+
+use nalgebra::Point2;
+
+use crate::{differential_growth::DifferentialGrowth, parallel, params::ParamSet};
+
+/// Returns the parameters at a point, see [`DifferentialGrowth::set_param_field()`].
+pub(crate) type ParamField = Arc<dyn Fn(&Point2<f64>) -> ParamSet + Send + Sync>;
+
+impl DifferentialGrowth {
+    /// Makes the parameters vary across space: `param_field` returns the parameters at a point
+    /// and replaces the parameters of the simulation there. It is sampled at every node at the
+    /// start of every tick, and at the middle of every edge to decide whether it is split.
+    ///
+    /// This covers smooth changes of density like gradients or noise fields, overrides set
+    /// with [`DifferentialGrowth::set_node_params()`] still take precedence. Start from
+    /// [`ParamSet::from_differential_growth()`] to only change some of the parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::ParamSet;
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Twice as dense on the right as on the left.
+    /// let base: ParamSet = ParamSet::from_differential_growth(&differential_growth);
+    /// differential_growth.set_param_field(move |point: &Point2<f64>| {
+    ///     let scale: f64 = if point.x > 0.0 { 0.5 } else { 1.0 };
+    ///     ParamSet {
+    ///         desired_separation: base.desired_separation * scale,
+    ///         max_edge_length: base.max_edge_length * scale,
+    ///         ..base
+    ///     }
+    /// });
+    ///
+    /// differential_growth.tick_n(100);
+    /// let points = differential_growth.get_points();
+    /// let right: usize = points.iter().filter(|point| point.x > 0.0).count();
+    /// assert!(right > points.len() - right);
+    /// ```
+    ///
+    pub fn set_param_field(&mut self, param_field: impl Fn(&Point2<f64>) -> ParamSet + Send + Sync + 'static) {
+        self.param_field = Some(Arc::new(param_field));
+    }
+
+    /// Removes the field set with [`DifferentialGrowth::set_param_field()`],
+    /// all nodes use the parameters of the simulation again.
+    pub fn clear_param_field(&mut self) {
+        self.param_field = None;
+        self.field_params.clear();
+        // Restores the limits of the nodes, keeping their overrides.
+        self.set_max_force(self.max_force);
+        self.set_max_speed(self.max_speed);
+    }
+
+    // Samples the field at every node, for the forces of this tick.
+    pub(crate) fn sample_param_field(&mut self) {
+        let param_field: ParamField = match &self.param_field {
+            Some(param_field) => param_field.clone(),
+            None => return,
+        };
+
+        let nodes = &self.nodes;
+        self.field_params = parallel::map(nodes.len(), |i| param_field(&nodes[i].position));
+
+        for i in 0..self.nodes.len() {
+            let overrides = self.node_params(i);
+            let field_params: &ParamSet = &self.field_params[i];
+            self.nodes[i].max_force = overrides.max_force.unwrap_or(field_params.max_force);
+            self.nodes[i].max_speed = overrides.max_speed.unwrap_or(field_params.max_speed);
+        }
+    }
+
+    // The parameters of the field at node `i` this tick, if any.
+    pub(crate) fn field_params_of(&self, i: usize) -> Option<&ParamSet> {
+        self.field_params.get(i)
+    }
+
+    // The maximum length of an edge with its middle at `middle`.
+    pub(crate) fn max_edge_length_at(&self, middle: &Point2<f64>) -> f64 {
+        match &self.param_field {
+            Some(param_field) => param_field(middle).max_edge_length,
+            None => self.max_edge_length,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use core::ops::RangeInclusive;
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, rng::Rng};
+
+/// Largest `max_edge_length / desired_separation` that still grows nicely.
+/// Above this, freshly split edges barely feel the separation force of their
+/// neighbors and the path stops developing folds.
+const MAX_EDGE_TO_SEPARATION: f64 = 0.5;
+/// Largest `max_speed / max_edge_length` before nodes can overshoot
+/// their neighbors in a single iteration and the path starts to tangle.
+const MAX_SPEED_TO_EDGE: f64 = 0.5;
+/// Smallest separation/cohesion ratio before cohesion wins and the path
+/// shrinks into a smooth blob instead of growing.
+const MIN_SEPARATION_COHESION_RATIO: f64 = 0.8;
+
+/// The set of parameters driving the differential growth algorithm.
+///
+/// # Examples
+///
+/// ```rust
+/// let params = differential_growth::ParamSet::default();
+/// assert!(params.is_stable());
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .params(params)
+///     .build();
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamSet {
+    /// The maximum force nodes can exert on eachother.
+    pub max_force: f64,
+    /// The maximum magnitude of a node's velocity.
+    pub max_speed: f64,
+    /// The desired separation between nodes.
+    pub desired_separation: f64,
+    /// The ratio between separation and cohesion forces.
+    pub separation_cohesion_ratio: f64,
+    /// The maximum length between two connected nodes.
+    pub max_edge_length: f64,
+}
+
+impl Default for ParamSet {
+    fn default() -> Self {
+        ParamSet {
+            max_force: 1.5,
+            max_speed: 1.0,
+            desired_separation: 14.0,
+            separation_cohesion_ratio: 1.1,
+            max_edge_length: 5.0,
+        }
+    }
+}
+
+/// Inclusive ranges to sample a [`ParamSet`] from.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamRanges {
+    pub max_force: RangeInclusive<f64>,
+    pub max_speed: RangeInclusive<f64>,
+    pub desired_separation: RangeInclusive<f64>,
+    pub separation_cohesion_ratio: RangeInclusive<f64>,
+    pub max_edge_length: RangeInclusive<f64>,
+}
+
+impl Default for ParamRanges {
+    fn default() -> Self {
+        ParamRanges {
+            max_force: 0.5..=3.0,
+            max_speed: 0.25..=3.0,
+            desired_separation: 4.0..=30.0,
+            separation_cohesion_ratio: 0.5..=2.0,
+            max_edge_length: 1.0..=15.0,
+        }
+    }
+}
+
+impl ParamSet {
+    /// Returns the parameters of an existing simulation.
+    pub fn from_differential_growth(differential_growth: &DifferentialGrowth) -> ParamSet {
+        ParamSet {
+            max_force: differential_growth.max_force,
+            max_speed: differential_growth.max_speed,
+            desired_separation: differential_growth.desired_separation,
+            separation_cohesion_ratio: differential_growth.separation_cohesion_ratio(),
+            max_edge_length: differential_growth.max_edge_length,
+        }
+    }
+
+    /// Applies these parameters to an existing simulation, including its existing nodes.
+    pub fn apply(&self, differential_growth: &mut DifferentialGrowth) {
+        differential_growth.set_max_force(self.max_force);
+        differential_growth.set_max_speed(self.max_speed);
+        differential_growth.set_desired_separation(self.desired_separation);
+        differential_growth.set_separation_cohesion_ratio(self.separation_cohesion_ratio);
+        differential_growth.set_max_edge_length(self.max_edge_length);
+    }
+
+    /// Whether the parameters respect the known stability relationships:
+    /// * `max_edge_length` is at most half of `desired_separation`.
+    /// * `max_speed` is at most half of `max_edge_length`.
+    /// * `separation_cohesion_ratio` is at least `0.8`.
+    ///
+    /// Parameters breaking these relationships usually produce a simulation
+    /// that doesn't grow, collapses or tangles.
+    pub fn is_stable(&self) -> bool {
+        self.max_edge_length <= self.desired_separation * MAX_EDGE_TO_SEPARATION
+            && self.max_speed <= self.max_edge_length * MAX_SPEED_TO_EDGE
+            && self.separation_cohesion_ratio >= MIN_SEPARATION_COHESION_RATIO
+    }
+
+    /// Samples a random parameter combination within `ranges`, respecting the
+    /// stability relationships described in [`ParamSet::is_stable()`].
+    ///
+    /// Parameters are sampled one after the other, narrowing the range of the next
+    /// parameter to what keeps the combination stable. When the given ranges don't
+    /// allow a stable combination the closest value within the range is used instead,
+    /// so the result always lies within `ranges`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ranges` - The ranges each parameter is sampled from.
+    /// * `seed` - The same seed always returns the same parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{ParamRanges, ParamSet};
+    ///
+    /// let params = ParamSet::random_within(&ParamRanges::default(), 42);
+    /// assert!(params.is_stable());
+    /// assert_eq!(params, ParamSet::random_within(&ParamRanges::default(), 42));
+    /// ```
+    ///
+    pub fn random_within(ranges: &ParamRanges, seed: u64) -> ParamSet {
+        let mut rng: Rng = Rng::new(seed);
+
+        let desired_separation: f64 = sample(&mut rng, &ranges.desired_separation, f64::MIN, f64::MAX);
+        let max_edge_length: f64 = sample(
+            &mut rng,
+            &ranges.max_edge_length,
+            f64::MIN,
+            desired_separation * MAX_EDGE_TO_SEPARATION,
+        );
+        let max_speed: f64 = sample(&mut rng, &ranges.max_speed, f64::MIN, max_edge_length * MAX_SPEED_TO_EDGE);
+        let max_force: f64 = sample(&mut rng, &ranges.max_force, f64::MIN, f64::MAX);
+        let separation_cohesion_ratio: f64 = sample(
+            &mut rng,
+            &ranges.separation_cohesion_ratio,
+            MIN_SEPARATION_COHESION_RATIO,
+            f64::MAX,
+        );
+
+        ParamSet {
+            max_force,
+            max_speed,
+            desired_separation,
+            separation_cohesion_ratio,
+            max_edge_length,
+        }
+    }
+}
+
+/// Parameters that change as the simulation progresses, interpolated linearly
+/// between keyframes. Before the first keyframe the parameters of the first one
+/// apply and after the last keyframe those of the last one.
+///
+/// Slowly increasing the desired separation is a standard trick for layered growth.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{ParamSchedule, ParamSet};
+///
+/// let schedule = ParamSchedule::new()
+///     .keyframe(0, ParamSet::default())
+///     .keyframe(200, ParamSet { desired_separation: 28.0, max_edge_length: 10.0, ..ParamSet::default() });
+/// assert_eq!(schedule.params_at(100).unwrap().desired_separation, 21.0);
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_param_schedule(schedule);
+///
+/// // The parameters of a tick are applied at its start.
+/// differential_growth.tick_n(101);
+/// assert_eq!(differential_growth.desired_separation, 21.0);
+/// assert_eq!(differential_growth.max_edge_length, 7.5);
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamSchedule {
+    keyframes: Vec<(usize, ParamSet)>,
+}
+
+impl ParamSchedule {
+    /// Returns a schedule without keyframes, it changes nothing.
+    pub fn new() -> ParamSchedule {
+        ParamSchedule::default()
+    }
+
+    /// Adds the parameters reached at `tick`, replacing a keyframe at the same tick.
+    #[must_use]
+    pub fn keyframe(mut self, tick: usize, params: ParamSet) -> Self {
+        match self.keyframes.binary_search_by_key(&tick, |(keyframe_tick, _)| *keyframe_tick) {
+            Ok(i) => self.keyframes[i] = (tick, params),
+            Err(i) => self.keyframes.insert(i, (tick, params)),
+        }
+        self
+    }
+
+    /// Returns the keyframes, ordered by tick.
+    pub fn keyframes(&self) -> &[(usize, ParamSet)] {
+        &self.keyframes
+    }
+
+    /// Returns the parameters at `tick`, or `None` without keyframes.
+    pub fn params_at(&self, tick: usize) -> Option<ParamSet> {
+        let next: usize = self.keyframes.partition_point(|(keyframe_tick, _)| *keyframe_tick <= tick);
+        match (next.checked_sub(1).map(|i| self.keyframes[i]), self.keyframes.get(next)) {
+            (Some((from_tick, from)), Some((to_tick, to))) => {
+                let t: f64 = (tick - from_tick) as f64 / (to_tick - from_tick) as f64;
+                Some(from.lerp(to, t))
+            }
+            (Some((_, params)), None) => Some(params),
+            (None, Some((_, params))) => Some(*params),
+            (None, None) => None,
+        }
+    }
+}
+
+impl ParamSet {
+    // Returns the parameters `t` of the way from these to `other`.
+    fn lerp(&self, other: &ParamSet, t: f64) -> ParamSet {
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+        ParamSet {
+            max_force: lerp(self.max_force, other.max_force),
+            max_speed: lerp(self.max_speed, other.max_speed),
+            desired_separation: lerp(self.desired_separation, other.desired_separation),
+            separation_cohesion_ratio: lerp(self.separation_cohesion_ratio, other.separation_cohesion_ratio),
+            max_edge_length: lerp(self.max_edge_length, other.max_edge_length),
+        }
+    }
+}
+
+// Samples from the intersection of `range` and `[min, max]`. If they don't
+// intersect the bound of `range` closest to the constraint is returned.
+fn sample(rng: &mut Rng, range: &RangeInclusive<f64>, min: f64, max: f64) -> f64 {
+    let low: f64 = range.start().max(min);
+    let high: f64 = range.end().min(max);
+
+    if low > high {
+        return if *range.end() < min { *range.end() } else { *range.start() };
+    }
+
+    rng.range(low, high)
+}
@@ -0,0 +1,151 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    constraints::{Boundary, BoundaryBehavior},
+    differential_growth::DifferentialGrowth,
+};
+
+/// The rectangle a [`BoundaryBehavior::Periodic`] boundary wraps around, like a torus.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Period {
+    min: Point2<f64>,
+    size: Vector2<f64>,
+}
+
+impl Period {
+    // Returns `None` for an empty rectangle, which has nothing to wrap around.
+    pub(crate) fn new(min: Point2<f64>, max: Point2<f64>) -> Option<Period> {
+        let size: Vector2<f64> = max - min;
+        if !(0..2).all(|axis| size[axis] > 0.0 && size[axis].is_finite()) {
+            return None;
+        }
+        Some(Period { min, size })
+    }
+
+    /// Returns the shortest difference between two positions, which may go across the edges.
+    pub(crate) fn wrap(&self, mut difference: Vector2<f64>) -> Vector2<f64> {
+        for axis in 0..2 {
+            difference[axis] -= self.size[axis] * (difference[axis] / self.size[axis]).round();
+        }
+        difference
+    }
+
+    /// Returns the position moved into the rectangle.
+    pub(crate) fn wrap_point(&self, mut position: Point2<f64>) -> Point2<f64> {
+        for axis in 0..2 {
+            position[axis] = self.min[axis] + (position[axis] - self.min[axis]).rem_euclid(self.size[axis]);
+        }
+        position
+    }
+
+    /// Returns the points to search around to find everything within `radius` of `point`,
+    /// the point itself and its images across the edges it is close to.
+    pub(crate) fn images(&self, point: &Point2<f64>, radius: f64) -> Vec<Point2<f64>> {
+        let shifts = |axis: usize| {
+            let mut shifts: Vec<f64> = vec![0.0];
+            if point[axis] - radius < self.min[axis] {
+                shifts.push(self.size[axis]);
+            }
+            if point[axis] + radius > self.min[axis] + self.size[axis] {
+                shifts.push(-self.size[axis]);
+            }
+            shifts
+        };
+
+        let (shifts_x, shifts_y): (Vec<f64>, Vec<f64>) = (shifts(0), shifts(1));
+        shifts_x
+            .iter()
+            .flat_map(|x| shifts_y.iter().map(move |y| point + Vector2::new(*x, *y)))
+            .collect()
+    }
+}
+
+impl Boundary {
+    // The period of a periodic boundary.
+    pub(crate) fn period(&self) -> Option<Period> {
+        match self.behavior {
+            BoundaryBehavior::Periodic => Period::new(self.min, self.max),
+            _ => None,
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    // The period of the boundary, when it is periodic.
+    pub(crate) fn period(&self) -> Option<Period> {
+        self.boundary().and_then(Boundary::period)
+    }
+
+    // The difference from `a` to `b`, the shortest way across a periodic boundary.
+    pub(crate) fn difference(&self, a: &Point2<f64>, b: &Point2<f64>) -> Vector2<f64> {
+        match self.period() {
+            Some(period) => period.wrap(b - a),
+            None => b - a,
+        }
+    }
+
+    // The point halfway between `a` and `b`, the shortest way across a periodic boundary.
+    pub(crate) fn middle(&self, a: &Point2<f64>, b: &Point2<f64>) -> Point2<f64> {
+        self.wrap_point(a + self.difference(a, b) / 2.0)
+    }
+
+    // The position moved into the rectangle of a periodic boundary.
+    pub(crate) fn wrap_point(&self, position: Point2<f64>) -> Point2<f64> {
+        match self.period() {
+            Some(period) => period.wrap_point(position),
+            None => position,
+        }
+    }
+
+    /// Returns the positions of the nodes with every edge as short as it is across a periodic
+    /// boundary, see [`BoundaryBehavior::Periodic`]. The path starts at the first node and may
+    /// run out of the rectangle, draw it once more shifted by the width and height of the
+    /// rectangle either way and clip it to the rectangle for a seamless tile. A closed path
+    /// that winds around the rectangle doesn't end where it starts.
+    ///
+    /// Without a periodic boundary these are the positions of the nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{Boundary, BoundaryBehavior, Topology};
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(8.0, 0.0), Point2::new(9.5, 0.0), Point2::new(-9.5, 0.0), Point2::new(-8.0, 0.0)];
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .topology(Topology::Open)
+    ///     .boundary(Boundary::new(Point2::new(-10.0, -10.0), Point2::new(10.0, 10.0), BoundaryBehavior::Periodic))
+    ///     .build();
+    ///
+    /// // The middle edge is only 1.0 long, across the edge of the rectangle.
+    /// assert_eq!(
+    ///     differential_growth.get_unwrapped_points(),
+    ///     vec![Point2::new(8.0, 0.0), Point2::new(9.5, 0.0), Point2::new(10.5, 0.0), Point2::new(12.0, 0.0)]
+    /// );
+    ///
+    /// // It grows as if the rectangle had no edges.
+    /// differential_growth.tick_n(50);
+    /// let points = differential_growth.get_unwrapped_points();
+    /// assert!(points.windows(2).all(|edge| nalgebra::distance(&edge[0], &edge[1]) < 5.0));
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_unwrapped_points(&self) -> Vec<Point2<f64>> {
+        let period: Period = match self.period() {
+            Some(period) => period,
+            None => return self.get_points(),
+        };
+
+        let mut points: Vec<Point2<f64>> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let point: Point2<f64> = match points.last() {
+                Some(previous) => previous + period.wrap(node.position - previous),
+                None => node.position,
+            };
+            points.push(point);
+        }
+        points
+    }
+}
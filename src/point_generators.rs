@@ -1,8 +1,14 @@
 use nalgebra::Point2;
-use std::{f64::consts::PI, ops::AddAssign};
+use core::{f64::consts::PI, ops::{AddAssign, Range}};
+
+use crate::prelude::*;
+use crate::{geometry, rng::Rng, topology::Topology};
 
 /// Helper function that returns points on a circle.
 /// 
+/// Rounding can give one point more or less than `amount_of_points`, use
+/// [`generate_points_on_arc()`] for an exact amount.
+/// 
 /// # Examples
 /// 
 /// ```rust
@@ -35,3 +41,412 @@ pub fn generate_points_on_circle(
 
     return points;
 }
+
+/// Helper function that returns evenly spaced points on a straight line from `start`
+/// to `end`, both included. Grow them with [`crate::Topology::Open`] to start from a
+/// seed line, like in the differential line article.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_line(Point2::new(-10.0, 0.0), Point2::new(10.0, 0.0), 5);
+/// assert_eq!(points, vec![
+///     Point2::new(-10.0, 0.0),
+///     Point2::new(-5.0, 0.0),
+///     Point2::new(0.0, 0.0),
+///     Point2::new(5.0, 0.0),
+///     Point2::new(10.0, 0.0),
+/// ]);
+/// ```
+///
+pub fn generate_points_on_line(start: Point2<f64>, end: Point2<f64>, amount_of_points: usize) -> Vec<Point2<f64>> {
+    match amount_of_points {
+        0 => Vec::new(),
+        1 => vec![start],
+        _ => (0..amount_of_points)
+            .map(|i| start + (end - start) * (i as f64 / (amount_of_points - 1) as f64))
+            .collect(),
+    }
+}
+
+/// Helper function that returns points on the outline of a regular polygon, its
+/// corners on a circle of `radius` around `center` and the first one on the right.
+///
+/// # Arguments
+///
+/// * `sides` - The amount of sides, 3 for a triangle. Fewer than 3 gives no points.
+/// * `points_per_side` - The amount of points from one corner up to the next, the corner included.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_polygon(Point2::new(0.0, 0.0), 10.0, 4, 4);
+/// assert_eq!(points.len(), 16);
+/// assert_eq!(points[0], Point2::new(10.0, 0.0));
+/// // Halfway along the first side.
+/// assert!((points[2] - Point2::new(5.0, 5.0)).norm() < 1e-9);
+/// ```
+///
+pub fn generate_points_on_polygon(
+    center: Point2<f64>,
+    radius: f64,
+    sides: usize,
+    points_per_side: usize,
+) -> Vec<Point2<f64>> {
+    if sides < 3 {
+        return Vec::new();
+    }
+
+    let corners: Vec<Point2<f64>> = (0..sides)
+        .map(|i| corner(center, radius, i as f64 / sides as f64))
+        .collect();
+    points_on_outline(&corners, points_per_side)
+}
+
+/// Helper function that returns points on the outline of a star, its tips on a circle of
+/// `outer_radius` around `center` and the first one on the right, the corners in between
+/// the tips on a circle of `inner_radius`.
+///
+/// # Arguments
+///
+/// * `tips` - The amount of tips. Fewer than 2 gives no points.
+/// * `points_per_side` - The amount of points from one corner up to the next, the corner included.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_star(Point2::new(0.0, 0.0), 10.0, 4.0, 5, 3);
+/// // 5 tips and 5 inner corners, 3 points per side.
+/// assert_eq!(points.len(), 30);
+/// assert!((points[0].coords.norm() - 10.0).abs() < 1e-9);
+/// assert!((points[3].coords.norm() - 4.0).abs() < 1e-9);
+/// ```
+///
+pub fn generate_points_on_star(
+    center: Point2<f64>,
+    outer_radius: f64,
+    inner_radius: f64,
+    tips: usize,
+    points_per_side: usize,
+) -> Vec<Point2<f64>> {
+    if tips < 2 {
+        return Vec::new();
+    }
+
+    let corners: Vec<Point2<f64>> = (0..2 * tips)
+        .map(|i| {
+            let radius: f64 = if i % 2 == 0 { outer_radius } else { inner_radius };
+            corner(center, radius, i as f64 / (2 * tips) as f64)
+        })
+        .collect();
+    points_on_outline(&corners, points_per_side)
+}
+
+/// Helper function that returns evenly spaced points on an Archimedean spiral around
+/// `center`, running counter-clockwise from the right and moving away from the center by
+/// `b` per radian. Grow them with [`crate::Topology::Open`] for layered growth.
+///
+/// # Arguments
+///
+/// * `a` - The distance of the first point from the center.
+/// * `b` - How far the spiral moves away from the center per radian, `2π * b` per turn.
+/// * `turns` - The amount of full turns, fractions included.
+/// * `amount_of_points` - The amount of points, the first and the last included.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_spiral(Point2::new(0.0, 0.0), 5.0, 1.0, 3.0, 200);
+/// assert_eq!(points.len(), 200);
+/// assert!((points[0] - Point2::new(5.0, 0.0)).norm() < 1e-9);
+///
+/// // Evenly spaced along the spiral, also where it turns tighter near the center.
+/// let spacings: Vec<f64> = points.windows(2).map(|pair| (pair[1] - pair[0]).norm()).collect();
+/// let (min, max) = spacings.iter().fold((f64::MAX, 0.0_f64), |(min, max), &spacing| (min.min(spacing), max.max(spacing)));
+/// assert!(max - min < 0.01 * max);
+/// ```
+///
+pub fn generate_points_on_spiral(
+    center: Point2<f64>,
+    a: f64,
+    b: f64,
+    turns: f64,
+    amount_of_points: usize,
+) -> Vec<Point2<f64>> {
+    match amount_of_points {
+        0 => return Vec::new(),
+        1 => return vec![Point2::new(center.x + a, center.y)],
+        _ => {}
+    }
+
+    // Equal angles leave wide gaps on the outer turns, so a finely sampled spiral is
+    // resampled to equal distances instead.
+    let samples: usize = (amount_of_points * 16).max((turns.abs() * 256.0) as usize);
+    let end: f64 = 2.0 * PI * turns;
+    let polyline: Vec<Point2<f64>> = (0..=samples)
+        .map(|i| {
+            let theta: f64 = end * i as f64 / samples as f64;
+            let radius: f64 = a + b * theta;
+            Point2::new(center.x + radius * f64::cos(theta), center.y + radius * f64::sin(theta))
+        })
+        .collect();
+    let length: f64 = polyline.windows(2).map(|pair| nalgebra::distance(&pair[0], &pair[1])).sum();
+    if length == 0.0 {
+        return vec![polyline[0]; amount_of_points];
+    }
+
+    geometry::resample_evenly(&polyline, false, length / (amount_of_points - 1) as f64)
+}
+
+/// Helper function that returns points on a circle with a smooth, random bumpy outline,
+/// so growth doesn't start out perfectly symmetric. The same seed always gives the same blob.
+///
+/// # Arguments
+///
+/// * `roughness` - The largest distance from the circle as a fraction of `radius`, `0.0` for a circle.
+/// * `seed` - The seed of the bumps.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let center = Point2::new(0.0, 0.0);
+/// let points = differential_growth::generate_noisy_circle(center, 10.0, 0.2, 7, 100);
+/// assert_eq!(points.len(), 100);
+/// assert!(points.iter().all(|point| (point.coords.norm() - 10.0).abs() <= 2.0 + 1e-9));
+///
+/// assert_eq!(points, differential_growth::generate_noisy_circle(center, 10.0, 0.2, 7, 100));
+/// assert_ne!(points, differential_growth::generate_noisy_circle(center, 10.0, 0.2, 8, 100));
+/// ```
+///
+pub fn generate_noisy_circle(
+    center: Point2<f64>,
+    radius: f64,
+    roughness: f64,
+    seed: u64,
+    amount_of_points: usize,
+) -> Vec<Point2<f64>> {
+    // A few low harmonics with random phases give bumps instead of jagged noise,
+    // weaker for the higher ones.
+    let mut rng: Rng = Rng::new(seed);
+    let harmonics: Vec<(f64, f64, f64)> = (2..=7)
+        .map(|frequency| {
+            let frequency: f64 = frequency as f64;
+            (frequency, rng.next_f64() / frequency, rng.range(0.0, 2.0 * PI))
+        })
+        .collect();
+    let total_amplitude: f64 = harmonics.iter().map(|(_, amplitude, _)| amplitude).sum();
+
+    (0..amount_of_points)
+        .map(|i| {
+            let theta: f64 = 2.0 * PI * i as f64 / amount_of_points as f64;
+            let bumps: f64 = harmonics
+                .iter()
+                .map(|(frequency, amplitude, phase)| amplitude * f64::sin(frequency * theta + phase))
+                .sum::<f64>();
+            let distance: f64 = if total_amplitude > 0.0 {
+                radius * (1.0 + roughness * bumps / total_amplitude)
+            } else {
+                radius
+            };
+            Point2::new(center.x + distance * f64::cos(theta), center.y + distance * f64::sin(theta))
+        })
+        .collect()
+}
+
+/// Helper function that returns points `spacing` apart on the outline of the axis-aligned
+/// rectangle between `min` and `max`, counter-clockwise from `min`. The spacing is stretched
+/// slightly so a whole number of points fits, to grow a frame along the edges of a composition.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_rectangle(Point2::new(0.0, 0.0), Point2::new(30.0, 20.0), 2.0);
+/// assert_eq!(points.len(), 50);
+/// assert_eq!(points[0], Point2::new(0.0, 0.0));
+/// assert_eq!(points[15], Point2::new(30.0, 0.0));
+/// ```
+///
+pub fn generate_points_on_rectangle(min: Point2<f64>, max: Point2<f64>, spacing: f64) -> Vec<Point2<f64>> {
+    let corners: [Point2<f64>; 4] = [min, Point2::new(max.x, min.y), max, Point2::new(min.x, max.y)];
+    geometry::resample_evenly(&corners, true, spacing)
+}
+
+/// Helper function that returns points `spacing` apart on the outline of the axis-aligned
+/// rectangle between `min` and `max` with corners rounded to `corner_radius`, counter-clockwise
+/// from the start of the bottom edge. The radius is limited to half the shortest side.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// let points = differential_growth::generate_points_on_rounded_rectangle(Point2::new(0.0, 0.0), Point2::new(30.0, 20.0), 5.0, 1.0);
+/// // 4 straight sides of 20 and 10 and a circle of radius 5 from the corners.
+/// let perimeter: f64 = 2.0 * (20.0 + 10.0) + 2.0 * std::f64::consts::PI * 5.0;
+/// assert_eq!(points.len(), perimeter.round() as usize);
+/// assert!((points[0] - Point2::new(5.0, 0.0)).norm() < 1e-9);
+/// ```
+///
+pub fn generate_points_on_rounded_rectangle(
+    min: Point2<f64>,
+    max: Point2<f64>,
+    corner_radius: f64,
+    spacing: f64,
+) -> Vec<Point2<f64>> {
+    let radius: f64 = corner_radius.clamp(0.0, (max.x - min.x).min(max.y - min.y).abs() / 2.0);
+    let centers: [Point2<f64>; 4] = [
+        Point2::new(max.x - radius, min.y + radius),
+        Point2::new(max.x - radius, max.y - radius),
+        Point2::new(min.x + radius, max.y - radius),
+        Point2::new(min.x + radius, min.y + radius),
+    ];
+
+    // Every corner is a quarter circle, from the end of one side to the start of the next.
+    let segments: usize = 64;
+    let mut outline: Vec<Point2<f64>> = Vec::with_capacity(4 * (segments + 1) + 1);
+    outline.push(Point2::new(min.x + radius, min.y));
+    for (quarter, center) in centers.iter().enumerate() {
+        for i in 0..=segments {
+            let turn: f64 = (quarter as f64 - 1.0 + i as f64 / segments as f64) / 4.0;
+            outline.push(corner(*center, radius, turn));
+        }
+    }
+    // The last corner ends where the outline started.
+    outline.dedup();
+    outline.pop();
+
+    geometry::resample_evenly(&outline, true, spacing)
+}
+
+/// Helper function that returns points on any parametric curve, `f` giving the point at
+/// every value of its parameter within `t_range`.
+///
+/// # Arguments
+///
+/// * `topology` - With [`Topology::Closed`] the end of the range is left out, so a curve that
+///   ends where it started doesn't repeat its first point. With [`Topology::Open`] both ends are included.
+/// * `evenly_spaced` - Spaces the points evenly along the curve instead of evenly in `t`,
+///   for curves that speed up and slow down.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Topology;
+/// use nalgebra::Point2;
+/// use std::f64::consts::PI;
+///
+/// // An ellipse, which moves faster along its long sides for equal steps in t.
+/// let ellipse = |t: f64| Point2::new(30.0 * t.cos(), 10.0 * t.sin());
+/// let points = differential_growth::generate_points_from_fn(ellipse, 0.0..2.0 * PI, 100, Topology::Closed, true);
+/// assert_eq!(points.len(), 100);
+///
+/// let spacings: Vec<f64> = (0..100).map(|i| (points[(i + 1) % 100] - points[i]).norm()).collect();
+/// let (min, max) = spacings.iter().fold((f64::MAX, 0.0_f64), |(min, max), &spacing| (min.min(spacing), max.max(spacing)));
+/// assert!(max - min < 0.01 * max);
+///
+/// let line = differential_growth::generate_points_from_fn(|t| Point2::new(t, 0.0), 0.0..10.0, 11, Topology::Open, false);
+/// assert_eq!(line[10], Point2::new(10.0, 0.0));
+/// ```
+///
+pub fn generate_points_from_fn(
+    f: impl Fn(f64) -> Point2<f64>,
+    t_range: Range<f64>,
+    amount_of_points: usize,
+    topology: Topology,
+    evenly_spaced: bool,
+) -> Vec<Point2<f64>> {
+    let closed: bool = topology == Topology::Closed;
+    let sample = |i: usize, samples: usize| {
+        let steps: usize = if closed { samples } else { samples.saturating_sub(1).max(1) };
+        f(t_range.start + (t_range.end - t_range.start) * i as f64 / steps as f64)
+    };
+
+    // Resampling a closed curve always gives at least a triangle.
+    if !evenly_spaced || amount_of_points < topology.min_nodes() {
+        return (0..amount_of_points).map(|i| sample(i, amount_of_points)).collect();
+    }
+
+    // The curve is sampled finely and resampled to equal distances along it.
+    let samples: usize = amount_of_points * 16;
+    let polyline: Vec<Point2<f64>> = (0..samples).map(|i| sample(i, samples)).collect();
+    let edges: usize = if closed { samples } else { samples - 1 };
+    let length: f64 = (0..edges)
+        .map(|i| nalgebra::distance(&polyline[i], &polyline[(i + 1) % samples]))
+        .sum();
+    if length == 0.0 {
+        return vec![polyline[0]; amount_of_points];
+    }
+
+    let gaps: usize = if closed { amount_of_points } else { amount_of_points - 1 };
+    geometry::resample_evenly(&polyline, closed, length / gaps as f64)
+}
+
+/// Helper function that returns exactly `amount_of_points` evenly spaced points on a circle,
+/// or on the arc of it that starts at `start_angle` and turns `sweep_angle` radians,
+/// counter-clockwise when positive and clockwise when negative.
+///
+/// A full turn or more gives a closed circle that doesn't repeat its first point, so
+/// `2π` gives a counter-clockwise and `-2π` a clockwise circle. A shorter arc is an open
+/// path and includes both of its ends.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+/// use std::f64::consts::PI;
+///
+/// let center = Point2::new(0.0, 0.0);
+/// for amount in [3, 7, 10, 1000] {
+///     assert_eq!(differential_growth::generate_points_on_arc(center, 10.0, amount, 0.0, 2.0 * PI).len(), amount);
+/// }
+///
+/// // The top half of a circle, clockwise from the left.
+/// let arc = differential_growth::generate_points_on_arc(center, 10.0, 5, PI, -PI);
+/// assert!((arc[0] - Point2::new(-10.0, 0.0)).norm() < 1e-9);
+/// assert!((arc[2] - Point2::new(0.0, 10.0)).norm() < 1e-9);
+/// assert!((arc[4] - Point2::new(10.0, 0.0)).norm() < 1e-9);
+/// ```
+///
+pub fn generate_points_on_arc(
+    center: Point2<f64>,
+    radius: f64,
+    amount_of_points: usize,
+    start_angle: f64,
+    sweep_angle: f64,
+) -> Vec<Point2<f64>> {
+    let full_turn: bool = sweep_angle.abs() >= 2.0 * PI;
+    let steps: usize = if full_turn { amount_of_points } else { amount_of_points.saturating_sub(1).max(1) };
+    let sweep_angle: f64 = if full_turn { 2.0 * PI * sweep_angle.signum() } else { sweep_angle };
+
+    (0..amount_of_points)
+        .map(|i| corner(center, radius, (start_angle + sweep_angle * i as f64 / steps as f64) / (2.0 * PI)))
+        .collect()
+}
+
+// The point on the circle of `radius` around `center` at `turn` full turns counter-clockwise.
+fn corner(center: Point2<f64>, radius: f64, turn: f64) -> Point2<f64> {
+    let theta: f64 = 2.0 * PI * turn;
+    Point2::new(center.x + radius * f64::cos(theta), center.y + radius * f64::sin(theta))
+}
+
+// Evenly spaced points on the closed outline through `corners`.
+fn points_on_outline(corners: &[Point2<f64>], points_per_side: usize) -> Vec<Point2<f64>> {
+    (0..corners.len())
+        .flat_map(|i| {
+            let (start, end): (Point2<f64>, Point2<f64>) = (corners[i], corners[(i + 1) % corners.len()]);
+            (0..points_per_side).map(move |j| start + (end - start) * (j as f64 / points_per_side as f64))
+        })
+        .collect()
+}
@@ -0,0 +1,18 @@
+// What the standard library prelude would bring in scope, for builds without `std`.
+
+pub(crate) use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap, HashSet};
+// Without `std` there is no hasher to seed, the ordered collections take their place.
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use crate::math::Float;
@@ -0,0 +1,94 @@
+#[cfg(feature = "profiling")]
+use std::time::{Duration, Instant};
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// How long the phases of the last tick took and how much they searched for neighbors,
+/// see [`DifferentialGrowth::profile()`]. Phases that run several times per tick,
+/// like the forces of higher order integrators, add up.
+#[cfg(feature = "profiling")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TickProfile {
+    /// The whole tick.
+    pub total: Duration,
+    /// Updating the spatial index, including rebuilds of the kd-tree or grid.
+    pub neighbor_index: Duration,
+    /// Computing the separation forces.
+    pub separation: Duration,
+    /// Computing the cohesion forces.
+    pub cohesion: Duration,
+    /// Inserting nodes on long edges and merging nodes on short ones.
+    pub growth: Duration,
+    /// The amount of radius queries on the spatial index.
+    pub neighbor_queries: usize,
+}
+
+// The parts of a tick that are timed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Phase {
+    NeighborIndex,
+    Separation,
+    Cohesion,
+    Growth,
+}
+
+impl DifferentialGrowth {
+    /// Returns how long the phases of the last tick took, to tune the parameters and
+    /// the [`crate::RebuildPolicy`] for performance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick_n(10);
+    ///
+    /// let profile = differential_growth.profile();
+    /// assert!(profile.separation + profile.cohesion <= profile.total);
+    /// // At least one query for the separation of every node.
+    /// assert!(profile.neighbor_queries >= differential_growth.nodes().len());
+    /// ```
+    ///
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    pub fn profile(&self) -> TickProfile {
+        self.profile
+    }
+
+    // Runs a whole tick, profiling it when enabled.
+    pub(crate) fn profiled_tick(&mut self, tick: impl FnOnce(&mut DifferentialGrowth)) {
+        #[cfg(feature = "profiling")]
+        {
+            let start: Instant = Instant::now();
+            self.profile = TickProfile::default();
+            self.neighbor_search.take_queries();
+            tick(self);
+            self.profile.neighbor_queries = self.neighbor_search.take_queries();
+            self.profile.total = start.elapsed();
+        }
+        #[cfg(not(feature = "profiling"))]
+        tick(self);
+    }
+
+    // Runs one phase of a tick, adding its time to the profile when enabled.
+    pub(crate) fn profiled<T>(&mut self, phase: Phase, run: impl FnOnce(&mut DifferentialGrowth) -> T) -> T {
+        #[cfg(feature = "profiling")]
+        {
+            let start: Instant = Instant::now();
+            let result: T = run(self);
+            let elapsed: Duration = start.elapsed();
+            match phase {
+                Phase::NeighborIndex => self.profile.neighbor_index += elapsed,
+                Phase::Separation => self.profile.separation += elapsed,
+                Phase::Cohesion => self.profile.cohesion += elapsed,
+                Phase::Growth => self.profile.growth += elapsed,
+            }
+            result
+        }
+        #[cfg(not(feature = "profiling"))]
+        {
+            let _ = phase;
+            run(self)
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use nalgebra::Point2;
+use numpy::{PyArray, PyArray2, PyReadonlyArray2};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// A [`DifferentialGrowth`] for Python, exported as `differential_growth.DifferentialGrowth`.
+///
+/// Points cross the boundary as numpy arrays of shape `(n, 2)`, so drawing a frame
+/// copies the positions once instead of creating a Python object per point.
+///
+/// ```python
+/// import numpy as np
+/// from differential_growth import DifferentialGrowth
+///
+/// angles = np.linspace(0, 2 * np.pi, 10, endpoint=False)
+/// points = np.column_stack((np.cos(angles), np.sin(angles))) * 10
+/// growth = DifferentialGrowth(points, 1.5, 1.0, 14.0, 1.1, 5.0)
+/// growth.tick_n(100)
+/// print(growth.points().shape, growth.iteration)
+/// ```
+///
+/// The extension module is built from this crate with the `extension-module` feature as a `cdylib`,
+/// for example with `cargo rustc --release --features extension-module --crate-type cdylib`, after
+/// which `libdifferential_growth.so` is renamed to `differential_growth.so` (or to
+/// `differential_growth.pyd` from `differential_growth.dll` on Windows).
+///
+/// Everything else of the simulation is available from Rust: build it there and convert it with `From`.
+#[pyclass(name = "DifferentialGrowth")]
+pub struct PyDifferentialGrowth {
+    differential_growth: DifferentialGrowth,
+}
+
+#[pymethods]
+impl PyDifferentialGrowth {
+    /// Returns a closed path through the points, an array of shape `(n, 2)`, see
+    /// [`DifferentialGrowth::try_new()`]. Raises a `ValueError` when the array has another
+    /// shape or the points and parameters can't grow.
+    #[new]
+    pub fn new(
+        points: PyReadonlyArray2<'_, f64>,
+        max_force: f64,
+        max_speed: f64,
+        desired_separation: f64,
+        separation_cohesion_ratio: f64,
+        max_edge_length: f64,
+    ) -> PyResult<PyDifferentialGrowth> {
+        if points.shape()[1] != 2 {
+            return Err(PyValueError::new_err("points must be an array of shape (n, 2)"));
+        }
+        let points: Vec<Point2<f64>> = points
+            .as_array()
+            .rows()
+            .into_iter()
+            .map(|row| Point2::new(row[0], row[1]))
+            .collect();
+
+        DifferentialGrowth::try_new(
+            points,
+            max_force,
+            max_speed,
+            desired_separation,
+            separation_cohesion_ratio,
+            max_edge_length,
+        )
+        .map(PyDifferentialGrowth::from)
+        .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    /// Returns a simulation with the default parameters that blooms from the point
+    /// at `x`, `y`, see [`DifferentialGrowth::from_seed()`].
+    #[staticmethod]
+    pub fn from_seed(x: f64, y: f64) -> PyDifferentialGrowth {
+        PyDifferentialGrowth::from(DifferentialGrowth::from_seed(Point2::new(x, y)))
+    }
+
+    /// Advances the simulation by one iteration.
+    pub fn tick(&mut self) {
+        self.differential_growth.tick();
+    }
+
+    /// Advances the simulation by `n` iterations, one call for a whole frame.
+    pub fn tick_n(&mut self, n: usize) {
+        self.differential_growth.tick_n(n);
+    }
+
+    /// Returns the positions of the nodes as a new array of shape `(n, 2)`.
+    pub fn points<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<f64>> {
+        let coordinates: Vec<f64> = self
+            .differential_growth
+            .nodes()
+            .iter()
+            .flat_map(|node| [node.position.x, node.position.y])
+            .collect();
+        PyArray::from_vec(py, coordinates).reshape([self.differential_growth.nodes().len(), 2])
+    }
+
+    /// Returns the amount of nodes, for `len()`.
+    pub fn __len__(&self) -> usize {
+        self.differential_growth.nodes().len()
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    #[getter]
+    pub fn iteration(&self) -> usize {
+        self.differential_growth.iteration()
+    }
+}
+
+impl From<DifferentialGrowth> for PyDifferentialGrowth {
+    fn from(differential_growth: DifferentialGrowth) -> Self {
+        PyDifferentialGrowth { differential_growth }
+    }
+}
+
+// The entry point of the extension module, Python looks it up by the name of the module.
+#[pymodule]
+fn differential_growth(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyDifferentialGrowth>()?;
+    Ok(())
+}
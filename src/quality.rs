@@ -0,0 +1,73 @@
+use crate::prelude::*;
+use crate::{
+    differential_growth::DifferentialGrowth,
+    insertion::{CurvatureWeighted, InsertionStrategy, Midpoint},
+    spatial::RebuildPolicy,
+};
+
+/// Bundles of internal settings trading fidelity for speed.
+///
+/// Use [`Quality::Draft`] for interactive previews and run the same seed again
+/// with [`Quality::Final`] for the render. Every tier is deterministic, but the
+/// tiers differ from eachother so a draft is a preview, not an exact match.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Quality;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut preview = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points.clone())
+///     .quality(Quality::Draft)
+///     .seed(42)
+///     .build();
+/// preview.tick();
+///
+/// let mut render = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .quality(Quality::Final)
+///     .seed(42)
+///     .build();
+/// render.tick();
+/// ```
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quality {
+    /// Rebuilds the spatial index every few ticks and inserts nodes at edge midpoints.
+    Draft,
+    /// The defaults: rebuilds the spatial index every tick and inserts nodes at edge midpoints.
+    #[default]
+    Balanced,
+    /// Rebuilds the spatial index every tick and inserts nodes on a smooth curve
+    /// through their neighbors, see [`CurvatureWeighted`].
+    Final,
+}
+
+impl Quality {
+    /// Returns when the spatial index is rebuilt at this quality.
+    pub fn rebuild_policy(&self) -> RebuildPolicy {
+        match self {
+            Quality::Draft => RebuildPolicy { interval: 4, slack: 0.0 },
+            Quality::Balanced | Quality::Final => RebuildPolicy::default(),
+        }
+    }
+
+    /// Returns where new nodes are inserted at this quality.
+    pub fn insertion_strategy(&self) -> Box<dyn InsertionStrategy> {
+        match self {
+            Quality::Draft | Quality::Balanced => Box::new(Midpoint),
+            Quality::Final => Box::new(CurvatureWeighted::default()),
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Applies the settings of a quality tier, replacing the current
+    /// rebuild policy and insertion strategy.
+    pub fn set_quality(&mut self, quality: Quality) {
+        self.set_rebuild_policy(quality.rebuild_policy());
+        self.insertion_strategy = quality.insertion_strategy();
+    }
+}
@@ -0,0 +1,124 @@
+use std::{io, path::Path};
+
+use nalgebra::Point2;
+use tiny_skia::{Color, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+/// How [`DifferentialGrowth::render_png()`] draws the path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RasterStyle {
+    /// The width of the stroke in pixels. Defaults to `1.0`.
+    pub stroke_width: f64,
+    /// The color of the path as RGBA. Defaults to black.
+    pub stroke: [u8; 4],
+    /// The background color as RGBA, `None` leaves the image transparent. Defaults to white.
+    pub background: Option<[u8; 4]>,
+    /// The space around the path in world units. Defaults to `10.0`.
+    pub padding: f64,
+    /// Whether edges are smoothed. Defaults to `true`.
+    pub anti_alias: bool,
+}
+
+impl Default for RasterStyle {
+    fn default() -> Self {
+        RasterStyle {
+            stroke_width: 1.0,
+            stroke: [0, 0, 0, 255],
+            background: Some([255, 255, 255, 255]),
+            padding: 10.0,
+            anti_alias: true,
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Draws the current path into a `width` by `height` pixel image. The path is fitted
+    /// into the image without stretching it and centered along the axis with room to spare.
+    ///
+    /// Returns an error when the image has no pixels.
+    pub fn render_pixmap(&self, width: u32, height: u32, style: &RasterStyle) -> io::Result<Pixmap> {
+        let mut pixmap: Pixmap = Pixmap::new(width, height)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the image has no pixels"))?;
+        if let Some([r, g, b, a]) = style.background {
+            pixmap.fill(Color::from_rgba8(r, g, b, a));
+        }
+
+        let (mut min, mut max): (Point2<f64>, Point2<f64>) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return Ok(pixmap),
+        };
+        min.iter_mut().for_each(|value| *value -= style.padding);
+        max.iter_mut().for_each(|value| *value += style.padding);
+
+        let mut scale: f64 = (f64::from(width) / (max.x - min.x)).min(f64::from(height) / (max.y - min.y));
+        // A single point without padding has no size to fit.
+        if !scale.is_finite() {
+            scale = 1.0;
+        }
+        let offset_x: f64 = (f64::from(width) - (max.x - min.x) * scale) / 2.0;
+        let offset_y: f64 = (f64::from(height) - (max.y - min.y) * scale) / 2.0;
+
+        let mut builder: PathBuilder = PathBuilder::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let x: f32 = ((node.position.x - min.x) * scale + offset_x) as f32;
+            let y: f32 = ((node.position.y - min.y) * scale + offset_y) as f32;
+            match i {
+                0 => builder.move_to(x, y),
+                _ => builder.line_to(x, y),
+            }
+        }
+        if self.topology == Topology::Closed {
+            builder.close();
+        }
+
+        // Paths of a single node have nothing to stroke.
+        if let Some(path) = builder.finish() {
+            let mut paint: Paint = Paint::default();
+            let [r, g, b, a] = style.stroke;
+            paint.set_color_rgba8(r, g, b, a);
+            paint.anti_alias = style.anti_alias;
+            let stroke: Stroke = Stroke {
+                width: style.stroke_width as f32,
+                line_cap: LineCap::Round,
+                line_join: LineJoin::Round,
+                ..Stroke::default()
+            };
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        }
+
+        Ok(pixmap)
+    }
+
+    /// Draws the current path like [`DifferentialGrowth::render_pixmap()`] and encodes it as PNG.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick_n(50);
+    ///
+    /// let style = differential_growth::RasterStyle {
+    ///     stroke_width: 2.0,
+    ///     ..Default::default()
+    /// };
+    /// let png: Vec<u8> = differential_growth.encode_png(800, 600, &style).unwrap();
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// assert!(differential_growth.encode_png(0, 600, &style).is_err());
+    /// ```
+    ///
+    pub fn encode_png(&self, width: u32, height: u32, style: &RasterStyle) -> io::Result<Vec<u8>> {
+        self.render_pixmap(width, height, style)?
+            .encode_png()
+            .map_err(io::Error::other)
+    }
+
+    /// Draws the current path like [`DifferentialGrowth::render_pixmap()`] and saves it as a PNG file,
+    /// to preview headless runs without a rendering stack.
+    pub fn render_png(&self, path: impl AsRef<Path>, width: u32, height: u32, style: &RasterStyle) -> io::Result<()> {
+        self.render_pixmap(width, height, style)?
+            .save_png(path)
+            .map_err(io::Error::other)
+    }
+}
@@ -0,0 +1,269 @@
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, node::Node, topology::Topology};
+
+/// The positions of the path every few ticks, recorded by [`DifferentialGrowth::start_recording()`],
+/// to scrub back through the growth or render it again without running the simulation.
+///
+/// Positions are stored as `f32`, at half the size of the simulation's own positions,
+/// which is plenty for drawing them.
+///
+/// # Examples
+///
+/// ```rust
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+///
+/// differential_growth.start_recording(5);
+/// differential_growth.tick_n(20);
+/// let recording = differential_growth.stop_recording().unwrap();
+///
+/// // The starting points and the state after 5, 10, 15 and 20 ticks.
+/// assert_eq!(recording.len(), 5);
+/// assert_eq!(recording.iteration(2), Some(10));
+/// assert_eq!(recording.replay(0).unwrap().len(), 10);
+///
+/// let last = recording.replay(4).unwrap();
+/// for (replayed, point) in last.iter().zip(differential_growth.get_points()) {
+///     assert!((replayed - point).norm() < 1e-4);
+/// }
+/// assert_eq!(recording.replay(5), None);
+///
+/// // The starting points are still there after 20 ticks, under the same ids.
+/// let first_ids = recording.replay_ids(0).unwrap();
+/// assert!(first_ids.iter().all(|id| recording.replay_ids(4).unwrap().contains(id)));
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    interval: usize,
+    topology: Topology,
+    iterations: Vec<usize>,
+    // Where the positions of every frame start in `positions`.
+    starts: Vec<usize>,
+    positions: Vec<[f32; 2]>,
+    // The ids of the nodes at `positions`.
+    ids: Vec<usize>,
+    // The magnitude of the force on the nodes at `positions`, if forces are recorded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    records_forces: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    forces: Vec<f32>,
+}
+
+impl Recording {
+    pub(crate) fn new(interval: usize, topology: Topology) -> Recording {
+        Recording {
+            interval: interval.max(1),
+            topology,
+            ..Recording::default()
+        }
+    }
+
+    // `forces` are the ids and force magnitudes of the nodes during the last tick,
+    // nodes that weren't there yet get 0.
+    pub(crate) fn record(&mut self, iteration: usize, nodes: &[Node], forces: &[(usize, f32)]) {
+        if self.records_forces {
+            let magnitudes: HashMap<usize, f32> = forces.iter().copied().collect();
+            self.forces
+                .extend(nodes.iter().map(|node| magnitudes.get(&node.id).copied().unwrap_or(0.0)));
+        }
+
+        self.iterations.push(iteration);
+        self.starts.push(self.positions.len());
+        self.positions
+            .extend(nodes.iter().map(|node| [node.position.x as f32, node.position.y as f32]));
+        self.ids.extend(nodes.iter().map(|node| node.id));
+    }
+
+    pub(crate) fn is_due(&self, iteration: usize) -> bool {
+        match self.iterations.last() {
+            Some(last) => iteration >= last + self.interval,
+            None => true,
+        }
+    }
+
+    /// Returns the amount of ticks between frames.
+    #[must_use]
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    /// Returns the topology of the recorded path.
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Returns the amount of recorded frames.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iterations.len()
+    }
+
+    /// Returns whether no frame was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iterations.is_empty()
+    }
+
+    /// Returns the iteration of the simulation at frame `frame_index`, if it was recorded.
+    #[must_use]
+    pub fn iteration(&self, frame_index: usize) -> Option<usize> {
+        self.iterations.get(frame_index).copied()
+    }
+
+    /// Returns the positions of the path at frame `frame_index`, like
+    /// [`DifferentialGrowth::get_points()`] returned them then, if it was recorded.
+    #[must_use]
+    pub fn replay(&self, frame_index: usize) -> Option<Vec<Point2<f64>>> {
+        let start: usize = *self.starts.get(frame_index)?;
+        let end: usize = self.starts.get(frame_index + 1).copied().unwrap_or(self.positions.len());
+
+        Some(
+            self.positions[start..end]
+                .iter()
+                .map(|[x, y]| Point2::new(f64::from(*x), f64::from(*y)))
+                .collect(),
+        )
+    }
+
+    /// Returns the ids of the nodes at frame `frame_index`, in the order of
+    /// [`Recording::replay()`], to follow nodes from frame to frame.
+    #[must_use]
+    pub fn replay_ids(&self, frame_index: usize) -> Option<&[usize]> {
+        let start: usize = *self.starts.get(frame_index)?;
+        let end: usize = self.starts.get(frame_index + 1).copied().unwrap_or(self.ids.len());
+
+        Some(&self.ids[start..end])
+    }
+
+    /// Returns whether the magnitudes of the forces on the nodes are recorded,
+    /// see [`DifferentialGrowth::start_recording_with_forces()`].
+    #[must_use]
+    pub fn records_forces(&self) -> bool {
+        self.records_forces
+    }
+
+    /// Returns the magnitude of the total force on every node during the tick before
+    /// frame `frame_index`, in the order of [`Recording::replay()`], if it was recorded
+    /// with forces. Nodes that didn't exist yet during that tick, like all nodes of the
+    /// first frame, have a force of 0.
+    #[must_use]
+    pub fn replay_forces(&self, frame_index: usize) -> Option<&[f32]> {
+        if !self.records_forces {
+            return None;
+        }
+        let start: usize = *self.starts.get(frame_index)?;
+        let end: usize = self.starts.get(frame_index + 1).copied().unwrap_or(self.forces.len());
+
+        Some(&self.forces[start..end])
+    }
+
+    /// Writes every frame as CSV, one point per line as `frame,iteration,id,x,y` after a header line,
+    /// followed by a `force` column when forces are recorded.
+    #[cfg(feature = "std")]
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        match self.records_forces {
+            true => writeln!(writer, "frame,iteration,id,x,y,force")?,
+            false => writeln!(writer, "frame,iteration,id,x,y")?,
+        }
+        for (frame_index, iteration) in self.iterations.iter().enumerate() {
+            let start: usize = self.starts[frame_index];
+            let end: usize = self.starts.get(frame_index + 1).copied().unwrap_or(self.positions.len());
+            for (k, ([x, y], id)) in self.positions[start..end].iter().zip(&self.ids[start..end]).enumerate() {
+                write!(writer, "{},{},{},{},{}", frame_index, iteration, id, x, y)?;
+                match self.forces.get(start + k) {
+                    Some(force) if self.records_forces => writeln!(writer, ",{}", force)?,
+                    _ => writeln!(writer)?,
+                }
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+impl DifferentialGrowth {
+    /// Starts recording the positions of the path every `interval` ticks into a
+    /// [`Recording`], beginning with the current positions. Replaces the recording
+    /// in progress.
+    pub fn start_recording(&mut self, interval: usize) {
+        let mut recording: Recording = Recording::new(interval, self.topology);
+        recording.record(self.iteration(), &self.nodes, &[]);
+        self.recording = Some(recording);
+    }
+
+    /// Starts recording like [`DifferentialGrowth::start_recording()`], together with the
+    /// magnitude of the total force on every node during the tick before every frame,
+    /// for heat maps of where the path was under stress. See [`Recording::replay_forces()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// differential_growth.start_recording_with_forces(5);
+    /// differential_growth.tick_n(20);
+    /// let recording = differential_growth.stop_recording().unwrap();
+    ///
+    /// assert!(recording.records_forces());
+    /// // No forces acted on the starting points yet.
+    /// assert!(recording.replay_forces(0).unwrap().iter().all(|&force| force == 0.0));
+    ///
+    /// let forces = recording.replay_forces(4).unwrap();
+    /// assert_eq!(forces.len(), recording.replay(4).unwrap().len());
+    /// assert!(forces.iter().any(|&force| force > 0.0));
+    /// ```
+    ///
+    pub fn start_recording_with_forces(&mut self, interval: usize) {
+        let mut recording: Recording = Recording::new(interval, self.topology);
+        recording.records_forces = true;
+        recording.record(self.iteration(), &self.nodes, &[]);
+        self.recording = Some(recording);
+    }
+
+    /// Returns the recording in progress, if any.
+    #[must_use]
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref()
+    }
+
+    /// Stops recording and returns the recording, if one was in progress.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take()
+    }
+
+    // Keeps the force magnitudes of the nodes when the recording will record them at the end
+    // of this tick. The forces must have been accumulated, evaluating them again for every stage
+    // of an integrator keeps the last one.
+    pub(crate) fn keep_force_magnitudes_if_due(&mut self) {
+        let due: bool = self
+            .recording
+            .as_ref()
+            .is_some_and(|recording| recording.records_forces && recording.is_due(self.iteration() + 1));
+        if due {
+            self.force_magnitudes.clear();
+            self.force_magnitudes.extend(
+                self.nodes
+                    .iter()
+                    .map(|node| (node.id, (node.acceleration * node.mass).norm() as f32)),
+            );
+        }
+    }
+
+    pub(crate) fn record_if_due(&mut self) {
+        let iteration: usize = self.iteration();
+        if let Some(recording) = self.recording.as_mut() {
+            if recording.is_due(iteration) {
+                recording.record(iteration, &self.nodes, &self.force_magnitudes);
+            }
+        }
+    }
+}
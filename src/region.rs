@@ -0,0 +1,175 @@
+use nalgebra::{distance, Point2};
+
+use crate::prelude::*;
+use crate::{access::NodesError, differential_growth::DifferentialGrowth, geometry};
+
+/// An area of the plane, to erase the nodes within with [`DifferentialGrowth::remove_nodes_in()`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Region {
+    /// The disk around `center`, including its edge.
+    Circle { center: Point2<f64>, radius: f64 },
+    /// The axis aligned rectangle between the corners `min` and `max`, including its edges.
+    Rect { min: Point2<f64>, max: Point2<f64> },
+    /// The inside of the closed polygon through the points, using the even-odd rule.
+    Polygon(Vec<Point2<f64>>),
+}
+
+impl Region {
+    /// Returns whether `point` lies within the region.
+    pub fn contains(&self, point: &Point2<f64>) -> bool {
+        match self {
+            Region::Circle { center, radius } => distance(center, point) <= *radius,
+            Region::Rect { min, max } => (0..2).all(|axis| min[axis] <= point[axis] && point[axis] <= max[axis]),
+            Region::Polygon(points) => points.len() >= 3 && geometry::polygon_contains(points, point),
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Removes the nodes within `region`, like an eraser, and returns how many were removed.
+    ///
+    /// The nodes on either side of an erased stretch are joined by a new edge, so a closed
+    /// path stays closed and an open path stays one line, cut short when its ends are erased.
+    /// The new edges are split as the path grows. Returns an error and leaves the path as it
+    /// was when too few nodes would be left to form a path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{NodesError, Region};
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 20);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// // Erase the right side of the circle.
+    /// let region = Region::Rect { min: Point2::new(5.0, -20.0), max: Point2::new(20.0, 20.0) };
+    /// assert_eq!(differential_growth.remove_nodes_in(&region), Ok(7));
+    /// assert_eq!(differential_growth.nodes().len(), 13);
+    /// assert!(differential_growth.nodes().iter().all(|node| node.position.x < 5.0));
+    /// differential_growth.tick_n(20);
+    ///
+    /// // Erasing everything would leave no path.
+    /// let everything = Region::Circle { center: Point2::new(0.0, 0.0), radius: 1000.0 };
+    /// assert_eq!(differential_growth.remove_nodes_in(&everything), Err(NodesError::TooFewNodes { len: 0, min: 3 }));
+    /// ```
+    ///
+    pub fn remove_nodes_in(&mut self, region: &Region) -> Result<usize, NodesError> {
+        let keep: Vec<bool> = self.nodes.iter().map(|node| !region.contains(&node.position)).collect();
+        let len: usize = keep.iter().filter(|&&keep| keep).count();
+        let min: usize = self.topology.min_nodes();
+        if len < min {
+            return Err(NodesError::TooFewNodes { len, min });
+        }
+
+        self.remove_nodes(&keep);
+        Ok(keep.len() - len)
+    }
+
+    /// Only splits edges with their middle within one of `regions`, so the path fills a stencil
+    /// while edges outside stop getting more nodes. The forces still act on all nodes, so the
+    /// path outside still relaxes and gets pushed around by the growth inside.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Region;
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// // Only the top half grows.
+    /// let top = Region::Rect { min: Point2::new(-100.0, 0.0), max: Point2::new(100.0, 100.0) };
+    /// differential_growth.set_growth_mask(vec![top]);
+    /// let mut stencil = differential_growth.clone();
+    /// differential_growth.tick_n(200);
+    ///
+    /// let below: usize = differential_growth.nodes().iter().filter(|node| node.position.y < 0.0).count();
+    /// assert!(differential_growth.nodes().len() - below > 2 * below);
+    ///
+    /// // A mask without regions stops the growth altogether.
+    /// stencil.set_growth_mask(Vec::new());
+    /// stencil.tick_n(200);
+    /// assert_eq!(stencil.nodes().len(), 10);
+    /// ```
+    ///
+    pub fn set_growth_mask(&mut self, regions: Vec<Region>) {
+        self.growth_mask = Some(regions);
+    }
+
+    /// Returns the regions of the growth mask, if any.
+    #[must_use]
+    pub fn growth_mask(&self) -> Option<&[Region]> {
+        self.growth_mask.as_deref()
+    }
+
+    /// Removes the growth mask, edges split everywhere again.
+    pub fn clear_growth_mask(&mut self) {
+        self.growth_mask = None;
+    }
+
+    // Whether an edge with its middle at `middle` may split.
+    pub(crate) fn grows_at(&self, middle: &Point2<f64>) -> bool {
+        match &self.growth_mask {
+            Some(regions) => regions.iter().any(|region| region.contains(middle)),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point2, Vector2};
+
+    use super::Region;
+    use crate::prelude::*;
+    use crate::{differential_growth::DifferentialGrowth, node_params::NodeParams};
+
+    #[test]
+    fn remove_nodes_in_keeps_node_values_aligned() {
+        let starting_points: Vec<Point2<f64>> = (0..10)
+            .map(|i| Point2::new(i as f64 * 4.0, if i % 2 == 0 { 0.0 } else { 4.0 }))
+            .collect();
+        let mut differential_growth = DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+        differential_growth.set_node_data((0..10).collect::<Vec<usize>>(), |a: &usize, _: &usize| *a);
+        for i in 0..10 {
+            differential_growth.freeze_node(i, i % 3 == 0);
+            if i % 2 == 0 {
+                let node_params: NodeParams = NodeParams {
+                    max_speed: Some(i as f64 + 2.0),
+                    ..NodeParams::default()
+                };
+                differential_growth.set_node_params(i, node_params);
+            }
+            if i % 4 == 1 {
+                differential_growth.apply_external_force(i, Vector2::new(i as f64, 0.0));
+            }
+        }
+
+        // Erases the nodes 2 to 5.
+        let region: Region = Region::Rect {
+            min: Point2::new(7.0, -1.0),
+            max: Point2::new(21.0, 5.0),
+        };
+        assert_eq!(differential_growth.remove_nodes_in(&region), Ok(4));
+
+        let originals: Vec<usize> = differential_growth.node_data::<usize>().unwrap().to_vec();
+        assert_eq!(originals, vec![0, 1, 6, 7, 8, 9]);
+        assert_eq!(differential_growth.frozen.len(), originals.len());
+        assert_eq!(differential_growth.external_forces.len(), originals.len());
+        for (index, &original) in originals.iter().enumerate() {
+            assert_eq!(differential_growth.is_node_frozen(index), original % 3 == 0);
+
+            let max_speed: Option<f64> = (original % 2 == 0).then_some(original as f64 + 2.0);
+            assert_eq!(differential_growth.node_params(index).max_speed, max_speed);
+            assert_eq!(differential_growth.nodes()[index].max_speed, max_speed.unwrap_or(differential_growth.max_speed));
+
+            let force: Vector2<f64> = match original % 4 {
+                1 => Vector2::new(original as f64, 0.0),
+                _ => Vector2::default(),
+            };
+            assert_eq!(differential_growth.external_forces[index], force);
+        }
+    }
+}
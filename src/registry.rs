@@ -0,0 +1,135 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::prelude::*;
+use crate::insertion::{CurvatureWeighted, InsertionStrategy, JitteredMidpoint, Midpoint};
+
+/// Named numeric parameters passed to a registered factory,
+/// for example as read from a config file.
+pub type Parameters = BTreeMap<String, f64>;
+
+type Factory<T> = Box<dyn Fn(&Parameters) -> Result<Box<T>, RegistryError> + Send + Sync>;
+
+/// Error returned when a registered piece can't be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// Nothing is registered under this name for this kind of piece.
+    UnknownName { kind: &'static str, name: String },
+    /// A required parameter is missing.
+    MissingParameter { name: String, parameter: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownName { kind, name } => write!(f, "no {} registered as \"{}\"", kind, name),
+            RegistryError::MissingParameter { name, parameter } => {
+                write!(f, "\"{}\" requires the parameter \"{}\"", name, parameter)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RegistryError {}
+
+/// Returns a parameter or the given default when it's missing.
+pub fn parameter_or(parameters: &Parameters, parameter: &str, default: f64) -> f64 {
+    parameters.get(parameter).copied().unwrap_or(default)
+}
+
+/// Returns a parameter or an error when it's missing.
+pub fn required_parameter(parameters: &Parameters, name: &str, parameter: &str) -> Result<f64, RegistryError> {
+    parameters
+        .get(parameter)
+        .copied()
+        .ok_or_else(|| RegistryError::MissingParameter {
+            name: name.to_string(),
+            parameter: parameter.to_string(),
+        })
+}
+
+/// Registry of user defined and built-in pieces of the simulation,
+/// registered by name so they can be referenced from config files and scripts.
+///
+/// [`Registry::default()`] contains the built-in pieces:
+/// * Insertion strategies: `midpoint`, `jittered_midpoint` (`jitter`, `seed`)
+///   and `curvature_weighted` (`weight`).
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Midpoint, Parameters, Registry};
+///
+/// let mut registry = Registry::default();
+/// registry.register_insertion_strategy("my_midpoint", |_parameters: &Parameters| Ok(Box::new(Midpoint)));
+///
+/// let mut parameters = Parameters::new();
+/// parameters.insert("weight".to_string(), 0.5);
+/// let strategy = registry.insertion_strategy("curvature_weighted", &parameters).unwrap();
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_insertion_strategy(strategy);
+///
+/// assert!(registry.insertion_strategy("unknown", &Parameters::new()).is_err());
+/// ```
+///
+pub struct Registry {
+    insertion_strategies: HashMap<String, Factory<dyn InsertionStrategy>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry: Registry = Registry::empty();
+
+        registry.register_insertion_strategy("midpoint", |_| Ok(Box::new(Midpoint)));
+        registry.register_insertion_strategy("jittered_midpoint", |parameters| {
+            Ok(Box::new(JitteredMidpoint::new(
+                required_parameter(parameters, "jittered_midpoint", "jitter")?,
+                parameter_or(parameters, "seed", 0.0) as u64,
+            )))
+        });
+        registry.register_insertion_strategy("curvature_weighted", |parameters| {
+            Ok(Box::new(CurvatureWeighted::new(parameter_or(parameters, "weight", 1.0))))
+        });
+
+        registry
+    }
+}
+
+impl Registry {
+    /// Returns a registry without any registered pieces, not even the built-in ones.
+    pub fn empty() -> Registry {
+        Registry {
+            insertion_strategies: HashMap::new(),
+        }
+    }
+
+    /// Registers an insertion strategy factory under `name`,
+    /// replacing any factory previously registered under that name.
+    pub fn register_insertion_strategy<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&Parameters) -> Result<Box<dyn InsertionStrategy>, RegistryError> + Send + Sync + 'static,
+    {
+        self.insertion_strategies.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Creates the insertion strategy registered under `name`.
+    pub fn insertion_strategy(&self, name: &str, parameters: &Parameters) -> Result<Box<dyn InsertionStrategy>, RegistryError> {
+        match self.insertion_strategies.get(name) {
+            Some(factory) => factory(parameters),
+            None => Err(RegistryError::UnknownName {
+                kind: "insertion strategy",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the names of all registered insertion strategies, sorted.
+    pub fn insertion_strategy_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.insertion_strategies.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
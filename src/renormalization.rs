@@ -0,0 +1,111 @@
+use core::ops::{AddAssign, SubAssign};
+
+use nalgebra::Vector2;
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// A periodic cleanup pass countering floating point drift in runs lasting days or weeks.
+///
+/// Recentering moves the nodes so their average position is the origin, where floating
+/// point numbers are most precise, and keeps track of the total translation in
+/// [`DifferentialGrowth::origin()`]. Everything placed in world coordinates, like
+/// boundaries, overlays, guide paths, trail maps and leaders, stays where it is, so
+/// only recenter paths that grow freely.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::Renormalization;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(1000.0, 1000.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_renormalization(Renormalization { interval: 50, ..Default::default() });
+///
+/// differential_growth.tick_n(50);
+/// let origin = differential_growth.origin();
+/// assert!((origin.x - 1000.0).abs() < 1.0 && (origin.y - 1000.0).abs() < 1.0);
+///
+/// // The points in world coordinates are the points plus the origin.
+/// let world: Vec<_> = differential_growth.get_points().iter().map(|point| point + origin).collect();
+/// assert!(world.iter().all(|point| (point.x - 1000.0).abs() < 50.0));
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Renormalization {
+    /// The amount of ticks between two passes. Defaults to `1000`.
+    pub interval: usize,
+    /// Whether the nodes are moved so their average position is the origin. Defaults to `true`.
+    pub recenter: bool,
+    /// Velocities slower than this are set to zero, so nodes at rest stop creeping.
+    /// Defaults to `1e-9`.
+    pub velocity_epsilon: f64,
+}
+
+impl Default for Renormalization {
+    fn default() -> Self {
+        Renormalization {
+            interval: 1000,
+            recenter: true,
+            velocity_epsilon: 1e-9,
+        }
+    }
+}
+
+impl DifferentialGrowth {
+    /// Runs a renormalization pass every `interval` ticks, see [`Renormalization`].
+    pub fn set_renormalization(&mut self, renormalization: Renormalization) {
+        self.renormalization = Some(renormalization);
+    }
+
+    /// Returns the renormalization, if any.
+    #[must_use]
+    pub fn renormalization(&self) -> Option<&Renormalization> {
+        self.renormalization.as_ref()
+    }
+
+    /// Stops the periodic renormalization passes.
+    pub fn clear_renormalization(&mut self) {
+        self.renormalization = None;
+    }
+
+    /// Returns the total translation recentering removed from the nodes.
+    /// Adding it to the positions of the nodes gives their position in world coordinates.
+    #[must_use]
+    pub fn origin(&self) -> Vector2<f64> {
+        self.origin
+    }
+
+    /// Runs a renormalization pass now, with the settings of `renormalization`.
+    /// The periodic passes call this.
+    pub fn renormalize(&mut self, renormalization: &Renormalization) {
+        for node in &mut self.nodes {
+            if node.velocity.norm() < renormalization.velocity_epsilon {
+                node.velocity = Vector2::default();
+            }
+        }
+
+        if renormalization.recenter && !self.nodes.is_empty() {
+            let mut sum: Vector2<f64> = Vector2::default();
+            for node in &self.nodes {
+                sum.add_assign(node.position.coords);
+            }
+            let center: Vector2<f64> = sum / self.nodes.len() as f64;
+
+            for node in &mut self.nodes {
+                node.position.sub_assign(center);
+            }
+            self.origin.add_assign(center);
+            // Every node moved, the spatial index must be rebuilt before it is used again.
+            self.neighbor_search.invalidate();
+        }
+    }
+
+    pub(crate) fn renormalize_if_due(&mut self) {
+        if let Some(renormalization) = self.renormalization {
+            if renormalization.interval > 0 && (self.iteration() + 1) % renormalization.interval == 0 {
+                self.renormalize(&renormalization);
+            }
+        }
+    }
+}
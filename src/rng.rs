@@ -0,0 +1,38 @@
+/// Small seeded pseudo random number generator (SplitMix64).
+///
+/// Implemented here instead of depending on `rand` so a seed produces the
+/// exact same sequence on every platform and for every version of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        // https://prng.di.unimi.it/splitmix64.c
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z: u64 = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        // Use the upper 53 bits, the precision of an f64 mantissa.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a value in `[min, max)`, or `min` when the range is empty.
+    pub(crate) fn range(&mut self, min: f64, max: f64) -> f64 {
+        if max <= min {
+            return min;
+        }
+        min + (max - min) * self.next_f64()
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    error::Error,
+    fmt,
+    mem::size_of,
+    ops::AddAssign,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    differential_growth::DifferentialGrowth,
+    multi_path::MultiPathGrowth,
+    node::Node,
+    params::ParamSet,
+    scene::{Scene, SceneOperation},
+};
+
+/// A rough estimate of the memory a node takes: the node itself, its snapshot,
+/// its copy in the spatial index and the per-node buffers of a tick.
+const BYTES_PER_NODE: usize = 4 * size_of::<Node>();
+
+/// Hard limits for running configs from untrusted sources, like the scenes users
+/// submit to a web playground, so a malicious or silly config can't take down the service.
+///
+/// Runs stop early when they hit a limit and keep what they grew so far, the
+/// [`SandboxReport`] tells which limit stopped them. Configs that can't be run
+/// within the limits at all are rejected with a [`SandboxError`] before anything
+/// is allocated for them.
+///
+/// The time budget is checked between ticks, a single tick isn't interrupted.
+/// The node limit keeps ticks short enough for that to work.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Sandbox, SandboxLimit, Scene, ScenePath, SeedShape};
+/// use nalgebra::Point2;
+/// use std::time::Duration;
+///
+/// let sandbox = Sandbox {
+///     max_nodes: 200,
+///     max_ticks: 1000,
+///     time_budget: Duration::from_secs(5),
+///     ..Default::default()
+/// };
+///
+/// // Asks for far more ticks than it's allowed.
+/// let scene = Scene {
+///     paths: vec![ScenePath::new(SeedShape::Circle { center: Point2::new(0.0, 0.0), radius: 10.0, points: 10 })],
+///     ticks: 1_000_000,
+///     ..Default::default()
+/// };
+///
+/// let (multi_path_growth, report) = sandbox.run_scene(&scene).unwrap();
+/// assert_eq!(report.truncated_by, Some(SandboxLimit::Nodes));
+/// assert!(report.nodes <= 200);
+/// assert_eq!(multi_path_growth.paths()[0].nodes().len(), report.nodes);
+///
+/// // Rejected before building a billion starting points.
+/// let scene = Scene {
+///     paths: vec![ScenePath::new(SeedShape::Circle { center: Point2::new(0.0, 0.0), radius: 10.0, points: 1_000_000_000 })],
+///     ..Default::default()
+/// };
+/// assert!(sandbox.run_scene(&scene).is_err());
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sandbox {
+    /// The maximum amount of nodes of all paths together. Defaults to `100_000`.
+    pub max_nodes: usize,
+    /// The maximum amount of ticks. Defaults to `10_000`.
+    pub max_ticks: usize,
+    /// The maximum estimated memory of the nodes, in bytes. Defaults to 256 MiB.
+    pub max_memory: usize,
+    /// The maximum time a run takes. Defaults to 10 seconds.
+    pub time_budget: Duration,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox {
+            max_nodes: 100_000,
+            max_ticks: 10_000,
+            max_memory: 256 * 1024 * 1024,
+            time_budget: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The limit that stopped a sandboxed run early.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SandboxLimit {
+    /// The paths reached the maximum amount of nodes.
+    Nodes,
+    /// The config asked for more than the maximum amount of ticks.
+    Ticks,
+    /// The paths reached the maximum estimated memory.
+    Memory,
+    /// The run took longer than the time budget.
+    Time,
+    /// A node stopped being a finite number, the parameters are unstable.
+    NotFinite,
+}
+
+/// What happened during a sandboxed run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SandboxReport {
+    /// The amount of ticks that ran.
+    pub ticks: usize,
+    /// The amount of nodes of all paths together at the end.
+    pub nodes: usize,
+    /// The estimated memory of the nodes at the end, in bytes.
+    pub estimated_memory: usize,
+    /// How long the run took.
+    pub elapsed: Duration,
+    /// The limit that stopped the run early, `None` when it ran all its ticks.
+    pub truncated_by: Option<SandboxLimit>,
+}
+
+/// Error returned when a config can't be run within the limits of a [`Sandbox`] at all.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SandboxError {
+    /// The starting points alone are more than the nodes the sandbox allows.
+    TooManyNodes { nodes: usize, max_nodes: usize },
+    /// A parameter isn't a positive finite number.
+    InvalidParameter { name: &'static str, value: f64 },
+}
+
+impl fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxError::TooManyNodes { nodes, max_nodes } => {
+                write!(f, "{} starting points exceed the limit of {} nodes", nodes, max_nodes)
+            }
+            SandboxError::InvalidParameter { name, value } => {
+                write!(f, "{} is {}, it must be a positive finite number", name, value)
+            }
+        }
+    }
+}
+
+impl Error for SandboxError {}
+
+impl Sandbox {
+    /// Builds and runs `scene` within the limits, see [`Scene::run()`].
+    /// Returns the simulation as far as it got together with a report,
+    /// or an error when the scene can't be run within the limits at all.
+    pub fn run_scene(&self, scene: &Scene) -> Result<(MultiPathGrowth, SandboxReport), SandboxError> {
+        let start: Instant = Instant::now();
+
+        let params = std::iter::once(&scene.params)
+            .chain(scene.paths.iter().filter_map(|path| path.params.as_ref()))
+            .chain(scene.schedule.iter().map(|change| &change.params))
+            .chain(scene.chapters.iter().filter_map(|chapter| match &chapter.operation {
+                SceneOperation::AddPath(path) => path.params.as_ref(),
+                _ => None,
+            }));
+        for params in params {
+            validate(params)?;
+        }
+
+        // Paths added by chapters are reserved from the start, so they always fit.
+        let added_points = |after: usize| -> usize {
+            scene
+                .chapters
+                .iter()
+                .filter(|chapter| chapter.tick >= after)
+                .map(|chapter| match &chapter.operation {
+                    SceneOperation::AddPath(path) => path.shape.point_count(),
+                    _ => 0,
+                })
+                .fold(0, usize::saturating_add)
+        };
+        let (max_nodes, node_limit): (usize, SandboxLimit) = self.node_limit();
+        let points: usize = scene
+            .paths
+            .iter()
+            .map(|path| path.shape.point_count())
+            .fold(added_points(0), usize::saturating_add);
+        if points > max_nodes {
+            return Err(SandboxError::TooManyNodes { nodes: points, max_nodes });
+        }
+
+        let mut multi_path_growth: MultiPathGrowth = scene.build();
+        let mut truncated_by: Option<SandboxLimit> = (scene.ticks > self.max_ticks).then_some(SandboxLimit::Ticks);
+        let mut ticks: usize = 0;
+        while ticks < scene.ticks.min(self.max_ticks) {
+            if start.elapsed() > self.time_budget {
+                truncated_by = Some(SandboxLimit::Time);
+                break;
+            }
+
+            // Every path may grow into an equal share of the nodes left, so together they can't exceed them.
+            let paths: &mut [DifferentialGrowth] = multi_path_growth.paths_mut();
+            let nodes: usize = paths.iter().map(|path| path.nodes.len()).sum();
+            let remaining: usize = max_nodes.saturating_sub(nodes.saturating_add(added_points(ticks)));
+            let share: usize = remaining / paths.len().max(1);
+            if share == 0 {
+                truncated_by = Some(node_limit);
+                break;
+            }
+            for path in paths.iter_mut() {
+                path.max_nodes = Some(path.nodes.len() + share);
+            }
+
+            multi_path_growth.tick();
+            ticks.add_assign(1);
+
+            if multi_path_growth.paths().iter().any(|path| !is_finite(path)) {
+                truncated_by = Some(SandboxLimit::NotFinite);
+                break;
+            }
+        }
+
+        // The paths of a scene have no maximum of their own.
+        for path in multi_path_growth.paths_mut() {
+            path.max_nodes = None;
+        }
+
+        let nodes: usize = multi_path_growth.paths().iter().map(|path| path.nodes.len()).sum();
+        let report: SandboxReport = self.report(ticks, nodes, start, truncated_by);
+        Ok((multi_path_growth, report))
+    }
+
+    /// Runs `differential_growth` for `ticks` ticks within the limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{Sandbox, SandboxLimit};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let sandbox = Sandbox { max_ticks: 50, ..Default::default() };
+    /// let report = sandbox.run(&mut differential_growth, 100);
+    /// assert_eq!(report.ticks, 50);
+    /// assert_eq!(report.truncated_by, Some(SandboxLimit::Ticks));
+    /// ```
+    ///
+    pub fn run(&self, differential_growth: &mut DifferentialGrowth, ticks: usize) -> SandboxReport {
+        let start: Instant = Instant::now();
+        let (max_nodes, node_limit): (usize, SandboxLimit) = self.node_limit();
+        let own_max_nodes: Option<usize> = differential_growth.max_nodes;
+        differential_growth.max_nodes = Some(own_max_nodes.map_or(max_nodes, |own| own.min(max_nodes)));
+
+        let mut truncated_by: Option<SandboxLimit> = (ticks > self.max_ticks).then_some(SandboxLimit::Ticks);
+        let mut ran: usize = 0;
+        while ran < ticks.min(self.max_ticks) {
+            if start.elapsed() > self.time_budget {
+                truncated_by = Some(SandboxLimit::Time);
+                break;
+            }
+            if differential_growth.nodes.len() >= max_nodes {
+                truncated_by = Some(node_limit);
+                break;
+            }
+
+            differential_growth.tick();
+            ran.add_assign(1);
+
+            if !is_finite(differential_growth) {
+                truncated_by = Some(SandboxLimit::NotFinite);
+                break;
+            }
+        }
+
+        differential_growth.max_nodes = own_max_nodes;
+        self.report(ran, differential_growth.nodes.len(), start, truncated_by)
+    }
+
+    // The maximum amount of nodes and the limit that decides it.
+    fn node_limit(&self) -> (usize, SandboxLimit) {
+        let max_nodes_in_memory: usize = self.max_memory / BYTES_PER_NODE;
+        if max_nodes_in_memory < self.max_nodes {
+            (max_nodes_in_memory, SandboxLimit::Memory)
+        } else {
+            (self.max_nodes, SandboxLimit::Nodes)
+        }
+    }
+
+    fn report(&self, ticks: usize, nodes: usize, start: Instant, truncated_by: Option<SandboxLimit>) -> SandboxReport {
+        SandboxReport {
+            ticks,
+            nodes,
+            estimated_memory: nodes.saturating_mul(BYTES_PER_NODE),
+            elapsed: start.elapsed(),
+            truncated_by,
+        }
+    }
+}
+
+fn validate(params: &ParamSet) -> Result<(), SandboxError> {
+    let values: [(&'static str, f64); 5] = [
+        ("max_force", params.max_force),
+        ("max_speed", params.max_speed),
+        ("desired_separation", params.desired_separation),
+        ("separation_cohesion_ratio", params.separation_cohesion_ratio),
+        ("max_edge_length", params.max_edge_length),
+    ];
+    match values.into_iter().find(|(_, value)| !(value.is_finite() && *value > 0.0)) {
+        Some((name, value)) => Err(SandboxError::InvalidParameter { name, value }),
+        None => Ok(()),
+    }
+}
+
+fn is_finite(differential_growth: &DifferentialGrowth) -> bool {
+    differential_growth
+        .nodes
+        .iter()
+        .all(|node| node.position.x.is_finite() && node.position.y.is_finite())
+}
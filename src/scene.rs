@@ -0,0 +1,288 @@
+use core::{f64::consts::PI, ops::AddAssign};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    constraints::{Attractor, Obstacle},
+    differential_growth::DifferentialGrowth,
+    multi_path::{Interaction, MultiPathGrowth, Operation, ParamChange},
+    overlay::ParamOverlay,
+    params::ParamSet,
+    topology::Topology,
+};
+
+/// The starting points of a path in a [`Scene`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeedShape {
+    /// `points` evenly spaced points on a circle.
+    Circle {
+        center: Point2<f64>,
+        radius: f64,
+        points: usize,
+    },
+    /// The given points.
+    Points(Vec<Point2<f64>>),
+}
+
+impl SeedShape {
+    /// Returns the starting points of the shape.
+    pub fn points(&self) -> Vec<Point2<f64>> {
+        match self {
+            SeedShape::Circle { center, radius, points } => (0..*points)
+                .map(|i| {
+                    let theta: f64 = 2.0 * PI * i as f64 / *points as f64;
+                    Point2::new(center.x + radius * theta.cos(), center.y + radius * theta.sin())
+                })
+                .collect(),
+            SeedShape::Points(points) => points.clone(),
+        }
+    }
+
+    // The amount of starting points, without generating them.
+    #[cfg(feature = "std")]
+    pub(crate) fn point_count(&self) -> usize {
+        match self {
+            SeedShape::Circle { points, .. } => *points,
+            SeedShape::Points(points) => points.len(),
+        }
+    }
+}
+
+/// A path of a [`Scene`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenePath {
+    /// The starting points of the path.
+    pub shape: SeedShape,
+    /// Whether the path is closed or open. Defaults to closed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub topology: Topology,
+    /// The collision group of the path, see [`Interaction::RepelOtherGroups`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub group: u32,
+    /// Parameters for this path only, instead of the parameters of the scene.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: Option<ParamSet>,
+}
+
+impl ScenePath {
+    /// Returns a closed path in group `0` using the parameters of the scene.
+    pub fn new(shape: SeedShape) -> ScenePath {
+        ScenePath {
+            shape,
+            topology: Topology::Closed,
+            group: 0,
+            params: None,
+        }
+    }
+}
+
+/// A structural change of a [`Scene`], the serializable counterpart of [`Operation`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SceneOperation {
+    /// Adds a path, built like the paths of the scene.
+    AddPath(ScenePath),
+    /// Freezes the path at the given index, counting the paths of the scene first
+    /// and the added paths after them in the order they were added.
+    Freeze(usize),
+    /// Lets the frozen path at the given index grow again.
+    Unfreeze(usize),
+    /// Freezes every path added so far.
+    FreezeAll,
+    /// Opens or closes the path at the given index.
+    SetTopology { path: usize, topology: Topology },
+    /// Adds an attractor acting on every path.
+    AddAttractor(Attractor),
+    /// Adds an obstacle acting on every path.
+    AddObstacle(Obstacle),
+    /// Adds a zone to every path.
+    AddZone(ParamOverlay),
+    /// Applies the same force to every node of every path.
+    SetConstantForce(Vector2<f64>),
+}
+
+/// A [`SceneOperation`] at a specific tick, so a scene can evolve in chapters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chapter {
+    /// The tick at the start of which the operation runs.
+    pub tick: usize,
+    pub operation: SceneOperation,
+}
+
+/// A complete description of an artwork: the seed paths, how they interact,
+/// zones, attractors, obstacles and parameter changes over time.
+///
+/// With the `serde` feature scenes can be stored in any serde format, so a
+/// single file reproduces the whole piece. Everything but the paths is optional.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Attractor, Scene, ScenePath, SeedShape};
+/// use nalgebra::Point2;
+///
+/// let scene = Scene {
+///     seed: 7,
+///     paths: vec![
+///         ScenePath::new(SeedShape::Circle { center: Point2::new(-15.0, 0.0), radius: 10.0, points: 10 }),
+///         ScenePath::new(SeedShape::Circle { center: Point2::new(15.0, 0.0), radius: 10.0, points: 10 }),
+///     ],
+///     attractors: vec![Attractor::new(Point2::new(0.0, 0.0), 40.0, 0.1)],
+///     ticks: 20,
+///     ..Default::default()
+/// };
+///
+/// let first = scene.run();
+/// let second = scene.run();
+/// assert_eq!(first.iteration(), 20);
+/// assert_eq!(first.get_points(), second.get_points());
+/// ```
+///
+/// Loading a scene from JSON:
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let json: &str = r#"{
+///     "seed": 1,
+///     "paths": [{ "shape": { "Circle": { "center": [0.0, 0.0], "radius": 10.0, "points": 10 } } }],
+///     "schedule": [{ "tick": 5, "params": {
+///         "max_force": 1.5, "max_speed": 1.0, "desired_separation": 20.0,
+///         "separation_cohesion_ratio": 1.1, "max_edge_length": 5.0
+///     } }],
+///     "ticks": 10
+/// }"#;
+///
+/// let scene: differential_growth::Scene = serde_json::from_str(json).unwrap();
+/// let multi_path_growth = scene.run();
+/// assert_eq!(multi_path_growth.paths()[0].desired_separation, 20.0);
+/// # }
+/// ```
+///
+/// A scene in chapters, growing a second layer around the first one:
+///
+/// ```rust
+/// use differential_growth::{Chapter, Scene, SceneOperation, ScenePath, SeedShape};
+/// use nalgebra::Point2;
+///
+/// let scene = Scene {
+///     paths: vec![ScenePath::new(SeedShape::Circle { center: Point2::new(0.0, 0.0), radius: 10.0, points: 10 })],
+///     chapters: vec![
+///         Chapter { tick: 20, operation: SceneOperation::FreezeAll },
+///         Chapter {
+///             tick: 20,
+///             operation: SceneOperation::AddPath(ScenePath::new(SeedShape::Circle {
+///                 center: Point2::new(0.0, 0.0),
+///                 radius: 60.0,
+///                 points: 30,
+///             })),
+///         },
+///     ],
+///     ticks: 30,
+///     ..Default::default()
+/// };
+///
+/// let multi_path_growth = scene.run();
+/// assert_eq!(multi_path_growth.paths().len(), 2);
+/// assert!(multi_path_growth.is_frozen(0));
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Scene {
+    /// The seed of the random number generators. Every path gets its own seed derived from it.
+    pub seed: u64,
+    /// The parameters of every path that doesn't have its own.
+    pub params: ParamSet,
+    /// How the paths act on eachother.
+    pub interaction: Interaction,
+    /// The paths and their starting points.
+    pub paths: Vec<ScenePath>,
+    /// Regions with a different growth rate and force magnitude, added to every path.
+    pub zones: Vec<ParamOverlay>,
+    /// Points pulling nodes towards them.
+    pub attractors: Vec<Attractor>,
+    /// Shapes pushing nodes out.
+    pub obstacles: Vec<Obstacle>,
+    /// Parameter changes at specific ticks.
+    pub schedule: Vec<ParamChange>,
+    /// Structural changes at specific ticks.
+    pub chapters: Vec<Chapter>,
+    /// The amount of ticks [`Scene::run()`] runs the scene for.
+    pub ticks: usize,
+}
+
+impl Scene {
+    /// Returns the simulation described by the scene, before its first tick.
+    pub fn build(&self) -> MultiPathGrowth {
+        let mut multi_path_growth: MultiPathGrowth = MultiPathGrowth::new(self.interaction);
+        multi_path_growth.attractors = self.attractors.clone();
+        multi_path_growth.obstacles = self.obstacles.clone();
+
+        for (i, scene_path) in self.paths.iter().enumerate() {
+            multi_path_growth.add_path(self.build_path(scene_path, i), scene_path.group);
+        }
+
+        for change in &self.schedule {
+            multi_path_growth.schedule_params(change.tick, change.params);
+        }
+
+        // Added paths get their seed after the paths of the scene.
+        let mut added_paths: usize = self.paths.len();
+        for chapter in &self.chapters {
+            let operation: Operation = match &chapter.operation {
+                SceneOperation::AddPath(scene_path) => {
+                    let path: DifferentialGrowth = self.build_path(scene_path, added_paths);
+                    added_paths.add_assign(1);
+                    Operation::AddPath {
+                        path: Box::new(path),
+                        group: scene_path.group,
+                    }
+                }
+                SceneOperation::Freeze(path) => Operation::Freeze(*path),
+                SceneOperation::Unfreeze(path) => Operation::Unfreeze(*path),
+                SceneOperation::FreezeAll => Operation::FreezeAll,
+                SceneOperation::SetTopology { path, topology } => Operation::SetTopology {
+                    path: *path,
+                    topology: *topology,
+                },
+                SceneOperation::AddAttractor(attractor) => Operation::AddAttractor(*attractor),
+                SceneOperation::AddObstacle(obstacle) => Operation::AddObstacle(obstacle.clone()),
+                SceneOperation::AddZone(zone) => Operation::AddZone(*zone),
+                SceneOperation::SetConstantForce(force) => Operation::SetConstantForce(*force),
+            };
+            multi_path_growth.schedule(chapter.tick, operation);
+        }
+
+        multi_path_growth
+    }
+
+    fn build_path(&self, scene_path: &ScenePath, i: usize) -> DifferentialGrowth {
+        let mut path: DifferentialGrowth = DifferentialGrowth::builder()
+            .points(scene_path.shape.points())
+            .params(scene_path.params.unwrap_or(self.params))
+            .topology(scene_path.topology)
+            .seed(self.seed.wrapping_add(i as u64))
+            .build();
+        for zone in &self.zones {
+            path.add_overlay(*zone);
+        }
+
+        path
+    }
+
+    /// Returns the simulation described by the scene after running it for `ticks` ticks.
+    pub fn run(&self) -> MultiPathGrowth {
+        let mut multi_path_growth: MultiPathGrowth = self.build();
+        for _ in 0..self.ticks {
+            multi_path_growth.tick();
+        }
+
+        multi_path_growth
+    }
+}
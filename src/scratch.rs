@@ -0,0 +1,21 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+
+/// Buffers a tick fills from scratch, kept between ticks so their allocations are reused.
+///
+/// Their contents mean nothing outside the tick that filled them. Every buffer is taken
+/// out while it is in use, so the rest of the simulation can still be borrowed.
+#[derive(Debug, Default)]
+pub(crate) struct Scratch {
+    // The positions at the start of the tick, to find the ranges that changed.
+    pub(crate) before: Vec<Point2<f64>>,
+    // The positions before the nodes moved, when crossings are undone or displacements watched.
+    pub(crate) previous_positions: Vec<Point2<f64>>,
+    // The indices of the nodes that move this tick.
+    pub(crate) moving: Vec<usize>,
+    // Whether every node moves this tick.
+    pub(crate) active: Vec<bool>,
+    pub(crate) separation_forces: Vec<Vector2<f64>>,
+    pub(crate) cohesion_forces: Vec<Vector2<f64>>,
+}
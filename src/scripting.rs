@@ -0,0 +1,191 @@
+use std::{error::Error, fmt, fs, path::Path};
+
+use nalgebra::{Point2, Vector2};
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::params::ParamSet;
+
+/// Error returned when a script can't be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptError {
+    message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "script error: {}", self.message)
+    }
+}
+
+impl Error for ScriptError {}
+
+/// Custom rules written as a [rhai](https://rhai.rs) script, loaded at runtime.
+///
+/// A script can define any of the following functions, rules that aren't
+/// defined keep the default behavior:
+/// * `fn params(tick, params)` - Called before every tick with the tick count and a map of
+///   the current parameters (`max_force`, `max_speed`, `desired_separation`,
+///   `separation_cohesion_ratio`, `max_edge_length` and `growth_probability`).
+///   Returns a map with the parameters to change.
+/// * `fn grow(x, y, length)` - Called for every edge longer than the maximum edge length
+///   with the edge's midpoint and length. Returns whether the edge is split.
+/// * `fn force(x, y)` - Called for every node, returns an extra force `[fx, fy]`
+///   acting on a node at that position.
+///
+/// Errors raised while running a rule skip that rule for the current call and
+/// are available through [`ScriptRules::last_error()`].
+///
+/// # Examples
+///
+/// ```rust
+/// let script = r#"
+///     fn params(tick, params) {
+///         #{ desired_separation: 14.0 + tick.to_float() * 0.01 }
+///     }
+///
+///     fn grow(x, y, length) {
+///         y > -5.0
+///     }
+///
+///     fn force(x, y) {
+///         [0.0, -0.01]
+///     }
+/// "#;
+///
+/// let rules = differential_growth::ScriptRules::from_source(script).unwrap();
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_script(rules);
+/// differential_growth.tick();
+/// assert!(differential_growth.script().unwrap().last_error().is_none());
+/// ```
+///
+pub struct ScriptRules {
+    engine: Engine,
+    ast: AST,
+    has_params: bool,
+    has_grow: bool,
+    has_force: bool,
+    last_error: Option<String>,
+}
+
+impl Clone for ScriptRules {
+    /// Returns the same rules on an engine of their own.
+    fn clone(&self) -> Self {
+        ScriptRules {
+            engine: Engine::new(),
+            ast: self.ast.clone(),
+            has_params: self.has_params,
+            has_grow: self.has_grow,
+            has_force: self.has_force,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+impl ScriptRules {
+    /// Compiles the rules from the source of a script.
+    pub fn from_source(source: &str) -> Result<ScriptRules, ScriptError> {
+        let engine: Engine = Engine::new();
+        let ast: AST = engine.compile(source).map_err(|error| ScriptError {
+            message: error.to_string(),
+        })?;
+
+        let defines = |name: &str| ast.iter_functions().any(|function| function.name == name);
+        let has_params: bool = defines("params");
+        let has_grow: bool = defines("grow");
+        let has_force: bool = defines("force");
+
+        Ok(ScriptRules {
+            engine,
+            ast,
+            has_params,
+            has_grow,
+            has_force,
+            last_error: None,
+        })
+    }
+
+    /// Compiles the rules from a script file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<ScriptRules, ScriptError> {
+        let source: String = fs::read_to_string(path).map_err(|error| ScriptError {
+            message: error.to_string(),
+        })?;
+        ScriptRules::from_source(&source)
+    }
+
+    /// Returns the last error raised while running one of the rules.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Returns the parameters after applying the `params` rule, if it's defined.
+    pub(crate) fn params(&mut self, tick: usize, params: &ParamSet, growth_probability: f64) -> Option<(ParamSet, f64)> {
+        if !self.has_params {
+            return None;
+        }
+
+        let mut current: Map = Map::new();
+        current.insert("max_force".into(), Dynamic::from_float(params.max_force));
+        current.insert("max_speed".into(), Dynamic::from_float(params.max_speed));
+        current.insert("desired_separation".into(), Dynamic::from_float(params.desired_separation));
+        current.insert("separation_cohesion_ratio".into(), Dynamic::from_float(params.separation_cohesion_ratio));
+        current.insert("max_edge_length".into(), Dynamic::from_float(params.max_edge_length));
+        current.insert("growth_probability".into(), Dynamic::from_float(growth_probability));
+
+        let changes: Map = self.call("params", (tick as i64, current))?;
+
+        let get = |name: &str, default: f64| -> f64 { changes.get(name).and_then(as_f64).unwrap_or(default) };
+
+        let new_params: ParamSet = ParamSet {
+            max_force: get("max_force", params.max_force),
+            max_speed: get("max_speed", params.max_speed),
+            desired_separation: get("desired_separation", params.desired_separation),
+            separation_cohesion_ratio: get("separation_cohesion_ratio", params.separation_cohesion_ratio),
+            max_edge_length: get("max_edge_length", params.max_edge_length),
+        };
+
+        Some((new_params, get("growth_probability", growth_probability)))
+    }
+
+    /// Returns whether an over-length edge should be split, defaulting to true.
+    pub(crate) fn grow(&mut self, middle: &Point2<f64>, length: f64) -> bool {
+        if !self.has_grow {
+            return true;
+        }
+
+        self.call("grow", (middle.x, middle.y, length)).unwrap_or(true)
+    }
+
+    /// Returns the extra force acting on a node at `position`, if the `force` rule is defined.
+    pub(crate) fn force(&mut self, position: &Point2<f64>) -> Option<Vector2<f64>> {
+        if !self.has_force {
+            return None;
+        }
+
+        let force: Array = self.call("force", (position.x, position.y))?;
+        match (force.first().and_then(as_f64), force.get(1).and_then(as_f64)) {
+            (Some(x), Some(y)) => Some(Vector2::new(x, y)),
+            _ => {
+                self.last_error = Some("force must return an array of two numbers".to_string());
+                None
+            }
+        }
+    }
+
+    fn call<T: Clone + Send + Sync + 'static>(&mut self, name: &str, args: impl rhai::FuncArgs) -> Option<T> {
+        let mut scope: Scope = Scope::new();
+        match self.engine.call_fn::<T>(&mut scope, &self.ast, name, args) {
+            Ok(result) => Some(result),
+            Err(error) => {
+                self.last_error = Some(error.to_string());
+                None
+            }
+        }
+    }
+}
+
+fn as_f64(value: &Dynamic) -> Option<f64> {
+    value.as_float().ok().or_else(|| value.as_int().ok().map(|int| int as f64))
+}
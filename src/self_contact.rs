@@ -0,0 +1,189 @@
+use core::ops::AddAssign;
+
+use nalgebra::{distance, Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, geometry, parallel};
+
+/// The closest point where the path comes to touching itself, see [`DifferentialGrowth::self_contact()`].
+///
+/// Edges are identified by the index of the node they start from,
+/// edge `i` runs from node `i` to the next node.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfContact {
+    /// The node closest to another part of the path.
+    pub node: usize,
+    /// The edge of that other part.
+    pub edge: usize,
+    /// The point on the edge closest to the node.
+    pub point: Point2<f64>,
+    /// The distance between the node and the point.
+    pub distance: f64,
+}
+
+/// A soft alternative to preventing self-intersections: a force pushing nodes away from
+/// other parts of the path, growing as they come closer than `distance`.
+///
+/// Parts of the path within twice `distance` of a node measured along the path are
+/// its own neighborhood and don't push it away.
+///
+/// # Examples
+///
+/// ```rust
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// // Folding this fast makes the path cross itself without self-avoidance.
+/// differential_growth.set_curvature_gain(0.3);
+/// differential_growth.set_self_avoidance(differential_growth::SelfAvoidance::new(10.0, 1.0));
+///
+/// differential_growth.tick_n(180);
+/// assert!(differential_growth.self_intersections().is_empty());
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfAvoidance {
+    /// The distance below which the force starts pushing.
+    pub distance: f64,
+    /// The force on a node touching another part of the path.
+    pub strength: f64,
+}
+
+impl SelfAvoidance {
+    /// Returns a SelfAvoidance that starts pushing at `distance`, up to `strength`.
+    pub fn new(distance: f64, strength: f64) -> SelfAvoidance {
+        SelfAvoidance { distance, strength }
+    }
+
+    /// Returns the force pushing a node along `offset`, the vector from
+    /// another part of the path to the node. It grows quadratically from
+    /// nothing at `distance` to `strength` at contact.
+    pub fn force(&self, offset: &Vector2<f64>) -> Vector2<f64> {
+        let magnitude: f64 = offset.magnitude();
+        if magnitude == 0.0 || magnitude >= self.distance {
+            return Vector2::default();
+        }
+
+        let closeness: f64 = 1.0 - magnitude / self.distance;
+        offset * (self.strength * closeness * closeness / magnitude)
+    }
+}
+
+impl DifferentialGrowth {
+    /// Returns how close the path comes to touching itself: the node closest to
+    /// another part of the path within the desired separation, if any.
+    /// Parts of the path within twice the desired separation of a node measured
+    /// along the path are its own neighborhood and never count as contact.
+    ///
+    /// Calling this after every tick tracks how close the path is to crossing itself,
+    /// for readouts or to tune [`SelfAvoidance`].
+    ///
+    /// This takes `&mut self` because the spatial index is rebuilt first when
+    /// nodes were inserted or removed since it was last built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// // A narrow U, its legs 4 apart.
+    /// let starting_points: Vec<Point2<f64>> = (0..=20)
+    ///     .map(|i| Point2::new(i as f64 * 2.0, 0.0))
+    ///     .chain((0..=20).rev().map(|i| Point2::new(i as f64 * 2.0, 4.0)))
+    ///     .collect();
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.topology = differential_growth::Topology::Open;
+    ///
+    /// let contact = differential_growth.self_contact().unwrap();
+    /// assert!((contact.distance - 4.0).abs() < 1e-9);
+    /// ```
+    ///
+    pub fn self_contact(&mut self) -> Option<SelfContact> {
+        let radius: f64 = self.desired_separation;
+        let close_nodes: Vec<Vec<usize>> = self.neighbor_search.within_radius_of_each(&self.nodes, radius);
+        let arc_lengths: Vec<f64> = self.arc_lengths();
+
+        (0..self.nodes.len())
+            .flat_map(|i| {
+                let position: Point2<f64> = self.nodes[i].position;
+                self.contacts(i, &close_nodes[i], &arc_lengths, radius)
+                    .into_iter()
+                    .map(move |(edge, point)| SelfContact {
+                        node: i,
+                        edge,
+                        point,
+                        distance: distance(&position, &point),
+                    })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    // The spatial index must be up to date, like during a tick.
+    pub(crate) fn get_self_avoidance_forces(&self, self_avoidance: &SelfAvoidance, active: &[bool]) -> Vec<Vector2<f64>> {
+        let arc_lengths: Vec<f64> = self.arc_lengths();
+
+        parallel::map(self.nodes.len(), |i| {
+            if !active[i] {
+                return Vector2::default();
+            }
+
+            let close_nodes: Vec<usize> = self.close_nodes(i, self_avoidance.distance);
+            let mut force: Vector2<f64> = Vector2::default();
+            for (_, point) in self.contacts(i, &close_nodes, &arc_lengths, self_avoidance.distance) {
+                force.add_assign(self_avoidance.force(&(self.nodes[i].position - point)));
+            }
+            force
+        })
+    }
+
+    // The arc length from the first node to every node. Closed paths have one more
+    // entry, the length of the whole loop.
+    fn arc_lengths(&self) -> Vec<f64> {
+        let mut arc_lengths: Vec<f64> = Vec::with_capacity(self.nodes.len() + 1);
+        let mut arc_length: f64 = 0.0;
+        arc_lengths.push(arc_length);
+        for (i, j) in self.topology.edges(self.nodes.len()) {
+            arc_length.add_assign(distance(&self.nodes[i].position, &self.nodes[j].position));
+            arc_lengths.push(arc_length);
+        }
+
+        arc_lengths
+    }
+
+    // Returns the edges near node `i` that are further than twice `radius` from it along
+    // the path, with their point closest to the node. The close nodes are within `radius`,
+    // the edges are the ones they start or end.
+    fn contacts(&self, i: usize, close_nodes: &[usize], arc_lengths: &[f64], radius: f64) -> Vec<(usize, Point2<f64>)> {
+        let len: usize = self.nodes.len();
+        let loop_length: Option<f64> = (arc_lengths.len() > len).then(|| arc_lengths[len]);
+        let position: Point2<f64> = self.nodes[i].position;
+
+        let mut edges: Vec<usize> = close_nodes
+            .iter()
+            .flat_map(|&k| [self.topology.prev(k, len), Some(k)])
+            .flatten()
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        edges
+            .into_iter()
+            .filter_map(|edge| {
+                let next: usize = self.topology.next(edge, len)?;
+                if edge == i || next == i {
+                    return None;
+                }
+
+                let (point, t): (Point2<f64>, f64) =
+                    geometry::closest_point_on_segment(&position, &self.nodes[edge].position, &self.nodes[next].position);
+                let mut along: f64 = (arc_lengths[edge] + (arc_lengths[edge + 1] - arc_lengths[edge]) * t - arc_lengths[i]).abs();
+                if let Some(loop_length) = loop_length {
+                    along = along.min(loop_length - along);
+                }
+
+                (along > 2.0 * radius && distance(&position, &point) <= radius).then_some((edge, point))
+            })
+            .collect()
+    }
+}
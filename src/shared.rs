@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use nalgebra::Point2;
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// The state of the simulation after a tick, as published by [`SharedGrowth`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GrowthFrame {
+    /// The positions of the nodes, see [`DifferentialGrowth::get_points()`].
+    pub points: Vec<Point2<f64>>,
+    /// The amount of ticks that had been executed.
+    pub iteration: usize,
+}
+
+/// Runs a simulation on a worker thread, so a heavy simulation doesn't stall the
+/// thread that renders it.
+///
+/// The worker keeps ticking and publishes a [`GrowthFrame`] after every tick.
+/// [`SharedGrowth::latest()`] hands out the last completed frame without waiting
+/// for the tick in progress. Frames are double buffered: once the render thread
+/// drops a frame its buffer is reused by the worker instead of allocating a new one.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::SharedGrowth;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+///
+/// let shared = SharedGrowth::spawn(differential_growth);
+/// while shared.latest().iteration < 20 {
+///     std::thread::yield_now();
+/// }
+///
+/// let frame = shared.latest();
+/// let differential_growth = shared.stop().unwrap();
+/// assert!(differential_growth.iteration() >= frame.iteration);
+/// assert!(frame.points.len() >= 10);
+/// ```
+///
+pub struct SharedGrowth {
+    latest: Arc<Mutex<Arc<GrowthFrame>>>,
+    running: Arc<AtomicBool>,
+    worker: Option<JoinHandle<DifferentialGrowth>>,
+}
+
+impl SharedGrowth {
+    /// Starts ticking `differential_growth` on a new thread.
+    pub fn spawn(mut differential_growth: DifferentialGrowth) -> SharedGrowth {
+        let latest: Arc<Mutex<Arc<GrowthFrame>>> = Arc::new(Mutex::new(Arc::new(GrowthFrame {
+            points: differential_growth.get_points(),
+            iteration: differential_growth.iteration(),
+        })));
+        let running: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
+
+        let worker: JoinHandle<DifferentialGrowth> = {
+            let latest: Arc<Mutex<Arc<GrowthFrame>>> = Arc::clone(&latest);
+            let running: Arc<AtomicBool> = Arc::clone(&running);
+            thread::spawn(move || {
+                let mut back: GrowthFrame = GrowthFrame::default();
+                while running.load(Ordering::Relaxed) {
+                    differential_growth.tick();
+                    differential_growth.get_points_into(&mut back.points);
+                    back.iteration = differential_growth.iteration();
+
+                    let front: Arc<GrowthFrame> = Arc::new(back);
+                    let previous: Arc<GrowthFrame> = match latest.lock() {
+                        Ok(mut latest) => core::mem::replace(&mut *latest, front),
+                        Err(_) => break,
+                    };
+                    // The previous frame is still in use when the render thread holds on to it.
+                    back = Arc::try_unwrap(previous).unwrap_or_default();
+                }
+                differential_growth
+            })
+        };
+
+        SharedGrowth {
+            latest,
+            running,
+            worker: Some(worker),
+        }
+    }
+
+    /// Returns the last frame the worker completed. Only clones an `Arc`.
+    #[must_use]
+    pub fn latest(&self) -> Arc<GrowthFrame> {
+        match self.latest.lock() {
+            Ok(latest) => Arc::clone(&latest),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Stops the worker after the tick in progress and returns the simulation,
+    /// or the panic of the worker if a tick panicked.
+    pub fn stop(mut self) -> thread::Result<DifferentialGrowth> {
+        self.running.store(false, Ordering::Relaxed);
+        match self.worker.take() {
+            Some(worker) => worker.join(),
+            None => unreachable!("the worker is only taken when stopping"),
+        }
+    }
+}
+
+impl Drop for SharedGrowth {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use crate::node::Node;
+
+/// The positions and velocities of all nodes as parallel arrays of scalars.
+///
+/// `Vec<Node>` stays the public representation of the path, but the force loops
+/// read from these arrays instead. Plain `f64` slices keep the hot loops free of
+/// the strided loads an array of structs needs, so the compiler can vectorize them.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeArrays {
+    pub(crate) xs: Vec<f64>,
+    pub(crate) ys: Vec<f64>,
+    pub(crate) vxs: Vec<f64>,
+    pub(crate) vys: Vec<f64>,
+}
+
+impl NodeArrays {
+    /// Copies the state of `nodes` into the arrays, reusing their allocations.
+    pub(crate) fn load(&mut self, nodes: &[Node]) {
+        self.xs.clear();
+        self.ys.clear();
+        self.vxs.clear();
+        self.vys.clear();
+
+        self.xs.extend(nodes.iter().map(|node| node.position.x));
+        self.ys.extend(nodes.iter().map(|node| node.position.y));
+        self.vxs.extend(nodes.iter().map(|node| node.velocity.x));
+        self.vys.extend(nodes.iter().map(|node| node.velocity.y));
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.xs.len()
+    }
+}
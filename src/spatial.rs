@@ -0,0 +1,517 @@
+use core::ops::AddAssign;
+#[cfg(feature = "profiling")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "std")]
+use kd_tree::{KdPoint, KdTree2};
+use nalgebra::Point2;
+#[cfg(feature = "std")]
+use nalgebra::Point3;
+
+use crate::prelude::*;
+use crate::{node::Node, params::ParamSet, periodic::Period};
+
+/// A spatial index answering the fixed-radius neighbor queries used to compute separation forces.
+///
+/// The index is rebuilt from the node positions and queries return indices
+/// into the slice of positions it was last rebuilt with.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{GridIndex, SpatialIndex};
+/// use nalgebra::{Point2, Point3};
+///
+/// let mut index = GridIndex::new(14.0);
+/// index.rebuild(&[Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(30.0, 0.0)]);
+///
+/// let mut close_points: Vec<usize> = Vec::new();
+/// index.query_radius(&Point2::new(1.0, 0.0), 14.0, &mut close_points);
+/// close_points.sort();
+/// assert_eq!(close_points, vec![0, 1]);
+/// ```
+///
+pub trait SpatialIndex: Send + Sync {
+    /// Rebuilds the index from the given positions.
+    fn rebuild(&mut self, positions: &[Point2<f64>]);
+
+    /// Appends the indices of all positions within `radius` of `point` to `out`.
+    fn query_radius(&self, point: &Point2<f64>, radius: f64, out: &mut Vec<usize>);
+}
+
+impl<T: SpatialIndex + ?Sized> SpatialIndex for Box<T> {
+    fn rebuild(&mut self, positions: &[Point2<f64>]) {
+        (**self).rebuild(positions)
+    }
+
+    fn query_radius(&self, point: &Point2<f64>, radius: f64, out: &mut Vec<usize>) {
+        (**self).query_radius(point, radius, out)
+    }
+}
+
+/// The position of a node in the kd-tree, remembering its index in the path.
+/// Only storing what's needed avoids cloning every node on each rebuild.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+struct IndexedPoint {
+    position: Point2<f64>,
+    index: usize,
+}
+
+#[cfg(feature = "std")]
+impl KdPoint for IndexedPoint {
+    type Scalar = f64;
+    type Dim = typenum::U2; // 2 dimensional tree.
+    fn at(&self, k: usize) -> f64 {
+        self.position[k]
+    }
+}
+
+/// The position of a node in a 3D kd-tree, remembering its index.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct IndexedPoint3 {
+    pub(crate) position: Point3<f64>,
+    pub(crate) index: usize,
+}
+
+#[cfg(feature = "std")]
+impl KdPoint for IndexedPoint3 {
+    type Scalar = f64;
+    type Dim = typenum::U3; // 3 dimensional tree.
+    fn at(&self, k: usize) -> f64 {
+        self.position[k]
+    }
+}
+
+/// Finds neighbors using a kd-tree. This is the default, it needs the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct KdTreeIndex {
+    tree: Option<KdTree2<IndexedPoint>>,
+}
+
+#[cfg(feature = "std")]
+impl KdTreeIndex {
+    /// Returns an empty KdTreeIndex.
+    pub fn new() -> KdTreeIndex {
+        KdTreeIndex::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl SpatialIndex for KdTreeIndex {
+    fn rebuild(&mut self, positions: &[Point2<f64>]) {
+        // Constructing a kdtree so we can optimise looking for neighbors.
+        // This technique is the single most important optimisation we can do.
+        let indexed_points: Vec<IndexedPoint> = positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| IndexedPoint { position: *position, index })
+            .collect();
+        self.tree = Some(KdTree2::build_by_ordered_float(indexed_points));
+    }
+
+    fn query_radius(&self, point: &Point2<f64>, radius: f64, out: &mut Vec<usize>) {
+        if let Some(tree) = &self.tree {
+            let query: IndexedPoint = IndexedPoint { position: *point, index: 0 };
+            out.extend(tree.within_radius(&query, radius).iter().map(|indexed| indexed.index));
+        }
+    }
+}
+
+// The spatial index used unless another one is set.
+#[cfg(feature = "std")]
+pub(crate) type DefaultIndex = KdTreeIndex;
+#[cfg(not(feature = "std"))]
+pub(crate) type DefaultIndex = GridIndex;
+
+/// Finds neighbors by hashing positions into a uniform grid.
+///
+/// The queries of this algorithm always use the same radius, so with a cell size
+/// close to `desired_separation` only a handful of cells are visited per query.
+/// Cells keep their allocations between rebuilds, so once the shape settles
+/// rebuilding no longer allocates.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::GridIndex;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .desired_separation(14.0)
+///     .spatial_index(GridIndex::new(14.0))
+///     .build();
+/// differential_growth.tick();
+/// ```
+///
+pub struct GridIndex {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    positions: Vec<Point2<f64>>,
+}
+
+impl Default for GridIndex {
+    /// Returns an empty GridIndex with cells as large as the default desired separation.
+    fn default() -> Self {
+        GridIndex::new(ParamSet::default().desired_separation)
+    }
+}
+
+impl GridIndex {
+    /// Returns an empty GridIndex.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_size` - The width and height of a grid cell, usually the desired separation.
+    ///
+    pub fn new(cell_size: f64) -> GridIndex {
+        GridIndex {
+            cell_size,
+            cells: HashMap::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Returns the width and height of a grid cell.
+    #[must_use]
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    fn cell(&self, x: f64, y: f64) -> (i64, i64) {
+        ((x / self.cell_size).floor() as i64, (y / self.cell_size).floor() as i64)
+    }
+}
+
+impl SpatialIndex for GridIndex {
+    fn rebuild(&mut self, positions: &[Point2<f64>]) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+
+        for (index, position) in positions.iter().enumerate() {
+            let cell: (i64, i64) = self.cell(position.x, position.y);
+            self.cells.entry(cell).or_default().push(index);
+        }
+
+        // Forget the cells nodes moved away from, otherwise the grid only ever grows.
+        self.cells.retain(|_, cell| !cell.is_empty());
+
+        self.positions.clear();
+        self.positions.extend_from_slice(positions);
+    }
+
+    fn query_radius(&self, point: &Point2<f64>, radius: f64, out: &mut Vec<usize>) {
+        let radius_sq: f64 = radius * radius;
+        let (min_x, min_y) = self.cell(point.x - radius, point.y - radius);
+        let (max_x, max_y) = self.cell(point.x + radius, point.y + radius);
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    out.extend(
+                        cell.iter()
+                            .copied()
+                            .filter(|&index| (self.positions[index] - point).norm_squared() <= radius_sq),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// When the spatial index used to find neighboring nodes is rebuilt.
+///
+/// Rebuilding the index every tick is exact but dominates the runtime for large paths.
+/// Between rebuilds, neighbors are searched within `desired_separation + slack` using
+/// the positions the index was built with and then filtered on their current positions.
+/// As long as no node moved more than half of the slack since the last rebuild this
+/// finds exactly the same neighbors, so the index is also rebuilt as soon as that happens.
+///
+/// Inserting or removing nodes always rebuilds the index.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::RebuildPolicy;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// // Exact, rebuilding whenever a node moved more than 2.0 since the last rebuild.
+/// differential_growth.set_rebuild_policy(RebuildPolicy { interval: usize::MAX, slack: 4.0 });
+/// differential_growth.tick();
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebuildPolicy {
+    /// The maximum amount of ticks between rebuilds. With a slack of `0.0`
+    /// an interval above 1 trades accuracy for speed.
+    pub interval: usize,
+    /// Extra search radius allowing nodes to move before a rebuild is needed.
+    pub slack: f64,
+}
+
+impl Default for RebuildPolicy {
+    /// Rebuilds the index every tick.
+    fn default() -> Self {
+        RebuildPolicy {
+            interval: 1,
+            slack: 0.0,
+        }
+    }
+}
+
+/// Finds neighboring nodes with a [`SpatialIndex`], cached across ticks according to a [`RebuildPolicy`].
+pub(crate) struct NeighborSearch {
+    pub(crate) policy: RebuildPolicy,
+    index: Box<dyn SpatialIndex>,
+    is_valid: bool,
+    built_positions: Vec<Point2<f64>>,
+    ticks_since_build: usize,
+    // How far nodes moved since the index was built, when it was built during this tick.
+    moved_this_tick: f64,
+    // Searches across the edges of a periodic boundary too.
+    period: Option<Period>,
+    // Counted while forces are computed in parallel.
+    #[cfg(feature = "profiling")]
+    queries: AtomicUsize,
+}
+
+impl NeighborSearch {
+    pub(crate) fn new(policy: RebuildPolicy, index: Box<dyn SpatialIndex>) -> NeighborSearch {
+        NeighborSearch {
+            policy,
+            index,
+            is_valid: false,
+            built_positions: Vec::new(),
+            ticks_since_build: 0,
+            moved_this_tick: 0.0,
+            period: None,
+            #[cfg(feature = "profiling")]
+            queries: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns and resets the amount of queries since the last call.
+    #[cfg(feature = "profiling")]
+    pub(crate) fn take_queries(&self) -> usize {
+        self.queries.swap(0, Ordering::Relaxed)
+    }
+
+    /// Sets the periodic boundary to search across, see [`crate::BoundaryBehavior::Periodic`].
+    pub(crate) fn set_period(&mut self, period: Option<Period>) {
+        if self.period != period {
+            self.period = period;
+            self.is_valid = false;
+        }
+    }
+
+    /// Replaces the spatial index, it is built on the next update.
+    pub(crate) fn set_index(&mut self, index: Box<dyn SpatialIndex>) {
+        self.index = index;
+        self.is_valid = false;
+    }
+
+    /// Forces a rebuild on the next update, needed whenever node indices change.
+    pub(crate) fn invalidate(&mut self) {
+        self.is_valid = false;
+    }
+
+    /// Counts a tick for the [`RebuildPolicy`], at the start of every tick.
+    pub(crate) fn next_tick(&mut self) {
+        self.ticks_since_build += 1;
+    }
+
+    /// Rebuilds the index if the policy requires it. Forces can be evaluated more than once
+    /// per tick, like by [`crate::Integrator::Verlet`], later evaluations reuse the index of
+    /// the first one and search further by how far the nodes moved in between.
+    pub(crate) fn update(&mut self, nodes: &[Node]) {
+        if self.needs_rebuild(nodes) {
+            self.rebuild(nodes);
+        } else if self.ticks_since_build == 0 {
+            self.moved_this_tick = self.max_displacement(nodes);
+        }
+    }
+
+    /// Returns the indices of all nodes within `radius` of any of `points`, sorted and without duplicates.
+    ///
+    /// Meant for queries in between ticks: the index is only rebuilt when nodes were
+    /// inserted or removed, otherwise the search radius is grown by how far nodes
+    /// moved since the last rebuild.
+    pub(crate) fn within_radius_of_points(&mut self, nodes: &[Node], points: &[Point2<f64>], radius: f64) -> Vec<usize> {
+        let max_displacement: f64 = self.prepare_query(nodes);
+
+        let mut close_nodes: Vec<usize> = Vec::new();
+        for point in points {
+            self.query_filtered(nodes, point, radius, max_displacement, &mut close_nodes);
+        }
+
+        close_nodes.sort_unstable();
+        close_nodes.dedup();
+        close_nodes
+    }
+
+    /// Returns the indices of all nodes within `radius` of every node, one list per node.
+    ///
+    /// Meant for queries in between ticks, like [`NeighborSearch::within_radius_of_points()`].
+    pub(crate) fn within_radius_of_each(&mut self, nodes: &[Node], radius: f64) -> Vec<Vec<usize>> {
+        let max_displacement: f64 = self.prepare_query(nodes);
+
+        nodes
+            .iter()
+            .map(|node| {
+                let mut close_nodes: Vec<usize> = Vec::new();
+                self.query_filtered(nodes, &node.position, radius, max_displacement, &mut close_nodes);
+                close_nodes
+            })
+            .collect()
+    }
+
+    // Rebuilds the index if nodes were inserted or removed and returns how far
+    // nodes moved since it was built.
+    fn prepare_query(&mut self, nodes: &[Node]) -> f64 {
+        if !self.is_valid || self.built_positions.len() != nodes.len() {
+            self.rebuild(nodes);
+            // The next tick rebuilds as if this query never happened.
+            self.ticks_since_build = self.policy.interval;
+        }
+
+        self.max_displacement(nodes)
+    }
+
+    // How far the nodes moved since the index was built, at most.
+    fn max_displacement(&self, nodes: &[Node]) -> f64 {
+        nodes
+            .iter()
+            .zip(self.built_positions.iter())
+            .map(|(node, built)| self.distance_sq(&node.position, built))
+            .fold(0.0, f64::max)
+            .sqrt()
+    }
+
+    fn query_filtered(
+        &self,
+        nodes: &[Node],
+        point: &Point2<f64>,
+        radius: f64,
+        max_displacement: f64,
+        close_nodes: &mut Vec<usize>,
+    ) {
+        let radius_sq: f64 = radius * radius;
+        let start: usize = close_nodes.len();
+        self.query_index(point, radius + max_displacement, close_nodes);
+        let mut k: usize = start;
+        while k < close_nodes.len() {
+            if self.distance_sq(&nodes[close_nodes[k]].position, point) <= radius_sq {
+                k.add_assign(1);
+            } else {
+                close_nodes.swap_remove(k);
+            }
+        }
+    }
+
+    fn rebuild(&mut self, nodes: &[Node]) {
+        // The positions are kept to check displacements against the slack
+        // and reusing the buffer avoids allocating on every rebuild.
+        self.built_positions.clear();
+        self.built_positions.extend(nodes.iter().map(|node| node.position));
+        self.index.rebuild(&self.built_positions);
+        self.is_valid = true;
+        self.ticks_since_build = 0;
+        self.moved_this_tick = 0.0;
+    }
+
+    /// Returns the indices of all nodes within `radius` of `node`, based on their current positions.
+    /// [`NeighborSearch::update()`] must have been called since the nodes last changed.
+    pub(crate) fn within_radius(&self, nodes: &[Node], node: &Node, radius: f64) -> Vec<usize> {
+        let mut close_nodes: Vec<usize> = Vec::new();
+
+        // An index built at the current positions needs no filtering.
+        if self.ticks_since_build == 0 && self.moved_this_tick == 0.0 {
+            self.query_index(&node.position, radius, &mut close_nodes);
+            return close_nodes;
+        }
+
+        // Within the tick the index was built the nodes moved by a known distance, later on
+        // the slack bounds how far they moved.
+        let margin: f64 = if self.ticks_since_build == 0 {
+            self.moved_this_tick
+        } else {
+            self.policy.slack
+        };
+        let radius_sq: f64 = radius * radius;
+        self.query_index(&node.position, radius + margin, &mut close_nodes);
+        close_nodes.retain(|&index| self.distance_sq(&nodes[index].position, &node.position) <= radius_sq);
+        close_nodes
+    }
+
+    // Appends the indices of the positions in the index within `radius` of `point`,
+    // also across the edges of a periodic boundary.
+    fn query_index(&self, point: &Point2<f64>, radius: f64, out: &mut Vec<usize>) {
+        #[cfg(feature = "profiling")]
+        self.queries.fetch_add(1, Ordering::Relaxed);
+
+        let period: &Period = match &self.period {
+            Some(period) => period,
+            None => return self.index.query_radius(point, radius, out),
+        };
+
+        let mut found: Vec<usize> = Vec::new();
+        for image in period.images(point, radius) {
+            self.index.query_radius(&image, radius, &mut found);
+        }
+        // A position can be close to several images when the rectangle is small.
+        found.sort_unstable();
+        found.dedup();
+        out.append(&mut found);
+    }
+
+    // The squared distance between two positions, the shortest way across a periodic boundary.
+    fn distance_sq(&self, a: &Point2<f64>, b: &Point2<f64>) -> f64 {
+        match &self.period {
+            Some(period) => period.wrap(a - b).norm_squared(),
+            None => (a - b).norm_squared(),
+        }
+    }
+
+    fn needs_rebuild(&self, nodes: &[Node]) -> bool {
+        if !self.is_valid || self.built_positions.len() != nodes.len() {
+            return true;
+        }
+
+        if self.ticks_since_build >= self.policy.interval {
+            return true;
+        }
+
+        if self.policy.slack > 0.0 {
+            let max_displacement_sq: f64 = (self.policy.slack / 2.0).powi(2);
+            return nodes
+                .iter()
+                .zip(self.built_positions.iter())
+                .any(|(node, built)| self.distance_sq(&node.position, built) > max_displacement_sq);
+        }
+
+        false
+    }
+}
+
+// Only the policy is state, the index itself is rebuilt from the nodes on the first tick.
+// Custom indices can't be serialized, deserializing falls back to the default index.
+#[cfg(feature = "serde")]
+impl serde::Serialize for NeighborSearch {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.policy.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NeighborSearch {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let policy: RebuildPolicy = RebuildPolicy::deserialize(deserializer)?;
+        Ok(NeighborSearch::new(policy, Box::new(DefaultIndex::default())))
+    }
+}
@@ -0,0 +1,108 @@
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{differential_growth::DifferentialGrowth, topology::Topology};
+
+/// A cubic Bézier curve from `start` to `end`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier {
+    pub start: Point2<f64>,
+    pub control1: Point2<f64>,
+    pub control2: Point2<f64>,
+    pub end: Point2<f64>,
+}
+
+impl CubicBezier {
+    /// Returns the point on the curve at `t`, between 0 at the start and 1 at the end.
+    pub fn point_at(&self, t: f64) -> Point2<f64> {
+        let u: f64 = 1.0 - t;
+        let coords = self.start.coords * (u * u * u)
+            + self.control1.coords * (3.0 * u * u * t)
+            + self.control2.coords * (3.0 * u * t * t)
+            + self.end.coords * (t * t * t);
+        Point2::from(coords)
+    }
+}
+
+impl DifferentialGrowth {
+    /// Returns a smooth curve through all nodes as one cubic Bézier segment per edge,
+    /// the Catmull-Rom spline of the nodes. Closed paths get a segment back to the first node.
+    ///
+    /// The segments map directly onto the `C` command of SVG paths and most vector tools.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let segments = differential_growth.get_smooth_path();
+    /// assert_eq!(segments.len(), 10);
+    /// assert_eq!(segments[0].start, differential_growth.nodes()[0].position);
+    /// assert_eq!(segments[9].end, differential_growth.nodes()[0].position);
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_smooth_path(&self) -> Vec<CubicBezier> {
+        let len: usize = self.nodes.len();
+        let position = |i: usize| self.nodes[i].position;
+
+        self.topology
+            .edges(len)
+            .map(|(i, j)| {
+                // Open paths repeat their endpoints so the curve ends there.
+                let (before, after): (usize, usize) = match self.topology {
+                    Topology::Closed => ((i + len - 1) % len, (j + 1) % len),
+                    Topology::Open => (i.saturating_sub(1), (j + 1).min(len - 1)),
+                };
+
+                let start: Point2<f64> = position(i);
+                let end: Point2<f64> = position(j);
+                CubicBezier {
+                    start,
+                    control1: start + (end - position(before)) / 6.0,
+                    control2: end - (position(after) - start) / 6.0,
+                    end,
+                }
+            })
+            .collect()
+    }
+
+    /// Get points along the smooth curve of [`DifferentialGrowth::get_smooth_path()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `samples_per_edge` - The amount of points per edge, starting at its first node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let points = differential_growth.get_smooth_points(4);
+    /// assert_eq!(points.len(), 40);
+    /// ```
+    ///
+    #[must_use]
+    pub fn get_smooth_points(&self, samples_per_edge: usize) -> Vec<Point2<f64>> {
+        let segments: Vec<CubicBezier> = self.get_smooth_path();
+        let mut points: Vec<Point2<f64>> = Vec::with_capacity(segments.len() * samples_per_edge + 1);
+
+        for segment in &segments {
+            for sample in 0..samples_per_edge {
+                points.push(segment.point_at(sample as f64 / samples_per_edge as f64));
+            }
+        }
+
+        // Open paths end at their last node.
+        if self.topology == Topology::Open && samples_per_edge > 0 {
+            if let Some(last) = segments.last() {
+                points.push(last.end);
+            }
+        }
+
+        points
+    }
+}
@@ -0,0 +1,109 @@
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{changes, differential_growth::DifferentialGrowth, params::ParamSet, watchdog::Snapshot};
+
+/// The nodes and parameters of a simulation at one moment, taken by
+/// [`DifferentialGrowth::snapshot()`] to go back to with [`DifferentialGrowth::restore()`].
+#[derive(Clone, Debug)]
+pub struct GrowthState {
+    snapshot: Snapshot,
+    params: ParamSet,
+}
+
+impl GrowthState {
+    /// Returns the amount of ticks the simulation had executed.
+    #[must_use]
+    pub fn iteration(&self) -> usize {
+        self.snapshot.iteration
+    }
+
+    /// Returns the parameters of the simulation.
+    #[must_use]
+    pub fn params(&self) -> &ParamSet {
+        &self.params
+    }
+}
+
+impl DifferentialGrowth {
+    /// Captures the nodes, their attached data, the parameters and the random state,
+    /// to undo later changes with [`DifferentialGrowth::restore()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    /// differential_growth.tick_n(10);
+    ///
+    /// let state = differential_growth.snapshot();
+    /// let points = differential_growth.get_points();
+    ///
+    /// // A bad tweak that ruins the form.
+    /// differential_growth.set_max_speed(50.0);
+    /// differential_growth.tick_n(10);
+    ///
+    /// differential_growth.restore(&state);
+    /// assert_eq!(differential_growth.get_points(), points);
+    /// assert_eq!(differential_growth.iteration(), 10);
+    /// assert_eq!(differential_growth.max_speed, 1.0);
+    /// ```
+    ///
+    #[must_use]
+    pub fn snapshot(&self) -> GrowthState {
+        GrowthState {
+            snapshot: self.capture_snapshot(),
+            params: ParamSet::from_differential_growth(self),
+        }
+    }
+
+    /// Goes back to the nodes and parameters of `state`. Settings that aren't part of a
+    /// [`ParamSet`], observers and forces are kept. The restored simulation continues
+    /// exactly like it did after the snapshot was taken.
+    ///
+    /// A [`crate::Watchdog`] rolls back to the restored state from then on, its own snapshot
+    /// could be of a later tick.
+    pub fn restore(&mut self, state: &GrowthState) {
+        let before: Vec<Point2<f64>> = self.get_points();
+
+        // The parameters go first, changing them also changes the parameters of the nodes.
+        state.params.apply(self);
+        self.restore_snapshot(state.snapshot.clone());
+        if let Some(watchdog) = self.watchdog.as_mut() {
+            watchdog.snapshot = state.snapshot.clone();
+        }
+        self.changes = changes::changed_ranges(&before, &self.nodes, &[]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use crate::prelude::*;
+    use crate::{DifferentialGrowth, Watchdog};
+
+    #[test]
+    fn restore_before_the_snapshot_of_the_watchdog() {
+        let starting_points: Vec<Point2<f64>> = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(8.0, 0.0),
+            Point2::new(8.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let mut differential_growth = DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+        differential_growth.set_watchdog(Watchdog::default());
+        differential_growth.tick_n(5);
+        let state = differential_growth.snapshot();
+
+        // The watchdog keeps a snapshot of tick 20.
+        differential_growth.tick_n(20);
+        differential_growth.restore(&state);
+        assert_eq!(differential_growth.watchdog.as_ref().unwrap().snapshot.iteration, 5);
+
+        differential_growth.tick_n(20);
+        assert_eq!(differential_growth.iteration(), 25);
+        assert_eq!(differential_growth.watchdog.as_ref().unwrap().snapshot.iteration, 25);
+        assert!(differential_growth.take_watchdog_events().is_empty());
+    }
+}
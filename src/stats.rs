@@ -0,0 +1,109 @@
+use core::ops::AddAssign;
+
+use nalgebra::distance;
+
+use crate::{changes::ChangeKind, differential_growth::DifferentialGrowth};
+
+/// Measurements of the current state of a [`DifferentialGrowth`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    /// The total length of all edges.
+    pub length: f64,
+    /// The amount of nodes.
+    pub node_count: usize,
+    /// The length of the shortest edge, `0.0` without edges.
+    pub min_edge_length: f64,
+    /// The average edge length, `0.0` without edges.
+    pub mean_edge_length: f64,
+    /// The length of the longest edge, `0.0` without edges.
+    pub max_edge_length: f64,
+    /// The amount of nodes inserted during the last tick.
+    pub nodes_added: usize,
+    /// The speed of the fastest node, how far it moved during the last tick.
+    pub max_node_speed: f64,
+}
+
+impl DifferentialGrowth {
+    /// Returns measurements of the current state, for readouts and stopping conditions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// while differential_growth.stats().length < 200.0 {
+    ///     differential_growth.tick();
+    /// }
+    ///
+    /// let stats = differential_growth.stats();
+    /// assert_eq!(stats.node_count, differential_growth.nodes().len());
+    /// assert!(stats.min_edge_length <= stats.mean_edge_length && stats.mean_edge_length <= stats.max_edge_length);
+    /// ```
+    ///
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        let mut stats: Stats = Stats {
+            node_count: self.nodes.len(),
+            nodes_added: self
+                .changed_ranges()
+                .iter()
+                .filter(|changed| changed.kind == ChangeKind::Inserted)
+                .map(|changed| changed.range.len())
+                .sum(),
+            max_node_speed: self.nodes.iter().map(|node| node.velocity.norm()).fold(0.0, f64::max),
+            ..Default::default()
+        };
+
+        let edge_lengths = self
+            .topology
+            .edges(self.nodes.len())
+            .map(|(i, j)| distance(&self.nodes[i].position, &self.nodes[j].position));
+
+        let mut edge_count: usize = 0;
+        stats.min_edge_length = f64::INFINITY;
+        for edge_length in edge_lengths {
+            stats.length.add_assign(edge_length);
+            stats.min_edge_length = stats.min_edge_length.min(edge_length);
+            stats.max_edge_length = stats.max_edge_length.max(edge_length);
+            edge_count.add_assign(1);
+        }
+
+        if edge_count == 0 {
+            stats.min_edge_length = 0.0;
+        } else {
+            stats.mean_edge_length = stats.length / edge_count as f64;
+        }
+
+        stats
+    }
+
+    /// Returns whether the path has effectively stopped changing, so batch renders can stop.
+    /// The path is settled when no node moved further than `velocity_epsilon` and
+    /// no more than `max_nodes_added` nodes were inserted during the last tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `velocity_epsilon` - The distance below which a node counts as standing still.
+    /// * `max_nodes_added` - The amount of inserted nodes that still counts as settled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// let mut differential_growth = differential_growth::DifferentialGrowth::builder()
+    ///     .points(starting_points)
+    ///     .max_nodes(60)
+    ///     .build();
+    ///
+    /// differential_growth.run_until(|differential_growth| differential_growth.is_settled(0.05, 0) || differential_growth.iteration() == 2000);
+    /// assert!(differential_growth.is_settled(0.05, 0));
+    /// ```
+    ///
+    #[must_use]
+    pub fn is_settled(&self, velocity_epsilon: f64, max_nodes_added: usize) -> bool {
+        let stats: Stats = self.stats();
+        stats.max_node_speed <= velocity_epsilon && stats.nodes_added <= max_nodes_added
+    }
+}
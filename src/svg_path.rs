@@ -0,0 +1,379 @@
+use core::{f64::consts::PI, fmt};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::geometry;
+use crate::prelude::*;
+
+/// Error returned when [`points_from_svg_path()`] can't parse the path data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SvgPathError {
+    /// `character` at byte `position` isn't a path command or a number.
+    UnexpectedCharacter { position: usize, character: char },
+    /// A number or flag was expected at byte `position`, for the command before it.
+    ExpectedNumber { position: usize },
+    /// The path data doesn't start with a moveto command.
+    MissingMoveTo,
+}
+
+impl fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgPathError::UnexpectedCharacter { position, character } => {
+                write!(f, "unexpected '{}' at {} in svg path", character, position)
+            }
+            SvgPathError::ExpectedNumber { position } => write!(f, "expected a number at {} in svg path", position),
+            SvgPathError::MissingMoveTo => write!(f, "svg path doesn't start with a moveto command"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvgPathError {}
+
+/// Returns starting points along the `d` attribute of an SVG `<path>`, like a logo or a
+/// hand-drawn shape exported from a vector editor, one list of points per subpath.
+///
+/// Every subpath is flattened, curves and arcs included, and resampled to points as close
+/// to `spacing` apart as fits its length. Subpaths closed with `Z` are meant for
+/// [`crate::Topology::Closed`] and don't repeat their first point, others for
+/// [`crate::Topology::Open`] and keep both ends.
+///
+/// Coordinates are used as they are, so y points down like in SVG. Flip them to draw
+/// in a y-up world.
+///
+/// # Examples
+///
+/// ```rust
+/// use nalgebra::Point2;
+///
+/// // A 20 by 10 rectangle with a circular hole of radius 3.
+/// let d = "M0,0 H20 V10 H0 Z M7,5 a3,3 0 1,0 6,0 a3,3 0 1,0 -6,0 z";
+/// let subpaths = differential_growth::points_from_svg_path(d, 1.0).unwrap();
+///
+/// assert_eq!(subpaths.len(), 2);
+/// assert_eq!(subpaths[0].len(), 60);
+/// assert_eq!(subpaths[0][0], Point2::new(0.0, 0.0));
+/// for point in &subpaths[1] {
+///     assert!(((point - Point2::new(10.0, 5.0)).norm() - 3.0).abs() < 0.05);
+/// }
+///
+/// assert!(differential_growth::points_from_svg_path("M0,0 L10,x", 1.0).is_err());
+/// ```
+///
+pub fn points_from_svg_path(d: &str, spacing: f64) -> Result<Vec<Vec<Point2<f64>>>, SvgPathError> {
+    let mut parser: Parser = Parser {
+        bytes: d.as_bytes(),
+        position: 0,
+    };
+    // Curves are flattened finer than the spacing, so resampling follows them closely.
+    let mut flattener: Flattener = Flattener {
+        tolerance: spacing / 4.0,
+        subpaths: Vec::new(),
+        current: Point2::origin(),
+        start: Point2::origin(),
+        last_control: None,
+    };
+
+    let mut command: Option<u8> = None;
+    loop {
+        parser.skip_separators();
+        let next: u8 = match parser.peek() {
+            Some(next) => next,
+            None => break,
+        };
+
+        if next.is_ascii_alphabetic() {
+            parser.position += 1;
+            command = Some(next);
+        } else {
+            // Numbers without a command repeat the last one, lines after a moveto.
+            command = match command {
+                Some(b'M') => Some(b'L'),
+                Some(b'm') => Some(b'l'),
+                Some(b'Z' | b'z') | None => {
+                    return Err(SvgPathError::UnexpectedCharacter {
+                        position: parser.position,
+                        character: char::from(next),
+                    })
+                }
+                other => other,
+            };
+        }
+
+        let command: u8 = command.unwrap_or_default();
+        if flattener.subpaths.is_empty() && command != b'M' && command != b'm' {
+            return Err(SvgPathError::MissingMoveTo);
+        }
+        flattener.command(command, &mut parser)?;
+    }
+
+    Ok(flattener
+        .subpaths
+        .into_iter()
+        .filter(|(points, _)| points.len() > 1)
+        .map(|(points, closed)| geometry::resample_evenly(&points, closed, spacing))
+        .collect())
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b',')) {
+            self.position += 1;
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, SvgPathError> {
+        self.skip_separators();
+        let start: usize = self.position;
+        let digits = |parser: &mut Parser| {
+            let from: usize = parser.position;
+            while parser.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+                parser.position += 1;
+            }
+            parser.position > from
+        };
+
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut has_digits: bool = digits(self);
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            self.position = start;
+            return Err(SvgPathError::ExpectedNumber { position: start });
+        }
+        // Only an exponent with digits belongs to the number.
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end: usize = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if !digits(self) {
+                self.position = mantissa_end;
+            }
+        }
+
+        core::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .ok_or(SvgPathError::ExpectedNumber { position: start })
+    }
+
+    fn point(&mut self) -> Result<Vector2<f64>, SvgPathError> {
+        Ok(Vector2::new(self.number()?, self.number()?))
+    }
+
+    // Flags of arcs can be written without separators, like `a5 5 0 1010 10`.
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_separators();
+        match self.peek() {
+            Some(flag @ (b'0' | b'1')) => {
+                self.position += 1;
+                Ok(flag == b'1')
+            }
+            _ => Err(SvgPathError::ExpectedNumber { position: self.position }),
+        }
+    }
+}
+
+struct Flattener {
+    tolerance: f64,
+    // The flattened points of every subpath and whether it was closed.
+    subpaths: Vec<(Vec<Point2<f64>>, bool)>,
+    current: Point2<f64>,
+    start: Point2<f64>,
+    // The last control point of a curve, with whether it was cubic, for the smooth curve commands.
+    last_control: Option<(Point2<f64>, bool)>,
+}
+
+impl Flattener {
+    fn command(&mut self, command: u8, parser: &mut Parser) -> Result<(), SvgPathError> {
+        // Lowercase commands are relative to the current point.
+        let origin: Vector2<f64> = if command.is_ascii_lowercase() {
+            self.current.coords
+        } else {
+            Vector2::default()
+        };
+        let mut last_control: Option<(Point2<f64>, bool)> = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point: Point2<f64> = Point2::from(origin + parser.point()?);
+                self.subpaths.push((vec![point], false));
+                self.current = point;
+                self.start = point;
+            }
+            b'L' => {
+                let point: Point2<f64> = Point2::from(origin + parser.point()?);
+                self.line_to(point);
+            }
+            b'H' => {
+                let x: f64 = origin.x + parser.number()?;
+                self.line_to(Point2::new(x, self.current.y));
+            }
+            b'V' => {
+                let y: f64 = origin.y + parser.number()?;
+                self.line_to(Point2::new(self.current.x, y));
+            }
+            b'C' | b'S' => {
+                let first: Point2<f64> = match command.to_ascii_uppercase() {
+                    b'C' => Point2::from(origin + parser.point()?),
+                    _ => self.reflected_control(true),
+                };
+                let second: Point2<f64> = Point2::from(origin + parser.point()?);
+                let end: Point2<f64> = Point2::from(origin + parser.point()?);
+                let start: Point2<f64> = self.current;
+                let length: f64 = nalgebra::distance(&start, &first)
+                    + nalgebra::distance(&first, &second)
+                    + nalgebra::distance(&second, &end);
+                self.curve_to(length, |t| {
+                    let u: f64 = 1.0 - t;
+                    Point2::from(
+                        start.coords * (u * u * u)
+                            + first.coords * (3.0 * u * u * t)
+                            + second.coords * (3.0 * u * t * t)
+                            + end.coords * (t * t * t),
+                    )
+                });
+                last_control = Some((second, true));
+            }
+            b'Q' | b'T' => {
+                let control: Point2<f64> = match command.to_ascii_uppercase() {
+                    b'Q' => Point2::from(origin + parser.point()?),
+                    _ => self.reflected_control(false),
+                };
+                let end: Point2<f64> = Point2::from(origin + parser.point()?);
+                let start: Point2<f64> = self.current;
+                let length: f64 = nalgebra::distance(&start, &control) + nalgebra::distance(&control, &end);
+                self.curve_to(length, |t| {
+                    let u: f64 = 1.0 - t;
+                    Point2::from(start.coords * (u * u) + control.coords * (2.0 * u * t) + end.coords * (t * t))
+                });
+                last_control = Some((control, false));
+            }
+            b'A' => {
+                let radii: Vector2<f64> = Vector2::new(parser.number()?.abs(), parser.number()?.abs());
+                let rotation: f64 = parser.number()?.to_radians();
+                let large_arc: bool = parser.flag()?;
+                let sweep: bool = parser.flag()?;
+                let end: Point2<f64> = Point2::from(origin + parser.point()?);
+                self.arc_to(radii, rotation, large_arc, sweep, end);
+            }
+            b'Z' => {
+                if let Some((points, closed)) = self.subpaths.last_mut() {
+                    if points.len() > 1 && points.last() == points.first() {
+                        points.pop();
+                    }
+                    *closed = true;
+                }
+                self.current = self.start;
+                // Drawing on after closing starts a new subpath at the same point.
+                self.subpaths.push((vec![self.start], false));
+            }
+            _ => {
+                return Err(SvgPathError::UnexpectedCharacter {
+                    position: parser.position - 1,
+                    character: char::from(command),
+                })
+            }
+        }
+
+        self.last_control = last_control;
+        Ok(())
+    }
+
+    fn line_to(&mut self, point: Point2<f64>) {
+        if let Some((points, _)) = self.subpaths.last_mut() {
+            points.push(point);
+        }
+        self.current = point;
+    }
+
+    // Adds points along a curve from the current point, `length` estimating its length.
+    fn curve_to(&mut self, length: f64, curve: impl Fn(f64) -> Point2<f64>) {
+        let segments: usize = self.segments(length);
+        for i in 1..=segments {
+            self.line_to(curve(i as f64 / segments as f64));
+        }
+    }
+
+    // The first control point of a smooth curve, the last one of a previous curve
+    // of the same kind mirrored around the current point.
+    fn reflected_control(&self, cubic: bool) -> Point2<f64> {
+        match self.last_control {
+            Some((control, was_cubic)) if was_cubic == cubic => self.current + (self.current - control),
+            _ => self.current,
+        }
+    }
+
+    // Converts the endpoint parameterization of SVG arcs to a center and angles,
+    // following the implementation notes of the SVG specification.
+    fn arc_to(&mut self, mut radii: Vector2<f64>, rotation: f64, large_arc: bool, sweep: bool, end: Point2<f64>) {
+        if radii.x == 0.0 || radii.y == 0.0 || self.current == end {
+            return self.line_to(end);
+        }
+
+        let (sin, cos): (f64, f64) = (f64::sin(rotation), f64::cos(rotation));
+        let half: Vector2<f64> = (self.current - end) / 2.0;
+        let start: Vector2<f64> = Vector2::new(cos * half.x + sin * half.y, -sin * half.x + cos * half.y);
+
+        // Radii too small to reach the end are scaled up.
+        let lambda: f64 = (start.x / radii.x).powi(2) + (start.y / radii.y).powi(2);
+        if lambda > 1.0 {
+            radii *= f64::sqrt(lambda);
+        }
+
+        let (rx_sq, ry_sq): (f64, f64) = (radii.x * radii.x, radii.y * radii.y);
+        let numerator: f64 = rx_sq * ry_sq - rx_sq * start.y * start.y - ry_sq * start.x * start.x;
+        let denominator: f64 = rx_sq * start.y * start.y + ry_sq * start.x * start.x;
+        let mut coefficient: f64 = f64::sqrt((numerator / denominator).max(0.0));
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+        let center: Vector2<f64> = Vector2::new(radii.x * start.y / radii.y, -radii.y * start.x / radii.x) * coefficient;
+        let middle: Vector2<f64> = (self.current.coords + end.coords) / 2.0;
+        let world_center: Vector2<f64> =
+            Vector2::new(cos * center.x - sin * center.y, sin * center.x + cos * center.y) + middle;
+
+        let angle = |u: Vector2<f64>, v: Vector2<f64>| f64::atan2(u.perp(&v), u.dot(&v));
+        let from: Vector2<f64> = Vector2::new((start.x - center.x) / radii.x, (start.y - center.y) / radii.y);
+        let to: Vector2<f64> = Vector2::new((-start.x - center.x) / radii.x, (-start.y - center.y) / radii.y);
+        let first_angle: f64 = angle(Vector2::new(1.0, 0.0), from);
+        let mut sweep_angle: f64 = angle(from, to);
+        if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        } else if sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        }
+
+        let segments: usize = self.segments(radii.x.max(radii.y) * sweep_angle.abs());
+        for i in 1..segments {
+            let theta: f64 = first_angle + sweep_angle * i as f64 / segments as f64;
+            let (x, y): (f64, f64) = (radii.x * f64::cos(theta), radii.y * f64::sin(theta));
+            self.line_to(Point2::from(Vector2::new(cos * x - sin * y, sin * x + cos * y) + world_center));
+        }
+        // The end is exact, not subject to rounding.
+        self.line_to(end);
+    }
+
+    fn segments(&self, length: f64) -> usize {
+        if !(self.tolerance > 0.0 && length.is_finite()) {
+            return 1;
+        }
+        f64::ceil(length / self.tolerance).clamp(1.0, 10_000.0) as usize
+    }
+}
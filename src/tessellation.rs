@@ -0,0 +1,69 @@
+use lyon::{
+    math::point,
+    path::Path,
+    tessellation::{BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, TessellationError, VertexBuffers},
+};
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// Triangles covering the inside of the path, in the formats GPU vertex and index buffers expect.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriangleMesh {
+    /// The corners of the triangles.
+    pub positions: Vec<[f32; 2]>,
+    /// Indices into the positions, every three of them a triangle.
+    pub indices: Vec<u32>,
+}
+
+impl DifferentialGrowth {
+    /// Tessellates the inside of the path into triangles, to fill the grown shape on the GPU.
+    ///
+    /// The inside follows the even-odd rule, like [`crate::Region::Polygon`], so the result
+    /// doesn't depend on which way the path goes around. An open path is filled as if its
+    /// ends were connected. Paths of less than 3 nodes have no inside and give an empty mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nalgebra::Point2;
+    ///
+    /// let starting_points = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(10.0, 10.0), Point2::new(0.0, 10.0)];
+    /// let differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+    ///
+    /// let mesh = differential_growth.to_mesh().unwrap();
+    /// assert_eq!(mesh.indices.len(), 6);
+    /// assert!(mesh.indices.iter().all(|&index| (index as usize) < mesh.positions.len()));
+    /// ```
+    ///
+    pub fn to_mesh(&self) -> Result<TriangleMesh, TessellationError> {
+        if self.nodes.len() < 3 {
+            return Ok(TriangleMesh::default());
+        }
+
+        let mut builder = Path::builder();
+        let mut points = self
+            .get_unwrapped_points()
+            .into_iter()
+            .map(|position| point(position.x as f32, position.y as f32));
+        if let Some(first) = points.next() {
+            builder.begin(first);
+        }
+        for point in points {
+            builder.line_to(point);
+        }
+        builder.end(true);
+        let path: Path = builder.build();
+
+        let mut buffers: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+        FillTessellator::new().tessellate_path(
+            &path,
+            &FillOptions::default().with_fill_rule(FillRule::EvenOdd),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position().to_array()),
+        )?;
+
+        Ok(TriangleMesh {
+            positions: buffers.vertices,
+            indices: buffers.indices,
+        })
+    }
+}
@@ -0,0 +1,214 @@
+use core::ops::AddAssign;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{
+    geometry,
+    scene::{ScenePath, SeedShape},
+};
+
+/// The outline of a single character, in font units where the em is 1 and y points up.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Glyph {
+    /// The closed contours of the outline relative to the origin of the glyph on the
+    /// baseline, flattened to points. Letters like `o` have a contour for their hole.
+    pub contours: Vec<Vec<Point2<f64>>>,
+    /// How far the next glyph starts from the origin of this one.
+    pub advance: f64,
+}
+
+/// A source of glyph outlines for [`TextLayout`].
+///
+/// Implement this on top of a font parsing crate like `ttf-parser`, flattening the
+/// curves of every outline into points and dividing by the units per em of the font.
+pub trait Font {
+    /// Returns the outline of `character`, or `None` when the font doesn't have it.
+    fn glyph(&self, character: char) -> Option<Glyph>;
+
+    /// Returns the extra space between `left` and `right` when they follow eachother,
+    /// negative to move them closer. Defaults to no kerning.
+    fn kerning(&self, _left: char, _right: char) -> f64 {
+        0.0
+    }
+}
+
+/// The contours of one character of a laid out text, see [`TextLayout::layout()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlacedGlyph {
+    pub character: char,
+    /// The index of the character among the visible characters of the text,
+    /// used as its collision group.
+    pub index: usize,
+    /// The contours of the glyph, scaled and moved to their place on the baseline.
+    pub contours: Vec<Vec<Point2<f64>>>,
+}
+
+/// Lays out a string along a baseline into seed paths, one collision group per character,
+/// so growing a word takes a few lines.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Font, Glyph, Interaction, Scene, TextLayout};
+/// use nalgebra::Point2;
+///
+/// // A font where every character is a square.
+/// struct Squares;
+///
+/// impl Font for Squares {
+///     fn glyph(&self, character: char) -> Option<Glyph> {
+///         let contours = if character == ' ' {
+///             Vec::new()
+///         } else {
+///             vec![vec![Point2::new(0.0, 0.0), Point2::new(0.6, 0.0), Point2::new(0.6, 0.7), Point2::new(0.0, 0.7)]]
+///         };
+///         Some(Glyph { contours, advance: 0.7 })
+///     }
+/// }
+///
+/// let layout = TextLayout::new(Point2::new(0.0, 0.0), 100.0);
+/// let glyphs = layout.layout(&Squares, "Hi you");
+/// assert_eq!(glyphs.len(), 5);
+/// assert_eq!(glyphs[1].contours[0][0], Point2::new(70.0, 0.0));
+///
+/// let scene = Scene {
+///     paths: layout.scene_paths(&Squares, "Hi you"),
+///     interaction: Interaction::RepelOtherGroups,
+///     ..Default::default()
+/// };
+/// let mut multi_path_growth = scene.build();
+/// multi_path_growth.tick();
+/// assert_eq!(multi_path_growth.groups(), &[0, 1, 2, 3, 4]);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextLayout {
+    /// Where the baseline of the first line starts.
+    pub origin: Point2<f64>,
+    /// The size of the em, the font units are multiplied by this.
+    pub size: f64,
+    /// Extra space between all characters, relative to the size.
+    pub tracking: f64,
+    /// The distance between the baselines of consecutive lines, relative to the size.
+    pub line_height: f64,
+}
+
+impl TextLayout {
+    /// Returns a layout without tracking and a line height of 1.2.
+    pub fn new(origin: Point2<f64>, size: f64) -> TextLayout {
+        TextLayout {
+            origin,
+            size,
+            tracking: 0.0,
+            line_height: 1.2,
+        }
+    }
+
+    /// Places every character of `text` on the baseline, applying tracking and kerning.
+    /// Lines are separated by `\n`, characters the font doesn't have are skipped
+    /// and characters without contours like spaces only move the next character.
+    pub fn layout(&self, font: &impl Font, text: &str) -> Vec<PlacedGlyph> {
+        let mut placed: Vec<PlacedGlyph> = Vec::new();
+        let mut pen: Point2<f64> = self.origin;
+        let mut previous: Option<char> = None;
+
+        for character in text.chars() {
+            if character == '\n' {
+                pen = Point2::new(self.origin.x, pen.y - self.line_height * self.size);
+                previous = None;
+                continue;
+            }
+
+            let glyph: Glyph = match font.glyph(character) {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+
+            if let Some(previous) = previous {
+                pen.x.add_assign(font.kerning(previous, character) * self.size);
+            }
+
+            if !glyph.contours.is_empty() {
+                let offset: Vector2<f64> = pen.coords;
+                placed.push(PlacedGlyph {
+                    character,
+                    index: placed.len(),
+                    contours: glyph
+                        .contours
+                        .iter()
+                        .map(|contour| contour.iter().map(|point| point * self.size + offset).collect())
+                        .collect(),
+                });
+            }
+
+            pen.x.add_assign((glyph.advance + self.tracking) * self.size);
+            previous = Some(character);
+        }
+
+        placed
+    }
+
+    /// Returns a closed [`ScenePath`] for every contour of the laid out text,
+    /// in the collision group of its character. With [`Interaction::RepelOtherGroups`]
+    /// the characters keep eachother at a distance while the contours of a single
+    /// character, like the hole of an `o`, grow freely.
+    ///
+    /// [`Interaction::RepelOtherGroups`]: crate::Interaction::RepelOtherGroups
+    pub fn scene_paths(&self, font: &impl Font, text: &str) -> Vec<ScenePath> {
+        self.layout(font, text)
+            .into_iter()
+            .flat_map(|glyph| {
+                let group: u32 = glyph.index as u32;
+                glyph.contours.into_iter().map(move |contour| ScenePath {
+                    group,
+                    ..ScenePath::new(SeedShape::Points(contour))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns starting points along the outlines of `text`, one closed loop of points
+/// `spacing` apart per contour, with the baseline of the first line on the x-axis.
+/// Feed them into separate paths, or a [`crate::Scene`] through [`TextLayout::scene_paths()`]
+/// to keep the characters apart.
+///
+/// # Arguments
+///
+/// * `font` - Where the outlines come from, like a `TtfFont` with the `font` feature.
+/// * `size` - The size of the em.
+/// * `spacing` - The distance between points along the outlines.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{Font, Glyph};
+/// use nalgebra::Point2;
+///
+/// // A font where every character is a square.
+/// struct Squares;
+///
+/// impl Font for Squares {
+///     fn glyph(&self, _character: char) -> Option<Glyph> {
+///         let square = vec![Point2::new(0.0, 0.0), Point2::new(0.5, 0.0), Point2::new(0.5, 0.5), Point2::new(0.0, 0.5)];
+///         Some(Glyph { contours: vec![square], advance: 0.7 })
+///     }
+/// }
+///
+/// let loops = differential_growth::points_from_text("ab", &Squares, 100.0, 5.0);
+/// assert_eq!(loops.len(), 2);
+/// assert_eq!(loops[0].len(), 40);
+/// assert_eq!(loops[1][0], Point2::new(70.0, 0.0));
+/// ```
+///
+pub fn points_from_text(text: &str, font: &impl Font, size: f64, spacing: f64) -> Vec<Vec<Point2<f64>>> {
+    TextLayout::new(Point2::origin(), size)
+        .layout(font, text)
+        .into_iter()
+        .flat_map(|glyph| glyph.contours)
+        .filter(|contour| contour.len() > 2)
+        .map(|contour| geometry::resample_evenly(&contour, true, spacing))
+        .collect()
+}
@@ -0,0 +1,258 @@
+/// How the nodes of a path are connected to eachother.
+///
+/// All wraparound index arithmetic goes through this type so the
+/// first and last node are handled the same way everywhere.
+///
+/// Every topology is a single path where each node has at most two neighbors,
+/// branching graphs of nodes aren't supported.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// The last node is connected to the first node.
+    #[default]
+    Closed,
+    /// The first and last node are endpoints with a single neighbor.
+    Open,
+}
+
+impl Topology {
+    /// Returns the index of the node following node `i` in a path of `len` nodes,
+    /// or `None` when `i` is the end of an open path.
+    ///
+    /// A path needs at least two nodes for a node to have a neighbor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// assert_eq!(Topology::Closed.next(0, 4), Some(1));
+    /// assert_eq!(Topology::Closed.next(3, 4), Some(0));
+    /// assert_eq!(Topology::Open.next(2, 4), Some(3));
+    /// assert_eq!(Topology::Open.next(3, 4), None);
+    ///
+    /// assert_eq!(Topology::Closed.next(0, 2), Some(1));
+    /// assert_eq!(Topology::Closed.next(1, 2), Some(0));
+    /// assert_eq!(Topology::Closed.next(0, 1), None);
+    /// assert_eq!(Topology::Open.next(0, 1), None);
+    /// assert_eq!(Topology::Closed.next(4, 4), None);
+    /// ```
+    ///
+    pub fn next(&self, i: usize, len: usize) -> Option<usize> {
+        if len < 2 || i >= len {
+            return None;
+        }
+
+        if i == len - 1 {
+            match self {
+                Topology::Closed => Some(0),
+                Topology::Open => None,
+            }
+        } else {
+            Some(i + 1)
+        }
+    }
+
+    /// Returns the index of the node preceding node `i` in a path of `len` nodes,
+    /// or `None` when `i` is the start of an open path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// assert_eq!(Topology::Closed.prev(0, 4), Some(3));
+    /// assert_eq!(Topology::Closed.prev(3, 4), Some(2));
+    /// assert_eq!(Topology::Open.prev(0, 4), None);
+    /// assert_eq!(Topology::Open.prev(1, 4), Some(0));
+    ///
+    /// assert_eq!(Topology::Closed.prev(0, 2), Some(1));
+    /// assert_eq!(Topology::Closed.prev(0, 1), None);
+    /// assert_eq!(Topology::Open.prev(0, 0), None);
+    /// assert_eq!(Topology::Closed.prev(5, 4), None);
+    /// ```
+    ///
+    pub fn prev(&self, i: usize, len: usize) -> Option<usize> {
+        if len < 2 || i >= len {
+            return None;
+        }
+
+        if i == 0 {
+            match self {
+                Topology::Closed => Some(len - 1),
+                Topology::Open => None,
+            }
+        } else {
+            Some(i - 1)
+        }
+    }
+
+    /// Returns the previous and next neighbor of node `i` in a path of `len` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// assert_eq!(Topology::Closed.neighbors(0, 3), (Some(2), Some(1)));
+    /// assert_eq!(Topology::Closed.neighbors(1, 3), (Some(0), Some(2)));
+    /// assert_eq!(Topology::Closed.neighbors(2, 3), (Some(1), Some(0)));
+    ///
+    /// assert_eq!(Topology::Open.neighbors(0, 3), (None, Some(1)));
+    /// assert_eq!(Topology::Open.neighbors(1, 3), (Some(0), Some(2)));
+    /// assert_eq!(Topology::Open.neighbors(2, 3), (Some(1), None));
+    /// ```
+    ///
+    pub fn neighbors(&self, i: usize, len: usize) -> (Option<usize>, Option<usize>) {
+        (self.prev(i, len), self.next(i, len))
+    }
+
+    /// Returns the least amount of nodes that still form a path with this topology,
+    /// a triangle for closed paths and a single edge for open paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// assert_eq!(Topology::Closed.min_nodes(), 3);
+    /// assert_eq!(Topology::Open.min_nodes(), 2);
+    /// ```
+    ///
+    pub fn min_nodes(&self) -> usize {
+        match self {
+            Topology::Closed => 3,
+            Topology::Open => 2,
+        }
+    }
+
+    /// Returns the amount of edges in a path of `len` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// assert_eq!(Topology::Closed.edge_count(4), 4);
+    /// assert_eq!(Topology::Open.edge_count(4), 3);
+    /// assert_eq!(Topology::Closed.edge_count(2), 2);
+    /// assert_eq!(Topology::Closed.edge_count(1), 0);
+    /// assert_eq!(Topology::Open.edge_count(0), 0);
+    /// ```
+    ///
+    pub fn edge_count(&self, len: usize) -> usize {
+        if len < 2 {
+            return 0;
+        }
+
+        match self {
+            Topology::Closed => len,
+            Topology::Open => len - 1,
+        }
+    }
+
+    /// Returns an iterator over the `(from, to)` node indices of every edge
+    /// in a path of `len` nodes, in path order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::Topology;
+    ///
+    /// let closed: Vec<(usize, usize)> = Topology::Closed.edges(3).collect();
+    /// assert_eq!(closed, vec![(0, 1), (1, 2), (2, 0)]);
+    ///
+    /// let open: Vec<(usize, usize)> = Topology::Open.edges(3).collect();
+    /// assert_eq!(open, vec![(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(Topology::Closed.edges(1).count(), 0);
+    /// ```
+    ///
+    pub fn edges(&self, len: usize) -> impl Iterator<Item = (usize, usize)> {
+        let topology: Topology = *self;
+        (0..topology.edge_count(len)).map(move |i| (i, (i + 1) % len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Topology;
+    use crate::prelude::*;
+
+    const TOPOLOGIES: [Topology; 2] = [Topology::Closed, Topology::Open];
+
+    #[test]
+    fn fewer_than_two_nodes_have_no_neighbors_or_edges() {
+        for topology in TOPOLOGIES {
+            for len in 0..2 {
+                for i in 0..3 {
+                    assert_eq!(topology.next(i, len), None);
+                    assert_eq!(topology.prev(i, len), None);
+                    assert_eq!(topology.neighbors(i, len), (None, None));
+                }
+                assert_eq!(topology.edge_count(len), 0);
+                assert_eq!(topology.edges(len).count(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn two_nodes() {
+        assert_eq!(Topology::Closed.neighbors(0, 2), (Some(1), Some(1)));
+        assert_eq!(Topology::Closed.neighbors(1, 2), (Some(0), Some(0)));
+        assert_eq!(Topology::Closed.edges(2).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+
+        assert_eq!(Topology::Open.neighbors(0, 2), (None, Some(1)));
+        assert_eq!(Topology::Open.neighbors(1, 2), (Some(0), None));
+        assert_eq!(Topology::Open.edges(2).collect::<Vec<_>>(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn wraps_around_only_when_closed() {
+        for len in 3..8 {
+            assert_eq!(Topology::Closed.next(len - 1, len), Some(0));
+            assert_eq!(Topology::Closed.prev(0, len), Some(len - 1));
+            assert_eq!(Topology::Closed.edges(len).last(), Some((len - 1, 0)));
+
+            assert_eq!(Topology::Open.next(len - 1, len), None);
+            assert_eq!(Topology::Open.prev(0, len), None);
+            assert_eq!(Topology::Open.edges(len).last(), Some((len - 2, len - 1)));
+        }
+    }
+
+    #[test]
+    fn indices_past_the_end_have_no_neighbors() {
+        for topology in TOPOLOGIES {
+            for len in 0..5 {
+                for i in len..len + 3 {
+                    assert_eq!(topology.neighbors(i, len), (None, None));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_and_edges_agree() {
+        for topology in TOPOLOGIES {
+            for len in 0..8 {
+                let edges: Vec<(usize, usize)> = topology.edges(len).collect();
+                assert_eq!(edges.len(), topology.edge_count(len));
+
+                for (k, &(from, to)) in edges.iter().enumerate() {
+                    assert_eq!(from, k);
+                    assert_eq!(topology.next(from, len), Some(to));
+                    assert_eq!(topology.prev(to, len), Some(from));
+                }
+
+                for i in 0..len {
+                    let (prev, next) = topology.neighbors(i, len);
+                    assert_eq!(prev, topology.prev(i, len));
+                    assert_eq!(next, topology.next(i, len));
+                    if let Some(next) = next {
+                        assert!(edges.contains(&(i, next)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use core::ops::{AddAssign, MulAssign};
+
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::node::Node;
+
+/// A grid the nodes deposit a trail into every tick, which fades and spreads out over
+/// time and feeds back into the simulation as a force and a growth multiplier.
+///
+/// With a positive `force` nodes are pushed away from where the path has been, so it
+/// avoids its own history. With a negative `force` nodes are pulled into existing trails,
+/// like the slime mold behind physarum simulations. Outside the grid the trail is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::TrailMap;
+/// use nalgebra::Point2;
+///
+/// let mut trail_map = TrailMap::new(Point2::new(-100.0, -100.0), Point2::new(100.0, 100.0), 2.0);
+/// trail_map.force = 0.5;
+/// trail_map.growth_response = -0.05;
+/// assert_eq!((trail_map.width(), trail_map.height()), (100, 100));
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_trail_map(trail_map);
+///
+/// for _ in 0..20 {
+///     differential_growth.tick();
+/// }
+///
+/// // The trail is strongest where the path is.
+/// let trail_map = differential_growth.trail_map().unwrap();
+/// let on_path = trail_map.value_at(&differential_growth.nodes()[0].position);
+/// assert!(on_path > trail_map.value_at(&Point2::new(90.0, 90.0)));
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrailMap {
+    /// The corner of the grid with the smallest coordinates.
+    pub min: Point2<f64>,
+    /// The width and height of a single cell.
+    pub cell_size: f64,
+    /// The amount every node adds to the trail per tick.
+    pub deposit: f64,
+    /// The fraction of the trail that fades away per tick, between 0 and 1.
+    pub decay: f64,
+    /// The fraction of every cell that is replaced by the average of its
+    /// neighborhood per tick, between 0 and 1. Blurs the trail.
+    pub diffusion: f64,
+    /// How strongly nodes are pushed down the slope of the trail,
+    /// positive to avoid the trail and negative to follow it.
+    pub force: f64,
+    /// How the trail changes the growth rate: the growth multiplier is
+    /// `1 + growth_response * value`, so negative values slow down growth
+    /// where the trail is strong. Never drops below 0.
+    pub growth_response: f64,
+    width: usize,
+    height: usize,
+    values: Vec<f64>,
+}
+
+impl TrailMap {
+    /// Returns an empty trail map covering the rectangle from `min` to `max`.
+    ///
+    /// Nodes deposit 1 per tick, 5% of the trail fades away per tick, 10% diffuses
+    /// and the trail doesn't affect the simulation until `force` or `growth_response` is set.
+    pub fn new(min: Point2<f64>, max: Point2<f64>, cell_size: f64) -> TrailMap {
+        let width: usize = ((max.x - min.x) / cell_size).ceil().max(1.0) as usize;
+        let height: usize = ((max.y - min.y) / cell_size).ceil().max(1.0) as usize;
+
+        TrailMap {
+            min,
+            cell_size,
+            deposit: 1.0,
+            decay: 0.05,
+            diffusion: 0.1,
+            force: 0.0,
+            growth_response: 0.0,
+            width,
+            height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    /// Returns the amount of cells along the x axis.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the amount of cells along the y axis.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value of every cell, row by row starting at `min`,
+    /// to draw the trail as a texture.
+    #[must_use]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Empties the trail.
+    pub fn clear(&mut self) {
+        self.values.iter_mut().for_each(|value| *value = 0.0);
+    }
+
+    /// Returns the trail at `point`, interpolated between the centers of the surrounding cells.
+    #[must_use]
+    pub fn value_at(&self, point: &Point2<f64>) -> f64 {
+        self.bilinear(point)
+            .iter()
+            .map(|&(cell, weight)| cell.map_or(0.0, |cell| self.values[cell] * weight))
+            .sum()
+    }
+
+    /// Returns the direction in which the trail increases the fastest at `point`,
+    /// scaled by how fast it increases per unit of distance.
+    #[must_use]
+    pub fn gradient_at(&self, point: &Point2<f64>) -> Vector2<f64> {
+        let step: f64 = self.cell_size;
+        let dx: Vector2<f64> = Vector2::new(step, 0.0);
+        let dy: Vector2<f64> = Vector2::new(0.0, step);
+
+        Vector2::new(
+            self.value_at(&(point + dx)) - self.value_at(&(point - dx)),
+            self.value_at(&(point + dy)) - self.value_at(&(point - dy)),
+        ) / (2.0 * step)
+    }
+
+    /// Returns the force the trail applies to a node at `point`.
+    #[must_use]
+    pub fn force_at(&self, point: &Point2<f64>) -> Vector2<f64> {
+        if self.force == 0.0 {
+            return Vector2::default();
+        }
+
+        -self.gradient_at(point) * self.force
+    }
+
+    /// Returns the growth multiplier at `point`.
+    #[must_use]
+    pub fn growth_multiplier_at(&self, point: &Point2<f64>) -> f64 {
+        (1.0 + self.growth_response * self.value_at(point)).max(0.0)
+    }
+
+    /// Adds `amount` to the trail at `point`, spread over the surrounding cells.
+    pub fn deposit_at(&mut self, point: &Point2<f64>, amount: f64) {
+        for (cell, weight) in self.bilinear(point) {
+            if let Some(cell) = cell {
+                self.values[cell].add_assign(amount * weight);
+            }
+        }
+    }
+
+    // Deposits the trail of every node and lets the trail fade and diffuse over `dt` ticks.
+    pub(crate) fn update(&mut self, nodes: &[Node], dt: f64) {
+        if self.diffusion > 0.0 {
+            self.diffuse(self.diffusion.min(1.0) * dt.min(1.0));
+        }
+
+        if self.decay > 0.0 {
+            let remaining: f64 = (1.0 - self.decay.min(1.0)).powf(dt);
+            self.values.iter_mut().for_each(|value| value.mul_assign(remaining));
+        }
+
+        for node in nodes {
+            self.deposit_at(&node.position, self.deposit * dt);
+        }
+    }
+
+    // Blends every cell with the average of its 3x3 neighborhood, cells outside the grid are skipped.
+    fn diffuse(&mut self, rate: f64) {
+        let mut diffused: Vec<f64> = Vec::with_capacity(self.values.len());
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut sum: f64 = 0.0;
+                let mut count: usize = 0;
+                for ny in y.saturating_sub(1)..(y + 2).min(self.height) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(self.width) {
+                        sum.add_assign(self.values[ny * self.width + nx]);
+                        count.add_assign(1);
+                    }
+                }
+
+                let value: f64 = self.values[y * self.width + x];
+                diffused.push(value + (sum / count as f64 - value) * rate);
+            }
+        }
+
+        self.values = diffused;
+    }
+
+    // Returns the 4 cells around `point` with their bilinear weights, `None` for cells outside the grid.
+    fn bilinear(&self, point: &Point2<f64>) -> [(Option<usize>, f64); 4] {
+        // Relative to the center of the first cell, in cells.
+        let x: f64 = (point.x - self.min.x) / self.cell_size - 0.5;
+        let y: f64 = (point.y - self.min.y) / self.cell_size - 0.5;
+        if !x.is_finite() || !y.is_finite() {
+            return [(None, 0.0); 4];
+        }
+        let (x0, y0): (f64, f64) = (x.floor(), y.floor());
+        let (tx, ty): (f64, f64) = (x - x0, y - y0);
+
+        let cell = |cx: f64, cy: f64| -> Option<usize> {
+            if cx < 0.0 || cy < 0.0 || cx >= self.width as f64 || cy >= self.height as f64 {
+                return None;
+            }
+            Some(cy as usize * self.width + cx as usize)
+        };
+
+        [
+            (cell(x0, y0), (1.0 - tx) * (1.0 - ty)),
+            (cell(x0 + 1.0, y0), tx * (1.0 - ty)),
+            (cell(x0, y0 + 1.0), (1.0 - tx) * ty),
+            (cell(x0 + 1.0, y0 + 1.0), tx * ty),
+        ]
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+use crate::params::ParamSet;
+
+/// A unit of length.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LengthUnit {
+    Millimeters,
+    Centimeters,
+    Inches,
+    /// Pixels at the given resolution in dots per inch.
+    /// SVG and CSS use 96 dpi.
+    Pixels { dpi: f64 },
+}
+
+impl LengthUnit {
+    /// Returns the length of one unit in millimeters.
+    pub fn millimeters(&self) -> f64 {
+        match *self {
+            LengthUnit::Millimeters => 1.0,
+            LengthUnit::Centimeters => 10.0,
+            LengthUnit::Inches => 25.4,
+            LengthUnit::Pixels { dpi } => 25.4 / dpi,
+        }
+    }
+}
+
+/// What the numbers of the simulation mean.
+///
+/// The algorithm itself is unitless: every tick is one time step and positions,
+/// speeds and forces are in world units, per tick and per tick². Units give those
+/// numbers a meaning, so parameters can be written in millimeters and seconds and
+/// exports know how large the result is.
+///
+/// Defaults to one world unit being an SVG pixel (1/96 inch) and 60 ticks per second.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{LengthUnit, Units};
+///
+/// // Every world unit is a millimeter, every tick is 1/30 of a second.
+/// let units = Units::new(LengthUnit::Millimeters, 30.0);
+///
+/// assert_eq!(units.to_world(2.0, LengthUnit::Centimeters), 20.0);
+/// assert_eq!(units.from_world(25.4, LengthUnit::Inches), 1.0);
+/// // 30 mm per second is 1 mm per tick.
+/// assert_eq!(units.speed_to_world(30.0, LengthUnit::Millimeters), 1.0);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Units {
+    /// The length of one world unit.
+    pub length: LengthUnit,
+    /// The amount of ticks that make up one second.
+    pub ticks_per_second: f64,
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Units {
+            length: LengthUnit::Pixels { dpi: 96.0 },
+            ticks_per_second: 60.0,
+        }
+    }
+}
+
+impl Units {
+    /// Returns Units where one world unit is one `length` and a tick lasts `1 / ticks_per_second` seconds.
+    pub fn new(length: LengthUnit, ticks_per_second: f64) -> Units {
+        Units {
+            length,
+            ticks_per_second,
+        }
+    }
+
+    /// Converts a length in `unit` to world units.
+    pub fn to_world(&self, value: f64, unit: LengthUnit) -> f64 {
+        value * unit.millimeters() / self.length.millimeters()
+    }
+
+    /// Converts a length in world units to `unit`.
+    pub fn from_world(&self, value: f64, unit: LengthUnit) -> f64 {
+        value * self.length.millimeters() / unit.millimeters()
+    }
+
+    /// Converts a speed in `unit` per second to world units per tick.
+    pub fn speed_to_world(&self, per_second: f64, unit: LengthUnit) -> f64 {
+        self.to_world(per_second, unit) / self.ticks_per_second
+    }
+
+    /// Converts a speed in world units per tick to `unit` per second.
+    pub fn speed_from_world(&self, per_tick: f64, unit: LengthUnit) -> f64 {
+        self.from_world(per_tick, unit) * self.ticks_per_second
+    }
+
+    /// Converts an acceleration in `unit` per second² to world units per tick².
+    /// Forces act on nodes as accelerations.
+    pub fn acceleration_to_world(&self, per_second_sq: f64, unit: LengthUnit) -> f64 {
+        self.to_world(per_second_sq, unit) / self.ticks_per_second.powi(2)
+    }
+
+    /// Converts an acceleration in world units per tick² to `unit` per second².
+    pub fn acceleration_from_world(&self, per_tick_sq: f64, unit: LengthUnit) -> f64 {
+        self.from_world(per_tick_sq, unit) * self.ticks_per_second.powi(2)
+    }
+}
+
+/// The parameters of the algorithm in physical units, see [`Units`].
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{LengthUnit, PhysicalParams, Units};
+///
+/// let physical = PhysicalParams {
+///     unit: LengthUnit::Millimeters,
+///     max_force: 900.0,
+///     max_speed: 30.0,
+///     desired_separation: 7.0,
+///     separation_cohesion_ratio: 1.1,
+///     max_edge_length: 2.5,
+/// };
+///
+/// let units = Units::new(LengthUnit::Millimeters, 30.0);
+/// let params = physical.to_param_set(&units);
+/// assert_eq!(params.max_force, 1.0);
+/// assert_eq!(params.max_speed, 1.0);
+/// assert_eq!(params.desired_separation, 7.0);
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicalParams {
+    /// The unit of all lengths below.
+    pub unit: LengthUnit,
+    /// The maximum force nodes can exert on eachother, per second².
+    pub max_force: f64,
+    /// The maximum magnitude of a node's velocity, per second.
+    pub max_speed: f64,
+    /// The desired separation between nodes.
+    pub desired_separation: f64,
+    /// The ratio between separation and cohesion forces, unitless.
+    pub separation_cohesion_ratio: f64,
+    /// The maximum length between two connected nodes.
+    pub max_edge_length: f64,
+}
+
+impl Default for PhysicalParams {
+    /// The default [`ParamSet`] in default [`Units`], expressed in millimeters.
+    fn default() -> Self {
+        PhysicalParams::from_param_set(&ParamSet::default(), &Units::default(), LengthUnit::Millimeters)
+    }
+}
+
+impl PhysicalParams {
+    /// Converts to the per tick world units the algorithm works with.
+    pub fn to_param_set(&self, units: &Units) -> ParamSet {
+        ParamSet {
+            max_force: units.acceleration_to_world(self.max_force, self.unit),
+            max_speed: units.speed_to_world(self.max_speed, self.unit),
+            desired_separation: units.to_world(self.desired_separation, self.unit),
+            separation_cohesion_ratio: self.separation_cohesion_ratio,
+            max_edge_length: units.to_world(self.max_edge_length, self.unit),
+        }
+    }
+
+    /// Converts from the per tick world units the algorithm works with.
+    pub fn from_param_set(params: &ParamSet, units: &Units, unit: LengthUnit) -> PhysicalParams {
+        PhysicalParams {
+            unit,
+            max_force: units.acceleration_from_world(params.max_force, unit),
+            max_speed: units.speed_from_world(params.max_speed, unit),
+            desired_separation: units.from_world(params.desired_separation, unit),
+            separation_cohesion_ratio: params.separation_cohesion_ratio,
+            max_edge_length: units.from_world(params.max_edge_length, unit),
+        }
+    }
+}
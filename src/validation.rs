@@ -0,0 +1,131 @@
+use core::fmt;
+
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::differential_growth::DifferentialGrowth;
+
+/// Error returned by [`DifferentialGrowth::try_new()`] for starting points and parameters
+/// that can't grow, instead of a simulation that silently does nothing or blows up.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GrowthError {
+    /// There are `len` starting points, fewer than the `min` a path can grow from.
+    TooFewPoints { len: usize, min: usize },
+    /// The starting point at `index` isn't a finite number.
+    NotFinite { index: usize },
+    /// A parameter isn't a positive finite number.
+    InvalidParameter { name: &'static str, value: f64 },
+    /// Nodes only push each other apart up to the desired separation, so edges never
+    /// stretch beyond `max_edge_length` to be split and the path doesn't grow.
+    MaxEdgeLengthTooLong { max_edge_length: f64, desired_separation: f64 },
+}
+
+impl fmt::Display for GrowthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrowthError::TooFewPoints { len, min } => {
+                write!(f, "{} starting points can't grow, at least {} are needed", len, min)
+            }
+            GrowthError::NotFinite { index } => write!(f, "starting point {} isn't finite", index),
+            GrowthError::InvalidParameter { name, value } => {
+                write!(f, "{} is {}, it must be a positive finite number", name, value)
+            }
+            GrowthError::MaxEdgeLengthTooLong {
+                max_edge_length,
+                desired_separation,
+            } => write!(
+                f,
+                "max_edge_length {} isn't smaller than desired_separation {}, the path won't grow",
+                max_edge_length, desired_separation
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GrowthError {}
+
+impl DifferentialGrowth {
+    /// Returns a DifferentialGrowth instance like [`DifferentialGrowth::new()`] does,
+    /// after checking the starting points and parameters can grow.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_points` - At least 1 finite starting point.
+    /// * `max_force` - The maximum force nodes can exert on eachother.
+    /// * `max_speed` - The maximum magnitude of a node's velocity.
+    /// * `desired_separation` - The desired separation between nodes.
+    /// * `separation_cohesion_ratio` - The ratio between separation and cohesion forces.
+    /// * `max_edge_len` - The maximum length between two connected nodes,
+    ///   smaller than `desired_separation`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use differential_growth::{DifferentialGrowth, GrowthError};
+    ///
+    /// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+    /// assert!(DifferentialGrowth::try_new(starting_points.clone(), 1.5, 1.0, 14.0, 1.1, 5.0).is_ok());
+    ///
+    /// assert_eq!(
+    ///     DifferentialGrowth::try_new(starting_points.clone(), 1.5, 0.0, 14.0, 1.1, 5.0).err(),
+    ///     Some(GrowthError::InvalidParameter { name: "max_speed", value: 0.0 })
+    /// );
+    /// assert_eq!(
+    ///     DifferentialGrowth::try_new(starting_points, 1.5, 1.0, 14.0, 1.1, 20.0).err(),
+    ///     Some(GrowthError::MaxEdgeLengthTooLong { max_edge_length: 20.0, desired_separation: 14.0 })
+    /// );
+    /// assert!(DifferentialGrowth::try_new(Vec::new(), 1.5, 1.0, 14.0, 1.1, 5.0).is_err());
+    /// ```
+    ///
+    pub fn try_new(
+        input_points: Vec<Point2<f64>>,
+        max_force: f64,
+        max_speed: f64,
+        desired_separation: f64,
+        separation_cohesion_ratio: f64,
+        max_edge_len: f64,
+    ) -> Result<DifferentialGrowth, GrowthError> {
+        // Missing nodes are added by the first tick.
+        let min: usize = 1;
+        if input_points.len() < min {
+            return Err(GrowthError::TooFewPoints {
+                len: input_points.len(),
+                min,
+            });
+        }
+        if let Some(index) = input_points
+            .iter()
+            .position(|point| !(point.x.is_finite() && point.y.is_finite()))
+        {
+            return Err(GrowthError::NotFinite { index });
+        }
+
+        let values: [(&'static str, f64); 5] = [
+            ("max_force", max_force),
+            ("max_speed", max_speed),
+            ("desired_separation", desired_separation),
+            ("separation_cohesion_ratio", separation_cohesion_ratio),
+            ("max_edge_length", max_edge_len),
+        ];
+        if let Some((name, value)) = values.into_iter().find(|(_, value)| !(value.is_finite() && *value > 0.0)) {
+            return Err(GrowthError::InvalidParameter { name, value });
+        }
+
+        if max_edge_len >= desired_separation {
+            return Err(GrowthError::MaxEdgeLengthTooLong {
+                max_edge_length: max_edge_len,
+                desired_separation,
+            });
+        }
+
+        Ok(DifferentialGrowth::new(
+            input_points,
+            max_force,
+            max_speed,
+            desired_separation,
+            separation_cohesion_ratio,
+            max_edge_len,
+        ))
+    }
+}
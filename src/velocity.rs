@@ -0,0 +1,88 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::prelude::*;
+use crate::{node::Node, rng::Rng, topology::Topology};
+
+/// The velocity nodes start out with.
+///
+/// Initial velocities strongly influence how the symmetry of the starting
+/// shape breaks during the first iterations.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::InitialVelocity;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let differential_growth = differential_growth::DifferentialGrowth::builder()
+///     .points(starting_points)
+///     .initial_velocity(InitialVelocity::Radial { speed: 0.5 })
+///     .build();
+///
+/// let velocity = differential_growth.nodes()[0].velocity;
+/// assert!((velocity.x - 0.5).abs() < 1e-9 && velocity.y.abs() < 1e-9);
+/// ```
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitialVelocity {
+    /// All nodes start at rest.
+    #[default]
+    Zero,
+    /// Nodes move away from the centroid of all nodes.
+    Radial { speed: f64 },
+    /// Nodes move in a random direction, the same seed gives the same directions.
+    Random { speed: f64, seed: u64 },
+    /// Nodes move along the path, in the direction of the next node.
+    Tangent { speed: f64 },
+}
+
+impl InitialVelocity {
+    /// Sets the velocity of every node.
+    pub(crate) fn apply(&self, nodes: &mut [Node], topology: Topology) {
+        match *self {
+            InitialVelocity::Zero => {
+                for node in nodes.iter_mut() {
+                    node.velocity = Vector2::default();
+                }
+            }
+            InitialVelocity::Radial { speed } => {
+                if nodes.is_empty() {
+                    return;
+                }
+                let centroid: Point2<f64> =
+                    Point2::from(nodes.iter().map(|node| node.position.coords).sum::<Vector2<f64>>() / nodes.len() as f64);
+                for node in nodes.iter_mut() {
+                    node.velocity = direction(node.position - centroid) * speed;
+                }
+            }
+            InitialVelocity::Random { speed, seed } => {
+                let mut rng: Rng = Rng::new(seed);
+                for node in nodes.iter_mut() {
+                    let angle: f64 = rng.range(0.0, core::f64::consts::TAU);
+                    node.velocity = Vector2::new(angle.cos(), angle.sin()) * speed;
+                }
+            }
+            InitialVelocity::Tangent { speed } => {
+                let len: usize = nodes.len();
+                let tangents: Vec<Vector2<f64>> = (0..len)
+                    .map(|i| {
+                        // Endpoints of an open path use their single edge.
+                        let (prev, next) = topology.neighbors(i, len);
+                        let from: Point2<f64> = prev.map_or(nodes[i].position, |prev| nodes[prev].position);
+                        let to: Point2<f64> = next.map_or(nodes[i].position, |next| nodes[next].position);
+                        direction(to - from)
+                    })
+                    .collect();
+                for (node, tangent) in nodes.iter_mut().zip(tangents) {
+                    node.velocity = tangent * speed;
+                }
+            }
+        }
+    }
+}
+
+// Zero vectors have no direction, normalizing them would result in NaN.
+fn direction(vector: Vector2<f64>) -> Vector2<f64> {
+    vector.try_normalize(0.0).unwrap_or_default()
+}
@@ -0,0 +1,97 @@
+use js_sys::Float64Array;
+use nalgebra::Point2;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::differential_growth::DifferentialGrowth;
+
+/// A [`DifferentialGrowth`] for the browser, exported to JavaScript as `DifferentialGrowth`.
+///
+/// Points cross the boundary as flat arrays of coordinates, `[x0, y0, x1, y1, ...]`,
+/// so drawing a frame copies the positions once instead of allocating an object per point.
+///
+/// ```js
+/// import init, { DifferentialGrowth } from "./differential_growth.js";
+///
+/// await init();
+/// const growth = new DifferentialGrowth(new Float64Array([10, 0, 0, 10, -10, 0, 0, -10]), 1.5, 1.0, 14.0, 1.1, 5.0);
+/// growth.tick();
+/// const points = growth.pointsFlat();
+/// ```
+///
+/// Everything else of the simulation is available from Rust: build it there and convert it
+/// with `From`. [`crate::Sandbox`] time limits need a clock `wasm32-unknown-unknown` doesn't have.
+#[wasm_bindgen(js_name = DifferentialGrowth)]
+pub struct WasmDifferentialGrowth {
+    differential_growth: DifferentialGrowth,
+}
+
+#[wasm_bindgen(js_class = DifferentialGrowth)]
+impl WasmDifferentialGrowth {
+    /// Returns a closed path through the points, see [`DifferentialGrowth::new()`].
+    /// A trailing coordinate without a partner is ignored.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        points_flat: &[f64],
+        max_force: f64,
+        max_speed: f64,
+        desired_separation: f64,
+        separation_cohesion_ratio: f64,
+        max_edge_length: f64,
+    ) -> WasmDifferentialGrowth {
+        let points: Vec<Point2<f64>> = points_flat
+            .chunks_exact(2)
+            .map(|coordinates| Point2::new(coordinates[0], coordinates[1]))
+            .collect();
+
+        WasmDifferentialGrowth {
+            differential_growth: DifferentialGrowth::new(
+                points,
+                max_force,
+                max_speed,
+                desired_separation,
+                separation_cohesion_ratio,
+                max_edge_length,
+            ),
+        }
+    }
+
+    /// Advances the simulation by one iteration.
+    pub fn tick(&mut self) {
+        self.differential_growth.tick();
+    }
+
+    /// Advances the simulation by `n` iterations, one call for a whole frame.
+    #[wasm_bindgen(js_name = tickN)]
+    pub fn tick_n(&mut self, n: usize) {
+        self.differential_growth.tick_n(n);
+    }
+
+    /// Returns the positions of the nodes as `[x0, y0, x1, y1, ...]`.
+    #[wasm_bindgen(js_name = pointsFlat)]
+    pub fn points_flat(&self) -> Float64Array {
+        let coordinates: Vec<f64> = self
+            .differential_growth
+            .nodes()
+            .iter()
+            .flat_map(|node| [node.position.x, node.position.y])
+            .collect();
+        Float64Array::from(coordinates.as_slice())
+    }
+
+    /// Returns the amount of nodes.
+    #[wasm_bindgen(js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.differential_growth.nodes().len()
+    }
+
+    /// Returns the amount of ticks that have been executed.
+    pub fn iteration(&self) -> usize {
+        self.differential_growth.iteration()
+    }
+}
+
+impl From<DifferentialGrowth> for WasmDifferentialGrowth {
+    fn from(differential_growth: DifferentialGrowth) -> Self {
+        WasmDifferentialGrowth { differential_growth }
+    }
+}
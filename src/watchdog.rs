@@ -0,0 +1,134 @@
+use nalgebra::Point2;
+
+use crate::prelude::*;
+use crate::{node::Node, node_data::NodeData, node_params::NodeParams, overlay::ParamOverlay, rng::Rng};
+
+/// Detects numerical blow-ups and recovers from them, so long unattended runs
+/// don't silently turn into garbage.
+///
+/// Every `snapshot_interval` ticks a snapshot of the simulation is kept. When a tick
+/// produces non-finite positions or moves a node further than `max_displacement`,
+/// the simulation is rolled back to the last snapshot, `max_speed` and `max_force`
+/// are multiplied by `damping` so the following ticks take smaller steps,
+/// and a [`WatchdogEvent`] is recorded.
+///
+/// # Examples
+///
+/// ```rust
+/// use differential_growth::{ParamOverlay, Watchdog};
+/// use nalgebra::Point2;
+///
+/// let starting_points = differential_growth::generate_points_on_circle(0.0, 0.0, 10.0, 10);
+/// let mut differential_growth = differential_growth::DifferentialGrowth::new(starting_points, 1.5, 1.0, 14.0, 1.1, 5.0);
+/// differential_growth.set_watchdog(Watchdog::default());
+///
+/// for _ in 0..15 {
+///     differential_growth.tick();
+/// }
+///
+/// // Something went badly wrong, like a broken value from a live control.
+/// differential_growth.add_overlay(ParamOverlay::new(Point2::new(0.0, 0.0), 100.0, 1.0, f64::NAN, 5));
+/// differential_growth.tick();
+///
+/// let events = differential_growth.take_watchdog_events();
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].rolled_back_to, 10);
+/// assert_eq!(differential_growth.iteration(), 10);
+/// assert!(differential_growth.nodes().iter().all(|node| node.position.x.is_finite()));
+/// ```
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Watchdog {
+    /// The amount of ticks between snapshots. Defaults to `10`.
+    pub snapshot_interval: usize,
+    /// The largest distance a node can move in one tick before it counts
+    /// as a blow-up, if any. Defaults to `None`, only non-finite values are detected.
+    pub max_displacement: Option<f64>,
+    /// What `max_speed` and `max_force` are multiplied with after every recovery. Defaults to `0.5`.
+    pub damping: f64,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Watchdog {
+            snapshot_interval: 10,
+            max_displacement: None,
+            damping: 0.5,
+        }
+    }
+}
+
+/// Why the [`Watchdog`] rolled back the simulation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlowUp {
+    /// A node ended up with a NaN or infinite position or velocity.
+    NonFinite { node: usize },
+    /// A node moved further than the maximum displacement in a single tick.
+    DisplacementSpike { node: usize, displacement: f64 },
+}
+
+/// A recovery from a blow-up, reported by the [`Watchdog`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WatchdogEvent {
+    /// The tick that blew up.
+    pub iteration: usize,
+    /// The tick the simulation was rolled back to.
+    pub rolled_back_to: usize,
+    /// What was detected.
+    pub reason: BlowUp,
+    /// The maximum speed after damping.
+    pub max_speed: f64,
+    /// The maximum force after damping.
+    pub max_force: f64,
+}
+
+/// The part of the simulation state that changes from tick to tick.
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshot {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) node_data: Option<Box<dyn NodeData>>,
+    pub(crate) node_params: Option<Vec<NodeParams>>,
+    pub(crate) frozen: Vec<bool>,
+    pub(crate) overlays: Vec<ParamOverlay>,
+    pub(crate) rng: Rng,
+    pub(crate) iteration: usize,
+}
+
+/// A [`Watchdog`] together with what it needs to recover.
+#[derive(Clone, Debug)]
+pub(crate) struct WatchdogState {
+    pub(crate) config: Watchdog,
+    pub(crate) snapshot: Snapshot,
+    pub(crate) events: Vec<WatchdogEvent>,
+}
+
+impl Watchdog {
+    /// Returns the first blow-up in `nodes`, given their positions before the tick.
+    pub(crate) fn detect(&self, nodes: &[Node], previous_positions: &[Point2<f64>]) -> Option<BlowUp> {
+        if let Some(node) = nodes.iter().position(|node| {
+            !(node.position.x.is_finite()
+                && node.position.y.is_finite()
+                && node.velocity.x.is_finite()
+                && node.velocity.y.is_finite())
+        }) {
+            return Some(BlowUp::NonFinite { node });
+        }
+
+        // Positions can only be compared while no nodes were inserted or removed.
+        let max_displacement: f64 = self.max_displacement?;
+        if nodes.len() != previous_positions.len() {
+            return None;
+        }
+
+        nodes
+            .iter()
+            .zip(previous_positions)
+            .map(|(node, previous)| nalgebra::distance(&node.position, previous))
+            .enumerate()
+            .find(|(_, displacement)| *displacement > max_displacement)
+            .map(|(node, displacement)| BlowUp::DisplacementSpike { node, displacement })
+    }
+}